@@ -90,6 +90,10 @@ pub struct RuntimeExpectations {
     #[serde(default)]
     pub state_machine: Option<String>,
     #[serde(default)]
+    pub view_model: Vec<String>,
+    #[serde(default)]
+    pub view_model_instance: Option<String>,
+    #[serde(default)]
     pub artboard: Option<String>,
     #[serde(default)]
     pub background: Option<String>,
@@ -112,6 +116,8 @@ impl Default for RuntimeExpectations {
             fps: runtime_fps(),
             animation: None,
             state_machine: None,
+            view_model: Vec::new(),
+            view_model_instance: None,
             artboard: None,
             background: None,
             width: runtime_dimension(),
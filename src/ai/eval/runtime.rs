@@ -82,6 +82,8 @@ pub fn render_runtime_evidence(
         state_machine: expectations.state_machine.clone(),
         inputs: Vec::new(),
         pointers: Vec::new(),
+        view_model: expectations.view_model.clone(),
+        view_model_instance: expectations.view_model_instance.clone(),
         artboard: expectations.artboard.clone(),
         width: expectations.width,
         height: expectations.height,
@@ -90,6 +92,7 @@ pub fn render_runtime_evidence(
         contact_sheet: false,
         preview: false,
         browser: None,
+        assets_dir: None,
     };
 
     match render::render(&options) {
@@ -54,7 +54,18 @@ pub fn run() {
 
     #[cfg(feature = "mcp")]
     if cli.mcp {
-        mcp::run_server();
+        let policy = mcp::PathPolicy::new(&cli.mcp_allow_root, cli.mcp_max_file_bytes)
+            .unwrap_or_else(|e| {
+                eprintln!("{}", e);
+                std::process::exit(1);
+            });
+        mcp::run_server(policy);
+        return;
+    }
+
+    #[cfg(feature = "mcp")]
+    if let Some(addr) = cli.mcp_http.as_deref() {
+        mcp::run_http_server(addr, cli.mcp_root.clone(), cli.mcp_max_file_bytes);
         return;
     }
 
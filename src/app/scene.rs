@@ -8,6 +8,8 @@ pub(super) fn run(command: Command, global_json: bool) {
             input,
             output,
             file_id,
+            assets,
+            assets_dir,
             json,
         } => {
             let json = json || global_json;
@@ -37,7 +39,16 @@ pub(super) fn run(command: Command, global_json: bool) {
                 .parent()
                 .filter(|parent| !parent.as_os_str().is_empty())
                 .unwrap_or_else(|| std::path::Path::new("."));
-            let scene = builder::build_scene(&spec, Some(base_dir)).unwrap_or_else(|e| {
+            let built = if assets == "external" {
+                builder::build_scene_with_external_assets(
+                    &spec,
+                    Some(base_dir),
+                    &builder::AssetPolicy::default(),
+                )
+            } else {
+                builder::build_scene(&spec, Some(base_dir)).map(|scene| (scene, Vec::new()))
+            };
+            let (scene, external) = built.unwrap_or_else(|e| {
                 if json {
                     json_error(
                         "generate",
@@ -61,19 +72,60 @@ pub(super) fn run(command: Command, global_json: bool) {
                 eprintln!("error writing {:?}: {}", output, e);
                 std::process::exit(1);
             });
+            let assets_manifest = (assets == "external").then(|| {
+                let dir = assets_dir.unwrap_or_else(|| {
+                    let stem = output
+                        .file_stem()
+                        .map(|stem| stem.to_string_lossy().into_owned())
+                        .unwrap_or_else(|| "output".to_string());
+                    output.with_file_name(format!("{stem}_assets"))
+                });
+                let riv_name = output
+                    .file_name()
+                    .map(|name| name.to_string_lossy().into_owned())
+                    .unwrap_or_default();
+                builder::write_external_assets(&dir, &riv_name, &external).unwrap_or_else(|e| {
+                    if json {
+                        json_error(
+                            "generate",
+                            "write-failed",
+                            format!("error writing external assets to {:?}: {}", dir, e),
+                        );
+                    }
+                    eprintln!("error writing external assets to {:?}: {}", dir, e);
+                    std::process::exit(1);
+                })
+            });
+            // Checks that need the encoded file, such as decoded audio lengths
+            // against animation timelines, come from the validator.
+            let warnings = validator::validate_riv(&bytes)
+                .map(|report| report.warnings)
+                .unwrap_or_default();
             if json {
                 #[derive(serde::Serialize)]
                 struct GenerateOutput {
                     bytes_written: usize,
                     output_path: String,
+                    #[serde(skip_serializing_if = "Option::is_none")]
+                    assets_manifest: Option<String>,
+                    #[serde(skip_serializing_if = "Vec::is_empty")]
+                    warnings: Vec<String>,
                 }
                 let result = GenerateOutput {
                     bytes_written: bytes.len(),
                     output_path: output.display().to_string(),
+                    assets_manifest: assets_manifest.map(|path| path.display().to_string()),
+                    warnings,
                 };
                 json_success("generate", &result);
             } else {
+                for warning in &warnings {
+                    eprintln!("warning: {}", warning);
+                }
                 eprintln!("wrote {} bytes to {:?}", bytes.len(), output);
+                if let Some(path) = &assets_manifest {
+                    eprintln!("wrote {} external asset(s) and {:?}", external.len(), path);
+                }
             }
         }
         Command::New {
@@ -13,6 +13,8 @@ pub(super) fn run(command: Command, global_json: bool) {
             state_machine,
             inputs,
             pointers,
+            view_model,
+            view_model_instance,
             artboard,
             width,
             height,
@@ -21,6 +23,7 @@ pub(super) fn run(command: Command, global_json: bool) {
             contact_sheet,
             preview,
             browser,
+            assets,
             json,
         } => {
             let json = json || global_json;
@@ -52,6 +55,8 @@ pub(super) fn run(command: Command, global_json: bool) {
                 state_machine,
                 inputs,
                 pointers,
+                view_model,
+                view_model_instance,
                 artboard,
                 width,
                 height,
@@ -60,6 +65,7 @@ pub(super) fn run(command: Command, global_json: bool) {
                 contact_sheet,
                 preview,
                 browser,
+                assets_dir: assets,
             };
             match render::render(&options) {
                 Ok(manifest) => {
@@ -102,6 +108,8 @@ pub(super) fn run(command: Command, global_json: bool) {
             candidate_animation,
             reference_state_machine,
             candidate_state_machine,
+            view_model,
+            view_model_instance,
             max_pixel_diff,
             json,
         } => {
@@ -137,6 +145,8 @@ pub(super) fn run(command: Command, global_json: bool) {
                 candidate_animation,
                 reference_state_machine,
                 candidate_state_machine,
+                view_model,
+                view_model_instance,
             };
             match compare::compare(&options) {
                 Ok(report) => {
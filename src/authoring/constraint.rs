@@ -333,7 +333,9 @@ fn resolve_reference(
         return Err(AuthoringDiagnostic::new(
             path,
             "unsupported_constraint_node",
-            format!("direct sibling '{id}' is a raw scene object without a typed transform"),
+            format!(
+                "direct sibling '{id}' has no typed transform; raw scene objects and layouts are placed elsewhere"
+            ),
         ));
     }
     Ok(Anchor { node: index, axis })
@@ -531,16 +533,20 @@ macro_rules! typed_node_transform {
             | VisualNode::Triangle { transform, .. }
             | VisualNode::Polygon { transform, .. }
             | VisualNode::Star { transform, .. }
+            | VisualNode::Path { transform, .. }
             | VisualNode::Text { transform, .. }
             | VisualNode::Image { transform, .. }
+            | VisualNode::NineSlice { transform, .. }
             | VisualNode::Grid { transform, .. }
             | VisualNode::Radial { transform, .. }
             | VisualNode::Mirror { transform, .. }
             | VisualNode::Distribute { transform, .. }
             | VisualNode::AlongPath { transform, .. }
             | VisualNode::Group { transform, .. }
-            | VisualNode::Instance { transform, .. } => Some(transform),
-            VisualNode::RawSceneObject { .. } => None,
+            | VisualNode::Skeleton { transform, .. }
+            | VisualNode::Instance { transform, .. }
+            | VisualNode::NestedArtboard { transform, .. } => Some(transform),
+            VisualNode::Layout { .. } | VisualNode::RawSceneObject { .. } => None,
         }
     };
 }
@@ -18,9 +18,17 @@ pub(crate) fn atan2(y: f64, x: f64) -> f64 {
     libm::atan2(y, x)
 }
 
+pub(crate) fn exp(x: f64) -> f64 {
+    libm::exp(x)
+}
+
+pub(crate) fn sqrt(x: f64) -> f64 {
+    libm::sqrt(x)
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{atan2, hypot, radians_from_degrees, sin_cos};
+    use super::{atan2, exp, hypot, radians_from_degrees, sin_cos, sqrt};
 
     #[test]
     fn degree_conversion_has_pinned_bits() {
@@ -43,4 +51,11 @@ mod tests {
             std::f64::consts::FRAC_PI_2.to_bits()
         );
     }
+
+    #[test]
+    fn spring_math_has_pinned_bits() {
+        assert_eq!(exp(0.0).to_bits(), 1.0_f64.to_bits());
+        assert_eq!(exp(1.0).to_bits(), 0x4005_bf0a_8b14_576a);
+        assert_eq!(sqrt(2.0).to_bits(), std::f64::consts::SQRT_2.to_bits());
+    }
 }
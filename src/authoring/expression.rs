@@ -1,6 +1,6 @@
 use std::collections::BTreeMap;
 
-use super::deterministic_math::radians_from_degrees;
+use super::deterministic_math::{radians_from_degrees, sin_cos};
 use super::spec::{AuthoringDiagnostic, Quantity, ScalarExpr, TransformSpec, Unit};
 
 #[derive(Debug, Clone, Copy)]
@@ -74,89 +74,218 @@ pub(crate) fn evaluate_transform(
     })
 }
 
+/// Deepest nesting an expression tree may reach, counting the root as one.
+pub(crate) const MAX_EXPRESSION_DEPTH: usize = 32;
+
 fn evaluate(
     expression: &ScalarExpr,
     path: &str,
     scope: &BTreeMap<String, Quantity>,
 ) -> Result<Evaluated, AuthoringDiagnostic> {
-    match expression {
-        ScalarExpr::Literal { value, unit } => {
-            let value_path = format!("{path}.value");
-            validate_scene_number(*value, &value_path)?;
-            canonicalize(
-                Evaluated {
-                    value: *value,
-                    unit: *unit,
-                },
-                &value_path,
-            )
-        }
-        ScalarExpr::Parameter { name } => {
-            let quantity = scope.get(name).ok_or_else(|| {
-                AuthoringDiagnostic::new(
-                    format!("{path}.name"),
-                    "unknown_parameter",
-                    format!("parameter '{name}' is not defined in this scope"),
-                )
-            })?;
-            validate_scene_number(quantity.value, path)?;
-            canonicalize(
-                Evaluated {
-                    value: quantity.value,
-                    unit: quantity.unit,
-                },
+    Evaluator { scope }.evaluate(expression, path, 1)
+}
+
+struct Evaluator<'a> {
+    scope: &'a BTreeMap<String, Quantity>,
+}
+
+impl Evaluator<'_> {
+    fn evaluate(
+        &self,
+        expression: &ScalarExpr,
+        path: &str,
+        depth: usize,
+    ) -> Result<Evaluated, AuthoringDiagnostic> {
+        if depth > MAX_EXPRESSION_DEPTH {
+            return Err(AuthoringDiagnostic::new(
                 path,
-            )
-        }
-        ScalarExpr::Add { left, right } => {
-            evaluate_binary(left, right, path, scope, |left, right| left + right)
+                "expression_depth_limit",
+                format!("expressions may nest at most {MAX_EXPRESSION_DEPTH} levels deep"),
+            ));
         }
-        ScalarExpr::Subtract { left, right } => {
-            evaluate_binary(left, right, path, scope, |left, right| left - right)
-        }
-        ScalarExpr::Multiply { value, factor } => {
-            validate_scene_number(*factor, &format!("{path}.factor"))?;
-            let evaluated = evaluate(value, &format!("{path}.value"), scope)?;
-            let result = evaluated.value * factor;
-            validate_scene_number(result, path)?;
-            Ok(Evaluated {
-                value: result,
-                unit: evaluated.unit,
-            })
-        }
-        ScalarExpr::Divide { value, divisor } => {
-            validate_scene_number(*divisor, &format!("{path}.divisor"))?;
-            if *divisor == 0.0 {
-                return Err(AuthoringDiagnostic::new(
-                    format!("{path}.divisor"),
-                    "division_by_zero",
-                    "expression divisor must not be zero",
-                ));
+        let operand = |operand: &ScalarExpr, field: &str| {
+            self.evaluate(operand, &format!("{path}.{field}"), depth + 1)
+        };
+
+        match expression {
+            ScalarExpr::Literal { value, unit } => {
+                let value_path = format!("{path}.value");
+                validate_scene_number(*value, &value_path)?;
+                canonicalize(
+                    Evaluated {
+                        value: *value,
+                        unit: *unit,
+                    },
+                    &value_path,
+                )
+            }
+            ScalarExpr::Parameter { name } => {
+                let quantity = self.scope.get(name).ok_or_else(|| {
+                    AuthoringDiagnostic::new(
+                        format!("{path}.name"),
+                        "unknown_parameter",
+                        format!("parameter '{name}' is not defined in this scope"),
+                    )
+                })?;
+                validate_scene_number(quantity.value, path)?;
+                canonicalize(
+                    Evaluated {
+                        value: quantity.value,
+                        unit: quantity.unit,
+                    },
+                    path,
+                )
+            }
+            ScalarExpr::Add { left, right } => {
+                let (left, right) = same_unit(
+                    operand(left, "left")?,
+                    operand(right, "right")?,
+                    &format!("{path}.right"),
+                )?;
+                finish(left.value + right.value, left.unit, path)
+            }
+            ScalarExpr::Subtract { left, right } => {
+                let (left, right) = same_unit(
+                    operand(left, "left")?,
+                    operand(right, "right")?,
+                    &format!("{path}.right"),
+                )?;
+                finish(left.value - right.value, left.unit, path)
+            }
+            ScalarExpr::Multiply { value, factor } => {
+                validate_scene_number(*factor, &format!("{path}.factor"))?;
+                let evaluated = operand(value, "value")?;
+                finish(evaluated.value * factor, evaluated.unit, path)
+            }
+            ScalarExpr::Divide { value, divisor } => {
+                validate_scene_number(*divisor, &format!("{path}.divisor"))?;
+                if *divisor == 0.0 {
+                    return Err(AuthoringDiagnostic::new(
+                        format!("{path}.divisor"),
+                        "division_by_zero",
+                        "expression divisor must not be zero",
+                    ));
+                }
+                let evaluated = operand(value, "value")?;
+                finish(evaluated.value / divisor, evaluated.unit, path)
+            }
+            ScalarExpr::Min { left, right } => {
+                let (left, right) = same_unit(
+                    operand(left, "left")?,
+                    operand(right, "right")?,
+                    &format!("{path}.right"),
+                )?;
+                finish(left.value.min(right.value), left.unit, path)
+            }
+            ScalarExpr::Max { left, right } => {
+                let (left, right) = same_unit(
+                    operand(left, "left")?,
+                    operand(right, "right")?,
+                    &format!("{path}.right"),
+                )?;
+                finish(left.value.max(right.value), left.unit, path)
+            }
+            ScalarExpr::Clamp { value, min, max } => {
+                let value = operand(value, "value")?;
+                let (value, min) = same_unit(value, operand(min, "min")?, &format!("{path}.min"))?;
+                let (value, max) = same_unit(value, operand(max, "max")?, &format!("{path}.max"))?;
+                if min.value > max.value {
+                    return Err(AuthoringDiagnostic::new(
+                        format!("{path}.max"),
+                        "invalid_clamp_range",
+                        "clamp maximum must not be less than its minimum",
+                    ));
+                }
+                finish(value.value.clamp(min.value, max.value), value.unit, path)
+            }
+            ScalarExpr::Product { left, right } => {
+                let left = operand(left, "left")?;
+                let right = operand(right, "right")?;
+                let unit = match (left.unit, right.unit) {
+                    (Unit::Scalar, unit) | (unit, Unit::Scalar) => unit,
+                    _ => {
+                        return Err(AuthoringDiagnostic::new(
+                            format!("{path}.right"),
+                            "unit_mismatch",
+                            format!(
+                                "cannot multiply {:?} by {:?}; one operand must be scalar",
+                                left.unit, right.unit
+                            ),
+                        ));
+                    }
+                };
+                finish(left.value * right.value, unit, path)
+            }
+            ScalarExpr::Quotient { left, right } => {
+                let left = operand(left, "left")?;
+                let right_path = format!("{path}.right");
+                let right = operand(right, "right")?;
+                let unit = match (left.unit, right.unit) {
+                    (numerator, denominator) if numerator == denominator => Unit::Scalar,
+                    (unit, Unit::Scalar) => unit,
+                    _ => {
+                        return Err(AuthoringDiagnostic::new(
+                            right_path,
+                            "unit_mismatch",
+                            format!(
+                                "cannot divide {:?} by {:?}; the divisor must be scalar or share the dividend's unit",
+                                left.unit, right.unit
+                            ),
+                        ));
+                    }
+                };
+                if right.value == 0.0 {
+                    return Err(AuthoringDiagnostic::new(
+                        right_path,
+                        "division_by_zero",
+                        "expression divisor must not be zero",
+                    ));
+                }
+                finish(left.value / right.value, unit, path)
+            }
+            ScalarExpr::Sin { angle } | ScalarExpr::Cos { angle } => {
+                let angle_path = format!("{path}.angle");
+                let radians = expect_unit(operand(angle, "angle")?, &angle_path, Unit::Radians)?;
+                let (sine, cosine) = sin_cos(radians);
+                let value = if matches!(expression, ScalarExpr::Sin { .. }) {
+                    sine
+                } else {
+                    cosine
+                };
+                finish(value, Unit::Scalar, path)
+            }
+            ScalarExpr::Round { value } => {
+                let value = operand(value, "value")?;
+                if value.unit == Unit::Radians {
+                    return Err(AuthoringDiagnostic::new(
+                        format!("{path}.value"),
+                        "unit_mismatch",
+                        "round applies to pixel and scalar values, not angles",
+                    ));
+                }
+                finish(value.value.round(), value.unit, path)
+            }
+            ScalarExpr::Lerp { from, to, t } => {
+                let (from, to) = same_unit(
+                    operand(from, "from")?,
+                    operand(to, "to")?,
+                    &format!("{path}.to"),
+                )?;
+                let t = expect_unit(operand(t, "t")?, &format!("{path}.t"), Unit::Scalar)?;
+                finish(from.value + (to.value - from.value) * t, from.unit, path)
             }
-            let evaluated = evaluate(value, &format!("{path}.value"), scope)?;
-            let result = evaluated.value / divisor;
-            validate_scene_number(result, path)?;
-            Ok(Evaluated {
-                value: result,
-                unit: evaluated.unit,
-            })
         }
     }
 }
 
-fn evaluate_binary(
-    left: &ScalarExpr,
-    right: &ScalarExpr,
+fn same_unit(
+    left: Evaluated,
+    right: Evaluated,
     path: &str,
-    scope: &BTreeMap<String, Quantity>,
-    operation: impl FnOnce(f64, f64) -> f64,
-) -> Result<Evaluated, AuthoringDiagnostic> {
-    let left = evaluate(left, &format!("{path}.left"), scope)?;
-    let right_path = format!("{path}.right");
-    let right = evaluate(right, &right_path, scope)?;
+) -> Result<(Evaluated, Evaluated), AuthoringDiagnostic> {
     if left.unit != right.unit {
         return Err(AuthoringDiagnostic::new(
-            right_path,
+            path,
             "unit_mismatch",
             format!(
                 "cannot combine {:?} with {:?}; operands must have compatible units",
@@ -164,12 +293,12 @@ fn evaluate_binary(
             ),
         ));
     }
-    let value = operation(left.value, right.value);
+    Ok((left, right))
+}
+
+fn finish(value: f64, unit: Unit, path: &str) -> Result<Evaluated, AuthoringDiagnostic> {
     validate_scene_number(value, path)?;
-    Ok(Evaluated {
-        value,
-        unit: left.unit,
-    })
+    Ok(Evaluated { value, unit })
 }
 
 fn canonicalize(evaluated: Evaluated, path: &str) -> Result<Evaluated, AuthoringDiagnostic> {
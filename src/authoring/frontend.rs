@@ -1,4 +1,6 @@
+mod artboards;
 mod compiler;
+mod model;
 mod motion;
 
 use std::collections::{BTreeMap, HashSet};
@@ -6,8 +8,8 @@ use std::collections::{BTreeMap, HashSet};
 use super::lower;
 use super::spec::{
     AUTHORING_FORMAT_VERSION, AuthoringArtboard, AuthoringDiagnostic, AuthoringError,
-    AuthoringSpec, BehaviorSection, LoweredAuthoring, MotionSection, Quantity, RawSceneFragment,
-    TransformSpec, Unit, VisualSection,
+    AuthoringSpec, BehaviorSection, LoweredAuthoring, ModelSection, MotionSection, Quantity,
+    RawSceneFragment, TransformSpec, Unit, VisualSection,
 };
 use super::validation::validate_numeric_values;
 use super::visual::VisualNode;
@@ -15,23 +17,39 @@ use compiler::AuthoringCompiler;
 
 pub fn lower_authoring(spec: &AuthoringSpec) -> Result<LoweredAuthoring, AuthoringError> {
     validate_authoring(spec)?;
-    AuthoringCompiler::new(spec)?.lower_motion()?.finish()
+    artboards::validate_artboards(spec)?;
+    AuthoringCompiler::new(spec)?
+        .lower_motion()?
+        .lower_model()?
+        .lower_artboards()?
+        .finish()
 }
 
 fn lower_target_graph(spec: &AuthoringSpec) -> Result<LoweredAuthoring, AuthoringError> {
+    lower_target_graph_with(spec, &lower::MorphResolutions::new())
+        .map_err(|error| rewrite_error_paths(spec, error))
+}
+
+/// Lowers the visual graph motion animates, with morphed paths resampled to
+/// `morph_resolutions`; error paths are left for the caller to rewrite.
+fn lower_target_graph_with(
+    spec: &AuthoringSpec,
+    morph_resolutions: &lower::MorphResolutions,
+) -> Result<LoweredAuthoring, AuthoringError> {
     if spec.motion.tracks.is_empty() {
-        return lower::lower_authoring(spec).map_err(|error| rewrite_error_paths(spec, error));
+        return lower::lower_authoring_with(spec, morph_resolutions);
     }
 
     let mut target_spec = spec.clone();
     target_spec.motion = MotionSection::default();
     target_spec.behavior = BehaviorSection::default();
-    lower::lower_authoring(&target_spec).map_err(|error| rewrite_error_paths(spec, error))
+    lower::lower_authoring_with(&target_spec, morph_resolutions)
 }
 
 fn validate_authoring(spec: &AuthoringSpec) -> Result<(), AuthoringError> {
     let mut name_diagnostics = validate_authored_names(spec);
     name_diagnostics.extend(motion::validate_motion(&spec.motion));
+    name_diagnostics.extend(model::validate_model(&spec.model));
     if !name_diagnostics.is_empty() {
         return Err(AuthoringError::many(name_diagnostics));
     }
@@ -131,6 +149,12 @@ fn validate_authored_names(spec: &AuthoringSpec) -> Vec<AuthoringDiagnostic> {
         "image",
         &mut diagnostics,
     );
+    validate_file_assets(
+        &spec.audio_assets,
+        "$.audio_assets",
+        "audio",
+        &mut diagnostics,
+    );
     validate_parameter_names(&spec.parameters, "$.parameters", &mut diagnostics);
 
     for (component_index, component) in spec.components.iter().enumerate() {
@@ -158,6 +182,13 @@ fn validate_authored_names(spec: &AuthoringSpec) -> Vec<AuthoringDiagnostic> {
         "$.motion.raw_animations",
         &mut diagnostics,
     );
+    for (index, event) in spec.behavior.events.iter().enumerate() {
+        validate_id(
+            event.id(),
+            &format!("$.behavior.events[{index}].id"),
+            &mut diagnostics,
+        );
+    }
     validate_fragment_names(
         &spec.behavior.raw_state_machines,
         "$.behavior.raw_state_machines",
@@ -209,8 +240,8 @@ fn validate_file_assets(
     }
 }
 
-fn validate_parameter_names(
-    parameters: &BTreeMap<String, Quantity>,
+fn validate_parameter_names<V>(
+    parameters: &BTreeMap<String, V>,
     path: &str,
     diagnostics: &mut Vec<AuthoringDiagnostic>,
 ) {
@@ -252,6 +283,20 @@ fn validate_node_name(node: &VisualNode, path: &str, diagnostics: &mut Vec<Autho
     if let Some(pattern) = node.pattern() {
         validate_node_name(pattern.item(), &format!("{path}.item"), diagnostics);
     }
+    if let VisualNode::Skeleton { chains, ik, .. } = node {
+        for (chain_index, chain) in chains.iter().enumerate() {
+            for (bone_index, bone) in chain.bones.iter().enumerate() {
+                validate_id(
+                    &bone.id,
+                    &format!("{path}.chains[{chain_index}].bones[{bone_index}].id"),
+                    diagnostics,
+                );
+            }
+        }
+        for (index, ik) in ik.iter().enumerate() {
+            validate_id(&ik.id, &format!("{path}.ik[{index}].id"), diagnostics);
+        }
+    }
     if let VisualNode::Instance { overrides, .. } = node {
         validate_parameter_names(overrides, &format!("{path}.overrides"), diagnostics);
     }
@@ -290,8 +335,10 @@ fn validate_component_definitions(spec: &AuthoringSpec) -> Result<(), AuthoringE
                     unit: Unit::Px,
                 },
             },
+            artboards: artboards::nesting_catalogue(spec),
             font_assets: spec.font_assets.clone(),
             image_assets: spec.image_assets.clone(),
+            audio_assets: spec.audio_assets.clone(),
             parameters: BTreeMap::new(),
             components: spec.components.clone(),
             visual: VisualSection {
@@ -304,6 +351,7 @@ fn validate_component_definitions(spec: &AuthoringSpec) -> Result<(), AuthoringE
             },
             motion: MotionSection::default(),
             behavior: BehaviorSection::default(),
+            model: ModelSection::default(),
         };
 
         if let Err(error) = lower::lower_authoring(&validation_spec) {
@@ -367,10 +415,7 @@ fn resolve_expanded_path(spec: &AuthoringSpec, path: &str) -> Option<String> {
         }
 
         if let Some((child_index, rest)) = take_index(remainder, ".children[") {
-            let VisualNode::Group { children, .. } = node else {
-                return None;
-            };
-            node = children.get(child_index)?;
+            node = node.children()?.get(child_index)?;
             resolved.push_str(&format!(".children[{child_index}]"));
             remainder = rest;
             continue;
@@ -0,0 +1,389 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use serde_json::Value;
+
+use super::super::lower::{self, file_asset_runtime_name};
+use super::super::spec::{
+    ArtboardDefinitionSpec, AuthoringArtboard, AuthoringDiagnostic, AuthoringError, AuthoringSpec,
+    LoweredAuthoring, ModelSection, VisualSection,
+};
+use super::super::visual::VisualNode;
+use super::{AuthoringCompiler, validate_authoring, validate_id};
+
+/// Artboard ids are unique across `$.artboard` and `$.artboards`, and no
+/// artboard may reach itself through `nested_artboard` nodes.
+pub(super) fn validate_artboards(spec: &AuthoringSpec) -> Result<(), AuthoringError> {
+    let mut diagnostics = Vec::new();
+    let mut ids = HashSet::from([spec.artboard.id.as_str()]);
+    for (index, definition) in spec.artboards.iter().enumerate() {
+        let path = format!("$.artboards[{index}].id");
+        validate_id(&definition.id, &path, &mut diagnostics);
+        if !ids.insert(definition.id.as_str()) {
+            diagnostics.push(AuthoringDiagnostic::new(
+                path,
+                "duplicate_artboard",
+                format!("artboard id '{}' is duplicated", definition.id),
+            ));
+        }
+    }
+    if !diagnostics.is_empty() {
+        return Err(AuthoringError::many(diagnostics));
+    }
+    detect_nested_cycles(spec).map_err(AuthoringError::one)
+}
+
+/// The artboards a scoped spec can nest: the primary one and every
+/// definition, without their visual trees, which nesting never reads.
+pub(super) fn nesting_catalogue(spec: &AuthoringSpec) -> Vec<ArtboardDefinitionSpec> {
+    std::iter::once(primary_definition(spec))
+        .chain(spec.artboards.iter().cloned())
+        .map(|definition| ArtboardDefinitionSpec {
+            visual: VisualSection::default(),
+            ..definition
+        })
+        .collect()
+}
+
+/// Lowers every `$.artboards` entry through the same pipeline as the primary
+/// artboard and assembles a multi-artboard scene. File assets are declared
+/// once on the primary artboard; the builder resolves them at file scope.
+pub(super) fn lower_artboards(
+    spec: &AuthoringSpec,
+    primary: LoweredAuthoring,
+) -> Result<LoweredAuthoring, AuthoringError> {
+    if spec.artboards.is_empty() {
+        return Ok(primary);
+    }
+
+    let LoweredAuthoring {
+        mut scene,
+        mut source_map,
+    } = primary;
+    for entry in &mut source_map.entries {
+        for scene_path in &mut entry.scene_paths {
+            *scene_path = rebased_scene_path(scene_path, 0, 0);
+        }
+    }
+    let Some(object) = scene.as_object_mut() else {
+        return Ok(LoweredAuthoring { scene, source_map });
+    };
+    let mut artboards = object.remove("artboard").into_iter().collect::<Vec<_>>();
+
+    let asset_count = spec.font_assets.len() + spec.image_assets.len() + spec.audio_assets.len();
+    for index in 0..spec.artboards.len() {
+        let scoped = scoped_spec(spec, index);
+        let lowered = validate_authoring(&scoped)
+            .and_then(|()| AuthoringCompiler::new(&scoped)?.lower_motion())
+            .map(AuthoringCompiler::into_lowered)
+            .map_err(|error| rescoped_error(error, index))?;
+
+        let asset_names = shared_asset_names(spec, &scoped.artboard.id);
+        let mut artboard = lowered.scene.get("artboard").cloned().unwrap_or_default();
+        if let Some(children) = artboard.get_mut("children").and_then(Value::as_array_mut) {
+            children.drain(..asset_count.min(children.len()));
+        }
+        rename_strings(&mut artboard, &asset_names);
+        artboards.push(artboard);
+
+        for mut entry in lowered.source_map.entries {
+            if entry.authored_path.starts_with("$.font_assets")
+                || entry.authored_path.starts_with("$.image_assets")
+                || entry.authored_path.starts_with("$.audio_assets")
+            {
+                continue;
+            }
+            entry.authored_path = rescoped_path(&entry.authored_path, index);
+            for scene_path in &mut entry.scene_paths {
+                *scene_path = rebased_scene_path(scene_path, index + 1, asset_count);
+            }
+            source_map.entries.push(entry);
+        }
+    }
+
+    object.insert("artboards".to_string(), Value::Array(artboards));
+    lower::validate_lowered_scene(&scene).map_err(|mut diagnostic| {
+        diagnostic.path = "$.artboards".to_string();
+        AuthoringError::one(diagnostic)
+    })?;
+    Ok(LoweredAuthoring { scene, source_map })
+}
+
+fn primary_definition(spec: &AuthoringSpec) -> ArtboardDefinitionSpec {
+    ArtboardDefinitionSpec {
+        id: spec.artboard.id.clone(),
+        width: spec.artboard.width,
+        height: spec.artboard.height,
+        visual: spec.visual.clone(),
+        motion: spec.motion.clone(),
+        behavior: spec.behavior.clone(),
+    }
+}
+
+/// The spec `$.artboards[index]` would be if it were the primary artboard.
+fn scoped_spec(spec: &AuthoringSpec, index: usize) -> AuthoringSpec {
+    let definition = &spec.artboards[index];
+    let mut artboards = nesting_catalogue(spec);
+    artboards.remove(index + 1);
+    AuthoringSpec {
+        authoring_format_version: spec.authoring_format_version,
+        artboard: AuthoringArtboard {
+            id: definition.id.clone(),
+            width: definition.width,
+            height: definition.height,
+        },
+        artboards,
+        font_assets: spec.font_assets.clone(),
+        image_assets: spec.image_assets.clone(),
+        audio_assets: spec.audio_assets.clone(),
+        parameters: spec.parameters.clone(),
+        components: spec.components.clone(),
+        visual: definition.visual.clone(),
+        motion: definition.motion.clone(),
+        behavior: definition.behavior.clone(),
+        model: ModelSection::default(),
+    }
+}
+
+fn rescoped_error(mut error: AuthoringError, index: usize) -> AuthoringError {
+    for diagnostic in &mut error.diagnostics {
+        diagnostic.path = rescoped_path(&diagnostic.path, index);
+    }
+    error
+}
+
+fn rescoped_path(path: &str, index: usize) -> String {
+    if path == "$.lowered_scene" {
+        return format!("$.artboards[{index}]");
+    }
+    if let Some(rest) = path.strip_prefix("$.artboard")
+        && !rest.starts_with('s')
+    {
+        return format!("$.artboards[{index}]{rest}");
+    }
+    for section in ["$.visual", "$.motion", "$.behavior"] {
+        if let Some(rest) = path.strip_prefix(section)
+            && (rest.is_empty() || rest.starts_with(['.', '[']))
+        {
+            return format!("$.artboards[{index}].{}{rest}", &section[2..]);
+        }
+    }
+    path.to_string()
+}
+
+/// Moves a `/artboard/...` pointer to `/artboards/{slot}/...`, accounting for
+/// the `dropped` leading file-asset children removed from that artboard.
+fn rebased_scene_path(path: &str, slot: usize, dropped: usize) -> String {
+    let Some(rest) = path.strip_prefix("/artboard") else {
+        return path.to_string();
+    };
+    if let Some(children) = rest.strip_prefix("/children/") {
+        let (index, tail) = children
+            .find('/')
+            .map_or((children, ""), |split| children.split_at(split));
+        if let Ok(index) = index.parse::<usize>() {
+            return format!(
+                "/artboards/{slot}/children/{}{tail}",
+                index.saturating_sub(dropped)
+            );
+        }
+    }
+    format!("/artboards/{slot}{rest}")
+}
+
+fn shared_asset_names(spec: &AuthoringSpec, artboard_id: &str) -> HashMap<String, String> {
+    [
+        ("font_asset", &spec.font_assets),
+        ("image_asset", &spec.image_assets),
+        ("audio_asset", &spec.audio_assets),
+    ]
+    .into_iter()
+    .flat_map(|(role, assets)| {
+        assets.keys().map(move |id| {
+            (
+                file_asset_runtime_name(artboard_id, id, role),
+                file_asset_runtime_name(&spec.artboard.id, id, role),
+            )
+        })
+    })
+    .collect()
+}
+
+fn rename_strings(value: &mut Value, names: &HashMap<String, String>) {
+    match value {
+        Value::String(text) => {
+            if let Some(renamed) = names.get(text.as_str()) {
+                text.clone_from(renamed);
+            }
+        }
+        Value::Array(values) => {
+            for value in values {
+                rename_strings(value, names);
+            }
+        }
+        Value::Object(object) => {
+            for value in object.values_mut() {
+                rename_strings(value, names);
+            }
+        }
+        _ => {}
+    }
+}
+
+struct NestedEdge {
+    target: String,
+    path: String,
+}
+
+fn detect_nested_cycles(spec: &AuthoringSpec) -> Result<(), AuthoringDiagnostic> {
+    let mut edges = BTreeMap::<&str, Vec<NestedEdge>>::new();
+    let mut order = vec![spec.artboard.id.as_str()];
+    edges.insert(
+        &spec.artboard.id,
+        collect_edges(spec, &spec.visual.nodes, "$.visual.nodes"),
+    );
+    for (index, definition) in spec.artboards.iter().enumerate() {
+        order.push(&definition.id);
+        edges.insert(
+            &definition.id,
+            collect_edges(
+                spec,
+                &definition.visual.nodes,
+                &format!("$.artboards[{index}].visual.nodes"),
+            ),
+        );
+    }
+
+    for start in order {
+        let mut trail = vec![start];
+        if let Some(path) = find_cycle(&edges, start, &mut trail) {
+            return Err(AuthoringDiagnostic::new(
+                format!("{path}.artboard"),
+                "nested_artboard_cycle",
+                format!("nested artboards form a cycle: {}", trail.join(" -> ")),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Depth-first search for a route back to `trail[0]`; on success `trail`
+/// holds the cycle and the returned path is the first nesting node on it.
+fn find_cycle<'s>(
+    edges: &BTreeMap<&'s str, Vec<NestedEdge>>,
+    current: &'s str,
+    trail: &mut Vec<&'s str>,
+) -> Option<String> {
+    for edge in edges.get(current).into_iter().flatten() {
+        let Some((&target, _)) = edges.get_key_value(edge.target.as_str()) else {
+            continue;
+        };
+        if target == trail[0] {
+            trail.push(target);
+            return Some(first_edge_path(edges, trail));
+        }
+        if trail.contains(&target) {
+            continue;
+        }
+        trail.push(target);
+        if let Some(path) = find_cycle(edges, target, trail) {
+            return Some(path);
+        }
+        trail.pop();
+    }
+    None
+}
+
+fn first_edge_path(edges: &BTreeMap<&str, Vec<NestedEdge>>, trail: &[&str]) -> String {
+    edges
+        .get(trail[0])
+        .into_iter()
+        .flatten()
+        .find(|edge| edge.target == trail[1])
+        .map(|edge| edge.path.clone())
+        .unwrap_or_default()
+}
+
+fn collect_edges(spec: &AuthoringSpec, nodes: &[VisualNode], list_path: &str) -> Vec<NestedEdge> {
+    let mut edges = Vec::new();
+    let mut visited_components = HashSet::new();
+    for (index, node) in nodes.iter().enumerate() {
+        collect_node_edges(
+            spec,
+            node,
+            &format!("{list_path}[{index}]"),
+            &mut visited_components,
+            &mut edges,
+        );
+    }
+    edges
+}
+
+fn collect_node_edges<'s>(
+    spec: &'s AuthoringSpec,
+    node: &'s VisualNode,
+    path: &str,
+    visited_components: &mut HashSet<&'s str>,
+    edges: &mut Vec<NestedEdge>,
+) {
+    if let Some(nested) = node.nested_artboard_node() {
+        edges.push(NestedEdge {
+            target: nested.artboard.to_string(),
+            path: path.to_string(),
+        });
+    }
+    if let Some(children) = node.children() {
+        for (index, child) in children.iter().enumerate() {
+            let child_path = format!("{path}.children[{index}]");
+            collect_node_edges(spec, child, &child_path, visited_components, edges);
+        }
+    }
+    if let Some(pattern) = node.pattern() {
+        let item_path = format!("{path}.item");
+        collect_node_edges(spec, pattern.item(), &item_path, visited_components, edges);
+    }
+    if let VisualNode::Instance { component, .. } = node
+        && visited_components.insert(component)
+        && let Some((component_index, definition)) = spec
+            .components
+            .iter()
+            .enumerate()
+            .find(|(_, candidate)| candidate.id == *component)
+    {
+        for (index, child) in definition.visual.iter().enumerate() {
+            let child_path = format!("$.components[{component_index}].visual[{index}]");
+            collect_node_edges(spec, child, &child_path, visited_components, edges);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scoped_paths_point_back_at_the_definition() {
+        assert_eq!(
+            rescoped_path("$.visual.nodes[0].transform.x", 2),
+            "$.artboards[2].visual.nodes[0].transform.x"
+        );
+        assert_eq!(rescoped_path("$.artboard.width", 0), "$.artboards[0].width");
+        assert_eq!(rescoped_path("$.motion", 1), "$.artboards[1].motion");
+        assert_eq!(
+            rescoped_path("$.components[0].visual", 1),
+            "$.components[0].visual"
+        );
+    }
+
+    #[test]
+    fn rebased_scene_paths_skip_dropped_asset_children() {
+        assert_eq!(rebased_scene_path("/artboard", 1, 2), "/artboards/1");
+        assert_eq!(
+            rebased_scene_path("/artboard/children/3/children/0", 1, 2),
+            "/artboards/1/children/1/children/0"
+        );
+        assert_eq!(
+            rebased_scene_path("/artboard/animations/0", 2, 2),
+            "/artboards/2/animations/0"
+        );
+    }
+}
@@ -1,5 +1,7 @@
 use super::super::spec::{AuthoringError, AuthoringSpec, LoweredAuthoring};
-use super::{lower_target_graph, motion, rewrite_error_paths, validate_runtime_names};
+use super::{
+    artboards, lower_target_graph, model, motion, rewrite_error_paths, validate_runtime_names,
+};
 
 pub(super) struct AuthoringCompiler<'a> {
     spec: &'a AuthoringSpec,
@@ -21,6 +23,27 @@ impl<'a> AuthoringCompiler<'a> {
         })
     }
 
+    pub(super) fn lower_model(self) -> Result<Self, AuthoringError> {
+        let lowered = model::lower_model(self.spec, self.lowered)
+            .map_err(|error| rewrite_error_paths(self.spec, error))?;
+        Ok(Self {
+            spec: self.spec,
+            lowered,
+        })
+    }
+
+    pub(super) fn lower_artboards(self) -> Result<Self, AuthoringError> {
+        let lowered = artboards::lower_artboards(self.spec, self.lowered)?;
+        Ok(Self {
+            spec: self.spec,
+            lowered,
+        })
+    }
+
+    pub(super) fn into_lowered(self) -> LoweredAuthoring {
+        self.lowered
+    }
+
     pub(super) fn finish(self) -> Result<LoweredAuthoring, AuthoringError> {
         validate_runtime_names(self.lowered)
     }
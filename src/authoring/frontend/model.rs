@@ -0,0 +1,665 @@
+mod converter;
+mod field;
+mod validation;
+
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
+
+use serde_json::{Value, json};
+
+use super::super::lower;
+use super::super::spec::{
+    AuthoringDiagnostic, AuthoringError, AuthoringSpec, LoweredAuthoring, ModelBindingDirection,
+    ModelBindingSpec, ModelPropertySpec, SourceMapEntry,
+};
+use super::motion::{checked_runtime_bindings, invalid_runtime_binding};
+
+use field::{BindingObject, ValueType};
+
+pub(super) use validation::validate_model;
+
+type BindingTargetIndex<'a> = HashMap<&'a str, IndexedBindingTarget>;
+
+enum IndexedBindingTarget {
+    Unique(Vec<BindingObject>),
+    Ambiguous,
+}
+
+struct ResolvedBinding {
+    binding_index: usize,
+    property_index: usize,
+    target_path: String,
+    property_key: u16,
+}
+
+struct EmittedConverters {
+    objects: Vec<Value>,
+    binding_converter_ids: Vec<Option<u64>>,
+    binding_converter_names: Vec<Vec<(String, usize)>>,
+}
+
+pub(super) fn lower_model(
+    spec: &AuthoringSpec,
+    mut lowered: LoweredAuthoring,
+) -> Result<LoweredAuthoring, AuthoringError> {
+    if spec.model.properties.is_empty() && spec.model.bind.is_empty() {
+        return Ok(lowered);
+    }
+
+    let mut resolved = resolve_bindings(spec, &lowered).map_err(AuthoringError::one)?;
+    validate_model_names(spec, &lowered).map_err(AuthoringError::one)?;
+
+    let existing = artboard_children(&lowered.scene);
+    let mut children_len = existing.len();
+    let enum_offset = count_objects(existing, &|object_type| {
+        matches!(
+            object_type,
+            "data_enum" | "data_enum_custom" | "data_enum_system"
+        )
+    });
+    let converter_offset = count_objects(existing, &converter::is_runtime_converter);
+    let view_model_id = count_objects(existing, &|object_type| object_type == "view_model");
+
+    let converters = emit_converters(spec, converter_offset);
+
+    for binding_position in 0..resolved.len() {
+        let binding_index = resolved[binding_position].binding_index;
+        let binding = &spec.model.bind[binding_index];
+        // The source path names the artboard's view model, then the bound
+        // property's index within it.
+        let mut data_bind = json!({
+            "type": "data_bind",
+            "property_key": resolved[binding_position].property_key,
+            "flags": direction_flags(binding.direction),
+            "source_path_ids": [view_model_id, resolved[binding_position].property_index]
+        });
+        if let (Some(converter_id), Some(object)) = (
+            converters.binding_converter_ids[binding_index],
+            data_bind.as_object_mut(),
+        ) {
+            object.insert("converter_id".to_string(), json!(converter_id));
+        }
+        let (container, index) = insert_data_bind(
+            &mut lowered.scene,
+            &resolved[binding_position].target_path,
+            data_bind,
+        )
+        .ok_or_else(|| {
+            AuthoringError::one(AuthoringDiagnostic::new(
+                format!("$.model.bind[{binding_index}].target"),
+                "invalid_binding_target",
+                format!(
+                    "binding target '{}' does not resolve to an insertable scene object",
+                    binding.target
+                ),
+            ))
+        })?;
+        shift_scene_paths(&mut lowered, &container, index);
+        if container == "/artboard/children" {
+            children_len += 1;
+        }
+        for pending in resolved.iter_mut().skip(binding_position + 1) {
+            shift_scene_path(&mut pending.target_path, &container, index);
+        }
+        let data_bind_path = format!("{container}/{index}");
+        let converter_names = &converters.binding_converter_names[binding_index];
+        lowered.source_map.entries.push(SourceMapEntry {
+            authored_id: binding.id.clone(),
+            authored_path: format!("$.model.bind[{binding_index}]"),
+            definition_path: None,
+            runtime_names: converter_names
+                .iter()
+                .map(|(name, _)| name.clone())
+                .collect(),
+            scene_paths: if converter_names.is_empty() {
+                vec![data_bind_path]
+            } else {
+                converter_names
+                    .iter()
+                    .map(|(_, offset)| format!("/artboard/children/{}", children_len + offset))
+                    .collect()
+            },
+        });
+    }
+
+    let mut appended = converters.objects;
+    let mut enums = HashMap::new();
+    for (property_index, property) in spec.model.properties.iter().enumerate() {
+        if let ModelPropertySpec::Enum { id, values, .. } = property {
+            let name = lower::runtime_name(&[spec.artboard.id.clone(), id.clone()], "data_enum");
+            let enum_id = enum_offset + enums.len() as u64;
+            let scene_path = format!("/artboard/children/{}", children_len + appended.len());
+            appended.push(json!({
+                "type": "data_enum",
+                "name": name,
+                "children": values
+                    .iter()
+                    .map(|value| json!({
+                        "type": "data_enum_value",
+                        "key": value,
+                        "value": value
+                    }))
+                    .collect::<Vec<_>>()
+            }));
+            enums.insert(property_index, (enum_id, name, scene_path));
+        }
+    }
+
+    let view_model_name = spec.artboard.id.clone();
+    let view_model_path = format!("/artboard/children/{}", children_len + appended.len());
+    let mut property_objects = Vec::with_capacity(spec.model.properties.len());
+    let mut instance = vec![json!({
+        "type": "view_model_instance",
+        "view_model_id": view_model_id
+    })];
+    for (property_index, property) in spec.model.properties.iter().enumerate() {
+        let mut runtime_names = vec![property.id().to_string()];
+        let mut scene_paths = vec![format!("{view_model_path}/children/{property_index}")];
+        let enum_id = enums
+            .get(&property_index)
+            .map(|(enum_id, enum_name, enum_path)| {
+                runtime_names.push(enum_name.clone());
+                scene_paths.push(enum_path.clone());
+                *enum_id
+            });
+        lowered.source_map.entries.push(SourceMapEntry {
+            authored_id: property.id().to_string(),
+            authored_path: format!("$.model.properties[{property_index}]"),
+            definition_path: None,
+            runtime_names,
+            scene_paths,
+        });
+        let (definition, value) = property_objects_for(property, property_index as u64, enum_id);
+        property_objects.push(definition);
+        instance.push(value);
+    }
+    lowered.source_map.entries.push(SourceMapEntry {
+        authored_id: spec.artboard.id.clone(),
+        authored_path: "$.model".to_string(),
+        definition_path: None,
+        runtime_names: vec![view_model_name.clone()],
+        scene_paths: vec![view_model_path],
+    });
+
+    appended.push(json!({
+        "type": "view_model",
+        "name": view_model_name,
+        "children": property_objects
+    }));
+    appended.extend(instance);
+    if let Some(children) = lowered
+        .scene
+        .pointer_mut("/artboard/children")
+        .and_then(Value::as_array_mut)
+    {
+        children.extend(appended);
+    }
+
+    lower::validate_lowered_scene(&lowered.scene).map_err(|mut diagnostic| {
+        diagnostic.path = "$.model".to_string();
+        AuthoringError::one(diagnostic)
+    })?;
+    Ok(lowered)
+}
+
+fn resolve_bindings(
+    spec: &AuthoringSpec,
+    lowered: &LoweredAuthoring,
+) -> Result<Vec<ResolvedBinding>, AuthoringDiagnostic> {
+    let targets = index_binding_targets(lowered)?;
+    let properties = spec
+        .model
+        .properties
+        .iter()
+        .enumerate()
+        .map(|(property_index, property)| (property.id(), (property_index, property)))
+        .collect::<HashMap<_, _>>();
+    let mut bound_fields = HashSet::new();
+    let mut resolved = Vec::with_capacity(spec.model.bind.len());
+    for (binding_index, binding) in spec.model.bind.iter().enumerate() {
+        let binding_path = format!("$.model.bind[{binding_index}]");
+        let (property_index, property) = properties
+            .get(binding.property.as_str())
+            .copied()
+            .ok_or_else(|| {
+                AuthoringDiagnostic::new(
+                    format!("{binding_path}.property"),
+                    "unknown_model_property",
+                    format!("model property '{}' is not defined", binding.property),
+                )
+            })?;
+        check_binding_types(binding, ValueType::of(property), &binding_path)?;
+
+        let objects = match targets.get(binding.target.as_str()) {
+            None => {
+                return Err(AuthoringDiagnostic::new(
+                    format!("{binding_path}.target"),
+                    "unknown_binding_target",
+                    format!("visual target '{}' is not defined", binding.target),
+                ));
+            }
+            Some(IndexedBindingTarget::Ambiguous) => {
+                return Err(AuthoringDiagnostic::new(
+                    format!("{binding_path}.target"),
+                    "ambiguous_binding_target",
+                    format!(
+                        "visual target '{}' resolves to more than one authored node",
+                        binding.target
+                    ),
+                ));
+            }
+            Some(IndexedBindingTarget::Unique(objects)) => objects,
+        };
+        let mut matches = objects.iter().filter_map(|object| {
+            field::property_key(binding.field, object).map(|key| (object, key))
+        });
+        let (object, property_key) = matches.next().ok_or_else(|| {
+            AuthoringDiagnostic::new(
+                format!("{binding_path}.field"),
+                "unsupported_binding_field",
+                format!(
+                    "visual target '{}' has no object that supports field '{}'",
+                    binding.target,
+                    field::name(binding.field)
+                ),
+            )
+        })?;
+        if matches.next().is_some() {
+            return Err(AuthoringDiagnostic::new(
+                format!("{binding_path}.field"),
+                "ambiguous_binding_field",
+                format!(
+                    "visual target '{}' has more than one object that supports field '{}'",
+                    binding.target,
+                    field::name(binding.field)
+                ),
+            ));
+        }
+        if !bound_fields.insert((object.scene_path.clone(), property_key)) {
+            return Err(AuthoringDiagnostic::new(
+                format!("{binding_path}.field"),
+                "duplicate_binding",
+                format!(
+                    "field '{}' of visual target '{}' is bound more than once",
+                    field::name(binding.field),
+                    binding.target
+                ),
+            ));
+        }
+        resolved.push(ResolvedBinding {
+            binding_index,
+            property_index,
+            target_path: object.scene_path.clone(),
+            property_key,
+        });
+    }
+    Ok(resolved)
+}
+
+fn check_binding_types(
+    binding: &ModelBindingSpec,
+    property_type: ValueType,
+    binding_path: &str,
+) -> Result<(), AuthoringDiagnostic> {
+    let mut value_type = property_type;
+    for (converter_index, converter) in binding.converters.iter().enumerate() {
+        value_type = converter::output_type(converter, value_type).ok_or_else(|| {
+            AuthoringDiagnostic::new(
+                format!("{binding_path}.converters[{converter_index}]"),
+                "incompatible_converter",
+                format!(
+                    "converter '{}' does not accept {} values",
+                    converter::name(converter),
+                    value_type.name()
+                ),
+            )
+        })?;
+    }
+    let field_type = field::value_type(binding.field);
+    if value_type != field_type {
+        return Err(AuthoringDiagnostic::new(
+            format!("{binding_path}.field"),
+            "incompatible_binding_type",
+            format!(
+                "field '{}' expects {} values but the binding produces {} values",
+                field::name(binding.field),
+                field_type.name(),
+                value_type.name()
+            ),
+        ));
+    }
+    Ok(())
+}
+
+fn index_binding_targets(
+    lowered: &LoweredAuthoring,
+) -> Result<BindingTargetIndex<'_>, AuthoringDiagnostic> {
+    let mut targets = HashMap::new();
+    for entry in lowered
+        .source_map
+        .entries
+        .iter()
+        .filter(|entry| entry.authored_path.starts_with("$.visual.nodes["))
+    {
+        let mut objects = Vec::new();
+        for (binding_index, binding) in checked_runtime_bindings(entry)?.into_iter().enumerate() {
+            let object_type = object_type_at(&lowered.scene, binding.scene_path)
+                .ok_or_else(|| invalid_runtime_binding(entry, binding))?;
+            let parent_type = parent_path(binding.scene_path)
+                .and_then(|parent| object_type_at(&lowered.scene, parent))
+                .map(str::to_string);
+            objects.push(BindingObject {
+                scene_path: binding.scene_path.to_string(),
+                object_type: object_type.to_string(),
+                parent_type,
+                is_primary: binding_index == 0,
+            });
+        }
+        match targets.entry(entry.authored_id.as_str()) {
+            Entry::Vacant(slot) => {
+                slot.insert(IndexedBindingTarget::Unique(objects));
+            }
+            Entry::Occupied(mut slot) => {
+                slot.insert(IndexedBindingTarget::Ambiguous);
+            }
+        }
+    }
+    Ok(targets)
+}
+
+fn validate_model_names(
+    spec: &AuthoringSpec,
+    lowered: &LoweredAuthoring,
+) -> Result<(), AuthoringDiagnostic> {
+    let existing = lowered
+        .source_map
+        .entries
+        .iter()
+        .flat_map(|entry| entry.runtime_names.iter().map(String::as_str))
+        .collect::<HashSet<_>>();
+    let names = std::iter::once((spec.artboard.id.as_str(), "$.model".to_string())).chain(
+        spec.model
+            .properties
+            .iter()
+            .enumerate()
+            .map(|(index, property)| (property.id(), format!("$.model.properties[{index}].id"))),
+    );
+    for (name, path) in names {
+        if existing.contains(name) {
+            return Err(AuthoringDiagnostic::new(
+                path,
+                "runtime_name_collision",
+                format!("runtime name '{name}' is generated or declared more than once"),
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn emit_converters(spec: &AuthoringSpec, converter_offset: u64) -> EmittedConverters {
+    let mut emitted = EmittedConverters {
+        objects: Vec::new(),
+        binding_converter_ids: Vec::with_capacity(spec.model.bind.len()),
+        binding_converter_names: Vec::with_capacity(spec.model.bind.len()),
+    };
+    for binding in &spec.model.bind {
+        let segments = [spec.artboard.id.clone(), binding.id.clone()];
+        let mut names = Vec::with_capacity(binding.converters.len() + 1);
+        let mut ids = Vec::with_capacity(binding.converters.len());
+        for (index, converter) in binding.converters.iter().enumerate() {
+            let name = lower::runtime_name(&segments, &format!("converter_{index}"));
+            ids.push(converter_offset + emitted.objects.len() as u64);
+            names.push((name.clone(), emitted.objects.len()));
+            emitted
+                .objects
+                .push(converter::definition(converter, &name));
+        }
+        let converter_id = match ids.as_slice() {
+            [] => None,
+            [single] => Some(*single),
+            _ => {
+                let name = lower::runtime_name(&segments, "converter_group");
+                let group_id = converter_offset + emitted.objects.len() as u64;
+                names.push((name.clone(), emitted.objects.len()));
+                emitted.objects.push(json!({
+                    "type": "data_converter_group",
+                    "name": name,
+                    "children": ids
+                        .iter()
+                        .map(|id| json!({
+                            "type": "data_converter_group_item",
+                            "converter_id": id
+                        }))
+                        .collect::<Vec<_>>()
+                }));
+                Some(group_id)
+            }
+        };
+        emitted.binding_converter_ids.push(converter_id);
+        emitted.binding_converter_names.push(names);
+    }
+    emitted
+}
+
+fn property_objects_for(
+    property: &ModelPropertySpec,
+    property_id: u64,
+    enum_id: Option<u64>,
+) -> (Value, Value) {
+    match property {
+        ModelPropertySpec::Number { id, default } => (
+            json!({"type": "view_model_property_number", "name": id}),
+            json!({
+                "type": "view_model_instance_number",
+                "view_model_property_id": property_id,
+                "value": default
+            }),
+        ),
+        ModelPropertySpec::String { id, default } => (
+            json!({"type": "view_model_property_string", "name": id}),
+            json!({
+                "type": "view_model_instance_string",
+                "view_model_property_id": property_id,
+                "value": default
+            }),
+        ),
+        ModelPropertySpec::Color { id, default } => (
+            json!({"type": "view_model_property_color", "name": id}),
+            json!({
+                "type": "view_model_instance_color",
+                "view_model_property_id": property_id,
+                "value": default
+            }),
+        ),
+        ModelPropertySpec::Boolean { id, default } => (
+            json!({"type": "view_model_property_boolean", "name": id}),
+            json!({
+                "type": "view_model_instance_boolean",
+                "view_model_property_id": property_id,
+                "value": default
+            }),
+        ),
+        ModelPropertySpec::Enum {
+            id,
+            values,
+            default,
+        } => {
+            let value = default
+                .as_ref()
+                .and_then(|default| values.iter().position(|value| value == default))
+                .unwrap_or(0);
+            (
+                json!({
+                    "type": "view_model_property_enum",
+                    "name": id,
+                    "enum_id": enum_id.unwrap_or(0)
+                }),
+                json!({
+                    "type": "view_model_instance_enum",
+                    "view_model_property_id": property_id,
+                    "value": value
+                }),
+            )
+        }
+        ModelPropertySpec::Trigger { id } => (
+            json!({"type": "view_model_property_trigger", "name": id}),
+            json!({
+                "type": "view_model_instance_trigger",
+                "view_model_property_id": property_id,
+                "value": 0
+            }),
+        ),
+        ModelPropertySpec::List { id } => (
+            json!({"type": "view_model_property_list", "name": id}),
+            json!({"type": "view_model_instance_list"}),
+        ),
+    }
+}
+
+fn direction_flags(direction: ModelBindingDirection) -> u64 {
+    match direction {
+        ModelBindingDirection::ToTarget => 0,
+        ModelBindingDirection::ToSource => 1,
+        ModelBindingDirection::TwoWay => 2,
+    }
+}
+
+fn artboard_children(scene: &Value) -> &[Value] {
+    scene
+        .pointer("/artboard/children")
+        .and_then(Value::as_array)
+        .map_or(&[], Vec::as_slice)
+}
+
+fn count_objects(objects: &[Value], matches: &dyn Fn(&str) -> bool) -> u64 {
+    objects
+        .iter()
+        .map(|object| {
+            let own = object
+                .get("type")
+                .and_then(Value::as_str)
+                .is_some_and(matches) as u64;
+            let nested = object
+                .get("children")
+                .and_then(Value::as_array)
+                .map_or(0, |children| count_objects(children, matches));
+            own + nested
+        })
+        .sum()
+}
+
+fn object_type_at<'a>(scene: &'a Value, path: &str) -> Option<&'a str> {
+    scene
+        .pointer(path)
+        .and_then(|object| object.get("type"))
+        .and_then(Value::as_str)
+}
+
+fn parent_path(scene_path: &str) -> Option<&str> {
+    let (container, _) = scene_path.rsplit_once('/')?;
+    container.strip_suffix("/children")
+}
+
+/// Inserts `data_bind` so it immediately follows the bound object in the
+/// flattened object stream: as the first child of containers, or as the
+/// next sibling of leaf objects. Returns the container path and index used.
+fn insert_data_bind(
+    scene: &mut Value,
+    target_path: &str,
+    data_bind: Value,
+) -> Option<(String, usize)> {
+    let target = scene.pointer(target_path)?;
+    let (container, mut index) = if target.get("children").is_some_and(Value::is_array) {
+        (format!("{target_path}/children"), 0)
+    } else {
+        let (container, own_index) = target_path.rsplit_once('/')?;
+        (container.to_string(), own_index.parse::<usize>().ok()? + 1)
+    };
+    let siblings = scene.pointer_mut(&container)?.as_array_mut()?;
+    while siblings
+        .get(index)
+        .and_then(|sibling| sibling.get("type"))
+        .and_then(Value::as_str)
+        == Some("data_bind")
+    {
+        index += 1;
+    }
+    siblings.insert(index, data_bind);
+    Some((container, index))
+}
+
+fn shift_scene_paths(lowered: &mut LoweredAuthoring, container: &str, index: usize) {
+    for entry in &mut lowered.source_map.entries {
+        for scene_path in &mut entry.scene_paths {
+            shift_scene_path(scene_path, container, index);
+        }
+    }
+}
+
+fn shift_scene_path(scene_path: &mut String, container: &str, index: usize) {
+    let Some(remainder) = scene_path
+        .strip_prefix(container)
+        .and_then(|remainder| remainder.strip_prefix('/'))
+    else {
+        return;
+    };
+    let (position, suffix) = match remainder.find('/') {
+        Some(split) => remainder.split_at(split),
+        None => (remainder, ""),
+    };
+    let Ok(position) = position.parse::<usize>() else {
+        return;
+    };
+    if position >= index {
+        *scene_path = format!("{container}/{}{suffix}", position + 1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inserted_siblings_shift_later_scene_paths_only() {
+        let mut path = "/artboard/children/2/children/1/children/0".to_string();
+        shift_scene_path(&mut path, "/artboard/children/2/children", 1);
+        assert_eq!(path, "/artboard/children/2/children/2/children/0");
+
+        let mut earlier = "/artboard/children/2/children/0".to_string();
+        shift_scene_path(&mut earlier, "/artboard/children/2/children", 1);
+        assert_eq!(earlier, "/artboard/children/2/children/0");
+
+        let mut unrelated = "/artboard/children/20".to_string();
+        shift_scene_path(&mut unrelated, "/artboard/children/2/children", 0);
+        assert_eq!(unrelated, "/artboard/children/20");
+    }
+
+    #[test]
+    fn data_binds_follow_leaf_targets_and_lead_container_children() {
+        let mut scene = json!({
+            "artboard": {
+                "children": [{
+                    "type": "shape",
+                    "children": [{"type": "rectangle"}, {"type": "fill"}]
+                }]
+            }
+        });
+
+        let leaf = insert_data_bind(
+            &mut scene,
+            "/artboard/children/0/children/0",
+            json!({"type": "data_bind"}),
+        );
+        assert_eq!(leaf, Some(("/artboard/children/0/children".to_string(), 1)));
+
+        let container = insert_data_bind(
+            &mut scene,
+            "/artboard/children/0",
+            json!({"type": "data_bind"}),
+        );
+        assert_eq!(
+            container,
+            Some(("/artboard/children/0/children".to_string(), 0))
+        );
+    }
+}
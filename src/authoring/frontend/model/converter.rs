@@ -0,0 +1,136 @@
+use serde_json::{Value, json};
+
+use super::super::super::spec::ModelConverterSpec;
+use super::field::ValueType;
+
+pub(super) fn name(converter: &ModelConverterSpec) -> &'static str {
+    match converter {
+        ModelConverterSpec::Add { .. } => "add",
+        ModelConverterSpec::Subtract { .. } => "subtract",
+        ModelConverterSpec::Multiply { .. } => "multiply",
+        ModelConverterSpec::Divide { .. } => "divide",
+        ModelConverterSpec::Round { .. } => "round",
+        ModelConverterSpec::RangeMap { .. } => "range_map",
+        ModelConverterSpec::DegreesToRadians => "degrees_to_radians",
+        ModelConverterSpec::Negate => "negate",
+        ModelConverterSpec::ToString { .. } => "to_string",
+    }
+}
+
+pub(super) fn output_type(converter: &ModelConverterSpec, input: ValueType) -> Option<ValueType> {
+    match converter {
+        ModelConverterSpec::Add { .. }
+        | ModelConverterSpec::Subtract { .. }
+        | ModelConverterSpec::Multiply { .. }
+        | ModelConverterSpec::Divide { .. }
+        | ModelConverterSpec::Round { .. }
+        | ModelConverterSpec::RangeMap { .. }
+        | ModelConverterSpec::DegreesToRadians => {
+            (input == ValueType::Number).then_some(ValueType::Number)
+        }
+        ModelConverterSpec::Negate => (input == ValueType::Boolean).then_some(ValueType::Boolean),
+        ModelConverterSpec::ToString { .. } => matches!(
+            input,
+            ValueType::Number
+                | ValueType::String
+                | ValueType::Color
+                | ValueType::Boolean
+                | ValueType::Enum
+        )
+        .then_some(ValueType::String),
+    }
+}
+
+pub(super) fn definition(converter: &ModelConverterSpec, runtime_name: &str) -> Value {
+    match converter {
+        ModelConverterSpec::Add { value } => operation(runtime_name, 0, *value),
+        ModelConverterSpec::Subtract { value } => operation(runtime_name, 1, *value),
+        ModelConverterSpec::Multiply { value } => operation(runtime_name, 2, *value),
+        ModelConverterSpec::Divide { value } => operation(runtime_name, 3, *value),
+        ModelConverterSpec::Round { decimals } => json!({
+            "type": "data_converter_rounder",
+            "name": runtime_name,
+            "decimals": decimals
+        }),
+        ModelConverterSpec::RangeMap {
+            min_input,
+            max_input,
+            min_output,
+            max_output,
+        } => json!({
+            "type": "data_converter_range_mapper",
+            "name": runtime_name,
+            "min_input": min_input,
+            "max_input": max_input,
+            "min_output": min_output,
+            "max_output": max_output
+        }),
+        ModelConverterSpec::DegreesToRadians => json!({
+            "type": "data_converter_system_degs_to_rads",
+            "name": runtime_name
+        }),
+        ModelConverterSpec::Negate => json!({
+            "type": "data_converter_boolean_negate",
+            "name": runtime_name
+        }),
+        ModelConverterSpec::ToString { decimals } => {
+            let mut definition = json!({
+                "type": "data_converter_to_string",
+                "name": runtime_name
+            });
+            if let (Some(decimals), Some(object)) = (decimals, definition.as_object_mut()) {
+                object.insert("decimals".to_string(), json!(decimals));
+            }
+            definition
+        }
+    }
+}
+
+fn operation(runtime_name: &str, operation_type: u64, value: f64) -> Value {
+    json!({
+        "type": "data_converter_operation_value",
+        "name": runtime_name,
+        "operation_type": operation_type,
+        "operation_value": value
+    })
+}
+
+pub(super) fn is_runtime_converter(object_type: &str) -> bool {
+    object_type == "scripted_data_converter"
+        || (object_type.starts_with("data_converter_")
+            && object_type != "data_converter_group_item")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numeric_converters_reject_non_numeric_inputs() {
+        let multiply = ModelConverterSpec::Multiply { value: 2.0 };
+
+        assert_eq!(
+            output_type(&multiply, ValueType::Number),
+            Some(ValueType::Number)
+        );
+        assert_eq!(output_type(&multiply, ValueType::Color), None);
+        assert_eq!(
+            output_type(
+                &ModelConverterSpec::ToString { decimals: None },
+                ValueType::Enum
+            ),
+            Some(ValueType::String)
+        );
+        assert_eq!(
+            output_type(&ModelConverterSpec::Negate, ValueType::Number),
+            None
+        );
+    }
+
+    #[test]
+    fn group_items_are_not_counted_as_runtime_converters() {
+        assert!(is_runtime_converter("data_converter_rounder"));
+        assert!(is_runtime_converter("data_converter_group"));
+        assert!(!is_runtime_converter("data_converter_group_item"));
+    }
+}
@@ -0,0 +1,179 @@
+use crate::builder::property_key_for_object;
+use crate::objects::core::property_keys;
+
+use super::super::super::spec::{ModelBindingField, ModelPropertySpec};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum ValueType {
+    Number,
+    String,
+    Color,
+    Boolean,
+    Enum,
+    Trigger,
+    List,
+}
+
+impl ValueType {
+    pub(super) fn of(property: &ModelPropertySpec) -> Self {
+        match property {
+            ModelPropertySpec::Number { .. } => Self::Number,
+            ModelPropertySpec::String { .. } => Self::String,
+            ModelPropertySpec::Color { .. } => Self::Color,
+            ModelPropertySpec::Boolean { .. } => Self::Boolean,
+            ModelPropertySpec::Enum { .. } => Self::Enum,
+            ModelPropertySpec::Trigger { .. } => Self::Trigger,
+            ModelPropertySpec::List { .. } => Self::List,
+        }
+    }
+
+    pub(super) fn name(self) -> &'static str {
+        match self {
+            Self::Number => "number",
+            Self::String => "string",
+            Self::Color => "color",
+            Self::Boolean => "boolean",
+            Self::Enum => "enum",
+            Self::Trigger => "trigger",
+            Self::List => "list",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(super) struct BindingObject {
+    pub(super) scene_path: String,
+    pub(super) object_type: String,
+    pub(super) parent_type: Option<String>,
+    pub(super) is_primary: bool,
+}
+
+pub(super) fn name(field: ModelBindingField) -> &'static str {
+    match field {
+        ModelBindingField::TransformX => "transform.x",
+        ModelBindingField::TransformY => "transform.y",
+        ModelBindingField::TransformRotation => "transform.rotation",
+        ModelBindingField::TransformScaleX => "transform.scale_x",
+        ModelBindingField::TransformScaleY => "transform.scale_y",
+        ModelBindingField::Opacity => "opacity",
+        ModelBindingField::Width => "width",
+        ModelBindingField::Height => "height",
+        ModelBindingField::Fill => "fill",
+        ModelBindingField::Stroke => "stroke",
+        ModelBindingField::StrokeWidth => "stroke.width",
+        ModelBindingField::Text => "text",
+    }
+}
+
+pub(super) fn value_type(field: ModelBindingField) -> ValueType {
+    match field {
+        ModelBindingField::Fill | ModelBindingField::Stroke => ValueType::Color,
+        ModelBindingField::Text => ValueType::String,
+        _ => ValueType::Number,
+    }
+}
+
+/// Returns the runtime property key the field writes on `object`, or `None`
+/// when the object does not carry that field.
+pub(super) fn property_key(field: ModelBindingField, object: &BindingObject) -> Option<u16> {
+    let object_type = object.object_type.as_str();
+    let parent_type = object.parent_type.as_deref();
+    match field {
+        ModelBindingField::TransformX
+        | ModelBindingField::TransformY
+        | ModelBindingField::TransformRotation
+        | ModelBindingField::TransformScaleX
+        | ModelBindingField::TransformScaleY
+        | ModelBindingField::Opacity => object
+            .is_primary
+            .then(|| property_key_for_object(object_type, animatable_name(field)))
+            .flatten(),
+        ModelBindingField::Width | ModelBindingField::Height => matches!(
+            object_type,
+            "ellipse" | "polygon" | "rectangle" | "star" | "triangle"
+        )
+        .then(|| property_key_for_object(object_type, animatable_name(field)))
+        .flatten(),
+        ModelBindingField::Fill => (object_type == "solid_color" && parent_type == Some("fill"))
+            .then(|| property_key_for_object(object_type, "color"))
+            .flatten(),
+        ModelBindingField::Stroke => (object_type == "solid_color"
+            && parent_type == Some("stroke"))
+        .then(|| property_key_for_object(object_type, "color"))
+        .flatten(),
+        ModelBindingField::StrokeWidth => {
+            (object_type == "stroke").then_some(property_keys::STROKE_THICKNESS)
+        }
+        ModelBindingField::Text => (object_type == "text_value_run")
+            .then(|| property_key_for_object(object_type, "text"))
+            .flatten(),
+    }
+}
+
+fn animatable_name(field: ModelBindingField) -> &'static str {
+    match field {
+        ModelBindingField::TransformX => "x",
+        ModelBindingField::TransformY => "y",
+        ModelBindingField::TransformRotation => "rotation",
+        ModelBindingField::TransformScaleX => "scale_x",
+        ModelBindingField::TransformScaleY => "scale_y",
+        ModelBindingField::Opacity => "opacity",
+        ModelBindingField::Width => "width",
+        ModelBindingField::Height => "height",
+        ModelBindingField::Fill | ModelBindingField::Stroke => "color",
+        ModelBindingField::StrokeWidth => "thickness",
+        ModelBindingField::Text => "text",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn object(object_type: &str, parent_type: Option<&str>, is_primary: bool) -> BindingObject {
+        BindingObject {
+            scene_path: "/artboard/children/0".to_string(),
+            object_type: object_type.to_string(),
+            parent_type: parent_type.map(str::to_string),
+            is_primary,
+        }
+    }
+
+    #[test]
+    fn transform_fields_only_bind_the_primary_object() {
+        assert!(
+            property_key(ModelBindingField::TransformX, &object("shape", None, true)).is_some()
+        );
+        assert!(
+            property_key(
+                ModelBindingField::TransformX,
+                &object("rectangle", Some("shape"), false)
+            )
+            .is_none()
+        );
+        assert!(
+            property_key(
+                ModelBindingField::Width,
+                &object("rectangle", Some("shape"), false)
+            )
+            .is_some()
+        );
+    }
+
+    #[test]
+    fn paint_fields_follow_the_owning_paint_container() {
+        let fill_color = object("solid_color", Some("fill"), false);
+        let stroke_color = object("solid_color", Some("stroke"), false);
+
+        assert!(property_key(ModelBindingField::Fill, &fill_color).is_some());
+        assert!(property_key(ModelBindingField::Fill, &stroke_color).is_none());
+        assert!(property_key(ModelBindingField::Stroke, &stroke_color).is_some());
+        assert_eq!(
+            property_key(
+                ModelBindingField::StrokeWidth,
+                &object("stroke", None, false)
+            ),
+            Some(property_keys::STROKE_THICKNESS)
+        );
+    }
+}
@@ -0,0 +1,226 @@
+use std::collections::HashSet;
+
+use crate::builder::parse_color;
+
+use super::super::super::expression::validate_scene_number;
+use super::super::super::spec::{
+    AuthoringDiagnostic, ModelConverterSpec, ModelPropertySpec, ModelSection,
+};
+use super::super::{is_authored_map_key, validate_id};
+
+const MAX_PROPERTIES: usize = 1_000;
+const MAX_BINDINGS: usize = 1_000;
+const MAX_ENUM_VALUES: usize = 1_000;
+const MAX_CONVERTERS: usize = 16;
+const MAX_DECIMALS: u64 = 10;
+
+pub(in crate::authoring::frontend) fn validate_model(
+    model: &ModelSection,
+) -> Vec<AuthoringDiagnostic> {
+    let mut diagnostics = Vec::new();
+    if model.properties.len() > MAX_PROPERTIES {
+        diagnostics.push(AuthoringDiagnostic::new(
+            "$.model.properties",
+            "model_property_limit",
+            format!("model property count must not exceed {MAX_PROPERTIES}"),
+        ));
+    }
+
+    let mut property_ids = HashSet::new();
+    for (property_index, property) in model.properties.iter().enumerate() {
+        let property_path = format!("$.model.properties[{property_index}]");
+        validate_property(property, &property_path, &mut diagnostics);
+        if !property_ids.insert(property.id()) {
+            diagnostics.push(AuthoringDiagnostic::new(
+                format!("{property_path}.id"),
+                "duplicate_model_property",
+                format!("model property id '{}' is duplicated", property.id()),
+            ));
+        }
+    }
+
+    if model.bind.len() > MAX_BINDINGS {
+        diagnostics.push(AuthoringDiagnostic::new(
+            "$.model.bind",
+            "model_binding_limit",
+            format!("model binding count must not exceed {MAX_BINDINGS}"),
+        ));
+    }
+    let mut binding_ids = HashSet::new();
+    for (binding_index, binding) in model.bind.iter().enumerate() {
+        let binding_path = format!("$.model.bind[{binding_index}]");
+        validate_id(&binding.id, &format!("{binding_path}.id"), &mut diagnostics);
+        if !binding_ids.insert(binding.id.as_str()) {
+            diagnostics.push(AuthoringDiagnostic::new(
+                format!("{binding_path}.id"),
+                "duplicate_model_binding",
+                format!("model binding id '{}' is duplicated", binding.id),
+            ));
+        }
+        if !property_ids.contains(binding.property.as_str()) {
+            diagnostics.push(AuthoringDiagnostic::new(
+                format!("{binding_path}.property"),
+                "unknown_model_property",
+                format!("model property '{}' is not defined", binding.property),
+            ));
+        }
+        if binding.target.trim().is_empty() {
+            diagnostics.push(AuthoringDiagnostic::new(
+                format!("{binding_path}.target"),
+                "invalid_binding_target",
+                "binding target must not be empty",
+            ));
+        }
+        if binding.converters.len() > MAX_CONVERTERS {
+            diagnostics.push(AuthoringDiagnostic::new(
+                format!("{binding_path}.converters"),
+                "model_converter_limit",
+                format!("binding converter count must not exceed {MAX_CONVERTERS}"),
+            ));
+        }
+        for (converter_index, converter) in binding.converters.iter().enumerate() {
+            validate_converter(
+                converter,
+                &format!("{binding_path}.converters[{converter_index}]"),
+                &mut diagnostics,
+            );
+        }
+    }
+
+    diagnostics
+}
+
+fn validate_property(
+    property: &ModelPropertySpec,
+    path: &str,
+    diagnostics: &mut Vec<AuthoringDiagnostic>,
+) {
+    if !is_authored_map_key(property.id()) {
+        diagnostics.push(AuthoringDiagnostic::new(
+            format!("{path}.id"),
+            "invalid_model_property",
+            format!(
+                "model property id '{}' must contain only ASCII letters, digits, '_' or '-'",
+                property.id()
+            ),
+        ));
+    }
+    match property {
+        ModelPropertySpec::Number { default, .. } => {
+            validate_number(*default, &format!("{path}.default"), diagnostics);
+        }
+        ModelPropertySpec::Color { default, .. } => {
+            if let Err(message) = parse_color(default) {
+                diagnostics.push(AuthoringDiagnostic::new(
+                    format!("{path}.default"),
+                    "invalid_color",
+                    message,
+                ));
+            }
+        }
+        ModelPropertySpec::Enum {
+            values, default, ..
+        } => {
+            if !(1..=MAX_ENUM_VALUES).contains(&values.len()) {
+                diagnostics.push(AuthoringDiagnostic::new(
+                    format!("{path}.values"),
+                    "model_enum_value_limit",
+                    format!("enum value count must be between 1 and {MAX_ENUM_VALUES}"),
+                ));
+            }
+            let mut seen = HashSet::new();
+            for (value_index, value) in values.iter().enumerate() {
+                let value_path = format!("{path}.values[{value_index}]");
+                if value.trim().is_empty() {
+                    diagnostics.push(AuthoringDiagnostic::new(
+                        value_path,
+                        "invalid_enum_value",
+                        "enum values must not be empty",
+                    ));
+                } else if !seen.insert(value.as_str()) {
+                    diagnostics.push(AuthoringDiagnostic::new(
+                        value_path,
+                        "duplicate_enum_value",
+                        format!("enum value '{value}' is duplicated"),
+                    ));
+                }
+            }
+            if let Some(default) = default
+                && !values.contains(default)
+            {
+                diagnostics.push(AuthoringDiagnostic::new(
+                    format!("{path}.default"),
+                    "unknown_enum_value",
+                    format!("enum default '{default}' is not one of the declared values"),
+                ));
+            }
+        }
+        ModelPropertySpec::String { .. }
+        | ModelPropertySpec::Boolean { .. }
+        | ModelPropertySpec::Trigger { .. }
+        | ModelPropertySpec::List { .. } => {}
+    }
+}
+
+fn validate_converter(
+    converter: &ModelConverterSpec,
+    path: &str,
+    diagnostics: &mut Vec<AuthoringDiagnostic>,
+) {
+    match converter {
+        ModelConverterSpec::Add { value }
+        | ModelConverterSpec::Subtract { value }
+        | ModelConverterSpec::Multiply { value } => {
+            validate_number(*value, &format!("{path}.value"), diagnostics)
+        }
+        ModelConverterSpec::Divide { value } => {
+            validate_number(*value, &format!("{path}.value"), diagnostics);
+            if *value == 0.0 {
+                diagnostics.push(AuthoringDiagnostic::new(
+                    format!("{path}.value"),
+                    "division_by_zero",
+                    "divide converters require a non-zero value",
+                ));
+            }
+        }
+        ModelConverterSpec::RangeMap {
+            min_input,
+            max_input,
+            min_output,
+            max_output,
+        } => {
+            validate_number(*min_input, &format!("{path}.min_input"), diagnostics);
+            validate_number(*max_input, &format!("{path}.max_input"), diagnostics);
+            validate_number(*min_output, &format!("{path}.min_output"), diagnostics);
+            validate_number(*max_output, &format!("{path}.max_output"), diagnostics);
+            if min_input == max_input {
+                diagnostics.push(AuthoringDiagnostic::new(
+                    format!("{path}.max_input"),
+                    "invalid_range",
+                    "range_map input bounds must differ",
+                ));
+            }
+        }
+        ModelConverterSpec::Round { decimals }
+        | ModelConverterSpec::ToString {
+            decimals: Some(decimals),
+        } => {
+            if *decimals > MAX_DECIMALS {
+                diagnostics.push(AuthoringDiagnostic::new(
+                    format!("{path}.decimals"),
+                    "invalid_decimals",
+                    format!("converter decimals must not exceed {MAX_DECIMALS}"),
+                ));
+            }
+        }
+        ModelConverterSpec::DegreesToRadians
+        | ModelConverterSpec::Negate
+        | ModelConverterSpec::ToString { decimals: None } => {}
+    }
+}
+
+fn validate_number(value: f64, path: &str, diagnostics: &mut Vec<AuthoringDiagnostic>) {
+    if let Err(diagnostic) = validate_scene_number(value, path) {
+        diagnostics.push(diagnostic);
+    }
+}
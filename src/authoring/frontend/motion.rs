@@ -1,26 +1,35 @@
 mod easing;
+mod morph;
 mod property;
+mod spring;
+mod stagger;
 mod timing;
 mod validation;
 
+use std::borrow::Cow;
 use std::collections::hash_map::Entry;
 use std::collections::{BTreeMap, HashMap, HashSet};
 
 use serde_json::{Value, json};
 
+use super::super::limits::MAX_EXPANDED_MOTION_KEYFRAMES;
 use super::super::lower;
 use super::super::spec::{
-    AuthoringDiagnostic, AuthoringError, AuthoringSpec, LoweredAuthoring, MotionInterpolation,
-    MotionLoop, RawSceneFragment, SourceMapEntry,
+    AuthoringDiagnostic, AuthoringError, AuthoringSpec, EventSpec, LoweredAuthoring,
+    MotionInterpolation, MotionLoop, PoseSpec, RawSceneFragment, SourceMapEntry,
 };
 
-use easing::{EasingEmission, ResolvedEasing};
+use easing::{EasingCurve, EasingEmission, ResolvedEasing};
+use morph::MorphPlan;
 use property::{MotionRuntimeObject, PoseValues};
 use timing::evaluate_frame_value;
 
 pub(super) use validation::validate_motion;
 
 type MotionTargetIndex<'a> = HashMap<&'a str, IndexedMotionTarget<'a>>;
+/// Per sorted keyframe: the spring easing and the `(frame offset, progress)`
+/// keyframes baked between it and the next keyframe.
+type BakedSprings = Vec<Option<(usize, Vec<(u64, f64)>)>>;
 
 enum IndexedMotionTarget<'a> {
     Unique(Vec<MotionRuntimeObject<'a>>),
@@ -28,9 +37,9 @@ enum IndexedMotionTarget<'a> {
 }
 
 #[derive(Clone, Copy, Debug)]
-struct RuntimeBinding<'a> {
+pub(super) struct RuntimeBinding<'a> {
     runtime_name: &'a str,
-    scene_path: &'a str,
+    pub(super) scene_path: &'a str,
 }
 
 struct ResolvedFrame {
@@ -41,9 +50,18 @@ struct ResolvedFrame {
     easing_index: Option<usize>,
 }
 
+/// One replay of a track: the whole track, or one copy of a staggered
+/// pattern with its poses resolved against that copy.
+struct TrackCopy<'a> {
+    authored_prefix: Option<String>,
+    offset: u64,
+    poses: Vec<Cow<'a, PoseValues>>,
+}
+
 struct LoweredTracks {
     fragments: Vec<RawSceneFragment>,
     easing_emissions: Vec<EasingEmission>,
+    keyframe_sources: Vec<SourceMapEntry>,
 }
 
 pub(super) fn lower_motion(
@@ -51,7 +69,16 @@ pub(super) fn lower_motion(
     lowered: LoweredAuthoring,
 ) -> Result<LoweredAuthoring, AuthoringError> {
     let easings = easing::resolve(spec).map_err(AuthoringError::one)?;
-    let poses = resolve_poses(spec, &lowered).map_err(AuthoringError::one)?;
+    let mut morphs = morph::plan(spec, &lowered).map_err(AuthoringError::one)?;
+    let lowered = if morphs.is_empty() {
+        lowered
+    } else {
+        let lowered = super::lower_target_graph_with(spec, morphs.resolutions())?;
+        morphs.align(&lowered).map_err(AuthoringError::one)?;
+        lowered
+    };
+    let motion_targets = index_motion_targets(&lowered).map_err(AuthoringError::one)?;
+    let poses = resolve_poses(spec, &motion_targets, &morphs).map_err(AuthoringError::one)?;
     if spec.motion.tracks.is_empty() {
         return Ok(lowered);
     }
@@ -59,7 +86,9 @@ pub(super) fn lower_motion(
     let LoweredTracks {
         fragments,
         easing_emissions,
-    } = lower_tracks(spec, &poses, &easings).map_err(AuthoringError::one)?;
+        keyframe_sources,
+    } = lower_tracks(spec, &lowered, &motion_targets, &poses, &easings)
+        .map_err(AuthoringError::one)?;
     let typed_count = fragments.len();
 
     let mut expanded = spec.clone();
@@ -71,42 +100,95 @@ pub(super) fn lower_motion(
         .chain(spec.motion.raw_animations.iter().cloned())
         .collect();
 
-    let mut lowered = lower::lower_authoring(&expanded)
+    let mut lowered = lower::lower_authoring_with(&expanded, morphs.resolutions())
         .map_err(|error| rewrite_motion_error_paths(error, typed_count))?;
     rewrite_motion_source_paths(&mut lowered, typed_count);
     easing::append_source_entries(&mut lowered, easing_emissions);
+    lowered.source_map.entries.extend(keyframe_sources);
     Ok(lowered)
 }
 
+/// Resolves every pose against absolute visual targets, except poses only
+/// staggered tracks use: those resolve once per pattern copy instead.
 fn resolve_poses(
     spec: &AuthoringSpec,
-    lowered: &LoweredAuthoring,
-) -> Result<Vec<PoseValues>, AuthoringDiagnostic> {
-    let motion_targets = index_motion_targets(lowered)?;
+    motion_targets: &MotionTargetIndex<'_>,
+    morphs: &MorphPlan,
+) -> Result<Vec<Option<PoseValues>>, AuthoringDiagnostic> {
+    let absolute_poses = spec
+        .motion
+        .tracks
+        .iter()
+        .filter(|track| track.stagger.is_none())
+        .flat_map(|track| {
+            track
+                .keyframes
+                .iter()
+                .map(|keyframe| keyframe.pose.as_str())
+        })
+        .collect::<HashSet<_>>();
+    let staggered_poses = spec
+        .motion
+        .tracks
+        .iter()
+        .filter(|track| track.stagger.is_some())
+        .flat_map(|track| {
+            track
+                .keyframes
+                .iter()
+                .map(|keyframe| keyframe.pose.as_str())
+        })
+        .collect::<HashSet<_>>();
     let mut poses = Vec::with_capacity(spec.motion.poses.len());
     for (pose_index, pose) in spec.motion.poses.iter().enumerate() {
-        let pose_path = format!("$.motion.poses[{pose_index}]");
-        let mut values = BTreeMap::new();
-        for (target_index, target) in pose.targets.iter().enumerate() {
-            let target_path = format!("{pose_path}.targets[{target_index}]");
-            let resolved_targets = resolve_motion_targets(
-                &motion_targets,
-                &target.target,
-                &format!("{target_path}.target"),
-            )?;
-            property::resolve_target_values(
-                spec,
-                target,
-                &target_path,
-                resolved_targets,
-                &mut values,
-            )?;
+        let id = pose.id.as_str();
+        if staggered_poses.contains(id) && !absolute_poses.contains(id) {
+            poses.push(None);
+            continue;
         }
-        poses.push(values);
+        poses.push(Some(resolve_pose(
+            spec,
+            motion_targets,
+            morphs,
+            pose,
+            pose_index,
+            None,
+        )?));
     }
     Ok(poses)
 }
 
+fn resolve_pose(
+    spec: &AuthoringSpec,
+    motion_targets: &MotionTargetIndex<'_>,
+    morphs: &MorphPlan,
+    pose: &PoseSpec,
+    pose_index: usize,
+    copy_prefix: Option<&str>,
+) -> Result<PoseValues, AuthoringDiagnostic> {
+    let pose_path = format!("$.motion.poses[{pose_index}]");
+    let mut values = BTreeMap::new();
+    for (target_index, target) in pose.targets.iter().enumerate() {
+        let target_path = format!("{pose_path}.targets[{target_index}]");
+        let target_id = match copy_prefix {
+            Some(prefix) => Cow::Owned(format!("{prefix}/{}", target.target)),
+            None => Cow::Borrowed(target.target.as_str()),
+        };
+        let resolved_targets =
+            resolve_motion_targets(motion_targets, &target_id, &format!("{target_path}.target"))?;
+        property::resolve_target_values(spec, target, &target_path, resolved_targets, &mut values)?;
+        morph::resolve_outline_values(
+            morphs,
+            pose_index,
+            target_index,
+            target,
+            resolved_targets,
+            &mut values,
+        )?;
+    }
+    Ok(values)
+}
+
 fn index_motion_targets(
     lowered: &LoweredAuthoring,
 ) -> Result<MotionTargetIndex<'_>, AuthoringDiagnostic> {
@@ -125,11 +207,14 @@ fn index_motion_targets(
                 .and_then(|object| object.get("type"))
                 .and_then(Value::as_str)
                 .ok_or_else(|| invalid_runtime_binding(entry, binding))?;
-            target.push(MotionRuntimeObject::from_binding(
-                binding.runtime_name,
-                object_type,
-                binding_index == 0,
-            ));
+            target.push(
+                MotionRuntimeObject::from_binding(
+                    binding.runtime_name,
+                    object_type,
+                    binding_index == 0,
+                )
+                .placed_in(&lowered.scene, binding.scene_path),
+            );
         }
         let indexed = IndexedMotionTarget::Unique(target);
         match targets.entry(entry.authored_id.as_str()) {
@@ -144,7 +229,7 @@ fn index_motion_targets(
     Ok(targets)
 }
 
-fn checked_runtime_bindings(
+pub(super) fn checked_runtime_bindings(
     entry: &SourceMapEntry,
 ) -> Result<Vec<RuntimeBinding<'_>>, AuthoringDiagnostic> {
     if entry.runtime_names.is_empty() {
@@ -180,7 +265,7 @@ fn invalid_binding_cardinality(entry: &SourceMapEntry) -> AuthoringDiagnostic {
     )
 }
 
-fn invalid_runtime_binding(
+pub(super) fn invalid_runtime_binding(
     entry: &SourceMapEntry,
     binding: RuntimeBinding<'_>,
 ) -> AuthoringDiagnostic {
@@ -223,7 +308,9 @@ fn resolve_motion_targets<'index, 'scene>(
 
 fn lower_tracks(
     spec: &AuthoringSpec,
-    poses: &[PoseValues],
+    lowered: &LoweredAuthoring,
+    motion_targets: &MotionTargetIndex<'_>,
+    poses: &[Option<PoseValues>],
     easings: &[ResolvedEasing],
 ) -> Result<LoweredTracks, AuthoringDiagnostic> {
     let pose_lookup = spec
@@ -240,6 +327,14 @@ fn lower_tracks(
         .collect::<HashMap<_, _>>();
     let mut fragments = Vec::with_capacity(spec.motion.tracks.len());
     let mut easing_emissions = easings.iter().map(EasingEmission::new).collect::<Vec<_>>();
+    let mut keyframe_sources = Vec::new();
+    let mut expanded_keyframes = 0_u64;
+    let event_ids = spec
+        .behavior
+        .events
+        .iter()
+        .map(EventSpec::id)
+        .collect::<HashSet<_>>();
 
     for (track_index, track) in spec.motion.tracks.iter().enumerate() {
         let track_path = format!("$.motion.tracks[{track_index}]");
@@ -324,24 +419,140 @@ fn lower_tracks(
             }
         }
 
-        let first = frames.first().ok_or_else(|| {
-            AuthoringDiagnostic::new(
+        let mut event_frames = BTreeMap::<&str, Vec<(u64, usize)>>::new();
+        for (event_index, track_event) in track.events.iter().enumerate() {
+            let event_path = format!("{track_path}.events[{event_index}]");
+            let frame = evaluate_frame_value(
+                &track_event.frame,
+                &format!("{event_path}.frame"),
+                &spec.parameters,
+                "invalid_frame",
+                "motion frames must be non-negative whole frame counts",
+            )?;
+            if frame > duration {
+                return Err(AuthoringDiagnostic::new(
+                    format!("{event_path}.frame"),
+                    "frame_out_of_range",
+                    format!("motion frame {frame} exceeds duration {duration}"),
+                ));
+            }
+            if !event_ids.contains(track_event.event.as_str()) {
+                return Err(AuthoringDiagnostic::new(
+                    format!("{event_path}.event"),
+                    "unknown_event",
+                    format!(
+                        "event '{}' is not declared in behavior.events",
+                        track_event.event
+                    ),
+                ));
+            }
+            let fired = event_frames.entry(track_event.event.as_str()).or_default();
+            if fired.iter().any(|(fired_frame, _)| *fired_frame == frame) {
+                return Err(AuthoringDiagnostic::new(
+                    format!("{event_path}.frame"),
+                    "duplicate_frame",
+                    format!(
+                        "event '{}' already fires at frame {frame}",
+                        track_event.event
+                    ),
+                ));
+            }
+            fired.push((frame, event_index));
+        }
+
+        if frames.is_empty() {
+            return Err(AuthoringDiagnostic::new(
                 format!("{track_path}.keyframes"),
                 "motion_keyframe_limit",
                 "motion tracks require at least two keyframes",
-            )
-        })?;
-        let expected_pose = poses
-            .get(first.pose_index)
-            .ok_or_else(|| pose_shape_mismatch(&track_path, first.authored_index))?;
-        for frame in frames.iter().skip(1) {
-            let pose = poses
-                .get(frame.pose_index)
-                .ok_or_else(|| pose_shape_mismatch(&track_path, frame.authored_index))?;
-            if !expected_pose.keys().eq(pose.keys()) {
-                return Err(pose_shape_mismatch(&track_path, frame.authored_index));
+            ));
+        }
+        let copies = match &track.stagger {
+            None => {
+                let copy_poses = frames
+                    .iter()
+                    .map(|frame| {
+                        poses
+                            .get(frame.pose_index)
+                            .and_then(Option::as_ref)
+                            .map(Cow::Borrowed)
+                            .ok_or_else(|| pose_shape_mismatch(&track_path, frame.authored_index))
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                vec![TrackCopy {
+                    authored_prefix: None,
+                    offset: 0,
+                    poses: copy_poses,
+                }]
+            }
+            Some(stagger) => {
+                let stagger_path = format!("{track_path}.stagger");
+                let copies = stagger::resolve_copies(spec, lowered, stagger, &stagger_path)?;
+                let property_count = spec
+                    .motion
+                    .poses
+                    .get(frames[0].pose_index)
+                    .map(|pose| pose.targets.iter().map(property::count).sum::<u64>())
+                    .unwrap_or(0);
+                expanded_keyframes = expanded_keyframes.saturating_add(
+                    property_count
+                        .saturating_mul(frames.len() as u64)
+                        .saturating_mul(copies.len() as u64),
+                );
+                if expanded_keyframes > MAX_EXPANDED_MOTION_KEYFRAMES {
+                    return Err(AuthoringDiagnostic::new(
+                        stagger_path,
+                        "motion_keyframe_expansion_limit",
+                        format!(
+                            "expanded motion keyframe count must not exceed {MAX_EXPANDED_MOTION_KEYFRAMES}"
+                        ),
+                    ));
+                }
+                let mut resolved = Vec::with_capacity(copies.len());
+                for copy in copies {
+                    let mut copy_poses = Vec::with_capacity(frames.len());
+                    for frame in &frames {
+                        let pose = spec.motion.poses.get(frame.pose_index).ok_or_else(|| {
+                            pose_shape_mismatch(&track_path, frame.authored_index)
+                        })?;
+                        // Staggered poses cannot morph outlines.
+                        copy_poses.push(Cow::Owned(resolve_pose(
+                            spec,
+                            motion_targets,
+                            &MorphPlan::default(),
+                            pose,
+                            frame.pose_index,
+                            Some(&copy.prefix),
+                        )?));
+                    }
+                    resolved.push(TrackCopy {
+                        authored_prefix: Some(copy.prefix),
+                        offset: copy.offset,
+                        poses: copy_poses,
+                    });
+                }
+                resolved
+            }
+        };
+        for copy in &copies {
+            for (frame, pose) in frames.iter().zip(&copy.poses).skip(1) {
+                if !copy.poses[0].keys().eq(pose.keys()) {
+                    return Err(pose_shape_mismatch(&track_path, frame.authored_index));
+                }
             }
         }
+        let duration = copies
+            .iter()
+            .map(|copy| copy.offset)
+            .max()
+            .and_then(|offset| duration.checked_add(offset))
+            .ok_or_else(|| {
+                AuthoringDiagnostic::new(
+                    format!("{track_path}.stagger.offset"),
+                    "invalid_stagger_offset",
+                    "staggered motion duration overflows",
+                )
+            })?;
 
         let referenced_easings = frames
             .iter()
@@ -349,50 +560,131 @@ fn lower_tracks(
             .collect::<HashSet<_>>();
         let mut interpolators = Vec::new();
         for (easing_index, easing) in easings.iter().enumerate() {
-            if referenced_easings.contains(&easing_index) {
+            if referenced_easings.contains(&easing_index)
+                && let Some(definition) = easing::definition(easing)
+            {
                 let interpolator_index = interpolators.len();
-                interpolators.push(easing::definition(easing));
+                interpolators.push(definition);
                 easing_emissions[easing_index].record_declaration(track_index, interpolator_index);
             }
         }
-
-        let mut keyframes = Vec::with_capacity(expected_pose.len());
-        for key in expected_pose.keys() {
-            let (object, property) = key;
-            let mut property_frames = Vec::with_capacity(frames.len());
-            for frame in &frames {
-                let value = poses
-                    .get(frame.pose_index)
-                    .and_then(|pose| pose.get(key))
-                    .copied()
-                    .ok_or_else(|| pose_shape_mismatch(&track_path, frame.authored_index))?;
-                let mut property_frame = json!({
-                    "frame": frame.frame,
-                    "value": value,
-                    "interpolation": if frame.easing_index.is_some() {
-                        "cubic"
-                    } else {
-                        interpolation_name(frame.interpolation)
-                    }
-                });
-                if let Some(easing_index) = frame.easing_index {
-                    let easing = easings.get(easing_index).ok_or_else(|| {
-                        AuthoringDiagnostic::new(
-                            format!("{track_path}.keyframes[{}].easing", frame.authored_index),
-                            "unknown_easing",
-                            "motion easing could not be resolved",
-                        )
-                    })?;
-                    if let Some(object) = property_frame.as_object_mut() {
+        let baked_springs = bake_springs(&frames, easings, track.fps, &track_path)?;
+
+        let mut keyframes = Vec::new();
+        for copy in &copies {
+            for key in copy.poses[0].keys() {
+                let (object, property) = key;
+                let keyframe_path = format!(
+                    "/artboard/animations/{track_index}/keyframes/{}",
+                    keyframes.len()
+                );
+                let mut property_frames = Vec::with_capacity(frames.len());
+                for (frame_position, frame) in frames.iter().enumerate() {
+                    let channel = copy.poses[frame_position]
+                        .get(key)
+                        .ok_or_else(|| pose_shape_mismatch(&track_path, frame.authored_index))?;
+                    let scene_path = format!("{keyframe_path}/frames/{}", property_frames.len());
+                    keyframe_sources.push(match &copy.authored_prefix {
+                        None => SourceMapEntry {
+                            authored_id: channel.authored_id.clone(),
+                            authored_path: channel.authored_path.clone(),
+                            definition_path: None,
+                            runtime_names: Vec::new(),
+                            scene_paths: vec![scene_path],
+                        },
+                        Some(prefix) => SourceMapEntry {
+                            authored_id: format!("{prefix}/{}", channel.authored_id),
+                            authored_path: format!("{track_path}.stagger"),
+                            definition_path: Some(channel.authored_path.clone()),
+                            runtime_names: Vec::new(),
+                            scene_paths: vec![scene_path],
+                        },
+                    });
+                    let interpolator = frame
+                        .easing_index
+                        .map(|easing_index| {
+                            easings.get(easing_index).ok_or_else(|| {
+                                AuthoringDiagnostic::new(
+                                    format!(
+                                        "{track_path}.keyframes[{}].easing",
+                                        frame.authored_index
+                                    ),
+                                    "unknown_easing",
+                                    "motion easing could not be resolved",
+                                )
+                            })
+                        })
+                        .transpose()?
+                        .filter(|easing| !matches!(easing.curve, EasingCurve::Spring(_)));
+                    let mut property_frame = json!({
+                        "frame": frame.frame + copy.offset,
+                        "value": channel.value,
+                        "interpolation": if interpolator.is_some() {
+                            "cubic"
+                        } else {
+                            interpolation_name(frame.interpolation)
+                        }
+                    });
+                    if let Some(easing) = interpolator
+                        && let Some(object) = property_frame.as_object_mut()
+                    {
                         object.insert("interpolator".to_string(), json!(easing.runtime_name));
                     }
+                    property_frames.push(property_frame);
+
+                    let Some((easing_index, baked)) = &baked_springs[frame_position] else {
+                        continue;
+                    };
+                    let next = copy
+                        .poses
+                        .get(frame_position + 1)
+                        .and_then(|pose| pose.get(key))
+                        .ok_or_else(|| pose_shape_mismatch(&track_path, frame.authored_index))?;
+                    for (offset, progress) in baked {
+                        easing_emissions[*easing_index].record_baked_frame(format!(
+                            "{keyframe_path}/frames/{}",
+                            property_frames.len()
+                        ));
+                        property_frames.push(json!({
+                            "frame": frame.frame + copy.offset + offset,
+                            "value": channel.value.interpolate(&next.value, *progress),
+                            "interpolation": "linear"
+                        }));
+                    }
                 }
-                property_frames.push(property_frame);
+                keyframes.push(json!({
+                    "object": object,
+                    "property": property.runtime_property(),
+                    "frames": property_frames
+                }));
+            }
+        }
+        // Event names are not mangled, so the trigger keys the event directly.
+        for (event, mut fired) in event_frames {
+            fired.sort_unstable();
+            let keyframe_path = format!(
+                "/artboard/animations/{track_index}/keyframes/{}",
+                keyframes.len()
+            );
+            let mut event_keyframes = Vec::with_capacity(fired.len());
+            for (frame, event_index) in fired {
+                keyframe_sources.push(SourceMapEntry {
+                    authored_id: event.to_string(),
+                    authored_path: format!("{track_path}.events[{event_index}]"),
+                    definition_path: None,
+                    runtime_names: Vec::new(),
+                    scene_paths: vec![format!("{keyframe_path}/frames/{}", event_keyframes.len())],
+                });
+                event_keyframes.push(json!({
+                    "frame": frame,
+                    "value": 0,
+                    "interpolation": "hold"
+                }));
             }
             keyframes.push(json!({
-                "object": object,
-                "property": property.name(),
-                "frames": property_frames
+                "object": event,
+                "property": "trigger",
+                "frames": event_keyframes
             }));
         }
 
@@ -420,9 +712,56 @@ fn lower_tracks(
     Ok(LoweredTracks {
         fragments,
         easing_emissions,
+        keyframe_sources,
     })
 }
 
+/// Bakes each spring-eased segment once per track, so every channel keyed by
+/// the track shares the same intermediate frames.
+fn bake_springs(
+    frames: &[ResolvedFrame],
+    easings: &[ResolvedEasing],
+    fps: u64,
+    track_path: &str,
+) -> Result<BakedSprings, AuthoringDiagnostic> {
+    let mut baked = Vec::with_capacity(frames.len());
+    for (position, frame) in frames.iter().enumerate() {
+        let spring = frame.easing_index.and_then(|easing_index| {
+            match easings.get(easing_index).map(|easing| &easing.curve) {
+                Some(EasingCurve::Spring(spring)) => Some((easing_index, *spring)),
+                _ => None,
+            }
+        });
+        let (Some((easing_index, spring)), Some(next)) = (spring, frames.get(position + 1)) else {
+            baked.push(None);
+            continue;
+        };
+        let segment = next.frame - frame.frame;
+        if segment > spring::MAX_SPRING_SEGMENT_FRAMES {
+            return Err(AuthoringDiagnostic::new(
+                format!("{track_path}.keyframes[{}].easing", frame.authored_index),
+                "spring_segment_limit",
+                format!(
+                    "spring-eased segments must not exceed {} frames",
+                    spring::MAX_SPRING_SEGMENT_FRAMES
+                ),
+            ));
+        }
+        if !spring.settles_within(segment, fps) {
+            return Err(AuthoringDiagnostic::new(
+                format!("{track_path}.keyframes[{}].easing", frame.authored_index),
+                "spring_not_settled",
+                format!(
+                    "spring has not settled within tolerance {} of its target after the {segment}-frame segment; lengthen the segment or raise damping or stiffness",
+                    spring.tolerance
+                ),
+            ));
+        }
+        baked.push(Some((easing_index, spring.bake(segment, fps))));
+    }
+    Ok(baked)
+}
+
 fn pose_shape_mismatch(track_path: &str, authored_index: usize) -> AuthoringDiagnostic {
     AuthoringDiagnostic::new(
         format!("{track_path}.keyframes[{authored_index}].pose"),
@@ -3,24 +3,39 @@ use serde_json::{Value, json};
 use super::super::super::expression::evaluate_expression;
 use super::super::super::lower;
 use super::super::super::spec::{
-    AuthoringDiagnostic, AuthoringSpec, LoweredAuthoring, MotionEasingSpec, ScalarExpr,
-    SourceMapEntry, Unit,
+    AuthoringDiagnostic, AuthoringSpec, EasingPreset, ElasticEasing, LoweredAuthoring,
+    MotionEasingSpec, ScalarExpr, SourceMapEntry, Unit,
 };
+use super::spring::{DEFAULT_SPRING_TOLERANCE, MAX_SPRING_TOLERANCE, MIN_SPRING_TOLERANCE, Spring};
 
 pub(super) struct ResolvedEasing {
     authored_index: usize,
     pub(super) id: String,
     pub(super) runtime_name: String,
-    x1: f64,
-    y1: f64,
-    x2: f64,
-    y2: f64,
+    pub(super) curve: EasingCurve,
+}
+
+/// Cubic and elastic curves lower to runtime interpolators; springs have no
+/// runtime counterpart and are baked into keyframes instead.
+pub(super) enum EasingCurve {
+    Cubic {
+        x1: f64,
+        y1: f64,
+        x2: f64,
+        y2: f64,
+    },
+    Elastic {
+        easing_value: u64,
+        amplitude: f64,
+        period: f64,
+    },
+    Spring(Spring),
 }
 
 pub(super) struct EasingEmission {
     authored_index: usize,
     authored_id: String,
-    runtime_name: String,
+    runtime_name: Option<String>,
     scene_paths: Vec<String>,
 }
 
@@ -29,7 +44,8 @@ impl EasingEmission {
         Self {
             authored_index: easing.authored_index,
             authored_id: easing.id.clone(),
-            runtime_name: easing.runtime_name.clone(),
+            runtime_name: (!matches!(easing.curve, EasingCurve::Spring(_)))
+                .then(|| easing.runtime_name.clone()),
             scene_paths: Vec::new(),
         }
     }
@@ -39,51 +55,115 @@ impl EasingEmission {
             "/artboard/animations/{animation_index}/interpolators/{interpolator_index}"
         ));
     }
+
+    pub(super) fn record_baked_frame(&mut self, scene_path: String) {
+        self.scene_paths.push(scene_path);
+    }
 }
 
 pub(super) fn resolve(spec: &AuthoringSpec) -> Result<Vec<ResolvedEasing>, AuthoringDiagnostic> {
     let mut resolved = Vec::with_capacity(spec.motion.easings.len());
     for (authored_index, easing) in spec.motion.easings.iter().enumerate() {
         let easing_path = format!("$.motion.easings[{authored_index}]");
-        match easing {
-            MotionEasingSpec::Cubic { id, x1, y1, x2, y2 } => {
-                resolved.push(ResolvedEasing {
-                    authored_index,
-                    id: id.clone(),
-                    runtime_name: lower::runtime_name(
-                        &[spec.artboard.id.clone(), id.clone()],
-                        "interpolator",
-                    ),
-                    x1: evaluate_time_control(x1, &format!("{easing_path}.x1"), spec)?,
-                    y1: evaluate_expression(
-                        y1,
-                        &format!("{easing_path}.y1"),
-                        &spec.parameters,
-                        Unit::Scalar,
-                    )?,
-                    x2: evaluate_time_control(x2, &format!("{easing_path}.x2"), spec)?,
-                    y2: evaluate_expression(
-                        y2,
-                        &format!("{easing_path}.y2"),
-                        &spec.parameters,
-                        Unit::Scalar,
-                    )?,
-                });
+        let curve = match easing {
+            MotionEasingSpec::Cubic { x1, y1, x2, y2, .. } => EasingCurve::Cubic {
+                x1: evaluate_time_control(x1, &format!("{easing_path}.x1"), spec)?,
+                y1: evaluate_expression(
+                    y1,
+                    &format!("{easing_path}.y1"),
+                    &spec.parameters,
+                    Unit::Scalar,
+                )?,
+                x2: evaluate_time_control(x2, &format!("{easing_path}.x2"), spec)?,
+                y2: evaluate_expression(
+                    y2,
+                    &format!("{easing_path}.y2"),
+                    &spec.parameters,
+                    Unit::Scalar,
+                )?,
+            },
+            MotionEasingSpec::Preset { preset, .. } => {
+                let [x1, y1, x2, y2] = preset_control_points(*preset);
+                EasingCurve::Cubic { x1, y1, x2, y2 }
             }
-        }
+            MotionEasingSpec::Elastic {
+                easing,
+                amplitude,
+                period,
+                ..
+            } => EasingCurve::Elastic {
+                easing_value: match easing {
+                    ElasticEasing::In => 0,
+                    ElasticEasing::Out => 1,
+                    ElasticEasing::InOut => 2,
+                },
+                amplitude: evaluate_positive(
+                    amplitude,
+                    &format!("{easing_path}.amplitude"),
+                    spec,
+                    "invalid_elastic_easing",
+                    "elastic amplitude must be greater than zero",
+                )?,
+                period: evaluate_positive(
+                    period,
+                    &format!("{easing_path}.period"),
+                    spec,
+                    "invalid_elastic_easing",
+                    "elastic period must be greater than zero",
+                )?,
+            },
+            MotionEasingSpec::Spring {
+                stiffness,
+                damping,
+                mass,
+                tolerance,
+                ..
+            } => EasingCurve::Spring(resolve_spring(
+                stiffness,
+                damping,
+                mass,
+                tolerance.as_ref(),
+                &easing_path,
+                spec,
+            )?),
+        };
+        resolved.push(ResolvedEasing {
+            authored_index,
+            id: easing.id().to_string(),
+            runtime_name: lower::runtime_name(
+                &[spec.artboard.id.clone(), easing.id().to_string()],
+                "interpolator",
+            ),
+            curve,
+        });
     }
     Ok(resolved)
 }
 
-pub(super) fn definition(easing: &ResolvedEasing) -> Value {
-    json!({
-        "name": easing.runtime_name,
-        "type": "cubic",
-        "x1": easing.x1,
-        "y1": easing.y1,
-        "x2": easing.x2,
-        "y2": easing.y2
-    })
+/// The runtime interpolator declaration, or `None` for a baked spring.
+pub(super) fn definition(easing: &ResolvedEasing) -> Option<Value> {
+    match easing.curve {
+        EasingCurve::Cubic { x1, y1, x2, y2 } => Some(json!({
+            "name": easing.runtime_name,
+            "type": "cubic",
+            "x1": x1,
+            "y1": y1,
+            "x2": x2,
+            "y2": y2
+        })),
+        EasingCurve::Elastic {
+            easing_value,
+            amplitude,
+            period,
+        } => Some(json!({
+            "name": easing.runtime_name,
+            "type": "elastic",
+            "easing_value": easing_value,
+            "amplitude": amplitude,
+            "period": period
+        })),
+        EasingCurve::Spring(_) => None,
+    }
 }
 
 pub(super) fn append_source_entries(
@@ -98,7 +178,7 @@ pub(super) fn append_source_entries(
                 authored_id: emission.authored_id,
                 authored_path: format!("$.motion.easings[{}]", emission.authored_index),
                 definition_path: None,
-                runtime_names: vec![emission.runtime_name],
+                runtime_names: emission.runtime_name.into_iter().collect(),
                 scene_paths: emission.scene_paths,
             }),
     );
@@ -119,3 +199,91 @@ fn evaluate_time_control(
     }
     Ok(value)
 }
+
+fn evaluate_positive(
+    expression: &ScalarExpr,
+    path: &str,
+    spec: &AuthoringSpec,
+    code: &str,
+    message: &str,
+) -> Result<f64, AuthoringDiagnostic> {
+    let value = evaluate_expression(expression, path, &spec.parameters, Unit::Scalar)?;
+    if value <= 0.0 {
+        return Err(AuthoringDiagnostic::new(path, code, message));
+    }
+    Ok(value)
+}
+
+fn resolve_spring(
+    stiffness: &ScalarExpr,
+    damping: &ScalarExpr,
+    mass: &ScalarExpr,
+    tolerance: Option<&ScalarExpr>,
+    easing_path: &str,
+    spec: &AuthoringSpec,
+) -> Result<Spring, AuthoringDiagnostic> {
+    let damping_path = format!("{easing_path}.damping");
+    let damping = evaluate_expression(damping, &damping_path, &spec.parameters, Unit::Scalar)?;
+    if damping <= 0.0 {
+        return Err(AuthoringDiagnostic::new(
+            damping_path,
+            "invalid_spring_easing",
+            "spring damping must be greater than zero",
+        ));
+    }
+    let tolerance = match tolerance {
+        Some(tolerance) => {
+            let tolerance_path = format!("{easing_path}.tolerance");
+            let value =
+                evaluate_expression(tolerance, &tolerance_path, &spec.parameters, Unit::Scalar)?;
+            if !(MIN_SPRING_TOLERANCE..=MAX_SPRING_TOLERANCE).contains(&value) {
+                return Err(AuthoringDiagnostic::new(
+                    tolerance_path,
+                    "invalid_spring_easing",
+                    format!(
+                        "spring tolerance must be between {MIN_SPRING_TOLERANCE} and {MAX_SPRING_TOLERANCE}"
+                    ),
+                ));
+            }
+            value
+        }
+        None => DEFAULT_SPRING_TOLERANCE,
+    };
+    Ok(Spring {
+        stiffness: evaluate_positive(
+            stiffness,
+            &format!("{easing_path}.stiffness"),
+            spec,
+            "invalid_spring_easing",
+            "spring stiffness must be greater than zero",
+        )?,
+        damping,
+        mass: evaluate_positive(
+            mass,
+            &format!("{easing_path}.mass"),
+            spec,
+            "invalid_spring_easing",
+            "spring mass must be greater than zero",
+        )?,
+        tolerance,
+    })
+}
+
+/// CSS keyword curves, the common back curves, and the Material Design
+/// standard and emphasized curves.
+fn preset_control_points(preset: EasingPreset) -> [f64; 4] {
+    match preset {
+        EasingPreset::Ease => [0.25, 0.1, 0.25, 1.0],
+        EasingPreset::EaseIn => [0.42, 0.0, 1.0, 1.0],
+        EasingPreset::EaseOut => [0.0, 0.0, 0.58, 1.0],
+        EasingPreset::EaseInOut => [0.42, 0.0, 0.58, 1.0],
+        EasingPreset::EaseInBack => [0.36, 0.0, 0.66, -0.56],
+        EasingPreset::EaseOutBack => [0.34, 1.56, 0.64, 1.0],
+        EasingPreset::EaseInOutBack => [0.68, -0.6, 0.32, 1.6],
+        EasingPreset::MaterialStandard => [0.2, 0.0, 0.0, 1.0],
+        EasingPreset::MaterialStandardAccelerate => [0.3, 0.0, 1.0, 1.0],
+        EasingPreset::MaterialStandardDecelerate => [0.0, 0.0, 0.0, 1.0],
+        EasingPreset::MaterialEmphasizedAccelerate => [0.3, 0.0, 0.8, 0.15],
+        EasingPreset::MaterialEmphasizedDecelerate => [0.05, 0.7, 0.1, 1.0],
+    }
+}
@@ -0,0 +1,650 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::f64::consts::{FRAC_PI_2, TAU};
+
+use serde_json::Value;
+
+use super::super::super::deterministic_math::{atan2, hypot, sin_cos};
+use super::super::super::expression::evaluate_expression;
+use super::super::super::lower::path_data::{
+    Contour, PathCommand, PathVertex, Point, build_contours, evaluate_path_data, match_orientation,
+    resample,
+};
+use super::super::super::lower::{MorphResolutions, evaluate_ratio_expression};
+use super::super::super::spec::{
+    AuthoringDiagnostic, AuthoringSpec, LoweredAuthoring, OutlineSpec, ParametricOutlineSpec,
+    PoseTargetSpec, Quantity, ScalarExpr, Unit,
+};
+use super::property::{
+    MotionRuntimeObject, MotionRuntimeRole, PoseChannel, PoseProperty, PoseValue, PoseValues,
+};
+
+/// Most vertices one morphed contour may be resampled to.
+const MAX_MORPH_VERTICES: usize = 256;
+
+/// Handle length, as a fraction of the radius, of the cubic that best
+/// approximates a quarter circle.
+const KAPPA: f64 = 0.552_284_749_830_793_6;
+
+struct MorphOutline {
+    target: String,
+    path: String,
+    contours: Vec<Contour>,
+}
+
+/// The outlines poses morph path targets to. Every outline of a target, and
+/// the target itself, is resampled to one vertex count per contour.
+#[derive(Default)]
+pub(super) struct MorphPlan {
+    resolutions: MorphResolutions,
+    /// Keyed by pose and target index.
+    outlines: BTreeMap<(usize, usize), MorphOutline>,
+    /// Each target's resampled rest outline, as lowered.
+    bases: HashMap<String, Vec<Contour>>,
+}
+
+impl MorphPlan {
+    pub(super) fn is_empty(&self) -> bool {
+        self.outlines.is_empty()
+    }
+
+    pub(super) fn resolutions(&self) -> &MorphResolutions {
+        &self.resolutions
+    }
+
+    /// Aligns every outline with its target as lowered at the planned
+    /// resolutions: same winding, and the start vertex that moves least.
+    pub(super) fn align(&mut self, lowered: &LoweredAuthoring) -> Result<(), AuthoringDiagnostic> {
+        for outline in self.outlines.values() {
+            if self.bases.contains_key(&outline.target) {
+                continue;
+            }
+            let counts = &self.resolutions[&outline.target];
+            let base = lowered_contours(lowered, &outline.target, &outline.path)?
+                .filter(|contours| {
+                    contours.len() == counts.len()
+                        && contours
+                            .iter()
+                            .zip(counts)
+                            .all(|(contour, count)| contour.vertices.len() == *count)
+                })
+                .ok_or_else(|| not_morphable(&outline.target, &outline.path))?;
+            self.bases.insert(outline.target.clone(), base);
+        }
+        for outline in self.outlines.values_mut() {
+            let base = &self.bases[&outline.target];
+            outline.contours = std::mem::take(&mut outline.contours)
+                .into_iter()
+                .zip(base)
+                .map(|(contour, base)| match_orientation(contour, base))
+                .collect();
+        }
+        Ok(())
+    }
+}
+
+/// Evaluates every pose outline and settles, per target contour, the vertex
+/// count that it and all its outlines resample to.
+pub(super) fn plan(
+    spec: &AuthoringSpec,
+    lowered: &LoweredAuthoring,
+) -> Result<MorphPlan, AuthoringDiagnostic> {
+    let staggered_poses = spec
+        .motion
+        .tracks
+        .iter()
+        .filter(|track| track.stagger.is_some())
+        .flat_map(|track| {
+            track
+                .keyframes
+                .iter()
+                .map(|keyframe| keyframe.pose.as_str())
+        })
+        .collect::<HashSet<_>>();
+    let mut plan = MorphPlan::default();
+    let mut bases = BTreeMap::new();
+    for (pose_index, pose) in spec.motion.poses.iter().enumerate() {
+        for (target_index, target) in pose.targets.iter().enumerate() {
+            let Some(outline) = &target.outline else {
+                continue;
+            };
+            let path = format!("$.motion.poses[{pose_index}].targets[{target_index}].outline");
+            if staggered_poses.contains(pose.id.as_str()) {
+                return Err(AuthoringDiagnostic::new(
+                    path,
+                    "unsupported_motion_property",
+                    "outline morphs cannot be staggered across pattern copies",
+                ));
+            }
+            // Unknown and ambiguous targets are reported when the pose resolves.
+            let Some(base) = lowered_contours(lowered, &target.target, &path)? else {
+                continue;
+            };
+            let contours = evaluate_outline(outline, &path, &spec.parameters)?;
+            check_topology(&contours, &base, &target.target, &path)?;
+            bases.entry(target.target.as_str()).or_insert(base);
+            plan.outlines.insert(
+                (pose_index, target_index),
+                MorphOutline {
+                    target: target.target.clone(),
+                    path,
+                    contours,
+                },
+            );
+        }
+    }
+
+    for (target, base) in bases {
+        let mut counts = base
+            .iter()
+            .map(|contour| contour.vertices.len())
+            .collect::<Vec<_>>();
+        for outline in plan
+            .outlines
+            .values()
+            .filter(|outline| outline.target == target)
+        {
+            for (contour_index, contour) in outline.contours.iter().enumerate() {
+                let count = contour.vertices.len();
+                if count > MAX_MORPH_VERTICES {
+                    return Err(vertex_limit(&outline.path, contour_index, count));
+                }
+                counts[contour_index] = counts[contour_index].max(count);
+            }
+        }
+        if let Some((contour_index, count)) = counts
+            .iter()
+            .enumerate()
+            .find(|(_, count)| **count > MAX_MORPH_VERTICES)
+        {
+            let outline = plan
+                .outlines
+                .values()
+                .find(|outline| outline.target == target)
+                .expect("planned targets have an outline");
+            return Err(vertex_limit(&outline.path, contour_index, *count));
+        }
+        for outline in plan
+            .outlines
+            .values_mut()
+            .filter(|outline| outline.target == target)
+        {
+            outline.contours = outline
+                .contours
+                .iter()
+                .zip(&counts)
+                .map(|(contour, count)| resample(contour, *count))
+                .collect();
+        }
+        plan.resolutions.insert(target.to_owned(), counts);
+    }
+    Ok(plan)
+}
+
+/// Adds vertex position and handle channels for a pose target's outline,
+/// with handle rotations unwrapped to turn the short way from rest.
+pub(super) fn resolve_outline_values(
+    plan: &MorphPlan,
+    pose_index: usize,
+    target_index: usize,
+    target: &PoseTargetSpec,
+    runtime_objects: &[MotionRuntimeObject<'_>],
+    values: &mut PoseValues,
+) -> Result<(), AuthoringDiagnostic> {
+    let Some(outline) = plan.outlines.get(&(pose_index, target_index)) else {
+        return Ok(());
+    };
+    let Some(base) = plan.bases.get(&outline.target) else {
+        return Ok(());
+    };
+    let vertices = runtime_objects
+        .iter()
+        .filter_map(|runtime_object| match runtime_object.role {
+            MotionRuntimeRole::Vertex(contour, vertex) => {
+                Some(((contour, vertex), *runtime_object))
+            }
+            _ => None,
+        })
+        .collect::<HashMap<_, _>>();
+    let expected = outline
+        .contours
+        .iter()
+        .map(|contour| contour.vertices.len())
+        .sum::<usize>();
+    if vertices.len() != expected {
+        return Err(not_morphable(&target.target, &outline.path));
+    }
+
+    for (contour_index, (contour, base_contour)) in outline.contours.iter().zip(base).enumerate() {
+        for (vertex_index, (vertex, rest)) in contour
+            .vertices
+            .iter()
+            .zip(&base_contour.vertices)
+            .enumerate()
+        {
+            let runtime_object = vertices
+                .get(&(contour_index, vertex_index))
+                .ok_or_else(|| not_morphable(&target.target, &outline.path))?;
+            let mut channels = vec![
+                (PoseProperty::VertexX, vertex.point.x),
+                (PoseProperty::VertexY, vertex.point.y),
+            ];
+            if runtime_object.object_type == "cubic_detached_vertex" {
+                let rest_in = rest_rotation(rest.point, rest.in_control);
+                let rest_out = rest_rotation(rest.point, rest.out_control);
+                let (in_rotation, in_distance) = handle(vertex.point, vertex.in_control, rest_in);
+                let (out_rotation, out_distance) =
+                    handle(vertex.point, vertex.out_control, rest_out);
+                channels.extend([
+                    (PoseProperty::InRotation, in_rotation),
+                    (PoseProperty::InDistance, in_distance),
+                    (PoseProperty::OutRotation, out_rotation),
+                    (PoseProperty::OutDistance, out_distance),
+                ]);
+            }
+            for (property, value) in channels {
+                values.insert(
+                    (runtime_object.runtime_name.to_owned(), property),
+                    PoseChannel {
+                        value: PoseValue::Number(value),
+                        authored_id: target.target.clone(),
+                        authored_path: outline.path.clone(),
+                    },
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+/// A handle's rotation as the lowered vertex stores it.
+fn rest_rotation(point: Point, control: Option<Point>) -> f64 {
+    control.map_or(0.0, |control| {
+        atan2(control.y - point.y, control.x - point.x)
+    })
+}
+
+/// A handle as rotation and distance from its vertex. Missing and zero-length
+/// handles keep the `reference` rotation, and others take the turn of their
+/// angle nearest to it.
+fn handle(point: Point, control: Option<Point>, reference: f64) -> (f64, f64) {
+    let Some(control) = control else {
+        return (reference, 0.0);
+    };
+    let distance = hypot(control.x - point.x, control.y - point.y);
+    if distance == 0.0 {
+        return (reference, 0.0);
+    }
+    let rotation = atan2(control.y - point.y, control.x - point.x);
+    (
+        rotation + TAU * ((reference - rotation) / TAU).round(),
+        distance,
+    )
+}
+
+/// Reads the contours of a lowered path target back from its `points_path`
+/// objects; `None` when the target does not resolve to one authored node.
+fn lowered_contours(
+    lowered: &LoweredAuthoring,
+    target: &str,
+    path: &str,
+) -> Result<Option<Vec<Contour>>, AuthoringDiagnostic> {
+    let mut entries = lowered.source_map.entries.iter().filter(|entry| {
+        entry.authored_id == target && entry.authored_path.starts_with("$.visual.nodes[")
+    });
+    let (Some(entry), None) = (entries.next(), entries.next()) else {
+        return Ok(None);
+    };
+    let mut contours = Vec::new();
+    for object in entry
+        .scene_paths
+        .iter()
+        .filter_map(|scene_path| lowered.scene.pointer(scene_path))
+        .filter(|object| object.get("type").and_then(Value::as_str) == Some("points_path"))
+    {
+        let vertices = object
+            .get("children")
+            .and_then(Value::as_array)
+            .and_then(|children| {
+                children
+                    .iter()
+                    .map(scene_vertex)
+                    .collect::<Option<Vec<_>>>()
+            })
+            .filter(|vertices| vertices.len() >= 2)
+            .ok_or_else(|| not_morphable(target, path))?;
+        contours.push(Contour {
+            vertices,
+            closed: object
+                .get("is_closed")
+                .and_then(Value::as_bool)
+                .unwrap_or(false),
+        });
+    }
+    if contours.is_empty() {
+        return Err(not_morphable(target, path));
+    }
+    Ok(Some(contours))
+}
+
+fn scene_vertex(object: &Value) -> Option<PathVertex> {
+    let number = |key: &str| object.get(key).and_then(Value::as_f64);
+    let point = Point {
+        x: number("x")?,
+        y: number("y")?,
+    };
+    match object.get("type")?.as_str()? {
+        "straight_vertex" => Some(PathVertex {
+            point,
+            in_control: None,
+            out_control: None,
+        }),
+        "cubic_detached_vertex" => {
+            let control = |rotation: &str, distance: &str| {
+                let (sin, cos) = sin_cos(number(rotation)?);
+                let distance = number(distance)?;
+                Some(Point {
+                    x: point.x + distance * cos,
+                    y: point.y + distance * sin,
+                })
+            };
+            Some(PathVertex {
+                point,
+                in_control: Some(control("in_rotation", "in_distance")?),
+                out_control: Some(control("out_rotation", "out_distance")?),
+            })
+        }
+        _ => None,
+    }
+}
+
+fn check_topology(
+    outline: &[Contour],
+    base: &[Contour],
+    target: &str,
+    path: &str,
+) -> Result<(), AuthoringDiagnostic> {
+    if outline.len() != base.len() {
+        return Err(AuthoringDiagnostic::new(
+            path,
+            "morph_topology_mismatch",
+            format!(
+                "outline has {} contours but path target '{target}' has {}",
+                outline.len(),
+                base.len()
+            ),
+        ));
+    }
+    for (contour_index, (outline, base)) in outline.iter().zip(base).enumerate() {
+        if outline.closed != base.closed {
+            let state = |closed: bool| if closed { "closed" } else { "open" };
+            return Err(AuthoringDiagnostic::new(
+                path,
+                "morph_topology_mismatch",
+                format!(
+                    "outline contour {contour_index} is {} but path target '{target}' contour {contour_index} is {}",
+                    state(outline.closed),
+                    state(base.closed)
+                ),
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn evaluate_outline(
+    outline: &OutlineSpec,
+    path: &str,
+    scope: &BTreeMap<String, Quantity>,
+) -> Result<Vec<Contour>, AuthoringDiagnostic> {
+    let shape = match outline {
+        OutlineSpec::Path(data) => return evaluate_path_data(data, path, scope),
+        OutlineSpec::Shape(shape) => shape,
+    };
+    let commands = shape_commands(shape, path, scope)?;
+    build_contours(&commands).map_err(|_| {
+        AuthoringDiagnostic::new(
+            path,
+            "invalid_path_data",
+            "parametric outline must draw a closed contour",
+        )
+    })
+}
+
+/// Draws a parametric outline centred on the origin, clockwise from the top
+/// like the runtime's own shapes.
+fn shape_commands(
+    shape: &ParametricOutlineSpec,
+    path: &str,
+    scope: &BTreeMap<String, Quantity>,
+) -> Result<Vec<PathCommand>, AuthoringDiagnostic> {
+    let (width, height) = match shape {
+        ParametricOutlineSpec::Ellipse { width, height }
+        | ParametricOutlineSpec::Rectangle { width, height, .. }
+        | ParametricOutlineSpec::Triangle { width, height }
+        | ParametricOutlineSpec::Polygon { width, height, .. }
+        | ParametricOutlineSpec::Star { width, height, .. } => (width, height),
+    };
+    let rx = dimension(width, &format!("{path}.width"), scope)? / 2.0;
+    let ry = dimension(height, &format!("{path}.height"), scope)? / 2.0;
+    let at = |x: f64, y: f64| Point { x, y };
+
+    let commands = match shape {
+        ParametricOutlineSpec::Ellipse { .. } => {
+            let (kx, ky) = (KAPPA * rx, KAPPA * ry);
+            vec![
+                PathCommand::Move(at(0.0, -ry)),
+                PathCommand::Cubic(at(kx, -ry), at(rx, -ky), at(rx, 0.0)),
+                PathCommand::Cubic(at(rx, ky), at(kx, ry), at(0.0, ry)),
+                PathCommand::Cubic(at(-kx, ry), at(-rx, ky), at(-rx, 0.0)),
+                PathCommand::Cubic(at(-rx, -ky), at(-kx, -ry), at(0.0, -ry)),
+                PathCommand::Close,
+            ]
+        }
+        ParametricOutlineSpec::Rectangle { corner_radius, .. } => {
+            let radius_path = format!("{path}.corner_radius");
+            let radius = corner_radius
+                .as_ref()
+                .map(|radius| evaluate_expression(radius, &radius_path, scope, Unit::Px))
+                .transpose()?
+                .unwrap_or(0.0);
+            if radius < 0.0 {
+                return Err(AuthoringDiagnostic::new(
+                    radius_path,
+                    "invalid_dimension",
+                    "corner radius must not be negative",
+                ));
+            }
+            rounded_rectangle(rx, ry, radius.min(rx).min(ry))
+        }
+        ParametricOutlineSpec::Triangle { .. } => vec![
+            PathCommand::Move(at(0.0, -ry)),
+            PathCommand::Line(at(rx, ry)),
+            PathCommand::Line(at(-rx, ry)),
+            PathCommand::Close,
+        ],
+        ParametricOutlineSpec::Polygon { points, .. } => {
+            let points = point_count(*points, 1, path)?;
+            ring(&vec![1.0; points], rx, ry)
+        }
+        ParametricOutlineSpec::Star {
+            points,
+            inner_radius,
+            ..
+        } => {
+            let points = point_count(*points, 2, path)?;
+            let inner_radius = evaluate_ratio_expression(
+                inner_radius,
+                &format!("{path}.inner_radius"),
+                scope,
+                "star inner radius must be between zero and one",
+            )?;
+            let radii = (0..points * 2)
+                .map(|index| if index % 2 == 0 { 1.0 } else { inner_radius })
+                .collect::<Vec<_>>();
+            ring(&radii, rx, ry)
+        }
+    };
+    Ok(commands)
+}
+
+fn dimension(
+    expression: &ScalarExpr,
+    path: &str,
+    scope: &BTreeMap<String, Quantity>,
+) -> Result<f64, AuthoringDiagnostic> {
+    let value = evaluate_expression(expression, path, scope, Unit::Px)?;
+    if value <= 0.0 {
+        return Err(AuthoringDiagnostic::new(
+            path,
+            "invalid_dimension",
+            "outline width and height must be greater than zero",
+        ));
+    }
+    Ok(value)
+}
+
+/// Checks a polygon or star point count, where each point draws
+/// `vertices_per_point` vertices.
+fn point_count(
+    points: u64,
+    vertices_per_point: usize,
+    path: &str,
+) -> Result<usize, AuthoringDiagnostic> {
+    let points_path = format!("{path}.points");
+    if points < 3 {
+        return Err(AuthoringDiagnostic::new(
+            points_path,
+            "invalid_points",
+            "polygon and star point counts must be at least three",
+        ));
+    }
+    usize::try_from(points)
+        .ok()
+        .filter(|points| points.saturating_mul(vertices_per_point) <= MAX_MORPH_VERTICES)
+        .ok_or_else(|| {
+            AuthoringDiagnostic::new(
+                points_path,
+                "morph_vertex_limit",
+                format!("morphed contours may have at most {MAX_MORPH_VERTICES} vertices"),
+            )
+        })
+}
+
+/// Straight edges through points evenly spaced around the ellipse, each at a
+/// fraction of its radius, starting at the top.
+fn ring(radii: &[f64], rx: f64, ry: f64) -> Vec<PathCommand> {
+    let step = TAU / radii.len() as f64;
+    let mut commands = radii
+        .iter()
+        .enumerate()
+        .map(|(index, radius)| {
+            let (sin, cos) = sin_cos(index as f64 * step - FRAC_PI_2);
+            let point = Point {
+                x: rx * radius * cos,
+                y: ry * radius * sin,
+            };
+            if index == 0 {
+                PathCommand::Move(point)
+            } else {
+                PathCommand::Line(point)
+            }
+        })
+        .collect::<Vec<_>>();
+    commands.push(PathCommand::Close);
+    commands
+}
+
+/// Straight edges joined by quarter-circle corners, starting at the end of
+/// the top-left corner; edges the corners consume entirely are skipped.
+fn rounded_rectangle(rx: f64, ry: f64, radius: f64) -> Vec<PathCommand> {
+    let at = |x: f64, y: f64| Point { x, y };
+    if radius == 0.0 {
+        return vec![
+            PathCommand::Move(at(-rx, -ry)),
+            PathCommand::Line(at(rx, -ry)),
+            PathCommand::Line(at(rx, ry)),
+            PathCommand::Line(at(-rx, ry)),
+            PathCommand::Close,
+        ];
+    }
+    let handle = radius * (1.0 - KAPPA);
+    let (inner_x, inner_y) = (rx - radius, ry - radius);
+    let mut commands = vec![PathCommand::Move(at(-inner_x, -ry))];
+    // Each corner turns a quarter from its entry edge; `(sx, sy)` picks the
+    // corner and `horizontal` whether it is entered along a horizontal edge.
+    for (sx, sy, horizontal) in [
+        (1.0, -1.0, true),
+        (1.0, 1.0, false),
+        (-1.0, 1.0, true),
+        (-1.0, -1.0, false),
+    ] {
+        let (entry, first, second, exit) = if horizontal {
+            (
+                at(sx * inner_x, sy * ry),
+                at(sx * (rx - handle), sy * ry),
+                at(sx * rx, sy * (ry - handle)),
+                at(sx * rx, sy * inner_y),
+            )
+        } else {
+            (
+                at(sx * rx, sy * inner_y),
+                at(sx * rx, sy * (ry - handle)),
+                at(sx * (rx - handle), sy * ry),
+                at(sx * inner_x, sy * ry),
+            )
+        };
+        let edge_length = if horizontal { inner_x } else { inner_y };
+        if edge_length > 0.0 {
+            commands.push(PathCommand::Line(entry));
+        }
+        commands.push(PathCommand::Cubic(first, second, exit));
+    }
+    commands.push(PathCommand::Close);
+    commands
+}
+
+fn vertex_limit(path: &str, contour_index: usize, count: usize) -> AuthoringDiagnostic {
+    AuthoringDiagnostic::new(
+        path,
+        "morph_vertex_limit",
+        format!(
+            "contour {contour_index} needs {count} vertices; morphed contours may have at most {MAX_MORPH_VERTICES}"
+        ),
+    )
+}
+
+fn not_morphable(target: &str, path: &str) -> AuthoringDiagnostic {
+    AuthoringDiagnostic::new(
+        path,
+        "unsupported_motion_property",
+        format!("motion target '{target}' is not a path node that can morph"),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use super::*;
+
+    #[test]
+    fn unwrapped_handles_turn_the_short_way_from_rest() {
+        let origin = Point { x: 0.0, y: 0.0 };
+        let below_left = Some(Point { x: -1.0, y: -0.01 });
+        let (rotation, distance) = handle(origin, below_left, PI);
+        assert!(rotation > PI && rotation < PI + 0.02, "{rotation}");
+        assert!((distance - hypot(1.0, 0.01)).abs() < 1e-12);
+        assert_eq!(handle(origin, Some(origin), 1.5), (1.5, 0.0));
+        assert_eq!(handle(origin, None, -1.5), (-1.5, 0.0));
+    }
+
+    #[test]
+    fn rounded_rectangles_skip_edges_their_corners_consume() {
+        assert_eq!(rounded_rectangle(10.0, 5.0, 0.0).len(), 5);
+        assert_eq!(rounded_rectangle(10.0, 5.0, 2.0).len(), 10);
+        // A pill: the vertical edges vanish.
+        assert_eq!(rounded_rectangle(10.0, 5.0, 5.0).len(), 8);
+        let contours = build_contours(&rounded_rectangle(10.0, 5.0, 5.0)).expect("pill");
+        assert_eq!(contours[0].vertices.len(), 6);
+    }
+}
@@ -1,6 +1,9 @@
 use std::collections::BTreeMap;
 
-use crate::builder::property_key_for_object;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::builder::{parse_color, property_key_for_object};
 
 use super::super::super::expression::evaluate_expression;
 use super::super::super::lower::evaluate_ratio_expression;
@@ -8,7 +11,7 @@ use super::super::super::spec::{
     AuthoringDiagnostic, AuthoringSpec, PoseTargetSpec, ScalarExpr, Unit,
 };
 
-#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub(super) enum PoseProperty {
     X,
     Y,
@@ -18,11 +21,73 @@ pub(super) enum PoseProperty {
     Opacity,
     Width,
     Height,
+    CornerRadius,
+    Fill,
+    StrokePaint,
+    StrokeWidth,
+    TrimStart,
+    TrimEnd,
+    TrimOffset,
+    GradientStopColor(usize),
+    GradientStopPosition(usize),
+    VertexX,
+    VertexY,
+    InRotation,
+    InDistance,
+    OutRotation,
+    OutDistance,
+}
+
+/// A resolved pose value; colours stay as authored hex strings so the
+/// builder emits `KeyFrameColor` for them.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(untagged)]
+pub(super) enum PoseValue {
+    Number(f64),
+    Color(String),
+}
+
+impl PoseValue {
+    /// A value `progress` of the way from `self` to `target`; colours mix
+    /// per channel and clamp, since spring progress may overshoot.
+    pub(super) fn interpolate(&self, target: &Self, progress: f64) -> Self {
+        match (self, target) {
+            (Self::Number(from), Self::Number(to)) => Self::Number(from + (to - from) * progress),
+            (Self::Color(from), Self::Color(to)) => match (parse_color(from), parse_color(to)) {
+                (Ok(from), Ok(to)) => Self::Color(mix_color(from, to, progress)),
+                _ => self.clone(),
+            },
+            _ => self.clone(),
+        }
+    }
+}
+
+/// Mixes two ARGB colours into an `#RRGGBBAA` literal.
+fn mix_color(from: u32, to: u32, progress: f64) -> String {
+    let [red, green, blue, alpha] = [16, 8, 0, 24].map(|shift| {
+        let from = f64::from((from >> shift) & 0xFF);
+        let to = f64::from((to >> shift) & 0xFF);
+        (from + (to - from) * progress).round().clamp(0.0, 255.0) as u8
+    });
+    format!("#{red:02X}{green:02X}{blue:02X}{alpha:02X}")
+}
+
+#[derive(Clone, Copy)]
+enum PoseExpression<'a> {
+    Scalar(&'a ScalarExpr),
+    Color(&'a str),
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub(super) enum MotionRuntimeRole {
     Geometry,
+    FillPaint,
+    StrokePaint,
+    Stroke,
+    Trim,
+    GradientStop(usize),
+    /// A path vertex, by contour and vertex index.
+    Vertex(usize, usize),
     Other,
 }
 
@@ -31,7 +96,7 @@ pub(super) struct MotionRuntimeObject<'a> {
     pub(super) runtime_name: &'a str,
     pub(super) object_type: &'a str,
     is_primary: bool,
-    role: MotionRuntimeRole,
+    pub(super) role: MotionRuntimeRole,
 }
 
 impl<'a> MotionRuntimeObject<'a> {
@@ -55,6 +120,53 @@ impl<'a> MotionRuntimeObject<'a> {
             role,
         }
     }
+
+    /// Refines the role of paint objects from where they sit in the scene:
+    /// a solid colour belongs to a fill or a stroke, and a gradient stop is
+    /// addressed by its index within the gradient. Path vertices are
+    /// addressed by their contour and position within it. Geometry inside a
+    /// shape nested in the node's shape, such as an inverse clip cover, is
+    /// not the node's own geometry.
+    pub(super) fn placed_in(mut self, scene: &Value, scene_path: &str) -> Self {
+        let parent = scene_path.rsplit_once("/children/");
+        let role = match self.object_type {
+            "stroke" => Some(MotionRuntimeRole::Stroke),
+            "trim_path" => Some(MotionRuntimeRole::Trim),
+            "gradient_stop" => parent
+                .and_then(|(_, index)| index.parse().ok())
+                .map(MotionRuntimeRole::GradientStop),
+            "straight_vertex" | "cubic_detached_vertex" => {
+                parent.and_then(|(contour_path, vertex)| {
+                    let (_, contour) = contour_path.rsplit_once("/children/")?;
+                    Some(MotionRuntimeRole::Vertex(
+                        contour.parse().ok()?,
+                        vertex.parse().ok()?,
+                    ))
+                })
+            }
+            "solid_color" => {
+                match parent
+                    .and_then(|(parent_path, _)| scene.pointer(parent_path))
+                    .and_then(|parent| parent.get("type"))
+                    .and_then(Value::as_str)
+                {
+                    Some("fill") => Some(MotionRuntimeRole::FillPaint),
+                    Some("stroke") => Some(MotionRuntimeRole::StrokePaint),
+                    _ => None,
+                }
+            }
+            _ if self.role == MotionRuntimeRole::Geometry => parent
+                .and_then(|(shape_path, _)| shape_path.rsplit_once("/children/"))
+                .and_then(|(outer_path, _)| scene.pointer(outer_path))
+                .filter(|outer| outer.get("type").and_then(Value::as_str) == Some("shape"))
+                .map(|_| MotionRuntimeRole::Other),
+            _ => None,
+        };
+        if let Some(role) = role {
+            self.role = role;
+        }
+        self
+    }
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -65,7 +177,30 @@ enum PropertyTarget<'a> {
 }
 
 impl PoseProperty {
-    pub(super) fn name(self) -> &'static str {
+    /// The authored name, relative to the pose target.
+    pub(super) fn label(self) -> String {
+        match self {
+            Self::CornerRadius => "corner_radius".to_string(),
+            Self::Fill => "fill".to_string(),
+            Self::StrokePaint => "stroke.paint".to_string(),
+            Self::StrokeWidth => "stroke.width".to_string(),
+            Self::TrimStart => "stroke.trim.start".to_string(),
+            Self::TrimEnd => "stroke.trim.end".to_string(),
+            Self::TrimOffset => "stroke.trim.offset".to_string(),
+            Self::GradientStopColor(index) => format!("gradient.stops[{index}].color"),
+            Self::GradientStopPosition(index) => format!("gradient.stops[{index}].position"),
+            Self::VertexX
+            | Self::VertexY
+            | Self::InRotation
+            | Self::InDistance
+            | Self::OutRotation
+            | Self::OutDistance => "outline".to_string(),
+            _ => self.runtime_property().to_string(),
+        }
+    }
+
+    /// The canonical SceneSpec property the keyframes animate.
+    pub(super) fn runtime_property(self) -> &'static str {
         match self {
             Self::X => "x",
             Self::Y => "y",
@@ -75,14 +210,32 @@ impl PoseProperty {
             Self::Opacity => "opacity",
             Self::Width => "width",
             Self::Height => "height",
+            Self::CornerRadius => "corner_radius",
+            Self::Fill | Self::StrokePaint | Self::GradientStopColor(_) => "color",
+            Self::StrokeWidth => "thickness",
+            Self::TrimStart => "trim_start",
+            Self::TrimEnd => "trim_end",
+            Self::TrimOffset => "trim_offset",
+            Self::GradientStopPosition(_) => "position",
+            Self::VertexX => "x",
+            Self::VertexY => "y",
+            Self::InRotation => "in_rotation",
+            Self::InDistance => "in_distance",
+            Self::OutRotation => "out_rotation",
+            Self::OutDistance => "out_distance",
         }
     }
 
     fn unit(self) -> Unit {
         match self {
-            Self::X | Self::Y | Self::Width | Self::Height => Unit::Px,
+            Self::X
+            | Self::Y
+            | Self::Width
+            | Self::Height
+            | Self::CornerRadius
+            | Self::StrokeWidth => Unit::Px,
             Self::Rotation => Unit::Radians,
-            Self::ScaleX | Self::ScaleY | Self::Opacity => Unit::Scalar,
+            _ => Unit::Scalar,
         }
     }
 
@@ -91,82 +244,160 @@ impl PoseProperty {
             Self::X | Self::Y | Self::Rotation | Self::ScaleX | Self::ScaleY | Self::Opacity => {
                 runtime_object.is_primary
             }
-            Self::Width | Self::Height => runtime_object.role == MotionRuntimeRole::Geometry,
+            Self::Width | Self::Height | Self::CornerRadius => {
+                runtime_object.role == MotionRuntimeRole::Geometry
+            }
+            Self::Fill => runtime_object.role == MotionRuntimeRole::FillPaint,
+            Self::StrokePaint => runtime_object.role == MotionRuntimeRole::StrokePaint,
+            Self::StrokeWidth => runtime_object.role == MotionRuntimeRole::Stroke,
+            Self::TrimStart | Self::TrimEnd | Self::TrimOffset => {
+                runtime_object.role == MotionRuntimeRole::Trim
+            }
+            Self::GradientStopColor(index) | Self::GradientStopPosition(index) => {
+                runtime_object.role == MotionRuntimeRole::GradientStop(index)
+            }
+            Self::VertexX
+            | Self::VertexY
+            | Self::InRotation
+            | Self::InDistance
+            | Self::OutRotation
+            | Self::OutDistance => matches!(runtime_object.role, MotionRuntimeRole::Vertex(..)),
         };
         has_target_role
-            && property_key_for_object(runtime_object.object_type, self.name()).is_some()
-    }
-
-    fn expression(self, target: &PoseTargetSpec) -> Option<&ScalarExpr> {
-        match self {
-            Self::X => target.transform.x.as_ref(),
-            Self::Y => target.transform.y.as_ref(),
-            Self::Rotation => target.transform.rotation.as_ref(),
-            Self::ScaleX => target.transform.scale_x.as_ref(),
-            Self::ScaleY => target.transform.scale_y.as_ref(),
-            Self::Opacity => target.opacity.as_ref(),
-            Self::Width => target.width.as_ref(),
-            Self::Height => target.height.as_ref(),
-        }
+            && property_key_for_object(runtime_object.object_type, self.runtime_property())
+                .is_some()
     }
 
     fn authored_path(self, target_path: &str) -> String {
         match self {
-            Self::Opacity | Self::Width | Self::Height => {
-                format!("{target_path}.{}", self.name())
+            Self::X | Self::Y | Self::Rotation | Self::ScaleX | Self::ScaleY => {
+                format!("{target_path}.transform.{}", self.label())
             }
-            _ => format!("{target_path}.transform.{}", self.name()),
+            _ => format!("{target_path}.{}", self.label()),
         }
     }
 
     fn evaluate(
         self,
-        expression: &ScalarExpr,
+        expression: PoseExpression<'_>,
         path: &str,
         spec: &AuthoringSpec,
-    ) -> Result<f64, AuthoringDiagnostic> {
+    ) -> Result<PoseValue, AuthoringDiagnostic> {
+        let expression = match expression {
+            PoseExpression::Color(color) => {
+                return parse_color(color)
+                    .map(|_| PoseValue::Color(color.to_string()))
+                    .map_err(|error| AuthoringDiagnostic::new(path, "invalid_color", error));
+            }
+            PoseExpression::Scalar(expression) => expression,
+        };
+        let ratio_message = match self {
+            Self::Opacity => Some("motion opacity must be between zero and one"),
+            Self::TrimStart | Self::TrimEnd => Some("motion trim must be between zero and one"),
+            Self::GradientStopPosition(_) => {
+                Some("motion gradient stop position must be between zero and one")
+            }
+            _ => None,
+        };
+        if let Some(message) = ratio_message {
+            return evaluate_ratio_expression(expression, path, &spec.parameters, message)
+                .map(PoseValue::Number);
+        }
+        let value = evaluate_expression(expression, path, &spec.parameters, self.unit())?;
         match self {
-            Self::Opacity => evaluate_ratio_expression(
-                expression,
+            Self::Width | Self::Height if value <= 0.0 => Err(AuthoringDiagnostic::new(
                 path,
-                &spec.parameters,
-                "motion opacity must be between zero and one",
-            ),
-            Self::Width | Self::Height => {
-                let value = evaluate_expression(expression, path, &spec.parameters, self.unit())?;
-                if value <= 0.0 {
-                    Err(AuthoringDiagnostic::new(
-                        path,
-                        "invalid_dimension",
-                        format!("motion {} must be greater than zero", self.name()),
-                    ))
-                } else {
-                    Ok(value)
-                }
-            }
-            _ => evaluate_expression(expression, path, &spec.parameters, self.unit()),
+                "invalid_dimension",
+                format!("motion {} must be greater than zero", self.label()),
+            )),
+            Self::CornerRadius | Self::StrokeWidth if value < 0.0 => Err(AuthoringDiagnostic::new(
+                path,
+                "invalid_dimension",
+                format!("motion {} must not be negative", self.label()),
+            )),
+            _ => Ok(PoseValue::Number(value)),
         }
     }
 }
 
-const POSE_PROPERTIES: [PoseProperty; 8] = [
-    PoseProperty::X,
-    PoseProperty::Y,
-    PoseProperty::Rotation,
-    PoseProperty::ScaleX,
-    PoseProperty::ScaleY,
-    PoseProperty::Opacity,
-    PoseProperty::Width,
-    PoseProperty::Height,
-];
+/// A resolved channel and the authored property it came from, so every
+/// generated keyframe can point back at it.
+#[derive(Clone, Debug)]
+pub(super) struct PoseChannel {
+    pub(super) value: PoseValue,
+    pub(super) authored_id: String,
+    pub(super) authored_path: String,
+}
+
+pub(super) type PoseValues = BTreeMap<(String, PoseProperty), PoseChannel>;
 
-pub(super) type PoseValues = BTreeMap<(String, PoseProperty), f64>;
+/// Every channel the target declares, in canonical property order.
+fn channels<'t>(target: &'t PoseTargetSpec) -> Vec<(PoseProperty, PoseExpression<'t>)> {
+    let scalar = |property, expression: Option<&'t ScalarExpr>| {
+        expression.map(|expression| (property, PoseExpression::Scalar(expression)))
+    };
+    let color = |property, color: Option<&'t String>| {
+        color.map(|color| (property, PoseExpression::Color(color.as_str())))
+    };
+    let stroke = target.stroke.as_ref();
+    let trim = stroke.and_then(|stroke| stroke.trim.as_ref());
+    let mut channels = [
+        scalar(PoseProperty::X, target.transform.x.as_ref()),
+        scalar(PoseProperty::Y, target.transform.y.as_ref()),
+        scalar(PoseProperty::Rotation, target.transform.rotation.as_ref()),
+        scalar(PoseProperty::ScaleX, target.transform.scale_x.as_ref()),
+        scalar(PoseProperty::ScaleY, target.transform.scale_y.as_ref()),
+        scalar(PoseProperty::Opacity, target.opacity.as_ref()),
+        scalar(PoseProperty::Width, target.width.as_ref()),
+        scalar(PoseProperty::Height, target.height.as_ref()),
+        scalar(PoseProperty::CornerRadius, target.corner_radius.as_ref()),
+        color(PoseProperty::Fill, target.fill.as_ref()),
+        color(
+            PoseProperty::StrokePaint,
+            stroke.and_then(|stroke| stroke.paint.as_ref()),
+        ),
+        scalar(
+            PoseProperty::StrokeWidth,
+            stroke.and_then(|stroke| stroke.width.as_ref()),
+        ),
+        scalar(
+            PoseProperty::TrimStart,
+            trim.and_then(|trim| trim.start.as_ref()),
+        ),
+        scalar(
+            PoseProperty::TrimEnd,
+            trim.and_then(|trim| trim.end.as_ref()),
+        ),
+        scalar(
+            PoseProperty::TrimOffset,
+            trim.and_then(|trim| trim.offset.as_ref()),
+        ),
+    ]
+    .into_iter()
+    .flatten()
+    .collect::<Vec<_>>();
+    for (index, stop) in target
+        .gradient
+        .iter()
+        .flat_map(|gradient| gradient.stops.iter())
+        .enumerate()
+    {
+        channels.extend(color(
+            PoseProperty::GradientStopColor(index),
+            stop.color.as_ref(),
+        ));
+        channels.extend(scalar(
+            PoseProperty::GradientStopPosition(index),
+            stop.position.as_ref(),
+        ));
+    }
+    channels
+}
 
+/// Declared channels; an outline counts once, since its vertex count is
+/// only known after resampling.
 pub(super) fn count(target: &PoseTargetSpec) -> u64 {
-    POSE_PROPERTIES
-        .into_iter()
-        .filter(|property| property.expression(target).is_some())
-        .count() as u64
+    channels(target).len() as u64 + u64::from(target.outline.is_some())
 }
 
 pub(super) fn resolve_target_values(
@@ -176,10 +407,7 @@ pub(super) fn resolve_target_values(
     runtime_objects: &[MotionRuntimeObject<'_>],
     values: &mut PoseValues,
 ) -> Result<(), AuthoringDiagnostic> {
-    for property in POSE_PROPERTIES {
-        let Some(expression) = property.expression(target) else {
-            continue;
-        };
+    for (property, expression) in channels(target) {
         let path = property.authored_path(target_path);
         let runtime_object = match target_for_property(runtime_objects, property) {
             PropertyTarget::Unsupported => {
@@ -189,7 +417,7 @@ pub(super) fn resolve_target_values(
                     format!(
                         "motion target '{}' does not resolve to an object that supports property '{}'",
                         target.target,
-                        property.name()
+                        property.label()
                     ),
                 ));
             }
@@ -200,15 +428,20 @@ pub(super) fn resolve_target_values(
                     format!(
                         "motion target '{}' resolves to more than one object that supports property '{}'",
                         target.target,
-                        property.name()
+                        property.label()
                     ),
                 ));
             }
             PropertyTarget::Unique(runtime_object) => runtime_object,
         };
         let value = property.evaluate(expression, &path, spec)?;
+        let channel = PoseChannel {
+            value,
+            authored_id: target.target.clone(),
+            authored_path: path.clone(),
+        };
         if values
-            .insert((runtime_object.runtime_name.to_owned(), property), value)
+            .insert((runtime_object.runtime_name.to_owned(), property), channel)
             .is_some()
         {
             return Err(AuthoringDiagnostic::new(
@@ -217,7 +450,7 @@ pub(super) fn resolve_target_values(
                 format!(
                     "motion target '{}' declares property '{}' more than once",
                     target.target,
-                    property.name()
+                    property.label()
                 ),
             ));
         }
@@ -264,9 +497,14 @@ mod tests {
             opacity: Some(literal(0.75, Unit::Scalar)),
             width: Some(literal(80.0, Unit::Px)),
             height: None,
+            corner_radius: None,
+            fill: Some("#FF0000".to_string()),
+            stroke: None,
+            gradient: None,
+            outline: None,
         };
 
-        assert_eq!(count(&target), 4);
+        assert_eq!(count(&target), 5);
     }
 
     #[test]
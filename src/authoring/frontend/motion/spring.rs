@@ -0,0 +1,146 @@
+use super::super::super::deterministic_math::{exp, sin_cos, sqrt};
+
+pub(super) const DEFAULT_SPRING_TOLERANCE: f64 = 0.001;
+pub(super) const MIN_SPRING_TOLERANCE: f64 = 0.0001;
+pub(super) const MAX_SPRING_TOLERANCE: f64 = 0.1;
+pub(super) const MAX_SPRING_SEGMENT_FRAMES: u64 = 10_000;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(super) struct Spring {
+    pub(super) stiffness: f64,
+    pub(super) damping: f64,
+    pub(super) mass: f64,
+    pub(super) tolerance: f64,
+}
+
+impl Spring {
+    /// Progress of a spring released at rest from 0 towards 1, `seconds`
+    /// after release.
+    fn progress(self, seconds: f64) -> f64 {
+        let natural = sqrt(self.stiffness / self.mass);
+        let ratio = self.damping / (2.0 * sqrt(self.stiffness * self.mass));
+        if ratio < 1.0 {
+            let damped = natural * sqrt(1.0 - ratio * ratio);
+            let (sine, cosine) = sin_cos(damped * seconds);
+            1.0 - exp(-ratio * natural * seconds) * (cosine + ratio * natural / damped * sine)
+        } else if ratio == 1.0 {
+            1.0 - exp(-natural * seconds) * (1.0 + natural * seconds)
+        } else {
+            let spread = natural * sqrt(ratio * ratio - 1.0);
+            let slow = -ratio * natural + spread;
+            let fast = -ratio * natural - spread;
+            1.0 - (fast * exp(slow * seconds) - slow * exp(fast * seconds)) / (fast - slow)
+        }
+    }
+
+    /// Whether the spring is within `tolerance` of its target at the end of a
+    /// segment `frames` long, so snapping onto the next keyframe is invisible.
+    pub(super) fn settles_within(self, frames: u64, fps: u64) -> bool {
+        (self.progress(frames as f64 / fps as f64) - 1.0).abs() <= self.tolerance
+    }
+
+    /// Samples the spring once per frame across a segment `frames` long and
+    /// returns the interior `(frame offset, progress)` keyframes a linear
+    /// interpolation needs to stay within `tolerance` of every sample. The
+    /// segment ends exactly on its target, as the next keyframe declares it.
+    pub(super) fn bake(self, frames: u64, fps: u64) -> Vec<(u64, f64)> {
+        let samples = (0..=frames)
+            .map(|frame| match frame {
+                0 => 0.0,
+                frame if frame == frames => 1.0,
+                frame => self.progress(frame as f64 / fps as f64),
+            })
+            .collect::<Vec<_>>();
+
+        // Each kept keyframe anchors a cone of slopes that keeps every sample
+        // passed since it within tolerance; the sample that leaves the cone
+        // promotes its predecessor to the next anchor.
+        let mut kept = Vec::new();
+        let mut anchor = 0;
+        let mut lowest = f64::NEG_INFINITY;
+        let mut highest = f64::INFINITY;
+        let mut index = 1;
+        while index < samples.len() {
+            let run = (index - anchor) as f64;
+            let slope = (samples[index] - samples[anchor]) / run;
+            if slope < lowest || slope > highest {
+                anchor = index - 1;
+                kept.push((anchor as u64, samples[anchor]));
+                lowest = f64::NEG_INFINITY;
+                highest = f64::INFINITY;
+                continue;
+            }
+            lowest = lowest.max((samples[index] - self.tolerance - samples[anchor]) / run);
+            highest = highest.min((samples[index] + self.tolerance - samples[anchor]) / run);
+            index += 1;
+        }
+        kept
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spring(damping: f64) -> Spring {
+        Spring {
+            stiffness: 170.0,
+            damping,
+            mass: 1.0,
+            tolerance: DEFAULT_SPRING_TOLERANCE,
+        }
+    }
+
+    fn linear_at(keyframes: &[(u64, f64)], frame: u64) -> f64 {
+        let (from, to) = keyframes
+            .windows(2)
+            .map(|pair| (pair[0], pair[1]))
+            .find(|(from, to)| (from.0..=to.0).contains(&frame))
+            .expect("frame inside the baked segment");
+        let t = (frame - from.0) as f64 / (to.0 - from.0) as f64;
+        from.1 + (to.1 - from.1) * t
+    }
+
+    #[test]
+    fn every_damping_regime_starts_at_rest_and_approaches_the_target() {
+        for damping in [8.0, 2.0 * 170.0_f64.sqrt(), 60.0] {
+            let spring = spring(damping);
+            assert_eq!(spring.progress(0.0), 0.0);
+            assert!(
+                (spring.progress(4.0) - 1.0).abs() < 1e-3,
+                "damping {damping}"
+            );
+        }
+        assert!(
+            spring(8.0).progress(0.25) > 1.0,
+            "underdamped springs overshoot"
+        );
+    }
+
+    #[test]
+    fn short_segments_leave_the_spring_unsettled() {
+        let spring = spring(12.0);
+        assert!(!spring.settles_within(30, 60));
+        assert!(spring.settles_within(90, 60));
+    }
+
+    #[test]
+    fn baked_keyframes_stay_within_tolerance_of_every_sample() {
+        let spring = spring(12.0);
+        let (frames, fps) = (90, 60);
+        let baked = spring.bake(frames, fps);
+        assert!(!baked.is_empty() && baked.len() < frames as usize / 2);
+        assert_eq!(baked, spring.bake(frames, fps));
+
+        let mut keyframes = vec![(0, 0.0)];
+        keyframes.extend(&baked);
+        keyframes.push((frames, 1.0));
+        for frame in 1..frames {
+            let sample = spring.progress(frame as f64 / fps as f64);
+            assert!(
+                (linear_at(&keyframes, frame) - sample).abs() <= spring.tolerance + 1e-12,
+                "frame {frame}"
+            );
+        }
+    }
+}
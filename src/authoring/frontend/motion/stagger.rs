@@ -0,0 +1,205 @@
+use super::super::super::spec::{
+    AuthoringDiagnostic, AuthoringSpec, LoweredAuthoring, Quantity, ScalarExpr, StaggerOffsetSpec,
+    StaggerSpec, Unit,
+};
+use super::timing::evaluate_frame_value;
+
+const OFFSET_MESSAGE: &str = "stagger offsets must resolve to non-negative whole frame counts";
+/// Names an expression offset binds per copy; authored parameters may not
+/// reuse them.
+const STAGGER_BINDINGS: [&str; 2] = ["copy_index", "copy_count"];
+
+pub(super) struct StaggerCopy {
+    /// Authored id of the generated cell; copy-relative pose targets resolve
+    /// beneath it.
+    pub(super) prefix: String,
+    pub(super) offset: u64,
+}
+
+/// Lists the copies a pattern node generated, in placement order, from the
+/// source-map entries its item produced.
+pub(super) fn resolve_copies(
+    spec: &AuthoringSpec,
+    lowered: &LoweredAuthoring,
+    stagger: &StaggerSpec,
+    stagger_path: &str,
+) -> Result<Vec<StaggerCopy>, AuthoringDiagnostic> {
+    let pattern_path = format!("{stagger_path}.pattern");
+    let pattern = lowered
+        .source_map
+        .entries
+        .iter()
+        .find(|entry| {
+            entry.authored_id == stagger.pattern
+                && entry.authored_path.starts_with("$.visual.nodes[")
+        })
+        .ok_or_else(|| {
+            AuthoringDiagnostic::new(
+                &pattern_path,
+                "unknown_stagger_pattern",
+                format!("visual node '{}' is not defined", stagger.pattern),
+            )
+        })?;
+    let item_path = format!("{}.item", pattern.authored_path);
+    let copy_prefix = format!("{}/", stagger.pattern);
+    let prefixes = lowered
+        .source_map
+        .entries
+        .iter()
+        .filter(|entry| {
+            entry.authored_path == item_path && entry.authored_id.starts_with(&copy_prefix)
+        })
+        .filter_map(|entry| entry.authored_id.rsplit_once('/'))
+        .map(|(prefix, _)| prefix.to_string())
+        .collect::<Vec<_>>();
+    if prefixes.is_empty() {
+        return Err(AuthoringDiagnostic::new(
+            pattern_path,
+            "unknown_stagger_pattern",
+            format!(
+                "visual node '{}' is not a grid, radial, distribute, along-path or mirror pattern",
+                stagger.pattern
+            ),
+        ));
+    }
+
+    let offsets = copy_offsets(
+        spec,
+        &stagger.offset,
+        prefixes.len(),
+        &format!("{stagger_path}.offset"),
+    )?;
+    Ok(prefixes
+        .into_iter()
+        .zip(offsets)
+        .map(|(prefix, offset)| StaggerCopy { prefix, offset })
+        .collect())
+}
+
+fn copy_offsets(
+    spec: &AuthoringSpec,
+    offset: &StaggerOffsetSpec,
+    count: usize,
+    offset_path: &str,
+) -> Result<Vec<u64>, AuthoringDiagnostic> {
+    let (step_frames, rank): (&ScalarExpr, fn(usize, usize) -> usize) = match offset {
+        StaggerOffsetSpec::Linear { step_frames } => (step_frames, |index, _| index),
+        StaggerOffsetSpec::Reverse { step_frames } => {
+            (step_frames, |index, count| count - 1 - index)
+        }
+        StaggerOffsetSpec::FromCenter { step_frames } => (step_frames, |index, count| {
+            (2 * index).abs_diff(count - 1) / 2
+        }),
+        StaggerOffsetSpec::Expression { frames } => {
+            if let Some(name) = STAGGER_BINDINGS
+                .into_iter()
+                .find(|name| spec.parameters.contains_key(*name))
+            {
+                return Err(AuthoringDiagnostic::new(
+                    format!("$.parameters.{name}"),
+                    "invalid_parameter",
+                    format!(
+                        "parameter '{name}' is reserved; stagger offset expressions bind it per copy"
+                    ),
+                ));
+            }
+            let path = format!("{offset_path}.frames");
+            let mut scope = spec.parameters.clone();
+            scope.insert(
+                "copy_count".to_string(),
+                Quantity {
+                    value: count as f64,
+                    unit: Unit::Scalar,
+                },
+            );
+            return (0..count)
+                .map(|index| {
+                    scope.insert(
+                        "copy_index".to_string(),
+                        Quantity {
+                            value: index as f64,
+                            unit: Unit::Scalar,
+                        },
+                    );
+                    evaluate_frame_value(
+                        frames,
+                        &path,
+                        &scope,
+                        "invalid_stagger_offset",
+                        OFFSET_MESSAGE,
+                    )
+                })
+                .collect();
+        }
+    };
+
+    let path = format!("{offset_path}.step_frames");
+    let step = evaluate_frame_value(
+        step_frames,
+        &path,
+        &spec.parameters,
+        "invalid_stagger_offset",
+        OFFSET_MESSAGE,
+    )?;
+    (0..count)
+        .map(|index| {
+            step.checked_mul(rank(index, count) as u64).ok_or_else(|| {
+                AuthoringDiagnostic::new(&path, "invalid_stagger_offset", OFFSET_MESSAGE)
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn offsets(offset: serde_json::Value, count: usize) -> Vec<u64> {
+        let spec = serde_json::from_value::<AuthoringSpec>(serde_json::json!({
+            "authoring_format_version": 0,
+            "artboard": {
+                "id": "stage",
+                "width": { "value": 100.0, "unit": "px" },
+                "height": { "value": 100.0, "unit": "px" }
+            },
+            "visual": {},
+            "motion": {},
+            "behavior": {}
+        }))
+        .expect("minimal spec");
+        let offset = serde_json::from_value(offset).expect("stagger offset");
+        copy_offsets(&spec, &offset, count, "$.offset").expect("offsets")
+    }
+
+    fn step(kind: &str) -> serde_json::Value {
+        serde_json::json!({
+            "kind": kind,
+            "step_frames": { "kind": "literal", "value": 3.0, "unit": "scalar" }
+        })
+    }
+
+    #[test]
+    fn ordered_offsets_rank_copies_by_placement() {
+        assert_eq!(offsets(step("linear"), 4), [0, 3, 6, 9]);
+        assert_eq!(offsets(step("reverse"), 4), [9, 6, 3, 0]);
+        assert_eq!(offsets(step("from_center"), 5), [6, 3, 0, 3, 6]);
+        assert_eq!(offsets(step("from_center"), 4), [3, 0, 0, 3]);
+    }
+
+    #[test]
+    fn expression_offsets_bind_the_copy_index_and_count() {
+        let expression = serde_json::json!({
+            "kind": "expression",
+            "frames": {
+                "kind": "add",
+                "left": {
+                    "kind": "multiply",
+                    "value": { "kind": "parameter", "name": "copy_index" },
+                    "factor": 2.0
+                },
+                "right": { "kind": "parameter", "name": "copy_count" }
+            }
+        });
+        assert_eq!(offsets(expression, 3), [3, 5, 7]);
+    }
+}
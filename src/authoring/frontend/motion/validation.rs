@@ -1,5 +1,6 @@
 use std::collections::{HashMap, HashSet};
 
+use super::super::super::limits::MAX_EXPANDED_MOTION_KEYFRAMES;
 use super::super::super::spec::{AuthoringDiagnostic, MotionInterpolation, MotionSection};
 use super::super::validate_id;
 use super::property;
@@ -9,7 +10,6 @@ const MAX_POSES: usize = 1_000;
 const MAX_POSE_TARGETS: usize = 1_000;
 const MAX_TRACKS: usize = 1_000;
 const MAX_TRACK_KEYFRAMES: usize = 1_000;
-const MAX_EXPANDED_MOTION_KEYFRAMES: u64 = 10_000;
 const MAX_FPS: u64 = 240;
 
 pub(in crate::authoring::frontend) fn validate_motion(
@@ -93,7 +93,7 @@ pub(in crate::authoring::frontend) fn validate_motion(
                 diagnostics.push(AuthoringDiagnostic::new(
                     target_path,
                     "empty_pose_target",
-                    "pose targets must declare at least one transform, opacity, width, or height property",
+                    "pose targets must declare at least one animatable property",
                 ));
             }
             property_count = property_count.saturating_add(target_property_count);
@@ -141,6 +141,15 @@ pub(in crate::authoring::frontend) fn validate_motion(
             "motion keyframe count",
             &mut diagnostics,
         );
+        if let Some(stagger) = &track.stagger
+            && stagger.pattern.trim().is_empty()
+        {
+            diagnostics.push(AuthoringDiagnostic::new(
+                format!("{track_path}.stagger.pattern"),
+                "invalid_stagger_pattern",
+                "stagger pattern must not be empty",
+            ));
+        }
         for (keyframe_index, keyframe) in track.keyframes.iter().enumerate() {
             let keyframe_path = format!("{track_path}.keyframes[{keyframe_index}]");
             if !pose_ids.contains(keyframe.pose.as_str()) {
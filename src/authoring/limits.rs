@@ -1,12 +1,18 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
-use super::spec::{AuthoringDiagnostic, AuthoringError, AuthoringSpec, ComponentSpec, PaintSpec};
-use super::visual::{PatternNodeRef, VisualNode};
+use super::spec::{
+    AuthoringDiagnostic, AuthoringError, AuthoringSpec, ComponentParameter, ComponentSpec,
+    PaintSpec, TypedParameter,
+};
+use super::visual::{PathDataSpec, PatternNodeRef, VisualNode};
 
 const MAX_COMPONENT_EXPANSION_DEPTH: usize = 64;
 const MAX_GENERATED_COMPONENT_NODES: u64 = 10_000;
 const MAX_PATTERN_AXIS_COUNT: u64 = 100;
 const MAX_GENERATED_PATTERN_NODES: u64 = 10_000;
+/// Canonical property-keyframe values one document's typed motion may expand
+/// to, counting every copy a staggered track replays on.
+pub(crate) const MAX_EXPANDED_MOTION_KEYFRAMES: u64 = 10_000;
 
 #[derive(Clone, Copy)]
 struct ComponentRef<'a> {
@@ -28,6 +34,9 @@ struct ExpansionContext {
     component_budget_path: Option<String>,
     generated_by_pattern: bool,
     pattern_budget_path: Option<String>,
+    /// Gradient stop counts of the paint arguments the enclosing component
+    /// expands with, so referenced paints are charged like literal ones.
+    paint_arguments: BTreeMap<String, u64>,
 }
 
 struct WorkItem<'a> {
@@ -69,7 +78,17 @@ pub(crate) fn validate_expansion_limits(spec: &AuthoringSpec) -> Result<(), Auth
         Vec::new(),
         &components,
         &mut ExpansionBudget::default(),
-    )
+    )?;
+    for (index, artboard) in spec.artboards.iter().enumerate() {
+        validate_nodes(
+            &artboard.visual.nodes,
+            &format!("$.artboards[{index}].visual.nodes"),
+            Vec::new(),
+            &components,
+            &mut ExpansionBudget::default(),
+        )?;
+    }
+    Ok(())
 }
 
 fn validate_nodes<'a>(
@@ -87,6 +106,7 @@ fn validate_nodes<'a>(
         component_budget_path: None,
         generated_by_pattern: false,
         pattern_budget_path: None,
+        paint_arguments: BTreeMap::new(),
     };
     push_nodes(&mut work, nodes, list_path, &root_expansion);
 
@@ -100,7 +120,7 @@ fn validate_nodes<'a>(
         validate_repeatable_pattern_node(node, &path, &expansion)?;
         let generated_nodes = expansion
             .multiplicity
-            .saturating_mul(generated_node_weight(node));
+            .saturating_mul(generated_node_weight(node, &expansion.paint_arguments));
         charge_generated_nodes(budget, &expansion, &path, generated_nodes)?;
 
         if let Some(children) = node.children() {
@@ -124,7 +144,12 @@ fn validate_nodes<'a>(
             continue;
         }
 
-        let VisualNode::Instance { component, .. } = node else {
+        let VisualNode::Instance {
+            component,
+            overrides,
+            ..
+        } = node
+        else {
             continue;
         };
         let Some(component_ref) = components.get(component.as_str()).copied() else {
@@ -146,6 +171,13 @@ fn validate_nodes<'a>(
         let mut next_expansion = expansion;
         next_expansion.active_components.push(component_ref.index);
         next_expansion.generated_by_component = true;
+        next_expansion.paint_arguments = component_ref
+            .spec
+            .parameters
+            .iter()
+            .chain(overrides)
+            .map(|(name, value)| (name.clone(), parameter_paint_child_count(value)))
+            .collect();
         if next_expansion.component_budget_path.is_none() {
             next_expansion.component_budget_path = Some(format!("{path}.component"));
         }
@@ -178,27 +210,71 @@ fn validate_repeatable_pattern_node(
     Ok(())
 }
 
-fn generated_node_weight(node: &VisualNode) -> u64 {
+fn generated_node_weight(node: &VisualNode, paint_arguments: &BTreeMap<String, u64>) -> u64 {
     if let Some(shape) = node.shape() {
         return 1_u64
-            .saturating_add(paint_child_count(shape.fill))
-            .saturating_add(
-                shape
-                    .stroke
-                    .map_or(0, |stroke| paint_child_count(&stroke.paint)),
-            );
+            .saturating_add(paint_child_count(shape.fill, paint_arguments))
+            .saturating_add(shape.stroke.map_or(0, |stroke| {
+                paint_child_count(&stroke.paint, paint_arguments)
+            }));
+    }
+
+    if let Some(path) = node.path_node() {
+        return 1_u64
+            .saturating_add(path_segment_count(path.path))
+            .saturating_add(paint_child_count(path.fill, paint_arguments))
+            .saturating_add(path.stroke.map_or(0, |stroke| {
+                paint_child_count(&stroke.paint, paint_arguments)
+            }));
+    }
+
+    if let Some(skeleton) = node.skeleton_node() {
+        let bones = skeleton
+            .chains
+            .iter()
+            .map(|chain| chain.bones.len())
+            .sum::<usize>();
+        return 1_u64
+            .saturating_add(u64::try_from(bones).unwrap_or(u64::MAX))
+            .saturating_add(u64::try_from(skeleton.ik.len()).unwrap_or(u64::MAX));
     }
 
     1_u64.saturating_add(
         node.text_node()
-            .map_or(0, |text| paint_child_count(text.fill)),
+            .map_or(0, |text| paint_child_count(text.fill, paint_arguments)),
     )
 }
 
-fn paint_child_count(paint: &PaintSpec) -> u64 {
+/// Approximates the vertices a path emits: one per typed segment, or one per
+/// SVG command letter.
+fn path_segment_count(path: &PathDataSpec) -> u64 {
+    let count = match path {
+        PathDataSpec::Svg(data) => data
+            .bytes()
+            .filter(|byte| byte.is_ascii_alphabetic() && !matches!(byte, b'e' | b'E'))
+            .count(),
+        PathDataSpec::Segments(segments) => segments.len(),
+    };
+    u64::try_from(count).unwrap_or(u64::MAX)
+}
+
+fn paint_child_count(paint: &PaintSpec, paint_arguments: &BTreeMap<String, u64>) -> u64 {
     match paint {
         PaintSpec::Solid(_) => 0,
         PaintSpec::Gradient(gradient) => u64::try_from(gradient.stops.len()).unwrap_or(u64::MAX),
+        PaintSpec::Parameter(reference) => paint_arguments
+            .get(&reference.parameter)
+            .copied()
+            .unwrap_or(0),
+    }
+}
+
+fn parameter_paint_child_count(parameter: &ComponentParameter) -> u64 {
+    match parameter {
+        ComponentParameter::Typed(TypedParameter::Paint { value }) => {
+            paint_child_count(value, &BTreeMap::new())
+        }
+        _ => 0,
     }
 }
 
@@ -7,16 +7,25 @@ use crate::builder::{SceneSpec, build_scene};
 use super::expression::{evaluate_expression, evaluate_quantity};
 use super::spec::{
     AUTHORING_FORMAT_VERSION, AuthoringDiagnostic, AuthoringError, AuthoringSourceMap,
-    AuthoringSpec, ComponentSpec, LoweredAuthoring, Quantity, ScalarExpr, SourceMapEntry, Unit,
+    AuthoringSpec, ComponentParameter, ComponentSpec, LoweredAuthoring, Quantity, ScalarExpr,
+    SourceMapEntry, Unit,
 };
 use super::visual::VisualNode;
 
+mod draw;
+mod event;
 mod image;
+mod layout;
+mod nested;
+mod nine_slice;
 mod node;
 mod paint;
+mod parameter;
 mod path;
+pub(super) mod path_data;
 mod pattern;
 mod shape;
+mod skeleton;
 mod text;
 
 #[derive(Clone, Copy)]
@@ -25,11 +34,24 @@ struct ComponentRef<'a> {
     spec: &'a ComponentSpec,
 }
 
+/// Vertex counts, per contour, that morphed path nodes resample to, keyed by
+/// authored id.
+pub(super) type MorphResolutions = BTreeMap<String, Vec<usize>>;
+
 struct Lowerer<'a> {
     spec: &'a AuthoringSpec,
+    morph_resolutions: &'a MorphResolutions,
     components: HashMap<&'a str, ComponentRef<'a>>,
     source_map: AuthoringSourceMap,
     runtime_names: HashSet<String>,
+    /// Shapes that clip a sibling group, keyed by authored id.
+    clip_sources: HashMap<String, draw::ClipSource>,
+    /// The drawable role of siblings that groups clip or reorder, keyed by
+    /// authored id.
+    draw_roles: HashMap<String, &'static str>,
+    /// The SceneSpec `skin` of path nodes a skeleton deforms, keyed by
+    /// authored id.
+    skin_bindings: HashMap<String, Value>,
 }
 
 struct NodeContext<'a> {
@@ -65,6 +87,13 @@ impl PaintTarget {
 }
 
 pub fn lower_authoring(spec: &AuthoringSpec) -> Result<LoweredAuthoring, AuthoringError> {
+    lower_authoring_with(spec, &MorphResolutions::new())
+}
+
+pub(super) fn lower_authoring_with(
+    spec: &AuthoringSpec,
+    morph_resolutions: &MorphResolutions,
+) -> Result<LoweredAuthoring, AuthoringError> {
     let mut diagnostics = Vec::new();
     if spec.authoring_format_version != AUTHORING_FORMAT_VERSION {
         diagnostics.push(AuthoringDiagnostic::new(
@@ -87,7 +116,8 @@ pub fn lower_authoring(spec: &AuthoringSpec) -> Result<LoweredAuthoring, Authori
             &format!("{component_path}.id"),
             &mut diagnostics,
         );
-        validate_parameters(
+        validate_component_parameters(
+            spec,
             &component.parameters,
             &format!("{component_path}.parameters"),
             &mut diagnostics,
@@ -132,9 +162,13 @@ pub fn lower_authoring(spec: &AuthoringSpec) -> Result<LoweredAuthoring, Authori
 
     Lowerer {
         spec,
+        morph_resolutions,
         components,
         source_map: AuthoringSourceMap::default(),
         runtime_names: HashSet::new(),
+        clip_sources: HashMap::new(),
+        draw_roles: HashMap::new(),
+        skin_bindings: HashMap::new(),
     }
     .lower()
 }
@@ -173,11 +207,16 @@ impl<'a> Lowerer<'a> {
 
         let spec = self.spec;
         let mut children = Vec::with_capacity(
-            spec.font_assets.len() + spec.image_assets.len() + spec.visual.nodes.len(),
+            spec.font_assets.len()
+                + spec.image_assets.len()
+                + spec.audio_assets.len()
+                + spec.visual.nodes.len()
+                + spec.behavior.events.len(),
         );
         for (list_path, role, assets) in [
             ("$.font_assets", "font_asset", &spec.font_assets),
             ("$.image_assets", "image_asset", &spec.image_assets),
+            ("$.audio_assets", "audio_asset", &spec.audio_assets),
         ] {
             for (id, source) in assets {
                 let index = children.len();
@@ -201,6 +240,8 @@ impl<'a> Lowerer<'a> {
         }
         let visual_offset = children.len();
         let mut component_stack = Vec::new();
+        self.register_draw_references(&spec.visual.nodes, "$.visual.nodes", None)
+            .map_err(AuthoringError::one)?;
         for (index, node) in self.spec.visual.nodes.iter().enumerate() {
             let authored_path = format!("$.visual.nodes[{index}]");
             let authored_id = node.id().to_string();
@@ -222,6 +263,13 @@ impl<'a> Lowerer<'a> {
                 .map_err(AuthoringError::one)?;
             children.push(child);
         }
+        for (index, event) in spec.behavior.events.iter().enumerate() {
+            let scene_path = format!("/artboard/children/{}", children.len());
+            let child = self
+                .lower_event(event, format!("$.behavior.events[{index}]"), scene_path)
+                .map_err(AuthoringError::one)?;
+            children.push(child);
+        }
 
         let animations = self
             .lower_raw_fragments(
@@ -257,22 +305,7 @@ impl<'a> Lowerer<'a> {
             "artboard": artboard
         });
 
-        let validation_scene = without_asset_sources(&scene);
-        let scene_spec =
-            serde_json::from_value::<SceneSpec>(validation_scene).map_err(|error| {
-                AuthoringError::one(AuthoringDiagnostic::new(
-                    "$.lowered_scene",
-                    "invalid_lowered_scene",
-                    error.to_string(),
-                ))
-            })?;
-        build_scene(&scene_spec, None).map_err(|error| {
-            AuthoringError::one(AuthoringDiagnostic::new(
-                "$.lowered_scene",
-                "builder_rejected_scene",
-                error.to_string(),
-            ))
-        })?;
+        validate_lowered_scene(&scene).map_err(AuthoringError::one)?;
 
         Ok(LoweredAuthoring {
             scene,
@@ -339,6 +372,25 @@ impl<'a> Lowerer<'a> {
     }
 }
 
+pub(super) fn validate_lowered_scene(scene: &Value) -> Result<(), AuthoringDiagnostic> {
+    let validation_scene = with_nested_artboard_stubs(without_asset_sources(scene));
+    let scene_spec = serde_json::from_value::<SceneSpec>(validation_scene).map_err(|error| {
+        AuthoringDiagnostic::new(
+            "$.lowered_scene",
+            "invalid_lowered_scene",
+            error.to_string(),
+        )
+    })?;
+    build_scene(&scene_spec, None).map_err(|error| {
+        AuthoringDiagnostic::new(
+            "$.lowered_scene",
+            "builder_rejected_scene",
+            error.to_string(),
+        )
+    })?;
+    Ok(())
+}
+
 pub(super) fn evaluate_ratio_expression(
     expression: &ScalarExpr,
     path: &str,
@@ -385,6 +437,28 @@ fn validate_parameters(
     }
 }
 
+fn validate_component_parameters(
+    spec: &AuthoringSpec,
+    parameters: &BTreeMap<String, ComponentParameter>,
+    path: &str,
+    diagnostics: &mut Vec<AuthoringDiagnostic>,
+) {
+    for (name, value) in parameters {
+        if name.trim().is_empty() {
+            diagnostics.push(AuthoringDiagnostic::new(
+                path,
+                "invalid_parameter",
+                "parameter names must not be empty",
+            ));
+        }
+        if let Err(diagnostic) =
+            parameter::validate_parameter_value(spec, value, &format!("{path}.{name}"))
+        {
+            diagnostics.push(diagnostic);
+        }
+    }
+}
+
 fn validate_sibling_ids(
     nodes: &[VisualNode],
     list_path: &str,
@@ -436,7 +510,7 @@ fn validate_fragment_ids(
     }
 }
 
-fn file_asset_runtime_name(artboard_id: &str, asset_id: &str, role: &str) -> String {
+pub(super) fn file_asset_runtime_name(artboard_id: &str, asset_id: &str, role: &str) -> String {
     runtime_name(&[artboard_id.to_string(), asset_id.to_string()], role)
 }
 
@@ -448,28 +522,154 @@ fn image_asset_runtime_name(artboard_id: &str, asset_id: &str) -> String {
     file_asset_runtime_name(artboard_id, asset_id, "image_asset")
 }
 
+fn audio_asset_runtime_name(artboard_id: &str, asset_id: &str) -> String {
+    file_asset_runtime_name(artboard_id, asset_id, "audio_asset")
+}
+
 fn without_asset_sources(scene: &Value) -> Value {
     let mut validation_scene = scene.clone();
-    let Some(children) = validation_scene
-        .pointer_mut("/artboard/children")
-        .and_then(Value::as_array_mut)
-    else {
-        return validation_scene;
+    for artboard in scene_artboards_mut(&mut validation_scene) {
+        let Some(children) = artboard.get_mut("children").and_then(Value::as_array_mut) else {
+            continue;
+        };
+        for child in children {
+            let is_file_asset = matches!(
+                child.get("type").and_then(Value::as_str),
+                Some("font_asset" | "image_asset" | "audio_asset")
+            );
+            let has_string_source = child.get("source").is_some_and(Value::is_string);
+            if is_file_asset
+                && has_string_source
+                && let Some(object) = child.as_object_mut()
+            {
+                object.remove("source");
+            }
+        }
+        stub_nine_slice_sizes(artboard);
+    }
+    validation_scene
+}
+
+/// Without its source bytes a nine-slice cannot read its image's size, so
+/// the validation scene declares the smallest size its insets fit; the real
+/// size is checked when the scene is generated.
+fn stub_nine_slice_sizes(value: &mut Value) {
+    match value {
+        Value::Array(items) => items.iter_mut().for_each(stub_nine_slice_sizes),
+        Value::Object(object) => {
+            if object.get("type").and_then(Value::as_str) == Some("nine_slice")
+                && !object.contains_key("image_width")
+            {
+                let inset = |name: &str| object.get(name).and_then(Value::as_f64).unwrap_or(0.0);
+                let width = (inset("left") + inset("right")).max(0.0).floor() + 1.0;
+                let height = (inset("top") + inset("bottom")).max(0.0).floor() + 1.0;
+                object.insert("image_width".to_string(), json!(width as u64));
+                object.insert("image_height".to_string(), json!(height as u64));
+            }
+            object.values_mut().for_each(stub_nine_slice_sizes);
+        }
+        _ => {}
+    }
+}
+
+fn scene_artboards_mut(scene: &mut Value) -> Vec<&mut Value> {
+    let Some(object) = scene.as_object_mut() else {
+        return Vec::new();
+    };
+    let mut artboards = Vec::new();
+    for (key, value) in object.iter_mut() {
+        match (key.as_str(), value) {
+            ("artboard", artboard) => artboards.push(artboard),
+            ("artboards", Value::Array(list)) => artboards.extend(list.iter_mut()),
+            _ => {}
+        }
+    }
+    artboards
+}
+
+/// A single artboard is lowered before the artboards it nests exist, so the
+/// builder check sees empty stand-ins carrying the referenced animations and
+/// state machines. The assembled multi-artboard scene needs no stubs.
+fn with_nested_artboard_stubs(mut scene: Value) -> Value {
+    let mut declared = HashSet::new();
+    let mut references = BTreeMap::<String, (Vec<String>, Vec<String>)>::new();
+    for artboard in scene_artboards_mut(&mut scene) {
+        if let Some(name) = artboard.get("name").and_then(Value::as_str) {
+            declared.insert(name.to_string());
+        }
+        collect_nested_references(artboard, &mut references);
+    }
+    references.retain(|name, _| !declared.contains(name));
+    if references.is_empty() {
+        return scene;
+    }
+
+    let stubs = references
+        .into_iter()
+        .map(|(name, (animations, state_machines))| {
+            json!({
+                "name": name,
+                "width": 1.0,
+                "height": 1.0,
+                "children": [],
+                "animations": animations
+                    .into_iter()
+                    .map(|name| json!({"name": name, "fps": 60, "duration": 1, "keyframes": []}))
+                    .collect::<Vec<_>>(),
+                "state_machines": state_machines
+                    .into_iter()
+                    .map(|name| json!({"name": name, "layers": []}))
+                    .collect::<Vec<_>>()
+            })
+        });
+    let Some(object) = scene.as_object_mut() else {
+        return scene;
+    };
+    let mut artboards = match object.remove("artboard") {
+        Some(primary) => vec![primary],
+        None => match object.remove("artboards") {
+            Some(Value::Array(list)) => list,
+            _ => Vec::new(),
+        },
+    };
+    artboards.extend(stubs);
+    object.insert("artboards".to_string(), Value::Array(artboards));
+    scene
+}
+
+fn collect_nested_references(
+    value: &Value,
+    references: &mut BTreeMap<String, (Vec<String>, Vec<String>)>,
+) {
+    let Some(children) = value.get("children").and_then(Value::as_array) else {
+        return;
     };
     for child in children {
-        let is_file_asset = matches!(
-            child.get("type").and_then(Value::as_str),
-            Some("font_asset" | "image_asset")
-        );
-        let has_string_source = child.get("source").is_some_and(Value::is_string);
-        if is_file_asset
-            && has_string_source
-            && let Some(object) = child.as_object_mut()
+        if child.get("type").and_then(Value::as_str) == Some("nested_artboard")
+            && let Some(source) = child.get("source_artboard").and_then(Value::as_str)
         {
-            object.remove("source");
+            let (animations, state_machines) = references.entry(source.to_string()).or_default();
+            for playback in child
+                .get("children")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+            {
+                let Some(name) = playback.get("animation").and_then(Value::as_str) else {
+                    continue;
+                };
+                let list = match playback.get("type").and_then(Value::as_str) {
+                    Some("nested_simple_animation") => &mut *animations,
+                    Some("nested_state_machine") => &mut *state_machines,
+                    _ => continue,
+                };
+                if !list.iter().any(|existing| existing == name) {
+                    list.push(name.to_string());
+                }
+            }
         }
+        collect_nested_references(child, references);
     }
-    validation_scene
 }
 
 pub(super) fn runtime_name(segments: &[String], role: &str) -> String {
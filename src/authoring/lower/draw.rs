@@ -0,0 +1,359 @@
+use std::collections::HashSet;
+use std::collections::hash_map::Entry;
+
+use serde_json::json;
+
+use super::super::spec::AuthoringDiagnostic;
+use super::super::visual::{ClipSpec, DrawPlacement, DrawRulesSpec, VisualNode, ZOrderSpec};
+use super::{LoweredObject, Lowerer, runtime_name};
+
+const MAX_DRAW_LAYERS: usize = 100;
+
+/// How a shape that clips a sibling group lowers.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(super) struct ClipSource {
+    pub(super) inverse: bool,
+    pub(super) visible: bool,
+}
+
+/// The clip and draw-order declarations of one group.
+#[derive(Clone, Copy)]
+pub(super) struct GroupDrawing<'s> {
+    pub(super) clip: Option<&'s ClipSpec>,
+    pub(super) z_order: Option<&'s ZOrderSpec>,
+    pub(super) draw_rules: Option<&'s DrawRulesSpec>,
+}
+
+impl<'a> Lowerer<'a> {
+    /// Checks the clip shapes and draw targets the groups in one sibling list
+    /// reference, and marks the clip shapes before any sibling lowers. The
+    /// builder resolves references in file order, so each must name an
+    /// earlier sibling.
+    pub(super) fn register_draw_references(
+        &mut self,
+        nodes: &[VisualNode],
+        list_path: &str,
+        parent_id: Option<&str>,
+    ) -> Result<(), AuthoringDiagnostic> {
+        for (index, node) in nodes.iter().enumerate() {
+            let VisualNode::Group {
+                clip,
+                z_order,
+                draw_rules,
+                ..
+            } = node
+            else {
+                continue;
+            };
+            let group_path = format!("{list_path}[{index}]");
+            if z_order.is_some() && draw_rules.is_some() {
+                return Err(AuthoringDiagnostic::new(
+                    format!("{group_path}.z_order"),
+                    "conflicting_draw_order",
+                    "a group declares z_order or draw_rules, not both",
+                ));
+            }
+
+            if let Some(clip) = clip {
+                let path = format!("{group_path}.clip.shape");
+                let source = earlier_sibling(
+                    nodes,
+                    index,
+                    &clip.shape,
+                    &path,
+                    "unknown_clip_shape",
+                    "clip_shape_order",
+                )?;
+                if source.shape().is_none() && source.path_node().is_none() {
+                    return Err(AuthoringDiagnostic::new(
+                        path,
+                        "invalid_clip_shape",
+                        format!("clip shape '{}' must be a shape or path node", clip.shape),
+                    ));
+                }
+                let source_id = sibling_authored_id(parent_id, &clip.shape);
+                self.draw_roles.insert(source_id.clone(), "shape");
+                let usage = ClipSource {
+                    inverse: clip.inverse,
+                    visible: clip.visible,
+                };
+                match self.clip_sources.entry(source_id) {
+                    Entry::Vacant(slot) => {
+                        slot.insert(usage);
+                    }
+                    Entry::Occupied(slot) if *slot.get() != usage => {
+                        return Err(AuthoringDiagnostic::new(
+                            format!("{group_path}.clip"),
+                            "conflicting_clip_source",
+                            format!(
+                                "shape '{}' clips several groups with different inverse or visible settings",
+                                clip.shape
+                            ),
+                        ));
+                    }
+                    Entry::Occupied(_) => {}
+                }
+            }
+
+            if let Some(z_order) = z_order {
+                let role = earlier_drawable(
+                    nodes,
+                    index,
+                    &z_order.drawable,
+                    &format!("{group_path}.z_order.drawable"),
+                )?;
+                self.draw_roles
+                    .insert(sibling_authored_id(parent_id, &z_order.drawable), role);
+            }
+            if let Some(draw_rules) = draw_rules {
+                let rules_path = format!("{group_path}.draw_rules");
+                if draw_rules.layers.is_empty() || draw_rules.layers.len() > MAX_DRAW_LAYERS {
+                    return Err(AuthoringDiagnostic::new(
+                        format!("{rules_path}.layers"),
+                        "invalid_draw_layer_count",
+                        format!("draw rules need between 1 and {MAX_DRAW_LAYERS} layers"),
+                    ));
+                }
+                let mut layer_ids = HashSet::new();
+                for (layer_index, layer) in draw_rules.layers.iter().enumerate() {
+                    let layer_path = format!("{rules_path}.layers[{layer_index}]");
+                    if layer.id.trim().is_empty() {
+                        return Err(AuthoringDiagnostic::new(
+                            format!("{layer_path}.id"),
+                            "invalid_id",
+                            "authored ids must not be empty",
+                        ));
+                    }
+                    if !layer_ids.insert(layer.id.as_str()) {
+                        return Err(AuthoringDiagnostic::new(
+                            format!("{layer_path}.id"),
+                            "duplicate_draw_layer",
+                            format!("draw layer id '{}' is duplicated", layer.id),
+                        ));
+                    }
+                    let role = earlier_drawable(
+                        nodes,
+                        index,
+                        &layer.drawable,
+                        &format!("{layer_path}.drawable"),
+                    )?;
+                    self.draw_roles
+                        .insert(sibling_authored_id(parent_id, &layer.drawable), role);
+                }
+                if let Some(active) = &draw_rules.active
+                    && !layer_ids.contains(active.as_str())
+                {
+                    return Err(AuthoringDiagnostic::new(
+                        format!("{rules_path}.active"),
+                        "unknown_draw_layer",
+                        format!("draw layer '{active}' is not declared"),
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Lowers a group's clipping shape and draw rules, which follow its
+    /// children starting at child index `first_index`.
+    pub(super) fn lower_group_drawing(
+        &self,
+        drawing: GroupDrawing<'_>,
+        authored_path: &str,
+        authored_id: &str,
+        runtime_segments: &[String],
+        scene_path: &str,
+        first_index: usize,
+    ) -> Result<Vec<LoweredObject>, AuthoringDiagnostic> {
+        let mut lowered = Vec::new();
+        if let Some(clip) = drawing.clip {
+            let source = self.sibling_drawable(
+                authored_id,
+                runtime_segments,
+                &clip.shape,
+                &format!("{authored_path}.clip.shape"),
+                "unknown_clip_shape",
+            )?;
+            let name = runtime_name(runtime_segments, "clip");
+            let mut object = json!({
+                "type": "clipping_shape",
+                "name": name,
+                "source": source
+            });
+            if clip.inverse {
+                // The source carries a cover rectangle; even-odd filling
+                // subtracts the shape from it.
+                object["fill_rule"] = json!("evenodd");
+            }
+            lowered.push(LoweredObject {
+                object,
+                runtime_names: vec![name],
+                scene_paths: vec![format!("{scene_path}/children/{}", first_index)],
+            });
+        }
+
+        let layers = match (drawing.z_order, drawing.draw_rules) {
+            (Some(z_order), _) => vec![(
+                "z_order".to_string(),
+                format!("{authored_path}.z_order"),
+                z_order.drawable.as_str(),
+                z_order.placement,
+            )],
+            (None, Some(draw_rules)) => draw_rules
+                .layers
+                .iter()
+                .enumerate()
+                .map(|(index, layer)| {
+                    (
+                        format!("draw_layer_{index}"),
+                        format!("{authored_path}.draw_rules.layers[{index}]"),
+                        layer.drawable.as_str(),
+                        layer.placement,
+                    )
+                })
+                .collect(),
+            (None, None) => return Ok(lowered),
+        };
+        let active = drawing
+            .draw_rules
+            .and_then(|draw_rules| {
+                let active = draw_rules.active.as_ref()?;
+                draw_rules
+                    .layers
+                    .iter()
+                    .position(|layer| layer.id == *active)
+            })
+            .unwrap_or(0);
+
+        let rules_name = runtime_name(runtime_segments, "draw_rules");
+        let rules_path = format!("{scene_path}/children/{}", first_index + lowered.len());
+        let mut runtime_names = vec![rules_name.clone()];
+        let mut scene_paths = vec![rules_path.clone()];
+        let mut targets = Vec::with_capacity(layers.len());
+        for (index, (role, layer_path, drawable, placement)) in layers.into_iter().enumerate() {
+            let drawable = self.sibling_drawable(
+                authored_id,
+                runtime_segments,
+                drawable,
+                &format!("{layer_path}.drawable"),
+                "unknown_draw_target",
+            )?;
+            let name = runtime_name(runtime_segments, &role);
+            runtime_names.push(name.clone());
+            scene_paths.push(format!("{rules_path}/children/{index}"));
+            targets.push(json!({
+                "type": "draw_target",
+                "name": name,
+                "drawable": drawable,
+                "placement_value": match placement {
+                    DrawPlacement::Before => 0,
+                    DrawPlacement::After => 1,
+                }
+            }));
+        }
+        lowered.push(LoweredObject {
+            object: json!({
+                "type": "draw_rules",
+                "name": rules_name,
+                "draw_target": runtime_names[active + 1],
+                "children": targets
+            }),
+            runtime_names,
+            scene_paths,
+        });
+        Ok(lowered)
+    }
+
+    /// The runtime name of the drawable a sibling lowered to. Siblings are
+    /// registered before their list lowers; a group generated by a pattern
+    /// has no siblings to reference.
+    fn sibling_drawable(
+        &self,
+        authored_id: &str,
+        runtime_segments: &[String],
+        sibling: &str,
+        path: &str,
+        unknown_code: &str,
+    ) -> Result<String, AuthoringDiagnostic> {
+        let parent_id = authored_id.rsplit_once('/').map(|(parent, _)| parent);
+        let role = self
+            .draw_roles
+            .get(&sibling_authored_id(parent_id, sibling))
+            .ok_or_else(|| {
+                AuthoringDiagnostic::new(
+                    path,
+                    unknown_code,
+                    format!("sibling '{sibling}' is not defined"),
+                )
+            })?;
+        let mut segments = runtime_segments[..runtime_segments.len() - 1].to_vec();
+        segments.push(sibling.to_string());
+        Ok(runtime_name(&segments, role))
+    }
+}
+
+fn sibling_authored_id(parent_id: Option<&str>, id: &str) -> String {
+    match parent_id {
+        Some(parent_id) => format!("{parent_id}/{id}"),
+        None => id.to_string(),
+    }
+}
+
+fn earlier_sibling<'n>(
+    nodes: &'n [VisualNode],
+    index: usize,
+    id: &str,
+    path: &str,
+    unknown_code: &str,
+    order_code: &str,
+) -> Result<&'n VisualNode, AuthoringDiagnostic> {
+    let position = nodes
+        .iter()
+        .position(|node| node.id() == id)
+        .ok_or_else(|| {
+            AuthoringDiagnostic::new(path, unknown_code, format!("sibling '{id}' is not defined"))
+        })?;
+    if position >= index {
+        return Err(AuthoringDiagnostic::new(
+            path,
+            order_code,
+            format!("sibling '{id}' must be declared before the group that references it"),
+        ));
+    }
+    Ok(&nodes[position])
+}
+
+fn earlier_drawable(
+    nodes: &[VisualNode],
+    index: usize,
+    id: &str,
+    path: &str,
+) -> Result<&'static str, AuthoringDiagnostic> {
+    let node = earlier_sibling(
+        nodes,
+        index,
+        id,
+        path,
+        "unknown_draw_target",
+        "draw_target_order",
+    )?;
+    if node.shape().is_some() || node.path_node().is_some() {
+        Ok("shape")
+    } else if node.text_node().is_some() {
+        Ok("text")
+    } else if node.image_node().is_some() {
+        Ok("image")
+    } else if node.nine_slice_node().is_some() {
+        Ok("nine_slice")
+    } else if node.nested_artboard_node().is_some() {
+        Ok("nested_artboard")
+    } else {
+        Err(AuthoringDiagnostic::new(
+            path,
+            "invalid_draw_target",
+            format!(
+                "draw target '{id}' must be a shape, path, text, image, nine-slice or nested artboard node"
+            ),
+        ))
+    }
+}
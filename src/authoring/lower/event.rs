@@ -0,0 +1,107 @@
+use serde_json::{Value, json};
+
+use super::super::spec::{
+    AuthoringDiagnostic, EventPropertySpec, EventSpec, OpenUrlTarget, SourceMapEntry,
+};
+use super::{Lowerer, audio_asset_runtime_name};
+
+impl<'a> Lowerer<'a> {
+    /// Lowers a `behavior.events` entry. Hosts match events and their
+    /// properties by name, so both keep their authored spelling.
+    pub(super) fn lower_event(
+        &mut self,
+        event: &EventSpec,
+        authored_path: String,
+        scene_path: String,
+    ) -> Result<Value, AuthoringDiagnostic> {
+        let id = event.id();
+        let mut lowered = match event {
+            EventSpec::General { .. } => json!({ "type": "event", "name": id }),
+            EventSpec::OpenUrl { url, target, .. } => {
+                if url.trim().is_empty() {
+                    return Err(AuthoringDiagnostic::new(
+                        format!("{authored_path}.url"),
+                        "invalid_event_url",
+                        "open_url events need a non-empty url",
+                    ));
+                }
+                json!({
+                    "type": "open_url_event",
+                    "name": id,
+                    "url": url,
+                    "target_value": open_url_target_value(*target)
+                })
+            }
+            EventSpec::Audio { asset, .. } => {
+                if !self.spec.audio_assets.contains_key(asset) {
+                    return Err(AuthoringDiagnostic::new(
+                        format!("{authored_path}.asset"),
+                        "unknown_audio_asset",
+                        format!("audio asset '{asset}' is not declared"),
+                    ));
+                }
+                json!({
+                    "type": "audio_event",
+                    "name": id,
+                    "asset": audio_asset_runtime_name(&self.spec.artboard.id, asset)
+                })
+            }
+        };
+
+        let mut runtime_names = vec![id.to_string()];
+        let mut scene_paths = vec![scene_path.clone()];
+        let mut properties = Vec::with_capacity(event.properties().len());
+        for (key, property) in event.properties() {
+            if key.trim().is_empty() {
+                return Err(AuthoringDiagnostic::new(
+                    format!("{authored_path}.properties"),
+                    "invalid_event_property",
+                    "event property keys must not be empty",
+                ));
+            }
+            scene_paths.push(format!("{scene_path}/children/{}", properties.len()));
+            runtime_names.push(key.clone());
+            properties.push(match property {
+                EventPropertySpec::Number { value } => json!({
+                    "type": "custom_property_number",
+                    "name": key,
+                    "property_value": value
+                }),
+                EventPropertySpec::Boolean { value } => json!({
+                    "type": "custom_property_boolean",
+                    "name": key,
+                    "property_value": value
+                }),
+                EventPropertySpec::String { value } => json!({
+                    "type": "custom_property_string",
+                    "name": key,
+                    "property_value": value
+                }),
+            });
+        }
+        if !properties.is_empty()
+            && let Some(object) = lowered.as_object_mut()
+        {
+            object.insert("children".to_string(), Value::Array(properties));
+        }
+
+        self.register_runtime_names(&runtime_names, &authored_path)?;
+        self.source_map.entries.push(SourceMapEntry {
+            authored_id: id.to_string(),
+            authored_path,
+            definition_path: None,
+            runtime_names,
+            scene_paths,
+        });
+        Ok(lowered)
+    }
+}
+
+fn open_url_target_value(target: OpenUrlTarget) -> u64 {
+    match target {
+        OpenUrlTarget::Blank => 0,
+        OpenUrlTarget::Parent => 1,
+        OpenUrlTarget::SelfTarget => 2,
+        OpenUrlTarget::Top => 3,
+    }
+}
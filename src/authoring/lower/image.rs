@@ -3,6 +3,7 @@ use serde_json::{Value, json};
 use super::super::expression::evaluate_transform;
 use super::super::spec::{AuthoringDiagnostic, SourceMapEntry};
 use super::super::visual::ImageNodeRef;
+use super::parameter::literal_field;
 use super::{Lowerer, NodeContext, image_asset_runtime_name, runtime_name};
 
 impl<'a> Lowerer<'a> {
@@ -21,6 +22,7 @@ impl<'a> Lowerer<'a> {
             scope,
         } = context;
 
+        let asset = literal_field(asset, &format!("{authored_path}.asset"))?;
         if !self.spec.image_assets.contains_key(asset) {
             return Err(AuthoringDiagnostic::new(
                 format!("{authored_path}.asset"),
@@ -0,0 +1,236 @@
+use std::collections::BTreeMap;
+
+use serde_json::{Value, json};
+
+use super::super::expression::evaluate_expression;
+use super::super::spec::{AuthoringDiagnostic, Quantity, ScalarExpr, SourceMapEntry, Unit};
+use super::super::visual::{
+    LayoutAlign, LayoutDirection, LayoutJustify, LayoutNodeRef, LayoutSizeSpec,
+};
+use super::{Lowerer, NodeContext, runtime_name, validate_sibling_ids_result};
+
+// Yoga style values as `LayoutComponentStyle` stores them.
+const UNITS_POINT: u64 = 1;
+const UNITS_PERCENT: u64 = 2;
+const UNITS_AUTO: u64 = 3;
+
+/// One axis of a layout's resolved size.
+struct AxisSize {
+    units: u64,
+    length: f64,
+    grows: bool,
+}
+
+impl<'a> Lowerer<'a> {
+    /// Lowers a layout to a `layout_component` whose first child is its
+    /// `layout_component_style`. `parent_direction` is the direction of the
+    /// enclosing layout when this one is a flex item of it.
+    pub(super) fn lower_layout(
+        &mut self,
+        layout: LayoutNodeRef<'_>,
+        context: NodeContext<'_>,
+        component_stack: &mut Vec<String>,
+        parent_direction: Option<LayoutDirection>,
+    ) -> Result<Value, AuthoringDiagnostic> {
+        let LayoutNodeRef {
+            direction,
+            gap,
+            padding,
+            align,
+            justify,
+            wrap,
+            width,
+            height,
+            clip,
+            children,
+        } = layout;
+        let NodeContext {
+            authored_path,
+            definition_path,
+            authored_id,
+            runtime_segments,
+            scene_path,
+            scope,
+        } = context;
+
+        validate_sibling_ids_result(children, &format!("{authored_path}.children"))?;
+        self.register_draw_references(
+            children,
+            &format!("{authored_path}.children"),
+            Some(&authored_id),
+        )?;
+        let gap = layout_length(gap, &format!("{authored_path}.gap"), scope)?;
+        let [top, right, bottom, left] = [
+            ("top", &padding.top),
+            ("right", &padding.right),
+            ("bottom", &padding.bottom),
+            ("left", &padding.left),
+        ]
+        .map(|(side, value)| {
+            layout_length(
+                value.as_ref(),
+                &format!("{authored_path}.padding.{side}"),
+                scope,
+            )
+        });
+        let width = resolve_axis(
+            width,
+            &format!("{authored_path}.width"),
+            scope,
+            parent_direction.map(|parent| parent == LayoutDirection::Row),
+        )?;
+        let height = resolve_axis(
+            height,
+            &format!("{authored_path}.height"),
+            scope,
+            parent_direction.map(|parent| parent == LayoutDirection::Column),
+        )?;
+
+        let layout_name = runtime_name(&runtime_segments, "layout");
+        let style_name = runtime_name(&runtime_segments, "layout_style");
+        let runtime_names = vec![layout_name.clone(), style_name.clone()];
+        self.register_runtime_names(&runtime_names, &format!("{authored_path}.id"))?;
+        self.source_map.entries.push(SourceMapEntry {
+            authored_id: authored_id.clone(),
+            authored_path: authored_path.clone(),
+            definition_path: definition_path.clone(),
+            runtime_names,
+            scene_paths: vec![scene_path.clone(), format!("{scene_path}/children/0")],
+        });
+
+        let mut lowered_children = Vec::with_capacity(children.len() + 1);
+        lowered_children.push(json!({
+            "type": "layout_component_style",
+            "name": style_name,
+            "flex_direction": match direction {
+                LayoutDirection::Column => 0,
+                LayoutDirection::Row => 2,
+            },
+            "flex_wrap": u64::from(wrap),
+            "align_items": match align {
+                LayoutAlign::Start => 1,
+                LayoutAlign::Center => 2,
+                LayoutAlign::End => 3,
+                LayoutAlign::Stretch => 4,
+            },
+            "justify_content": match justify {
+                LayoutJustify::Start => 0,
+                LayoutJustify::Center => 1,
+                LayoutJustify::End => 2,
+                LayoutJustify::SpaceBetween => 3,
+                LayoutJustify::SpaceAround => 4,
+                LayoutJustify::SpaceEvenly => 5,
+            },
+            "gap_horizontal": gap,
+            "gap_vertical": gap,
+            "padding_top": top?,
+            "padding_right": right?,
+            "padding_bottom": bottom?,
+            "padding_left": left?,
+            "width_units": width.units,
+            "height_units": height.units,
+            "flex_grow": if width.grows || height.grows { 1.0 } else { 0.0 }
+        }));
+        for (index, child) in children.iter().enumerate() {
+            let child_context = NodeContext {
+                authored_path: format!("{authored_path}.children[{index}]"),
+                definition_path: definition_path
+                    .as_ref()
+                    .map(|path| format!("{path}.children[{index}]")),
+                authored_id: format!("{authored_id}/{}", child.id()),
+                runtime_segments: {
+                    let mut segments = runtime_segments.clone();
+                    segments.push(child.id().to_string());
+                    segments
+                },
+                scene_path: format!("{scene_path}/children/{}", index + 1),
+                scope,
+            };
+            // Only direct child layouts are flex items; everything else keeps
+            // its own transform inside the layout.
+            let lowered = match child.layout_node() {
+                Some(child_layout) => self.lower_layout(
+                    child_layout,
+                    child_context,
+                    component_stack,
+                    Some(direction),
+                )?,
+                None => self.lower_node(child, child_context, component_stack)?,
+            };
+            lowered_children.push(lowered);
+        }
+
+        Ok(json!({
+            "type": "layout_component",
+            "name": layout_name,
+            "style": style_name,
+            "clip": clip,
+            "width": width.length,
+            "height": height.length,
+            "children": lowered_children
+        }))
+    }
+}
+
+fn layout_length(
+    expression: Option<&ScalarExpr>,
+    path: &str,
+    scope: &BTreeMap<String, Quantity>,
+) -> Result<f64, AuthoringDiagnostic> {
+    let Some(expression) = expression else {
+        return Ok(0.0);
+    };
+    let value = evaluate_expression(expression, path, scope, Unit::Px)?;
+    if value < 0.0 {
+        return Err(AuthoringDiagnostic::new(
+            path,
+            "invalid_dimension",
+            "layout gaps and padding must not be negative",
+        ));
+    }
+    Ok(value)
+}
+
+/// Resolves one axis. `on_main_axis` is `None` for a layout without a parent
+/// layout, where filling means the full parent size on both axes; a flex
+/// item fills its parent's main axis by growing and its cross axis by size.
+fn resolve_axis(
+    size: &LayoutSizeSpec,
+    path: &str,
+    scope: &BTreeMap<String, Quantity>,
+    on_main_axis: Option<bool>,
+) -> Result<AxisSize, AuthoringDiagnostic> {
+    match size {
+        LayoutSizeSpec::Fixed { value } => {
+            let path = format!("{path}.value");
+            let length = evaluate_expression(value, &path, scope, Unit::Px)?;
+            if length <= 0.0 {
+                return Err(AuthoringDiagnostic::new(
+                    path,
+                    "invalid_dimension",
+                    "fixed layout sizes must be greater than zero",
+                ));
+            }
+            Ok(AxisSize {
+                units: UNITS_POINT,
+                length,
+                grows: false,
+            })
+        }
+        LayoutSizeSpec::Hug => Ok(AxisSize {
+            units: UNITS_AUTO,
+            length: 0.0,
+            grows: false,
+        }),
+        LayoutSizeSpec::Fill if on_main_axis == Some(true) => Ok(AxisSize {
+            units: UNITS_AUTO,
+            length: 0.0,
+            grows: true,
+        }),
+        LayoutSizeSpec::Fill => Ok(AxisSize {
+            units: UNITS_PERCENT,
+            length: 100.0,
+            grows: false,
+        }),
+    }
+}
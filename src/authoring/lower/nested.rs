@@ -0,0 +1,206 @@
+use serde_json::{Value, json};
+
+use super::super::expression::evaluate_transform;
+use super::super::spec::{ArtboardDefinitionSpec, AuthoringDiagnostic, SourceMapEntry};
+use super::super::visual::NestedArtboardNodeRef;
+use super::{Lowerer, NodeContext, runtime_name};
+
+impl<'a> Lowerer<'a> {
+    pub(super) fn lower_nested_artboard(
+        &mut self,
+        nested: NestedArtboardNodeRef<'_>,
+        context: NodeContext<'_>,
+    ) -> Result<Value, AuthoringDiagnostic> {
+        let NestedArtboardNodeRef {
+            artboard,
+            animation,
+            state_machine,
+            transform,
+        } = nested;
+        let NodeContext {
+            authored_path,
+            definition_path,
+            authored_id,
+            runtime_segments,
+            scene_path,
+            scope,
+        } = context;
+
+        if artboard == self.spec.artboard.id {
+            return Err(AuthoringDiagnostic::new(
+                format!("{authored_path}.artboard"),
+                "nested_artboard_cycle",
+                format!("artboard '{artboard}' cannot nest itself"),
+            ));
+        }
+        let Some(source) = self
+            .spec
+            .artboards
+            .iter()
+            .find(|definition| definition.id == artboard)
+        else {
+            return Err(AuthoringDiagnostic::new(
+                format!("{authored_path}.artboard"),
+                "unknown_artboard",
+                format!("artboard '{artboard}' is not declared in $.artboards"),
+            ));
+        };
+
+        let transform_values =
+            evaluate_transform(transform, &format!("{authored_path}.transform"), scope)?;
+        let anchor_name = runtime_name(&runtime_segments, "nested_artboard_anchor");
+        let nested_name = runtime_name(&runtime_segments, "nested_artboard");
+        let nested_scene_path = format!("{scene_path}/children/0");
+        let mut runtime_names = vec![anchor_name.clone(), nested_name.clone()];
+        let mut scene_paths = vec![scene_path.clone(), nested_scene_path.clone()];
+
+        let mut playback = Vec::new();
+        if let Some(animation) = animation {
+            let target = animation_runtime_name(source, animation).ok_or_else(|| {
+                unknown_playback(
+                    &authored_path,
+                    "animation",
+                    animation,
+                    artboard,
+                    &animation_names(source),
+                )
+            })?;
+            let name = runtime_name(&runtime_segments, "nested_animation");
+            runtime_names.push(name.clone());
+            scene_paths.push(format!("{nested_scene_path}/children/{}", playback.len()));
+            playback.push(json!({
+                "type": "nested_simple_animation",
+                "name": name,
+                "animation": target,
+                "is_playing": true
+            }));
+        }
+        if let Some(state_machine) = state_machine {
+            let target = state_machine_runtime_name(source, state_machine).ok_or_else(|| {
+                unknown_playback(
+                    &authored_path,
+                    "state_machine",
+                    state_machine,
+                    artboard,
+                    &state_machine_names(source),
+                )
+            })?;
+            let name = runtime_name(&runtime_segments, "nested_state_machine");
+            runtime_names.push(name.clone());
+            scene_paths.push(format!("{nested_scene_path}/children/{}", playback.len()));
+            playback.push(json!({
+                "type": "nested_state_machine",
+                "name": name,
+                "animation": target
+            }));
+        }
+
+        self.register_runtime_names(&runtime_names, &format!("{authored_path}.id"))?;
+        self.source_map.entries.push(SourceMapEntry {
+            authored_id,
+            authored_path,
+            definition_path,
+            runtime_names,
+            scene_paths,
+        });
+
+        let mut nested = json!({
+            "type": "nested_artboard",
+            "name": nested_name,
+            "source_artboard": runtime_name(&[artboard.to_string()], "artboard")
+        });
+        if !playback.is_empty()
+            && let Some(object) = nested.as_object_mut()
+        {
+            object.insert("children".to_string(), Value::Array(playback));
+        }
+        Ok(json!({
+            "type": "node",
+            "name": anchor_name,
+            "x": transform_values.x,
+            "y": transform_values.y,
+            "rotation": transform_values.rotation,
+            "scale_x": transform_values.scale_x,
+            "scale_y": transform_values.scale_y,
+            "children": [nested]
+        }))
+    }
+}
+
+/// Typed tracks lower to `auth__<artboard>__<track>__animation`; raw
+/// animations keep the runtime name their fragment declares.
+fn animation_runtime_name(source: &ArtboardDefinitionSpec, authored: &str) -> Option<String> {
+    if source
+        .motion
+        .tracks
+        .iter()
+        .any(|track| track.id == authored)
+    {
+        return Some(runtime_name(
+            &[source.id.clone(), authored.to_string()],
+            "animation",
+        ));
+    }
+    fragment_runtime_name(&source.motion.raw_animations, authored)
+}
+
+fn state_machine_runtime_name(source: &ArtboardDefinitionSpec, authored: &str) -> Option<String> {
+    fragment_runtime_name(&source.behavior.raw_state_machines, authored)
+}
+
+fn fragment_runtime_name(
+    fragments: &[super::super::spec::RawSceneFragment],
+    authored: &str,
+) -> Option<String> {
+    fragments
+        .iter()
+        .find(|fragment| fragment.id == authored)
+        .and_then(|fragment| fragment.value.get("name"))
+        .and_then(Value::as_str)
+        .map(str::to_string)
+}
+
+fn animation_names(source: &ArtboardDefinitionSpec) -> Vec<&str> {
+    source
+        .motion
+        .tracks
+        .iter()
+        .map(|track| track.id.as_str())
+        .chain(
+            source
+                .motion
+                .raw_animations
+                .iter()
+                .map(|fragment| fragment.id.as_str()),
+        )
+        .collect()
+}
+
+fn state_machine_names(source: &ArtboardDefinitionSpec) -> Vec<&str> {
+    source
+        .behavior
+        .raw_state_machines
+        .iter()
+        .map(|fragment| fragment.id.as_str())
+        .collect()
+}
+
+fn unknown_playback(
+    authored_path: &str,
+    field: &str,
+    name: &str,
+    artboard: &str,
+    available: &[&str],
+) -> AuthoringDiagnostic {
+    let kind = field.replace('_', " ");
+    let available = if available.is_empty() {
+        "none".to_string()
+    } else {
+        available.join(", ")
+    };
+    AuthoringDiagnostic::new(
+        format!("{authored_path}.{field}"),
+        format!("unknown_nested_{field}"),
+        format!("artboard '{artboard}' has no {kind} '{name}'; available: {available}"),
+    )
+}
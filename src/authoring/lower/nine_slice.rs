@@ -0,0 +1,91 @@
+use serde_json::{Value, json};
+
+use super::super::expression::{evaluate_expression, evaluate_transform};
+use super::super::spec::{AuthoringDiagnostic, SourceMapEntry, Unit};
+use super::super::visual::NineSliceNodeRef;
+use super::parameter::literal_field;
+use super::{Lowerer, NodeContext, image_asset_runtime_name, runtime_name};
+
+impl<'a> Lowerer<'a> {
+    /// Lowers to one SceneSpec `nine_slice` without an anchor node: the
+    /// slicer keeps the corners undistorted by compensating for the image's
+    /// own scale, so the transform, and every pose on it, lands on the image.
+    pub(super) fn lower_nine_slice(
+        &mut self,
+        nine_slice: NineSliceNodeRef<'_>,
+        context: NodeContext<'_>,
+    ) -> Result<Value, AuthoringDiagnostic> {
+        let NineSliceNodeRef {
+            asset,
+            insets,
+            edges,
+            center,
+            transform,
+        } = nine_slice;
+        let NodeContext {
+            authored_path,
+            definition_path,
+            authored_id,
+            runtime_segments,
+            scene_path,
+            scope,
+        } = context;
+
+        let asset = literal_field(asset, &format!("{authored_path}.asset"))?;
+        if !self.spec.image_assets.contains_key(asset) {
+            return Err(AuthoringDiagnostic::new(
+                format!("{authored_path}.asset"),
+                "unknown_image_asset",
+                format!("image asset '{asset}' is not declared"),
+            ));
+        }
+        let mut inset_values = [0.0; 4];
+        for (value, (name, expression)) in inset_values.iter_mut().zip([
+            ("left", &insets.left),
+            ("top", &insets.top),
+            ("right", &insets.right),
+            ("bottom", &insets.bottom),
+        ]) {
+            let path = format!("{authored_path}.insets.{name}");
+            *value = evaluate_expression(expression, &path, scope, Unit::Px)?;
+            if *value < 0.0 {
+                return Err(AuthoringDiagnostic::new(
+                    path,
+                    "invalid_dimension",
+                    "nine-slice insets must not be negative",
+                ));
+            }
+        }
+        let [left, top, right, bottom] = inset_values;
+
+        let transform_values =
+            evaluate_transform(transform, &format!("{authored_path}.transform"), scope)?;
+        let name = runtime_name(&runtime_segments, "nine_slice");
+        let runtime_names = vec![name.clone()];
+        self.register_runtime_names(&runtime_names, &format!("{authored_path}.id"))?;
+        self.source_map.entries.push(SourceMapEntry {
+            authored_id,
+            authored_path,
+            definition_path,
+            runtime_names,
+            scene_paths: vec![scene_path],
+        });
+
+        Ok(json!({
+            "type": "nine_slice",
+            "name": name,
+            "asset": image_asset_runtime_name(&self.spec.artboard.id, asset),
+            "left": left,
+            "top": top,
+            "right": right,
+            "bottom": bottom,
+            "edges": edges.scene_name(),
+            "center": center.scene_name(),
+            "x": transform_values.x,
+            "y": transform_values.y,
+            "rotation": transform_values.rotation,
+            "scale_x": transform_values.scale_x,
+            "scale_y": transform_values.scale_y
+        }))
+    }
+}
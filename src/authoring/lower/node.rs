@@ -4,7 +4,12 @@ use super::super::constraint::resolve_group_constraints;
 use super::super::expression::evaluate_transform;
 use super::super::spec::{AuthoringDiagnostic, SourceMapEntry};
 use super::super::visual::VisualNode;
-use super::{Lowerer, NodeContext, collect_named_paths, runtime_name, validate_sibling_ids_result};
+use super::draw::GroupDrawing;
+use super::parameter::ComponentArguments;
+use super::{
+    LoweredObject, Lowerer, NodeContext, collect_named_paths, runtime_name,
+    validate_sibling_ids_result,
+};
 
 impl<'a> Lowerer<'a> {
     pub(super) fn lower_node(
@@ -19,12 +24,27 @@ impl<'a> Lowerer<'a> {
         if let Some(shape) = node.shape() {
             return self.lower_shape(shape, context);
         }
+        if let Some(path) = node.path_node() {
+            return self.lower_path(path, context);
+        }
         if let Some(text) = node.text_node() {
             return self.lower_text(text, context);
         }
         if let Some(image) = node.image_node() {
             return self.lower_image(image, context);
         }
+        if let Some(nine_slice) = node.nine_slice_node() {
+            return self.lower_nine_slice(nine_slice, context);
+        }
+        if let Some(nested) = node.nested_artboard_node() {
+            return self.lower_nested_artboard(nested, context);
+        }
+        if let Some(layout) = node.layout_node() {
+            return self.lower_layout(layout, context, component_stack, None);
+        }
+        if let Some(skeleton) = node.skeleton_node() {
+            return self.lower_skeleton(skeleton, context, component_stack);
+        }
 
         let NodeContext {
             authored_path,
@@ -40,11 +60,19 @@ impl<'a> Lowerer<'a> {
                 transform,
                 constraints,
                 children,
+                clip,
+                z_order,
+                draw_rules,
                 ..
             } => {
                 validate_sibling_ids_result(children, &format!("{authored_path}.children"))?;
                 let children =
                     resolve_group_constraints(children, constraints, &authored_path, scope)?;
+                self.register_draw_references(
+                    &children,
+                    &format!("{authored_path}.children"),
+                    Some(&authored_id),
+                )?;
                 let transform_values =
                     evaluate_transform(transform, &format!("{authored_path}.transform"), scope)?;
                 let wrapper_name = runtime_name(&runtime_segments, "group");
@@ -52,6 +80,7 @@ impl<'a> Lowerer<'a> {
                     std::slice::from_ref(&wrapper_name),
                     &format!("{authored_path}.id"),
                 )?;
+                let entry_index = self.source_map.entries.len();
                 self.source_map.entries.push(SourceMapEntry {
                     authored_id: authored_id.clone(),
                     authored_path: authored_path.clone(),
@@ -84,6 +113,33 @@ impl<'a> Lowerer<'a> {
                     )?);
                 }
 
+                // Clipping and draw rules follow the children, and so every
+                // sibling they reference.
+                let drawing = self.lower_group_drawing(
+                    GroupDrawing {
+                        clip: clip.as_ref(),
+                        z_order: z_order.as_ref(),
+                        draw_rules: draw_rules.as_ref(),
+                    },
+                    &authored_path,
+                    &authored_id,
+                    &runtime_segments,
+                    &scene_path,
+                    lowered_children.len(),
+                )?;
+                for LoweredObject {
+                    object,
+                    runtime_names,
+                    scene_paths,
+                } in drawing
+                {
+                    self.register_runtime_names(&runtime_names, &format!("{authored_path}.id"))?;
+                    let entry = &mut self.source_map.entries[entry_index];
+                    entry.runtime_names.extend(runtime_names);
+                    entry.scene_paths.extend(scene_paths);
+                    lowered_children.push(object);
+                }
+
                 Ok(json!({
                     "type": "node",
                     "name": wrapper_name,
@@ -117,25 +173,12 @@ impl<'a> Lowerer<'a> {
                         format!("component cycle detected: {}", cycle.join(" -> ")),
                     ));
                 }
-                for (name, quantity) in overrides {
-                    if !component_ref.spec.parameters.contains_key(name) {
-                        return Err(AuthoringDiagnostic::new(
-                            format!("{authored_path}.overrides.{name}"),
-                            "unknown_override",
-                            format!(
-                                "component '{}' has no parameter named '{name}'",
-                                component_ref.spec.id
-                            ),
-                        ));
-                    }
-                    if !quantity.value.is_finite() {
-                        return Err(AuthoringDiagnostic::new(
-                            format!("{authored_path}.overrides.{name}.value"),
-                            "non_finite",
-                            "numeric values must be finite",
-                        ));
-                    }
-                }
+                let arguments = ComponentArguments::resolve(
+                    self.spec,
+                    component_ref,
+                    overrides,
+                    &authored_path,
+                )?;
 
                 let transform_values =
                     evaluate_transform(transform, &format!("{authored_path}.transform"), scope)?;
@@ -152,9 +195,12 @@ impl<'a> Lowerer<'a> {
                     scene_paths: vec![scene_path.clone()],
                 });
 
-                let mut component_scope = self.spec.parameters.clone();
-                component_scope.extend(component_ref.spec.parameters.clone());
-                component_scope.extend(overrides.clone());
+                self.register_draw_references(
+                    &component_ref.spec.visual,
+                    &format!("{authored_path}.expanded"),
+                    Some(&authored_id),
+                )?;
+                let component_scope = arguments.numeric_scope(self.spec.parameters.clone());
                 component_stack.push(component.clone());
                 let mut lowered_children = Vec::with_capacity(component_ref.spec.visual.len());
                 for (index, child) in component_ref.spec.visual.iter().enumerate() {
@@ -167,18 +213,23 @@ impl<'a> Lowerer<'a> {
                     let mut child_runtime_segments = runtime_segments.clone();
                     child_runtime_segments.push(child.id().to_string());
                     let child_scene_path = format!("{scene_path}/children/{index}");
-                    match self.lower_node(
-                        child,
-                        NodeContext {
-                            authored_path: child_authored_path,
-                            definition_path: child_definition_path,
-                            authored_id: child_authored_id,
-                            runtime_segments: child_runtime_segments,
-                            scene_path: child_scene_path,
-                            scope: &component_scope,
-                        },
-                        component_stack,
-                    ) {
+                    let lowered = arguments
+                        .bind(child, &child_authored_path)
+                        .and_then(|child| {
+                            self.lower_node(
+                                &child,
+                                NodeContext {
+                                    authored_path: child_authored_path,
+                                    definition_path: child_definition_path,
+                                    authored_id: child_authored_id,
+                                    runtime_segments: child_runtime_segments,
+                                    scene_path: child_scene_path,
+                                    scope: &component_scope,
+                                },
+                                component_stack,
+                            )
+                        });
+                    match lowered {
                         Ok(child) => lowered_children.push(child),
                         Err(error) => {
                             component_stack.pop();
@@ -229,14 +280,21 @@ impl<'a> Lowerer<'a> {
             | VisualNode::Triangle { .. }
             | VisualNode::Polygon { .. }
             | VisualNode::Star { .. }
+            | VisualNode::Path { .. }
             | VisualNode::Text { .. }
             | VisualNode::Image { .. }
+            | VisualNode::NineSlice { .. }
+            | VisualNode::NestedArtboard { .. }
+            | VisualNode::Layout { .. }
+            | VisualNode::Skeleton { .. }
             | VisualNode::Grid { .. }
             | VisualNode::Radial { .. }
             | VisualNode::Mirror { .. }
             | VisualNode::Distribute { .. }
             | VisualNode::AlongPath { .. } => {
-                unreachable!("shape, text, image, and pattern nodes are handled above")
+                unreachable!(
+                    "shape, path, text, image, nine-slice, nested artboard, layout, skeleton and pattern nodes are handled above"
+                )
             }
         }
     }
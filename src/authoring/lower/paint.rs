@@ -6,6 +6,7 @@ use super::super::expression::evaluate_expression;
 use super::super::spec::{
     AuthoringDiagnostic, GradientKind, PaintSpec, Quantity, TrimPathMode, TrimPathSpec, Unit,
 };
+use super::parameter::{literal_field, unresolved_reference};
 use super::{LoweredObject, Lowerer, PaintTarget, evaluate_ratio_expression, runtime_name};
 
 impl<'a> Lowerer<'a> {
@@ -142,6 +143,10 @@ impl<'a> Lowerer<'a> {
                         ));
                     }
                     previous_position = Some(position);
+                    let color = literal_field(
+                        &stop.color,
+                        &format!("{authored_path}.stops[{index}].color"),
+                    )?;
 
                     let stop_name =
                         target.runtime_name(runtime_segments, &format!("gradient_stop_{index}"));
@@ -150,7 +155,7 @@ impl<'a> Lowerer<'a> {
                     children.push(json!({
                         "type": "gradient_stop",
                         "name": stop_name,
-                        "color": stop.color.as_str(),
+                        "color": color,
                         "position": position
                     }));
                 }
@@ -173,6 +178,7 @@ impl<'a> Lowerer<'a> {
                     scene_paths,
                 })
             }
+            PaintSpec::Parameter(reference) => Err(unresolved_reference(reference, authored_path)),
         }
     }
 }
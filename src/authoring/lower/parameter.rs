@@ -0,0 +1,323 @@
+use std::collections::BTreeMap;
+
+use crate::builder::parse_color;
+
+use super::super::spec::{
+    AuthoringDiagnostic, AuthoringSpec, ComponentParameter, PaintSpec, ParameterKind, ParameterRef,
+    ParameterizedString, Quantity, TypedParameter,
+};
+use super::super::visual::VisualNode;
+use super::ComponentRef;
+
+/// The parameter values one instance expands its component with: declared
+/// defaults, replaced by any overrides.
+pub(super) struct ComponentArguments<'a> {
+    values: BTreeMap<&'a str, &'a ComponentParameter>,
+}
+
+impl<'a> ComponentArguments<'a> {
+    /// Type-checks `overrides` against the component's declarations.
+    pub(super) fn resolve(
+        spec: &AuthoringSpec,
+        component_ref: ComponentRef<'a>,
+        overrides: &'a BTreeMap<String, ComponentParameter>,
+        authored_path: &str,
+    ) -> Result<Self, AuthoringDiagnostic> {
+        let mut values = component_ref
+            .spec
+            .parameters
+            .iter()
+            .map(|(name, value)| (name.as_str(), value))
+            .collect::<BTreeMap<_, _>>();
+        for (name, value) in overrides {
+            let override_path = format!("{authored_path}.overrides.{name}");
+            let Some(declared) = values.get(name.as_str()) else {
+                return Err(AuthoringDiagnostic::new(
+                    override_path,
+                    "unknown_override",
+                    format!(
+                        "component '{}' has no parameter named '{name}'",
+                        component_ref.spec.id
+                    ),
+                ));
+            };
+            if declared.kind() != value.kind() {
+                return Err(AuthoringDiagnostic::new(
+                    override_path,
+                    "parameter_type_mismatch",
+                    format!(
+                        "override '{name}' is a {}, but component '{}' declares it as a {} at $.components[{}].parameters.{name}",
+                        value.kind().label(),
+                        component_ref.spec.id,
+                        declared.kind().label(),
+                        component_ref.index
+                    ),
+                ));
+            }
+            validate_parameter_value(spec, value, &override_path)?;
+            values.insert(name, value);
+        }
+        Ok(Self { values })
+    }
+
+    /// Extends `scope` with the numeric arguments scalar expressions read.
+    pub(super) fn numeric_scope(
+        &self,
+        mut scope: BTreeMap<String, Quantity>,
+    ) -> BTreeMap<String, Quantity> {
+        scope.extend(self.values.iter().filter_map(|(name, value)| match value {
+            ComponentParameter::Quantity(quantity) => Some((name.to_string(), *quantity)),
+            ComponentParameter::Typed(_) => None,
+        }));
+        scope
+    }
+
+    /// Copies a component visual with every typed parameter reference
+    /// replaced by its argument. Nested instances keep their own references
+    /// for their own expansion.
+    pub(super) fn bind(
+        &self,
+        node: &VisualNode,
+        path: &str,
+    ) -> Result<VisualNode, AuthoringDiagnostic> {
+        let mut node = node.clone();
+        self.bind_node(&mut node, path)?;
+        Ok(node)
+    }
+
+    fn bind_node(&self, node: &mut VisualNode, path: &str) -> Result<(), AuthoringDiagnostic> {
+        match node {
+            VisualNode::Ellipse { fill, stroke, .. }
+            | VisualNode::Rectangle { fill, stroke, .. }
+            | VisualNode::Triangle { fill, stroke, .. }
+            | VisualNode::Polygon { fill, stroke, .. }
+            | VisualNode::Star { fill, stroke, .. }
+            | VisualNode::Path { fill, stroke, .. } => {
+                self.bind_paint(fill, &format!("{path}.fill"))?;
+                if let Some(stroke) = stroke {
+                    self.bind_paint(&mut stroke.paint, &format!("{path}.stroke.paint"))?;
+                }
+            }
+            VisualNode::Text {
+                text, font, fill, ..
+            } => {
+                self.bind_string(text, ParameterKind::String, &format!("{path}.text"))?;
+                if let Some(font) = font {
+                    self.bind_string(font, ParameterKind::FontAsset, &format!("{path}.font"))?;
+                }
+                self.bind_paint(fill, &format!("{path}.fill"))?;
+            }
+            VisualNode::Image { asset, .. } | VisualNode::NineSlice { asset, .. } => {
+                self.bind_string(asset, ParameterKind::ImageAsset, &format!("{path}.asset"))?;
+            }
+            VisualNode::Grid { item, .. }
+            | VisualNode::Radial { item, .. }
+            | VisualNode::Mirror { item, .. }
+            | VisualNode::Distribute { item, .. }
+            | VisualNode::AlongPath { item, .. } => {
+                self.bind_node(item, &format!("{path}.item"))?;
+            }
+            VisualNode::Group { children, .. }
+            | VisualNode::Layout { children, .. }
+            | VisualNode::Skeleton { children, .. } => {
+                for (index, child) in children.iter_mut().enumerate() {
+                    self.bind_node(child, &format!("{path}.children[{index}]"))?;
+                }
+            }
+            VisualNode::Instance { .. }
+            | VisualNode::NestedArtboard { .. }
+            | VisualNode::RawSceneObject { .. } => {}
+        }
+        Ok(())
+    }
+
+    fn bind_paint(&self, paint: &mut PaintSpec, path: &str) -> Result<(), AuthoringDiagnostic> {
+        match paint {
+            PaintSpec::Solid(_) => {}
+            PaintSpec::Gradient(gradient) => {
+                for (index, stop) in gradient.stops.iter_mut().enumerate() {
+                    self.bind_string(
+                        &mut stop.color,
+                        ParameterKind::Color,
+                        &format!("{path}.stops[{index}].color"),
+                    )?;
+                }
+            }
+            PaintSpec::Parameter(reference) => {
+                *paint = match self.argument(reference, path)? {
+                    TypedParameter::Color { value } => PaintSpec::Solid(value.clone()),
+                    TypedParameter::Paint { value } => value.clone(),
+                    other => {
+                        return Err(type_mismatch(reference, other, "color or paint", path));
+                    }
+                };
+            }
+        }
+        Ok(())
+    }
+
+    fn bind_string(
+        &self,
+        value: &mut ParameterizedString,
+        expected: ParameterKind,
+        path: &str,
+    ) -> Result<(), AuthoringDiagnostic> {
+        let ParameterizedString::Parameter(reference) = value else {
+            return Ok(());
+        };
+        let argument = self.argument(reference, path)?;
+        let bound = match argument {
+            TypedParameter::Color { value }
+            | TypedParameter::String { value }
+            | TypedParameter::FontAsset { value }
+            | TypedParameter::ImageAsset { value }
+                if argument.kind() == expected =>
+            {
+                value
+            }
+            other => return Err(type_mismatch(reference, other, expected.label(), path)),
+        };
+        *value = ParameterizedString::Literal(bound.clone());
+        Ok(())
+    }
+
+    fn argument(
+        &self,
+        reference: &ParameterRef,
+        path: &str,
+    ) -> Result<&'a TypedParameter, AuthoringDiagnostic> {
+        match self.values.get(reference.parameter.as_str()) {
+            Some(ComponentParameter::Typed(value)) => Ok(value),
+            Some(ComponentParameter::Quantity(_)) => Err(AuthoringDiagnostic::new(
+                path,
+                "parameter_type_mismatch",
+                format!(
+                    "parameter '{}' is a quantity and can only be read by scalar expressions",
+                    reference.parameter
+                ),
+            )),
+            None => Err(AuthoringDiagnostic::new(
+                path,
+                "unknown_parameter",
+                format!(
+                    "component parameter '{}' is not declared",
+                    reference.parameter
+                ),
+            )),
+        }
+    }
+}
+
+fn type_mismatch(
+    reference: &ParameterRef,
+    value: &TypedParameter,
+    expected: &str,
+    path: &str,
+) -> AuthoringDiagnostic {
+    AuthoringDiagnostic::new(
+        path,
+        "parameter_type_mismatch",
+        format!(
+            "parameter '{}' is a {}, but this field needs a {expected}",
+            reference.parameter,
+            value.kind().label()
+        ),
+    )
+}
+
+/// Checks a parameter default or override value on its own terms.
+pub(super) fn validate_parameter_value(
+    spec: &AuthoringSpec,
+    value: &ComponentParameter,
+    path: &str,
+) -> Result<(), AuthoringDiagnostic> {
+    let value_path = format!("{path}.value");
+    let typed = match value {
+        ComponentParameter::Quantity(quantity) => {
+            if !quantity.value.is_finite() {
+                return Err(AuthoringDiagnostic::new(
+                    value_path,
+                    "non_finite",
+                    "numeric values must be finite",
+                ));
+            }
+            return Ok(());
+        }
+        ComponentParameter::Typed(typed) => typed,
+    };
+    match typed {
+        TypedParameter::Color { value } => validate_color(value, &value_path),
+        TypedParameter::Paint { value } => match value {
+            PaintSpec::Solid(color) => validate_color(color, &value_path),
+            PaintSpec::Gradient(gradient) => {
+                for (index, stop) in gradient.stops.iter().enumerate() {
+                    let stop_path = format!("{value_path}.stops[{index}].color");
+                    let Some(color) = stop.color.literal() else {
+                        return Err(literal_required(&stop_path));
+                    };
+                    validate_color(color, &stop_path)?;
+                }
+                Ok(())
+            }
+            PaintSpec::Parameter(_) => Err(literal_required(&value_path)),
+        },
+        TypedParameter::String { .. } => Ok(()),
+        TypedParameter::FontAsset { value } => {
+            if !spec.font_assets.contains_key(value) {
+                return Err(AuthoringDiagnostic::new(
+                    value_path,
+                    "unknown_font_asset",
+                    format!("font asset '{value}' is not declared"),
+                ));
+            }
+            Ok(())
+        }
+        TypedParameter::ImageAsset { value } => {
+            if !spec.image_assets.contains_key(value) {
+                return Err(AuthoringDiagnostic::new(
+                    value_path,
+                    "unknown_image_asset",
+                    format!("image asset '{value}' is not declared"),
+                ));
+            }
+            Ok(())
+        }
+    }
+}
+
+fn validate_color(color: &str, path: &str) -> Result<(), AuthoringDiagnostic> {
+    parse_color(color)
+        .map(|_| ())
+        .map_err(|message| AuthoringDiagnostic::new(path, "invalid_color", message))
+}
+
+fn literal_required(path: &str) -> AuthoringDiagnostic {
+    AuthoringDiagnostic::new(
+        path,
+        "invalid_parameter_value",
+        "parameter values must be literal; they cannot reference other parameters",
+    )
+}
+
+/// Reads a field outside any component expansion, where parameter
+/// references have nothing to resolve against.
+pub(super) fn literal_field<'v>(
+    value: &'v ParameterizedString,
+    path: &str,
+) -> Result<&'v str, AuthoringDiagnostic> {
+    match value {
+        ParameterizedString::Literal(value) => Ok(value),
+        ParameterizedString::Parameter(reference) => Err(unresolved_reference(reference, path)),
+    }
+}
+
+pub(super) fn unresolved_reference(reference: &ParameterRef, path: &str) -> AuthoringDiagnostic {
+    AuthoringDiagnostic::new(
+        path,
+        "unresolved_parameter_reference",
+        format!(
+            "parameter '{}' can only be referenced from component visuals",
+            reference.parameter
+        ),
+    )
+}
@@ -0,0 +1,973 @@
+use std::collections::BTreeMap;
+use std::f64::consts::{FRAC_PI_2, PI};
+
+use super::super::deterministic_math::{atan2, hypot, radians_from_degrees, sin_cos, sqrt};
+use super::super::expression::evaluate_expression;
+use super::super::spec::{AuthoringDiagnostic, Quantity, ScalarExpr, Unit};
+use super::super::visual::{PathDataSpec, PathSegmentSpec};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Point {
+    pub x: f64,
+    pub y: f64,
+}
+
+impl Point {
+    fn lerp(self, to: Self, t: f64) -> Self {
+        Self {
+            x: self.x + (to.x - self.x) * t,
+            y: self.y + (to.y - self.y) * t,
+        }
+    }
+
+    fn reflect_about(self, center: Self) -> Self {
+        Self {
+            x: 2.0 * center.x - self.x,
+            y: 2.0 * center.y - self.y,
+        }
+    }
+}
+
+/// One absolute drawing command, shared by SVG strings and typed segments.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum PathCommand {
+    Move(Point),
+    Line(Point),
+    Cubic(Point, Point, Point),
+    Quad(Point, Point),
+    Arc {
+        radius_x: f64,
+        radius_y: f64,
+        rotation: f64,
+        large_arc: bool,
+        sweep: bool,
+        to: Point,
+    },
+    Close,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct PathVertex {
+    pub point: Point,
+    pub in_control: Option<Point>,
+    pub out_control: Option<Point>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Contour {
+    pub vertices: Vec<PathVertex>,
+    pub closed: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum PathDataError {
+    Syntax { offset: usize, message: String },
+    MissingMove { command: usize },
+    DegenerateContour { command: usize },
+}
+
+/// Parses SVG path data into absolute commands. Smooth and horizontal or
+/// vertical commands are resolved here so contour building only sees the
+/// primitive forms.
+pub(crate) fn parse_svg_path(data: &str) -> Result<Vec<PathCommand>, PathDataError> {
+    let mut tokens = SvgTokens {
+        bytes: data.as_bytes(),
+        offset: 0,
+    };
+    let mut commands = Vec::new();
+    let mut cursor = Point { x: 0.0, y: 0.0 };
+    let mut start = cursor;
+    let mut last_cubic_control = None;
+    let mut last_quad_control = None;
+    let mut command = None;
+
+    while let Some(next) = tokens.peek() {
+        if command.is_none() && !matches!(next, b'M' | b'm') {
+            return Err(tokens.error("path data must start with a move command"));
+        }
+        let letter = if next.is_ascii_alphabetic() {
+            tokens.offset += 1;
+            next
+        } else {
+            match command {
+                // Coordinates after a move continue as implicit lines.
+                Some(b'M') => b'L',
+                Some(b'm') => b'l',
+                Some(b'Z' | b'z') | None => return Err(tokens.error("expected a path command")),
+                Some(previous) => previous,
+            }
+        };
+        command = Some(letter);
+
+        let relative = letter.is_ascii_lowercase();
+        let origin = if relative {
+            cursor
+        } else {
+            Point { x: 0.0, y: 0.0 }
+        };
+        let offset_point = |x: f64, y: f64| Point {
+            x: origin.x + x,
+            y: origin.y + y,
+        };
+        let mut cubic_control = None;
+        let mut quad_control = None;
+        match letter.to_ascii_uppercase() {
+            b'M' => {
+                let to = offset_point(tokens.number()?, tokens.number()?);
+                commands.push(PathCommand::Move(to));
+                cursor = to;
+                start = to;
+            }
+            b'L' => {
+                let to = offset_point(tokens.number()?, tokens.number()?);
+                commands.push(PathCommand::Line(to));
+                cursor = to;
+            }
+            b'H' => {
+                let x = tokens.number()? + origin.x;
+                cursor = Point { x, y: cursor.y };
+                commands.push(PathCommand::Line(cursor));
+            }
+            b'V' => {
+                let y = tokens.number()? + origin.y;
+                cursor = Point { x: cursor.x, y };
+                commands.push(PathCommand::Line(cursor));
+            }
+            b'C' => {
+                let first = offset_point(tokens.number()?, tokens.number()?);
+                let second = offset_point(tokens.number()?, tokens.number()?);
+                let to = offset_point(tokens.number()?, tokens.number()?);
+                commands.push(PathCommand::Cubic(first, second, to));
+                cubic_control = Some(second);
+                cursor = to;
+            }
+            b'S' => {
+                let first = last_cubic_control
+                    .map_or(cursor, |control: Point| control.reflect_about(cursor));
+                let second = offset_point(tokens.number()?, tokens.number()?);
+                let to = offset_point(tokens.number()?, tokens.number()?);
+                commands.push(PathCommand::Cubic(first, second, to));
+                cubic_control = Some(second);
+                cursor = to;
+            }
+            b'Q' => {
+                let control = offset_point(tokens.number()?, tokens.number()?);
+                let to = offset_point(tokens.number()?, tokens.number()?);
+                commands.push(PathCommand::Quad(control, to));
+                quad_control = Some(control);
+                cursor = to;
+            }
+            b'T' => {
+                let control = last_quad_control
+                    .map_or(cursor, |control: Point| control.reflect_about(cursor));
+                let to = offset_point(tokens.number()?, tokens.number()?);
+                commands.push(PathCommand::Quad(control, to));
+                quad_control = Some(control);
+                cursor = to;
+            }
+            b'A' => {
+                let radius_x = tokens.number()?;
+                let radius_y = tokens.number()?;
+                let rotation = radians_from_degrees(tokens.number()?);
+                let large_arc = tokens.flag()?;
+                let sweep = tokens.flag()?;
+                let to = offset_point(tokens.number()?, tokens.number()?);
+                commands.push(PathCommand::Arc {
+                    radius_x,
+                    radius_y,
+                    rotation,
+                    large_arc,
+                    sweep,
+                    to,
+                });
+                cursor = to;
+            }
+            b'Z' => {
+                commands.push(PathCommand::Close);
+                cursor = start;
+            }
+            _ => {
+                tokens.offset -= 1;
+                return Err(tokens.error(&format!("unknown path command '{}'", char::from(letter))));
+            }
+        }
+        last_cubic_control = cubic_control;
+        last_quad_control = quad_control;
+    }
+
+    Ok(commands)
+}
+
+struct SvgTokens<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl SvgTokens<'_> {
+    /// Skips separators and returns the next significant byte.
+    fn peek(&mut self) -> Option<u8> {
+        while let Some(&byte) = self.bytes.get(self.offset) {
+            if byte.is_ascii_whitespace() || byte == b',' {
+                self.offset += 1;
+            } else {
+                return Some(byte);
+            }
+        }
+        None
+    }
+
+    fn number(&mut self) -> Result<f64, PathDataError> {
+        self.peek();
+        let start = self.offset;
+        let mut end = start;
+        if matches!(self.bytes.get(end), Some(b'+' | b'-')) {
+            end += 1;
+        }
+        let digits_start = end;
+        while self.bytes.get(end).is_some_and(u8::is_ascii_digit) {
+            end += 1;
+        }
+        let mut digits = end - digits_start;
+        if self.bytes.get(end) == Some(&b'.') {
+            end += 1;
+            let fraction_start = end;
+            while self.bytes.get(end).is_some_and(u8::is_ascii_digit) {
+                end += 1;
+            }
+            digits += end - fraction_start;
+        }
+        if digits == 0 {
+            return Err(self.error("expected a number"));
+        }
+        if matches!(self.bytes.get(end), Some(b'e' | b'E')) {
+            let mut exponent_end = end + 1;
+            if matches!(self.bytes.get(exponent_end), Some(b'+' | b'-')) {
+                exponent_end += 1;
+            }
+            let exponent_digits = exponent_end;
+            while self.bytes.get(exponent_end).is_some_and(u8::is_ascii_digit) {
+                exponent_end += 1;
+            }
+            if exponent_end > exponent_digits {
+                end = exponent_end;
+            }
+        }
+        let text = std::str::from_utf8(&self.bytes[start..end]).unwrap_or_default();
+        let value = text
+            .parse::<f64>()
+            .map_err(|_| self.error("expected a number"))?;
+        if !value.is_finite() {
+            return Err(self.error("numbers must be finite"));
+        }
+        self.offset = end;
+        Ok(value)
+    }
+
+    /// Arc flags are single characters and may be written without separators.
+    fn flag(&mut self) -> Result<bool, PathDataError> {
+        let flag = match self.peek() {
+            Some(b'0') => false,
+            Some(b'1') => true,
+            _ => return Err(self.error("expected an arc flag of 0 or 1")),
+        };
+        self.offset += 1;
+        Ok(flag)
+    }
+
+    fn error(&self, message: &str) -> PathDataError {
+        PathDataError::Syntax {
+            offset: self.offset,
+            message: message.to_string(),
+        }
+    }
+}
+
+/// Groups commands into contours of vertices with absolute cubic controls.
+/// Quadratic and arc segments are raised to cubics, and a closing point that
+/// repeats the contour start is folded into the first vertex.
+pub(crate) fn build_contours(commands: &[PathCommand]) -> Result<Vec<Contour>, PathDataError> {
+    let mut contours = Vec::new();
+    let mut current: Option<(usize, Contour)> = None;
+    let mut start: Option<(usize, Point)> = None;
+
+    for (index, command) in commands.iter().enumerate() {
+        if let PathCommand::Move(point) = command {
+            finish_contour(&mut contours, current.take())?;
+            start = Some((index, *point));
+            current = Some((index, open_contour(*point)));
+            continue;
+        }
+        if current.is_none() {
+            // Drawing after a close restarts at the closed contour's start.
+            let Some((_, point)) = start else {
+                return Err(PathDataError::MissingMove { command: index });
+            };
+            if matches!(command, PathCommand::Close) {
+                continue;
+            }
+            current = Some((index, open_contour(point)));
+        }
+        let Some((_, contour)) = current.as_mut() else {
+            continue;
+        };
+        let from = contour
+            .vertices
+            .last()
+            .map_or(Point { x: 0.0, y: 0.0 }, |vertex| vertex.point);
+        match *command {
+            PathCommand::Move(_) => {}
+            PathCommand::Line(to) => push_line(contour, to),
+            PathCommand::Cubic(first, second, to) => push_cubic(contour, first, second, to),
+            PathCommand::Quad(control, to) => push_cubic(
+                contour,
+                from.lerp(control, 2.0 / 3.0),
+                to.lerp(control, 2.0 / 3.0),
+                to,
+            ),
+            PathCommand::Arc {
+                radius_x,
+                radius_y,
+                rotation,
+                large_arc,
+                sweep,
+                to,
+            } => {
+                if from == to {
+                    continue;
+                }
+                match arc_to_cubics(from, radius_x, radius_y, rotation, large_arc, sweep, to) {
+                    Some(segments) => {
+                        for (first, second, end) in segments {
+                            push_cubic(contour, first, second, end);
+                        }
+                    }
+                    None => push_line(contour, to),
+                }
+            }
+            PathCommand::Close => {
+                if let Some((index, mut contour)) = current.take() {
+                    if contour.vertices.len() > 1
+                        && contour.vertices.first().map(|vertex| vertex.point)
+                            == contour.vertices.last().map(|vertex| vertex.point)
+                    {
+                        let last = contour.vertices.pop();
+                        if let (Some(first), Some(last)) = (contour.vertices.first_mut(), last) {
+                            first.in_control = last.in_control;
+                        }
+                    }
+                    contour.closed = true;
+                    finish_contour(&mut contours, Some((index, contour)))?;
+                }
+            }
+        }
+    }
+    finish_contour(&mut contours, current)?;
+    Ok(contours)
+}
+
+/// Evaluates path data into contours. SVG errors point at the string;
+/// typed segment errors point at the offending segment.
+pub(crate) fn evaluate_path_data(
+    data: &PathDataSpec,
+    path: &str,
+    scope: &BTreeMap<String, Quantity>,
+) -> Result<Vec<Contour>, AuthoringDiagnostic> {
+    let (commands, command_path) = match data {
+        PathDataSpec::Svg(data) => (
+            parse_svg_path(data).map_err(|error| path_data_diagnostic(error, path, None))?,
+            None,
+        ),
+        PathDataSpec::Segments(segments) => {
+            let mut commands = Vec::with_capacity(segments.len());
+            for (index, segment) in segments.iter().enumerate() {
+                commands.push(evaluate_segment(
+                    segment,
+                    &format!("{path}[{index}]"),
+                    scope,
+                )?);
+            }
+            (commands, Some(path))
+        }
+    };
+    let contours = build_contours(&commands)
+        .map_err(|error| path_data_diagnostic(error, path, command_path))?;
+    if contours.is_empty() {
+        return Err(AuthoringDiagnostic::new(
+            path,
+            "invalid_path_data",
+            "path data must draw at least one contour",
+        ));
+    }
+    Ok(contours)
+}
+
+fn evaluate_segment(
+    segment: &PathSegmentSpec,
+    path: &str,
+    scope: &BTreeMap<String, Quantity>,
+) -> Result<PathCommand, AuthoringDiagnostic> {
+    let length = |expression: &ScalarExpr, field: &str| {
+        evaluate_expression(expression, &format!("{path}.{field}"), scope, Unit::Px)
+    };
+    let point = |x: &ScalarExpr, y: &ScalarExpr, x_field: &str, y_field: &str| {
+        Ok::<_, AuthoringDiagnostic>(Point {
+            x: length(x, x_field)?,
+            y: length(y, y_field)?,
+        })
+    };
+    Ok(match segment {
+        PathSegmentSpec::Move { x, y } => PathCommand::Move(point(x, y, "x", "y")?),
+        PathSegmentSpec::Line { x, y } => PathCommand::Line(point(x, y, "x", "y")?),
+        PathSegmentSpec::Cubic {
+            x1,
+            y1,
+            x2,
+            y2,
+            x,
+            y,
+        } => PathCommand::Cubic(
+            point(x1, y1, "x1", "y1")?,
+            point(x2, y2, "x2", "y2")?,
+            point(x, y, "x", "y")?,
+        ),
+        PathSegmentSpec::Quad { x1, y1, x, y } => {
+            PathCommand::Quad(point(x1, y1, "x1", "y1")?, point(x, y, "x", "y")?)
+        }
+        PathSegmentSpec::Arc {
+            rx,
+            ry,
+            rotation,
+            large_arc,
+            sweep,
+            x,
+            y,
+        } => PathCommand::Arc {
+            radius_x: length(rx, "rx")?,
+            radius_y: length(ry, "ry")?,
+            rotation: rotation
+                .as_ref()
+                .map(|rotation| {
+                    evaluate_expression(rotation, &format!("{path}.rotation"), scope, Unit::Radians)
+                })
+                .transpose()?
+                .unwrap_or(0.0),
+            large_arc: *large_arc,
+            sweep: *sweep,
+            to: point(x, y, "x", "y")?,
+        },
+        PathSegmentSpec::Close => PathCommand::Close,
+    })
+}
+
+fn path_data_diagnostic(
+    error: PathDataError,
+    path: &str,
+    command_path: Option<&str>,
+) -> AuthoringDiagnostic {
+    let at_command = |command: usize| {
+        command_path.map_or_else(|| path.to_string(), |list| format!("{list}[{command}]"))
+    };
+    match error {
+        PathDataError::Syntax { offset, message } => AuthoringDiagnostic::new(
+            path,
+            "invalid_path_data",
+            format!("{message} at byte {offset}"),
+        ),
+        PathDataError::MissingMove { command } => AuthoringDiagnostic::new(
+            at_command(command),
+            "invalid_path_data",
+            "path segments must start with a move",
+        ),
+        PathDataError::DegenerateContour { command } => AuthoringDiagnostic::new(
+            at_command(command),
+            "invalid_path_data",
+            "every contour must draw at least one segment after its move",
+        ),
+    }
+}
+
+fn open_contour(point: Point) -> Contour {
+    Contour {
+        vertices: vec![PathVertex {
+            point,
+            in_control: None,
+            out_control: None,
+        }],
+        closed: false,
+    }
+}
+
+fn finish_contour(
+    contours: &mut Vec<Contour>,
+    contour: Option<(usize, Contour)>,
+) -> Result<(), PathDataError> {
+    let Some((command, contour)) = contour else {
+        return Ok(());
+    };
+    if contour.vertices.len() < 2 {
+        return Err(PathDataError::DegenerateContour { command });
+    }
+    contours.push(contour);
+    Ok(())
+}
+
+fn push_line(contour: &mut Contour, to: Point) {
+    contour.vertices.push(PathVertex {
+        point: to,
+        in_control: None,
+        out_control: None,
+    });
+}
+
+fn push_cubic(contour: &mut Contour, first: Point, second: Point, to: Point) {
+    if let Some(previous) = contour.vertices.last_mut() {
+        previous.out_control = Some(first);
+    }
+    contour.vertices.push(PathVertex {
+        point: to,
+        in_control: Some(second),
+        out_control: None,
+    });
+}
+
+/// Converts an SVG endpoint arc to cubic segments of at most a quarter turn
+/// each, following the centre parameterisation in SVG 1.1 appendix F.6.5.
+/// Returns `None` when a zero radius degrades the arc to a straight line.
+fn arc_to_cubics(
+    from: Point,
+    radius_x: f64,
+    radius_y: f64,
+    rotation: f64,
+    large_arc: bool,
+    sweep: bool,
+    to: Point,
+) -> Option<Vec<(Point, Point, Point)>> {
+    let mut radius_x = radius_x.abs();
+    let mut radius_y = radius_y.abs();
+    if radius_x == 0.0 || radius_y == 0.0 {
+        return None;
+    }
+    let (sin_phi, cos_phi) = sin_cos(rotation);
+    let half_dx = (from.x - to.x) / 2.0;
+    let half_dy = (from.y - to.y) / 2.0;
+    let x1 = cos_phi * half_dx + sin_phi * half_dy;
+    let y1 = -sin_phi * half_dx + cos_phi * half_dy;
+
+    let lambda = (x1 * x1) / (radius_x * radius_x) + (y1 * y1) / (radius_y * radius_y);
+    if lambda > 1.0 {
+        let scale = sqrt(lambda);
+        radius_x *= scale;
+        radius_y *= scale;
+    }
+    let rx2 = radius_x * radius_x;
+    let ry2 = radius_y * radius_y;
+    let numerator = rx2 * ry2 - rx2 * y1 * y1 - ry2 * x1 * x1;
+    let denominator = rx2 * y1 * y1 + ry2 * x1 * x1;
+    let mut coefficient = sqrt((numerator / denominator).max(0.0));
+    if large_arc == sweep {
+        coefficient = -coefficient;
+    }
+    let center_x1 = coefficient * radius_x * y1 / radius_y;
+    let center_y1 = -coefficient * radius_y * x1 / radius_x;
+    let center = Point {
+        x: cos_phi * center_x1 - sin_phi * center_y1 + (from.x + to.x) / 2.0,
+        y: sin_phi * center_x1 + cos_phi * center_y1 + (from.y + to.y) / 2.0,
+    };
+
+    let start_angle = atan2((y1 - center_y1) / radius_y, (x1 - center_x1) / radius_x);
+    let end_angle = atan2((-y1 - center_y1) / radius_y, (-x1 - center_x1) / radius_x);
+    let mut sweep_angle = end_angle - start_angle;
+    if !sweep && sweep_angle > 0.0 {
+        sweep_angle -= 2.0 * PI;
+    } else if sweep && sweep_angle < 0.0 {
+        sweep_angle += 2.0 * PI;
+    }
+
+    let segment_count = (sweep_angle.abs() / FRAC_PI_2).ceil().max(1.0) as usize;
+    let step = sweep_angle / segment_count as f64;
+    let (quarter_sin, quarter_cos) = sin_cos(step / 4.0);
+    let handle = 4.0 / 3.0 * quarter_sin / quarter_cos;
+    let map = |unit_x: f64, unit_y: f64| Point {
+        x: center.x + cos_phi * radius_x * unit_x - sin_phi * radius_y * unit_y,
+        y: center.y + sin_phi * radius_x * unit_x + cos_phi * radius_y * unit_y,
+    };
+
+    let mut segments = Vec::with_capacity(segment_count);
+    for index in 0..segment_count {
+        let (sin_start, cos_start) = sin_cos(start_angle + step * index as f64);
+        let (sin_end, cos_end) = sin_cos(start_angle + step * (index + 1) as f64);
+        let end = if index + 1 == segment_count {
+            to
+        } else {
+            map(cos_end, sin_end)
+        };
+        segments.push((
+            map(
+                cos_start - handle * sin_start,
+                sin_start + handle * cos_start,
+            ),
+            map(cos_end + handle * sin_end, sin_end - handle * cos_end),
+            end,
+        ));
+    }
+    Some(segments)
+}
+
+#[derive(Clone, Copy)]
+struct CubicSegment {
+    start: Point,
+    first: Point,
+    second: Point,
+    end: Point,
+}
+
+impl CubicSegment {
+    /// Length of the control polygon, an upper bound on the arc length.
+    fn control_length(&self) -> f64 {
+        distance(self.start, self.first)
+            + distance(self.first, self.second)
+            + distance(self.second, self.end)
+    }
+
+    /// Splits the curve at its parameter midpoint without changing its shape.
+    fn split(&self) -> (Self, Self) {
+        let start_first = self.start.lerp(self.first, 0.5);
+        let first_second = self.first.lerp(self.second, 0.5);
+        let second_end = self.second.lerp(self.end, 0.5);
+        let left = start_first.lerp(first_second, 0.5);
+        let right = first_second.lerp(second_end, 0.5);
+        let middle = left.lerp(right, 0.5);
+        (
+            Self {
+                start: self.start,
+                first: start_first,
+                second: left,
+                end: middle,
+            },
+            Self {
+                start: middle,
+                first: right,
+                second: second_end,
+                end: self.end,
+            },
+        )
+    }
+
+    fn reversed(&self) -> Self {
+        Self {
+            start: self.end,
+            first: self.second,
+            second: self.first,
+            end: self.start,
+        }
+    }
+}
+
+fn distance(from: Point, to: Point) -> f64 {
+    hypot(to.x - from.x, to.y - from.y)
+}
+
+/// Every segment of a contour as an explicit cubic; straight segments get
+/// handles at one and two thirds of their length.
+fn cubic_segments(contour: &Contour) -> Vec<CubicSegment> {
+    let count = contour.vertices.len();
+    let segment_count = if contour.closed { count } else { count - 1 };
+    (0..segment_count)
+        .map(|index| {
+            let from = &contour.vertices[index];
+            let to = &contour.vertices[(index + 1) % count];
+            CubicSegment {
+                start: from.point,
+                first: from
+                    .out_control
+                    .unwrap_or_else(|| from.point.lerp(to.point, 1.0 / 3.0)),
+                second: to
+                    .in_control
+                    .unwrap_or_else(|| to.point.lerp(from.point, 1.0 / 3.0)),
+                end: to.point,
+            }
+        })
+        .collect()
+}
+
+fn contour_from_segments(segments: &[CubicSegment], closed: bool) -> Contour {
+    let mut vertices = segments
+        .iter()
+        .map(|segment| PathVertex {
+            point: segment.start,
+            in_control: None,
+            out_control: Some(segment.first),
+        })
+        .collect::<Vec<_>>();
+    if !closed && let Some(last) = segments.last() {
+        vertices.push(PathVertex {
+            point: last.end,
+            in_control: None,
+            out_control: None,
+        });
+    }
+    let count = vertices.len();
+    for (index, segment) in segments.iter().enumerate() {
+        vertices[(index + 1) % count].in_control = Some(segment.second);
+    }
+    Contour { vertices, closed }
+}
+
+/// Resamples a contour to `count` vertices of explicit cubics by splitting
+/// the longest segment at its midpoint until the count is reached, so the
+/// outline is unchanged. `count` must not be below the current vertex count.
+pub(crate) fn resample(contour: &Contour, count: usize) -> Contour {
+    let mut segments = cubic_segments(contour);
+    let segment_count = if contour.closed {
+        count
+    } else {
+        count.saturating_sub(1)
+    };
+    while segments.len() < segment_count {
+        let mut longest = 0;
+        for (index, segment) in segments.iter().enumerate() {
+            if segment.control_length() > segments[longest].control_length() {
+                longest = index;
+            }
+        }
+        let (left, right) = segments[longest].split();
+        segments.splice(longest..=longest, [left, right]);
+    }
+    contour_from_segments(&segments, contour.closed)
+}
+
+/// Reorders a resampled `outline` to follow `base`, which has the same vertex
+/// count: closed contours take the base winding and then the cyclic start
+/// with the least squared vertex displacement; open contours take whichever
+/// direction displaces less.
+pub(crate) fn match_orientation(outline: Contour, base: &Contour) -> Contour {
+    // A closed contour reversed this way still starts at its first vertex.
+    let reverse = |contour: &Contour| {
+        let segments = cubic_segments(contour)
+            .iter()
+            .rev()
+            .map(CubicSegment::reversed)
+            .collect::<Vec<_>>();
+        contour_from_segments(&segments, contour.closed)
+    };
+    let displacement = |contour: &Contour, offset: usize| {
+        let count = contour.vertices.len();
+        base.vertices
+            .iter()
+            .enumerate()
+            .map(|(index, vertex)| {
+                let point = contour.vertices[(index + offset) % count].point;
+                (point.x - vertex.point.x).powi(2) + (point.y - vertex.point.y).powi(2)
+            })
+            .sum::<f64>()
+    };
+
+    if !outline.closed {
+        let reversed = reverse(&outline);
+        return if displacement(&reversed, 0) < displacement(&outline, 0) {
+            reversed
+        } else {
+            outline
+        };
+    }
+    let mut outline = if signed_area(&outline) * signed_area(base) < 0.0 {
+        reverse(&outline)
+    } else {
+        outline
+    };
+    let mut best = (0, displacement(&outline, 0));
+    for offset in 1..outline.vertices.len() {
+        let cost = displacement(&outline, offset);
+        if cost < best.1 {
+            best = (offset, cost);
+        }
+    }
+    outline.vertices.rotate_left(best.0);
+    outline
+}
+
+/// Twice the signed area of the control polygon; its sign is the winding.
+fn signed_area(contour: &Contour) -> f64 {
+    let points = cubic_segments(contour)
+        .iter()
+        .flat_map(|segment| [segment.start, segment.first, segment.second])
+        .collect::<Vec<_>>();
+    let count = points.len();
+    (0..count)
+        .map(|index| {
+            let from = points[index];
+            let to = points[(index + 1) % count];
+            from.x * to.y - to.x * from.y
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        Contour, PathCommand, PathDataError, Point, build_contours, match_orientation,
+        parse_svg_path, resample, signed_area,
+    };
+
+    fn point(x: f64, y: f64) -> Point {
+        Point { x, y }
+    }
+
+    fn contours_of(data: &str) -> Vec<Contour> {
+        build_contours(&parse_svg_path(data).expect("valid path data")).expect("valid contours")
+    }
+
+    #[test]
+    fn parses_relative_implicit_and_compact_commands() {
+        let commands =
+            parse_svg_path("m10,10 20 0v20h-20z M-1e1.5L.5-.5").expect("valid path data");
+        assert_eq!(
+            commands,
+            [
+                PathCommand::Move(point(10.0, 10.0)),
+                PathCommand::Line(point(30.0, 10.0)),
+                PathCommand::Line(point(30.0, 30.0)),
+                PathCommand::Line(point(10.0, 30.0)),
+                PathCommand::Close,
+                PathCommand::Move(point(-10.0, 0.5)),
+                PathCommand::Line(point(0.5, -0.5)),
+            ]
+        );
+    }
+
+    #[test]
+    fn resolves_smooth_curves_by_reflection() {
+        let commands = parse_svg_path("M0 0C0 10 10 10 10 0S20 -10 20 0Q25 5 30 0T40 0")
+            .expect("valid path data");
+        assert_eq!(
+            commands[2],
+            PathCommand::Cubic(point(10.0, -10.0), point(20.0, -10.0), point(20.0, 0.0))
+        );
+        assert_eq!(
+            commands[4],
+            PathCommand::Quad(point(35.0, -5.0), point(40.0, 0.0))
+        );
+    }
+
+    #[test]
+    fn reports_syntax_errors_with_byte_offsets() {
+        assert_eq!(
+            parse_svg_path("L 0 0"),
+            Err(PathDataError::Syntax {
+                offset: 0,
+                message: "path data must start with a move command".to_string()
+            })
+        );
+        assert!(matches!(
+            parse_svg_path("M 0 0 L 10"),
+            Err(PathDataError::Syntax { offset: 10, .. })
+        ));
+        assert!(matches!(
+            parse_svg_path("M 0 0 A 5 5 0 2 0 10 0"),
+            Err(PathDataError::Syntax { offset: 14, .. })
+        ));
+        assert!(matches!(
+            parse_svg_path("M 0 0 X 1"),
+            Err(PathDataError::Syntax { offset: 6, .. })
+        ));
+    }
+
+    #[test]
+    fn closing_on_the_start_point_folds_into_the_first_vertex() {
+        let contours = contours_of("M0 0L10 0C10 10 0 10 0 0Z");
+        assert_eq!(contours.len(), 1);
+        let contour = &contours[0];
+        assert!(contour.closed);
+        assert_eq!(contour.vertices.len(), 2);
+        assert_eq!(contour.vertices[0].in_control, Some(point(0.0, 10.0)));
+        assert_eq!(contour.vertices[1].out_control, Some(point(10.0, 10.0)));
+    }
+
+    #[test]
+    fn quadratic_controls_follow_the_two_thirds_rule() {
+        let contours = contours_of("M0 0Q30 30 60 0");
+        let vertices = &contours[0].vertices;
+        assert_eq!(vertices[0].out_control, Some(point(20.0, 20.0)));
+        assert_eq!(vertices[1].in_control, Some(point(40.0, 20.0)));
+    }
+
+    #[test]
+    fn arcs_split_into_quarter_turns_that_stay_on_the_circle() {
+        let contours = contours_of("M0 0A50 50 0 1 1 0 100");
+        let vertices = &contours[0].vertices;
+        assert_eq!(vertices.len(), 3);
+        assert_eq!(vertices[2].point, point(0.0, 100.0));
+        let middle = vertices[1].point;
+        assert!((middle.x - 50.0).abs() < 1e-9 && (middle.y - 50.0).abs() < 1e-9);
+
+        let line = contours_of("M0 0A0 10 0 0 1 10 0");
+        assert_eq!(line[0].vertices[1].in_control, None);
+    }
+
+    #[test]
+    fn drawing_after_close_restarts_at_the_contour_start() {
+        let contours = contours_of("M0 0L10 0L10 10ZL0 10L-10 10");
+        assert_eq!(contours.len(), 2);
+        assert_eq!(contours[1].vertices[0].point, point(0.0, 0.0));
+        assert!(!contours[1].closed);
+    }
+
+    #[test]
+    fn rejects_contours_without_a_segment() {
+        assert_eq!(
+            build_contours(&parse_svg_path("M0 0L10 0M5 5").expect("valid path data")),
+            Err(PathDataError::DegenerateContour { command: 2 })
+        );
+        assert_eq!(
+            build_contours(&[PathCommand::Line(point(1.0, 1.0))]),
+            Err(PathDataError::MissingMove { command: 0 })
+        );
+    }
+
+    #[test]
+    fn resampling_splits_the_longest_segments_without_moving_the_outline() {
+        let square = &contours_of("M0 0H40V10H0Z")[0];
+        let resampled = resample(square, 6);
+        assert_eq!(resampled.vertices.len(), 6);
+        let points = resampled
+            .vertices
+            .iter()
+            .map(|vertex| vertex.point)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            points,
+            [
+                point(0.0, 0.0),
+                point(20.0, 0.0),
+                point(40.0, 0.0),
+                point(40.0, 10.0),
+                point(20.0, 10.0),
+                point(0.0, 10.0)
+            ]
+        );
+        assert_eq!(resample(square, 4).vertices.len(), 4);
+    }
+
+    #[test]
+    fn orientation_follows_the_base_winding_and_nearest_start() {
+        let base = &contours_of("M0 0H10V10H0Z")[0];
+        let outline = contours_of("M10 10V0H0V10Z").remove(0);
+        assert!(signed_area(&outline) * signed_area(base) < 0.0);
+        let aligned = match_orientation(outline, base);
+        assert!(signed_area(&aligned) * signed_area(base) > 0.0);
+        let points = aligned
+            .vertices
+            .iter()
+            .map(|vertex| vertex.point)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            points,
+            [
+                point(0.0, 0.0),
+                point(10.0, 0.0),
+                point(10.0, 10.0),
+                point(0.0, 10.0)
+            ]
+        );
+    }
+}
@@ -1,12 +1,22 @@
+use std::collections::BTreeMap;
+
 use serde_json::{Value, json};
 
-use super::super::expression::{evaluate_expression, evaluate_transform};
-use super::super::spec::{AuthoringDiagnostic, SourceMapEntry, Unit};
-use super::super::visual::ShapeNodeRef;
+use super::super::deterministic_math::{atan2, hypot};
+use super::super::expression::{evaluate_expression, evaluate_transform, validate_scene_number};
+use super::super::spec::{
+    AuthoringDiagnostic, PaintSpec, Quantity, SourceMapEntry, StrokeSpec, Unit,
+};
+use super::super::visual::{PathNodeRef, ShapeNodeRef};
+use super::path_data::{PathVertex, Point, evaluate_path_data, resample};
 use super::{
     LoweredObject, Lowerer, NodeContext, PaintTarget, evaluate_ratio_expression, runtime_name,
 };
 
+/// The side of the cover rectangle an inverse clip source subtracts itself
+/// from, far larger than any practical artboard.
+const INVERSE_CLIP_EXTENT: f64 = 100_000.0;
+
 impl<'a> Lowerer<'a> {
     pub(super) fn lower_shape(
         &mut self,
@@ -94,50 +104,12 @@ impl<'a> Lowerer<'a> {
                 )
             })
             .transpose()?;
-        let stroke_thickness = stroke
-            .map(|stroke| {
-                evaluate_expression(
-                    &stroke.width,
-                    &format!("{authored_path}.stroke.width"),
-                    scope,
-                    Unit::Px,
-                )
-            })
-            .transpose()?;
-        if stroke_thickness.is_some_and(|thickness| thickness <= 0.0) {
-            return Err(AuthoringDiagnostic::new(
-                format!("{authored_path}.stroke.width"),
-                "invalid_dimension",
-                "stroke width must be greater than zero",
-            ));
-        }
+        let stroke_thickness = evaluate_stroke_thickness(stroke, &authored_path, scope)?;
         let transform_values =
             evaluate_transform(transform, &format!("{authored_path}.transform"), scope)?;
 
         let shape_name = runtime_name(&runtime_segments, "shape");
         let geometry_name = runtime_name(&runtime_segments, "geometry");
-        let fill_name = runtime_name(&runtime_segments, "fill");
-        let LoweredObject {
-            object: fill_paint,
-            runtime_names: fill_runtime_names,
-            scene_paths: fill_scene_paths,
-        } = self.lower_paint(
-            fill,
-            &format!("{authored_path}.fill"),
-            &runtime_segments,
-            &format!("{scene_path}/children/1/children/0"),
-            scope,
-            PaintTarget::Fill,
-        )?;
-        let mut runtime_names = vec![shape_name.clone(), geometry_name.clone(), fill_name.clone()];
-        runtime_names.extend(fill_runtime_names);
-        let mut scene_paths = vec![
-            scene_path.clone(),
-            format!("{scene_path}/children/0"),
-            format!("{scene_path}/children/1"),
-        ];
-        scene_paths.extend(fill_scene_paths);
-
         let mut geometry = json!({
             "type": geometry_type,
             "name": geometry_name,
@@ -161,58 +133,138 @@ impl<'a> Lowerer<'a> {
             }
         }
 
-        let mut children = vec![
-            geometry,
-            json!({
-                "type": "fill",
-                "name": fill_name,
-                "children": [fill_paint]
-            }),
-        ];
-        if let (Some(stroke), Some(thickness)) = (stroke, stroke_thickness) {
-            let stroke_name = runtime_name(&runtime_segments, "stroke");
-            let LoweredObject {
-                object: stroke_paint,
-                runtime_names: stroke_runtime_names,
-                scene_paths: stroke_scene_paths,
-            } = self.lower_paint(
-                &stroke.paint,
-                &format!("{authored_path}.stroke.paint"),
-                &runtime_segments,
-                &format!("{scene_path}/children/2/children/0"),
-                scope,
-                PaintTarget::Stroke,
-            )?;
-            runtime_names.push(stroke_name.clone());
-            runtime_names.extend(stroke_runtime_names);
-            scene_paths.push(format!("{scene_path}/children/2"));
-            scene_paths.extend(stroke_scene_paths);
-
-            let mut stroke_children = vec![stroke_paint];
-            if let Some(trim) = &stroke.trim {
-                let LoweredObject {
-                    object,
-                    runtime_names: trim_runtime_names,
-                    scene_paths: trim_scene_paths,
-                } = self.lower_trim_path(
-                    trim,
-                    &format!("{authored_path}.stroke.trim"),
-                    &runtime_segments,
-                    &format!("{scene_path}/children/2/children/1"),
-                    scope,
-                )?;
-                runtime_names.extend(trim_runtime_names);
-                scene_paths.extend(trim_scene_paths);
-                stroke_children.push(object);
+        let mut parts = ShapeParts {
+            children: vec![geometry],
+            runtime_names: vec![shape_name.clone(), geometry_name],
+            scene_paths: vec![scene_path.clone(), format!("{scene_path}/children/0")],
+        };
+        self.lower_shape_paints(
+            ShapePaints {
+                fill,
+                stroke,
+                stroke_thickness,
+            },
+            &authored_path,
+            &runtime_segments,
+            &scene_path,
+            scope,
+            &mut parts,
+        )?;
+        self.apply_clip_source(&authored_id, &runtime_segments, &scene_path, &mut parts);
+        let ShapeParts {
+            children,
+            runtime_names,
+            scene_paths,
+        } = parts;
+
+        self.register_runtime_names(&runtime_names, &format!("{authored_path}.id"))?;
+        self.source_map.entries.push(SourceMapEntry {
+            authored_id,
+            authored_path,
+            definition_path,
+            runtime_names,
+            scene_paths,
+        });
+
+        Ok(json!({
+            "type": "shape",
+            "name": shape_name,
+            "x": transform_values.x,
+            "y": transform_values.y,
+            "rotation": transform_values.rotation,
+            "scale_x": transform_values.scale_x,
+            "scale_y": transform_values.scale_y,
+            "children": children
+        }))
+    }
+
+    pub(super) fn lower_path(
+        &mut self,
+        path: PathNodeRef<'_>,
+        context: NodeContext<'_>,
+    ) -> Result<Value, AuthoringDiagnostic> {
+        let NodeContext {
+            authored_path,
+            definition_path,
+            authored_id,
+            runtime_segments,
+            scene_path,
+            scope,
+        } = context;
+
+        let mut contours = evaluate_path_data(path.path, &format!("{authored_path}.path"), scope)?;
+        if let Some(counts) = self.morph_resolutions.get(&authored_id) {
+            // Morph targets share one vertex count per contour with every
+            // outline they morph to.
+            if counts.len() != contours.len() {
+                return Err(AuthoringDiagnostic::new(
+                    format!("{authored_path}.path"),
+                    "morph_topology_mismatch",
+                    "path contours changed between morph planning and lowering",
+                ));
             }
+            contours = contours
+                .iter()
+                .zip(counts)
+                .map(|(contour, count)| resample(contour, *count))
+                .collect();
+        }
+        let stroke_thickness = evaluate_stroke_thickness(path.stroke, &authored_path, scope)?;
+        let transform_values =
+            evaluate_transform(path.transform, &format!("{authored_path}.transform"), scope)?;
 
-            children.push(json!({
-                "type": "stroke",
-                "name": stroke_name,
-                "thickness": thickness,
-                "children": stroke_children
-            }));
+        let shape_name = runtime_name(&runtime_segments, "shape");
+        let mut parts = ShapeParts {
+            children: Vec::with_capacity(contours.len() + 2),
+            runtime_names: vec![shape_name.clone()],
+            scene_paths: vec![scene_path.clone()],
+        };
+        for (contour_index, contour) in contours.iter().enumerate() {
+            let contour_name = runtime_name(&runtime_segments, &format!("path_{contour_index}"));
+            let contour_scene_path = format!("{scene_path}/children/{contour_index}");
+            parts.runtime_names.push(contour_name.clone());
+            parts.scene_paths.push(contour_scene_path.clone());
+            let mut vertices = Vec::with_capacity(contour.vertices.len());
+            for (vertex_index, vertex) in contour.vertices.iter().enumerate() {
+                let vertex_name = runtime_name(
+                    &runtime_segments,
+                    &format!("path_{contour_index}_vertex_{vertex_index}"),
+                );
+                parts.runtime_names.push(vertex_name.clone());
+                parts
+                    .scene_paths
+                    .push(format!("{contour_scene_path}/children/{vertex_index}"));
+                vertices.push(lower_vertex(vertex, vertex_name, &authored_path)?);
+            }
+            let mut points_path = json!({
+                "type": "points_path",
+                "name": contour_name,
+                "is_closed": contour.closed,
+                "children": vertices
+            });
+            if let Some(skin) = self.skin_bindings.get(&authored_id) {
+                points_path["skin"] = skin.clone();
+            }
+            parts.children.push(points_path);
         }
+        self.lower_shape_paints(
+            ShapePaints {
+                fill: path.fill,
+                stroke: path.stroke,
+                stroke_thickness,
+            },
+            &authored_path,
+            &runtime_segments,
+            &scene_path,
+            scope,
+            &mut parts,
+        )?;
+        self.apply_clip_source(&authored_id, &runtime_segments, &scene_path, &mut parts);
+        let ShapeParts {
+            children,
+            runtime_names,
+            scene_paths,
+        } = parts;
 
         self.register_runtime_names(&runtime_names, &format!("{authored_path}.id"))?;
         self.source_map.entries.push(SourceMapEntry {
@@ -234,4 +286,224 @@ impl<'a> Lowerer<'a> {
             "children": children
         }))
     }
+
+    /// Hides the paints of a shape that clips a sibling group unless it is
+    /// meant to stay visible, and gives an inverse clip source a cover
+    /// rectangle for the even-odd clip to subtract it from.
+    fn apply_clip_source(
+        &self,
+        authored_id: &str,
+        runtime_segments: &[String],
+        scene_path: &str,
+        parts: &mut ShapeParts,
+    ) {
+        let Some(source) = self.clip_sources.get(authored_id).copied() else {
+            return;
+        };
+        if !source.visible {
+            for child in &mut parts.children {
+                if matches!(child["type"].as_str(), Some("fill" | "stroke")) {
+                    child["is_visible"] = json!(false);
+                }
+            }
+        }
+        if source.inverse {
+            let cover_index = parts.children.len();
+            let cover_name = runtime_name(runtime_segments, "clip_cover");
+            let geometry_name = runtime_name(runtime_segments, "clip_cover_geometry");
+            parts.runtime_names.push(cover_name.clone());
+            parts.runtime_names.push(geometry_name.clone());
+            parts
+                .scene_paths
+                .push(format!("{scene_path}/children/{cover_index}"));
+            parts
+                .scene_paths
+                .push(format!("{scene_path}/children/{cover_index}/children/0"));
+            parts.children.push(json!({
+                "type": "shape",
+                "name": cover_name,
+                "children": [{
+                    "type": "rectangle",
+                    "name": geometry_name,
+                    "width": INVERSE_CLIP_EXTENT,
+                    "height": INVERSE_CLIP_EXTENT,
+                    "origin_x": 0.5,
+                    "origin_y": 0.5
+                }]
+            }));
+        }
+    }
+
+    /// Lowers the fill and optional stroke that follow a shape's geometry
+    /// children, so their child indices start after the geometry.
+    fn lower_shape_paints(
+        &mut self,
+        paints: ShapePaints<'_>,
+        authored_path: &str,
+        runtime_segments: &[String],
+        scene_path: &str,
+        scope: &BTreeMap<String, Quantity>,
+        parts: &mut ShapeParts,
+    ) -> Result<(), AuthoringDiagnostic> {
+        let ShapePaints {
+            fill,
+            stroke,
+            stroke_thickness,
+        } = paints;
+        let fill_index = parts.children.len();
+        let fill_name = runtime_name(runtime_segments, "fill");
+        let LoweredObject {
+            object: fill_paint,
+            runtime_names: fill_runtime_names,
+            scene_paths: fill_scene_paths,
+        } = self.lower_paint(
+            fill,
+            &format!("{authored_path}.fill"),
+            runtime_segments,
+            &format!("{scene_path}/children/{fill_index}/children/0"),
+            scope,
+            PaintTarget::Fill,
+        )?;
+        parts.runtime_names.push(fill_name.clone());
+        parts.runtime_names.extend(fill_runtime_names);
+        parts
+            .scene_paths
+            .push(format!("{scene_path}/children/{fill_index}"));
+        parts.scene_paths.extend(fill_scene_paths);
+        parts.children.push(json!({
+            "type": "fill",
+            "name": fill_name,
+            "children": [fill_paint]
+        }));
+
+        let (Some(stroke), Some(thickness)) = (stroke, stroke_thickness) else {
+            return Ok(());
+        };
+        let stroke_index = fill_index + 1;
+        let stroke_name = runtime_name(runtime_segments, "stroke");
+        let LoweredObject {
+            object: stroke_paint,
+            runtime_names: stroke_runtime_names,
+            scene_paths: stroke_scene_paths,
+        } = self.lower_paint(
+            &stroke.paint,
+            &format!("{authored_path}.stroke.paint"),
+            runtime_segments,
+            &format!("{scene_path}/children/{stroke_index}/children/0"),
+            scope,
+            PaintTarget::Stroke,
+        )?;
+        parts.runtime_names.push(stroke_name.clone());
+        parts.runtime_names.extend(stroke_runtime_names);
+        parts
+            .scene_paths
+            .push(format!("{scene_path}/children/{stroke_index}"));
+        parts.scene_paths.extend(stroke_scene_paths);
+
+        let mut stroke_children = vec![stroke_paint];
+        if let Some(trim) = &stroke.trim {
+            let LoweredObject {
+                object,
+                runtime_names: trim_runtime_names,
+                scene_paths: trim_scene_paths,
+            } = self.lower_trim_path(
+                trim,
+                &format!("{authored_path}.stroke.trim"),
+                runtime_segments,
+                &format!("{scene_path}/children/{stroke_index}/children/1"),
+                scope,
+            )?;
+            parts.runtime_names.extend(trim_runtime_names);
+            parts.scene_paths.extend(trim_scene_paths);
+            stroke_children.push(object);
+        }
+
+        parts.children.push(json!({
+            "type": "stroke",
+            "name": stroke_name,
+            "thickness": thickness,
+            "children": stroke_children
+        }));
+        Ok(())
+    }
+}
+
+struct ShapePaints<'s> {
+    fill: &'s PaintSpec,
+    stroke: Option<&'s StrokeSpec>,
+    stroke_thickness: Option<f64>,
+}
+
+struct ShapeParts {
+    children: Vec<Value>,
+    runtime_names: Vec<String>,
+    scene_paths: Vec<String>,
+}
+
+fn evaluate_stroke_thickness(
+    stroke: Option<&StrokeSpec>,
+    authored_path: &str,
+    scope: &BTreeMap<String, Quantity>,
+) -> Result<Option<f64>, AuthoringDiagnostic> {
+    let stroke_thickness = stroke
+        .map(|stroke| {
+            evaluate_expression(
+                &stroke.width,
+                &format!("{authored_path}.stroke.width"),
+                scope,
+                Unit::Px,
+            )
+        })
+        .transpose()?;
+    if stroke_thickness.is_some_and(|thickness| thickness <= 0.0) {
+        return Err(AuthoringDiagnostic::new(
+            format!("{authored_path}.stroke.width"),
+            "invalid_dimension",
+            "stroke width must be greater than zero",
+        ));
+    }
+    Ok(stroke_thickness)
+}
+
+/// Emits a straight vertex, or a detached cubic vertex whose handles are
+/// stored as polar offsets from the vertex in radians and pixels.
+fn lower_vertex(
+    vertex: &PathVertex,
+    name: String,
+    authored_path: &str,
+) -> Result<Value, AuthoringDiagnostic> {
+    let Point { x, y } = vertex.point;
+    let value_path = format!("{authored_path}.path");
+    validate_scene_number(x, &value_path)?;
+    validate_scene_number(y, &value_path)?;
+    if vertex.in_control.is_none() && vertex.out_control.is_none() {
+        return Ok(json!({
+            "type": "straight_vertex",
+            "name": name,
+            "x": x,
+            "y": y
+        }));
+    }
+    let handle = |control: Option<Point>| {
+        let (rotation, distance) = control.map_or((0.0, 0.0), |control| {
+            (
+                atan2(control.y - y, control.x - x),
+                hypot(control.x - x, control.y - y),
+            )
+        });
+        validate_scene_number(distance, &value_path)?;
+        Ok::<_, AuthoringDiagnostic>((rotation, distance))
+    };
+    let (in_rotation, in_distance) = handle(vertex.in_control)?;
+    let (out_rotation, out_distance) = handle(vertex.out_control)?;
+    Ok(json!({
+        "type": "cubic_detached_vertex",
+        "name": name,
+        "x": x,
+        "y": y,
+        "in_rotation": in_rotation,
+        "in_distance": in_distance,
+        "out_rotation": out_rotation,
+        "out_distance": out_distance
+    }))
 }
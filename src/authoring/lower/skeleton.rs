@@ -0,0 +1,546 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use serde_json::{Value, json};
+
+use super::super::expression::{evaluate_expression, evaluate_transform};
+use super::super::spec::{AuthoringDiagnostic, Quantity, SourceMapEntry, Unit};
+use super::super::visual::{BoneBindingSpec, IkSpec, SkeletonNodeRef, SkinFalloff, VisualNode};
+use super::{
+    Lowerer, NodeContext, evaluate_ratio_expression, runtime_name, validate_id,
+    validate_sibling_ids_result,
+};
+
+/// The runtime blends at most four bones per vertex.
+const MAX_SKIN_INFLUENCES: u8 = 4;
+
+/// One bone of a skeleton, resolved from its chain.
+struct RigBone<'s> {
+    id: &'s str,
+    authored_path: String,
+    definition_path: Option<String>,
+    /// The position of a chain's root bone; `None` for a bone that starts at
+    /// the tip of its parent.
+    origin: Option<(f64, f64)>,
+    length: f64,
+    rotation: f64,
+    /// Bones from the root bone to this one, inclusive.
+    depth: u64,
+}
+
+/// An IK constraint resolved to the tip bone it lowers under.
+struct RigIk<'s> {
+    spec: &'s IkSpec,
+    index: usize,
+    target: String,
+    strength: f64,
+    parent_bone_count: u64,
+}
+
+/// A skeleton with its chains resolved and its children and constraints
+/// assigned to the bones they lower under.
+struct Rig<'s> {
+    authored_path: &'s str,
+    authored_id: &'s str,
+    runtime_segments: &'s [String],
+    scope: &'s BTreeMap<String, Quantity>,
+    definition_path: Option<&'s str>,
+    children: &'s [VisualNode],
+    bones: Vec<RigBone<'s>>,
+    /// Per bone, the bones that start at its tip: the next bone of its chain
+    /// first, then the chains branching from it.
+    bone_children: Vec<Vec<usize>>,
+    /// Per bone, the indices of the children rigidly bound to it.
+    rigid: Vec<Vec<usize>>,
+    iks: Vec<Vec<RigIk<'s>>>,
+    root_bones: Vec<usize>,
+}
+
+impl<'a> Lowerer<'a> {
+    /// Lowers a skeleton to a `node` holding one `root_bone` per root chain,
+    /// followed by the children no bone carries. Rigidly bound children and
+    /// IK constraints lower inside their bone; skinned paths stay at the
+    /// skeleton level and carry a `skin` on every contour.
+    pub(super) fn lower_skeleton(
+        &mut self,
+        skeleton: SkeletonNodeRef<'_>,
+        context: NodeContext<'_>,
+        component_stack: &mut Vec<String>,
+    ) -> Result<Value, AuthoringDiagnostic> {
+        let NodeContext {
+            authored_path,
+            definition_path,
+            authored_id,
+            runtime_segments,
+            scene_path,
+            scope,
+        } = context;
+
+        validate_sibling_ids_result(skeleton.children, &format!("{authored_path}.children"))?;
+        self.register_draw_references(
+            skeleton.children,
+            &format!("{authored_path}.children"),
+            Some(&authored_id),
+        )?;
+        let transform_values = evaluate_transform(
+            skeleton.transform,
+            &format!("{authored_path}.transform"),
+            scope,
+        )?;
+        let rig = self.resolve_rig(
+            skeleton,
+            Rig {
+                authored_path: &authored_path,
+                authored_id: &authored_id,
+                runtime_segments: &runtime_segments,
+                scope,
+                definition_path: definition_path.as_deref(),
+                children: skeleton.children,
+                bones: Vec::new(),
+                bone_children: Vec::new(),
+                rigid: Vec::new(),
+                iks: Vec::new(),
+                root_bones: Vec::new(),
+            },
+        )?;
+
+        let wrapper_name = runtime_name(&runtime_segments, "skeleton");
+        self.register_runtime_names(
+            std::slice::from_ref(&wrapper_name),
+            &format!("{authored_path}.id"),
+        )?;
+        self.source_map.entries.push(SourceMapEntry {
+            authored_id: authored_id.clone(),
+            authored_path: authored_path.clone(),
+            definition_path: definition_path.clone(),
+            runtime_names: vec![wrapper_name.clone()],
+            scene_paths: vec![scene_path.clone()],
+        });
+
+        let mut lowered_children = Vec::with_capacity(skeleton.children.len());
+        for &bone in &rig.root_bones {
+            let bone_scene_path = format!("{scene_path}/children/{}", lowered_children.len());
+            lowered_children.push(self.lower_rig_bone(
+                &rig,
+                bone,
+                bone_scene_path,
+                component_stack,
+            )?);
+        }
+        let bound: HashSet<usize> = rig.rigid.iter().flatten().copied().collect();
+        for index in (0..skeleton.children.len()).filter(|index| !bound.contains(index)) {
+            let child_scene_path = format!("{scene_path}/children/{}", lowered_children.len());
+            lowered_children.push(self.lower_rig_child(
+                &rig,
+                index,
+                child_scene_path,
+                component_stack,
+            )?);
+        }
+
+        Ok(json!({
+            "type": "node",
+            "name": wrapper_name,
+            "x": transform_values.x,
+            "y": transform_values.y,
+            "rotation": transform_values.rotation,
+            "scale_x": transform_values.scale_x,
+            "scale_y": transform_values.scale_y,
+            "children": lowered_children
+        }))
+    }
+
+    /// Resolves the chains, bindings and IK constraints of a skeleton. Bone
+    /// ids, IK ids and child ids share one namespace so each has a distinct
+    /// authored id.
+    fn resolve_rig<'s>(
+        &mut self,
+        skeleton: SkeletonNodeRef<'s>,
+        mut rig: Rig<'s>,
+    ) -> Result<Rig<'s>, AuthoringDiagnostic> {
+        let path = rig.authored_path;
+        let scope = rig.scope;
+        let mut ids: HashSet<&str> = skeleton.children.iter().map(VisualNode::id).collect();
+        let mut bone_indices = HashMap::new();
+
+        for (chain_index, chain) in skeleton.chains.iter().enumerate() {
+            let chain_path = format!("{path}.chains[{chain_index}]");
+            let mut parent: Option<usize> = match &chain.parent {
+                Some(parent) => {
+                    if chain.x.is_some() || chain.y.is_some() {
+                        return Err(AuthoringDiagnostic::new(
+                            format!("{chain_path}.parent"),
+                            "invalid_bone_chain",
+                            "a chain starts either at x and y or at the tip of its parent, not both",
+                        ));
+                    }
+                    Some(*bone_indices.get(parent.as_str()).ok_or_else(|| {
+                        AuthoringDiagnostic::new(
+                            format!("{chain_path}.parent"),
+                            "unknown_bone",
+                            format!("parent bone '{parent}' must belong to an earlier chain"),
+                        )
+                    })?)
+                }
+                None => None,
+            };
+            let origin = match parent {
+                Some(_) => None,
+                None => {
+                    let coordinate = |value: &Option<_>, axis: &str| {
+                        value.as_ref().map_or(Ok(0.0), |value| {
+                            evaluate_expression(
+                                value,
+                                &format!("{chain_path}.{axis}"),
+                                scope,
+                                Unit::Px,
+                            )
+                        })
+                    };
+                    Some((coordinate(&chain.x, "x")?, coordinate(&chain.y, "y")?))
+                }
+            };
+
+            for (bone_index, bone) in chain.bones.iter().enumerate() {
+                let bone_path = format!("{chain_path}.bones[{bone_index}]");
+                let id_path = format!("{bone_path}.id");
+                let mut diagnostics = Vec::new();
+                validate_id(&bone.id, &id_path, &mut diagnostics);
+                if let Some(first) = diagnostics.into_iter().next() {
+                    return Err(first);
+                }
+                if !ids.insert(&bone.id) {
+                    return Err(duplicate_id(&bone.id, id_path));
+                }
+                let length_path = format!("{bone_path}.length");
+                let length = evaluate_expression(&bone.length, &length_path, scope, Unit::Px)?;
+                if length <= 0.0 {
+                    return Err(AuthoringDiagnostic::new(
+                        length_path,
+                        "invalid_dimension",
+                        "bone length must be greater than zero",
+                    ));
+                }
+                let rotation = bone.angle.as_ref().map_or(Ok(0.0), |angle| {
+                    evaluate_expression(angle, &format!("{bone_path}.angle"), scope, Unit::Radians)
+                })?;
+
+                let index = rig.bones.len();
+                rig.bones.push(RigBone {
+                    id: &bone.id,
+                    definition_path: rig.definition_path.map(|definition| {
+                        format!("{definition}.chains[{chain_index}].bones[{bone_index}]")
+                    }),
+                    authored_path: bone_path,
+                    origin: if bone_index == 0 { origin } else { None },
+                    length,
+                    rotation,
+                    depth: parent.map_or(1, |parent| rig.bones[parent].depth + 1),
+                });
+                rig.bone_children.push(Vec::new());
+                rig.rigid.push(Vec::new());
+                rig.iks.push(Vec::new());
+                match parent {
+                    Some(parent) => rig.bone_children[parent].push(index),
+                    None => rig.root_bones.push(index),
+                }
+                bone_indices.insert(bone.id.as_str(), index);
+                parent = Some(index);
+            }
+        }
+
+        let bone_index = |bone: &str, path: String| {
+            bone_indices.get(bone).copied().ok_or_else(|| {
+                AuthoringDiagnostic::new(
+                    path,
+                    "unknown_bone",
+                    format!("bone '{bone}' is not declared by this skeleton"),
+                )
+            })
+        };
+
+        let mut bound = HashSet::new();
+        for (index, binding) in skeleton.bindings.iter().enumerate() {
+            let binding_path = format!("{path}.bindings[{index}]");
+            let node = match binding {
+                BoneBindingSpec::Rigid { node, .. } | BoneBindingSpec::Skinned { node, .. } => node,
+            };
+            let node_path = format!("{binding_path}.node");
+            let child_index = skeleton
+                .children
+                .iter()
+                .position(|child| child.id() == node)
+                .ok_or_else(|| {
+                    AuthoringDiagnostic::new(
+                        &node_path,
+                        "unknown_node",
+                        format!("bound node '{node}' is not a child of this skeleton"),
+                    )
+                })?;
+            if !bound.insert(child_index) {
+                return Err(AuthoringDiagnostic::new(
+                    node_path,
+                    "duplicate_binding",
+                    format!("node '{node}' is bound more than once"),
+                ));
+            }
+
+            match binding {
+                BoneBindingSpec::Rigid { bone, .. } => {
+                    let bone = bone_index(bone, format!("{binding_path}.bone"))?;
+                    rig.rigid[bone].push(child_index);
+                }
+                BoneBindingSpec::Skinned {
+                    bones,
+                    falloff,
+                    radius,
+                    max_influences,
+                    ..
+                } => {
+                    if skeleton.children[child_index].path_node().is_none() {
+                        return Err(AuthoringDiagnostic::new(
+                            node_path,
+                            "invalid_skin_binding",
+                            format!("skinned node '{node}' must be a path node"),
+                        ));
+                    }
+                    let mut skin_bones = Vec::with_capacity(bones.len());
+                    let mut seen = HashSet::new();
+                    for (position, bone) in bones.iter().enumerate() {
+                        let bone_path = format!("{binding_path}.bones[{position}]");
+                        if !seen.insert(bone.as_str()) {
+                            return Err(AuthoringDiagnostic::new(
+                                bone_path,
+                                "invalid_skin_binding",
+                                format!("bone '{bone}' is listed twice"),
+                            ));
+                        }
+                        let bone = bone_index(bone, bone_path)?;
+                        skin_bones.push(rig_bone_name(&rig, bone));
+                    }
+                    if skin_bones.is_empty() {
+                        return Err(AuthoringDiagnostic::new(
+                            format!("{binding_path}.bones"),
+                            "invalid_skin_binding",
+                            "a skinned binding lists at least one bone",
+                        ));
+                    }
+                    let mut skin = json!({
+                        "bones": skin_bones,
+                        "falloff": match falloff {
+                            SkinFalloff::Distance => "distance",
+                            SkinFalloff::Heat => "heat",
+                        }
+                    });
+                    if let Some(radius) = radius {
+                        let radius_path = format!("{binding_path}.radius");
+                        if *falloff != SkinFalloff::Heat {
+                            return Err(AuthoringDiagnostic::new(
+                                radius_path,
+                                "invalid_skin_binding",
+                                "radius only applies to the heat falloff",
+                            ));
+                        }
+                        let radius = evaluate_expression(radius, &radius_path, scope, Unit::Px)?;
+                        if radius <= 0.0 {
+                            return Err(AuthoringDiagnostic::new(
+                                radius_path,
+                                "invalid_dimension",
+                                "skin radius must be greater than zero",
+                            ));
+                        }
+                        skin["radius"] = json!(radius);
+                    }
+                    if let Some(max_influences) = max_influences {
+                        if !(1..=MAX_SKIN_INFLUENCES).contains(max_influences) {
+                            return Err(AuthoringDiagnostic::new(
+                                format!("{binding_path}.max_influences"),
+                                "invalid_skin_binding",
+                                format!(
+                                    "max_influences must be between 1 and {MAX_SKIN_INFLUENCES}"
+                                ),
+                            ));
+                        }
+                        skin["max_influences"] = json!(max_influences);
+                    }
+                    self.skin_bindings
+                        .insert(format!("{}/{node}", rig.authored_id), skin);
+                }
+            }
+        }
+
+        for (index, ik) in skeleton.ik.iter().enumerate() {
+            let ik_path = format!("{path}.ik[{index}]");
+            let id_path = format!("{ik_path}.id");
+            let mut diagnostics = Vec::new();
+            validate_id(&ik.id, &id_path, &mut diagnostics);
+            if let Some(first) = diagnostics.into_iter().next() {
+                return Err(first);
+            }
+            if !ids.insert(&ik.id) {
+                return Err(duplicate_id(&ik.id, id_path));
+            }
+            let bone = bone_index(&ik.bone, format!("{ik_path}.bone"))?;
+            let depth = rig.bones[bone].depth;
+            let bone_count = ik.bone_count.unwrap_or(depth);
+            if bone_count == 0 || bone_count > depth {
+                return Err(AuthoringDiagnostic::new(
+                    format!("{ik_path}.bone_count"),
+                    "invalid_bone_count",
+                    format!(
+                        "IK '{}' can turn between 1 and {depth} bones ending at '{}'",
+                        ik.id, ik.bone
+                    ),
+                ));
+            }
+            // The builder resolves targets in file order, so only nodes that
+            // have already lowered are eligible.
+            let target = self
+                .source_map
+                .entries
+                .iter()
+                .find(|entry| entry.authored_id == ik.target)
+                .and_then(|entry| entry.runtime_names.first())
+                .cloned()
+                .ok_or_else(|| {
+                    AuthoringDiagnostic::new(
+                        format!("{ik_path}.target"),
+                        "unknown_ik_target",
+                        format!(
+                            "IK target '{}' must be the authored id of a node declared before the skeleton",
+                            ik.target
+                        ),
+                    )
+                })?;
+            let strength = ik.strength.as_ref().map_or(Ok(1.0), |strength| {
+                evaluate_ratio_expression(
+                    strength,
+                    &format!("{ik_path}.strength"),
+                    scope,
+                    "IK strength must be between zero and one",
+                )
+            })?;
+            rig.iks[bone].push(RigIk {
+                spec: ik,
+                index,
+                target,
+                strength,
+                parent_bone_count: bone_count - 1,
+            });
+        }
+
+        Ok(rig)
+    }
+
+    /// Lowers a bone with the bones that start at its tip, its rigidly bound
+    /// children and the IK constraints that end at it.
+    fn lower_rig_bone(
+        &mut self,
+        rig: &Rig<'_>,
+        bone: usize,
+        scene_path: String,
+        component_stack: &mut Vec<String>,
+    ) -> Result<Value, AuthoringDiagnostic> {
+        let RigBone {
+            id,
+            authored_path,
+            definition_path,
+            origin,
+            length,
+            rotation,
+            ..
+        } = &rig.bones[bone];
+        let name = rig_bone_name(rig, bone);
+        self.register_runtime_names(std::slice::from_ref(&name), &format!("{authored_path}.id"))?;
+        self.source_map.entries.push(SourceMapEntry {
+            authored_id: format!("{}/{id}", rig.authored_id),
+            authored_path: authored_path.clone(),
+            definition_path: definition_path.clone(),
+            runtime_names: vec![name.clone()],
+            scene_paths: vec![scene_path.clone()],
+        });
+
+        let mut children = Vec::new();
+        for &next in &rig.bone_children[bone] {
+            let next_scene_path = format!("{scene_path}/children/{}", children.len());
+            children.push(self.lower_rig_bone(rig, next, next_scene_path, component_stack)?);
+        }
+        for &child in &rig.rigid[bone] {
+            let child_scene_path = format!("{scene_path}/children/{}", children.len());
+            children.push(self.lower_rig_child(rig, child, child_scene_path, component_stack)?);
+        }
+        for ik in &rig.iks[bone] {
+            let ik_path = format!("{}.ik[{}]", rig.authored_path, ik.index);
+            let mut segments = rig.runtime_segments.to_vec();
+            segments.push(ik.spec.id.clone());
+            let ik_name = runtime_name(&segments, "ik");
+            self.register_runtime_names(std::slice::from_ref(&ik_name), &format!("{ik_path}.id"))?;
+            self.source_map.entries.push(SourceMapEntry {
+                authored_id: format!("{}/{}", rig.authored_id, ik.spec.id),
+                definition_path: rig
+                    .definition_path
+                    .map(|definition| format!("{definition}.ik[{}]", ik.index)),
+                authored_path: ik_path,
+                runtime_names: vec![ik_name.clone()],
+                scene_paths: vec![format!("{scene_path}/children/{}", children.len())],
+            });
+            children.push(json!({
+                "type": "ik_constraint",
+                "name": ik_name,
+                "target": ik.target,
+                "strength": ik.strength,
+                "invert_direction": ik.spec.invert,
+                "parent_bone_count": ik.parent_bone_count
+            }));
+        }
+
+        let mut object = match origin {
+            Some((x, y)) => json!({ "type": "root_bone", "x": x, "y": y }),
+            None => json!({ "type": "bone" }),
+        };
+        object["name"] = json!(name);
+        object["length"] = json!(length);
+        object["rotation"] = json!(rotation);
+        object["children"] = Value::Array(children);
+        Ok(object)
+    }
+
+    fn lower_rig_child(
+        &mut self,
+        rig: &Rig<'_>,
+        index: usize,
+        scene_path: String,
+        component_stack: &mut Vec<String>,
+    ) -> Result<Value, AuthoringDiagnostic> {
+        let child = &rig.children[index];
+        let mut runtime_segments = rig.runtime_segments.to_vec();
+        runtime_segments.push(child.id().to_string());
+        self.lower_node(
+            child,
+            NodeContext {
+                authored_path: format!("{}.children[{index}]", rig.authored_path),
+                definition_path: rig
+                    .definition_path
+                    .map(|path| format!("{path}.children[{index}]")),
+                authored_id: format!("{}/{}", rig.authored_id, child.id()),
+                runtime_segments,
+                scene_path,
+                scope: rig.scope,
+            },
+            component_stack,
+        )
+    }
+}
+
+fn rig_bone_name(rig: &Rig<'_>, bone: usize) -> String {
+    let mut segments = rig.runtime_segments.to_vec();
+    segments.push(rig.bones[bone].id.to_string());
+    runtime_name(&segments, "bone")
+}
+
+fn duplicate_id(id: &str, path: String) -> AuthoringDiagnostic {
+    AuthoringDiagnostic::new(
+        path,
+        "duplicate_id",
+        format!("authored id '{id}' is duplicated among the skeleton's bones, IK and children"),
+    )
+}
@@ -3,6 +3,7 @@ use serde_json::{Value, json};
 use super::super::expression::{evaluate_expression, evaluate_transform};
 use super::super::spec::{AuthoringDiagnostic, SourceMapEntry, TextAlign, TextOverflow, Unit};
 use super::super::visual::TextNodeRef;
+use super::parameter::literal_field;
 use super::{
     LoweredObject, Lowerer, NodeContext, PaintTarget, evaluate_ratio_expression,
     font_asset_runtime_name, runtime_name,
@@ -39,8 +40,10 @@ impl<'a> Lowerer<'a> {
             scope,
         } = context;
 
+        let content = literal_field(content, &format!("{authored_path}.text"))?;
         let font_asset = font
             .map(|id| {
+                let id = literal_field(id, &format!("{authored_path}.font"))?;
                 if !self.spec.font_assets.contains_key(id) {
                     return Err(AuthoringDiagnostic::new(
                         format!("{authored_path}.font"),
@@ -12,14 +12,23 @@ use schemars::schema_for;
 use serde_json::Value;
 
 pub use spec::{
-    AUTHORING_FORMAT_VERSION, AuthoringArtboard, AuthoringDiagnostic, AuthoringError,
-    AuthoringSourceMap, AuthoringSpec, BehaviorSection, ComponentSpec, ConstraintAxis,
-    ConstraintSpec, GradientKind, GradientPaintSpec, GradientStopSpec, LoweredAuthoring,
-    MotionEasingSpec, MotionInterpolation, MotionLoop, MotionSection, MotionTrackSpec, PaintSpec,
-    PoseKeyframeSpec, PoseSpec, PoseTargetSpec, Quantity, RawSceneFragment, ScalarExpr,
-    SourceMapEntry, StrokeSpec, TransformSpec, Unit, VisualSection,
+    AUTHORING_FORMAT_VERSION, ArtboardDefinitionSpec, AuthoringArtboard, AuthoringDiagnostic,
+    AuthoringError, AuthoringSourceMap, AuthoringSpec, BehaviorSection, ComponentParameter,
+    ComponentSpec, ConstraintAxis, ConstraintSpec, EasingPreset, ElasticEasing, EventPropertySpec,
+    EventSpec, GradientKind, GradientPaintSpec, GradientStopSpec, LoweredAuthoring,
+    ModelBindingDirection, ModelBindingField, ModelBindingSpec, ModelConverterSpec,
+    ModelPropertySpec, ModelSection, MotionEasingSpec, MotionInterpolation, MotionLoop,
+    MotionSection, MotionTrackSpec, OpenUrlTarget, OutlineSpec, PaintSpec, ParameterRef,
+    ParameterizedString, ParametricOutlineSpec, PoseGradientSpec, PoseGradientStopSpec,
+    PoseKeyframeSpec, PoseSpec, PoseStrokeSpec, PoseTargetSpec, PoseTrimSpec, Quantity,
+    RawSceneFragment, ScalarExpr, SourceMapEntry, StaggerOffsetSpec, StaggerSpec, StrokeSpec,
+    TrackEventSpec, TransformSpec, Unit, VisualSection,
+};
+pub use visual::{
+    ClipSpec, DrawLayerSpec, DrawPlacement, DrawRulesSpec, LayoutAlign, LayoutDirection,
+    LayoutJustify, LayoutPaddingSpec, LayoutSizeSpec, MirrorAxis, NineSliceInsetsSpec,
+    NineSliceTileMode, PathDataSpec, PathPointSpec, PathSegmentSpec, VisualNode, ZOrderSpec,
 };
-pub use visual::{MirrorAxis, PathPointSpec, VisualNode};
 
 pub fn lower_authoring_json(input: &str) -> Result<LoweredAuthoring, AuthoringError> {
     let spec = serde_json::from_str::<AuthoringSpec>(input).map_err(|error| {
@@ -5,7 +5,7 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
-use super::visual::VisualNode;
+use super::visual::{PathDataSpec, VisualNode};
 
 pub const AUTHORING_FORMAT_VERSION: u32 = 0;
 
@@ -16,16 +16,23 @@ pub struct AuthoringSpec {
     pub authoring_format_version: u32,
     pub artboard: AuthoringArtboard,
     #[serde(default)]
+    #[schemars(length(max = 100))]
+    pub artboards: Vec<ArtboardDefinitionSpec>,
+    #[serde(default)]
     pub font_assets: BTreeMap<String, String>,
     #[serde(default)]
     pub image_assets: BTreeMap<String, String>,
     #[serde(default)]
+    pub audio_assets: BTreeMap<String, String>,
+    #[serde(default)]
     pub parameters: BTreeMap<String, Quantity>,
     #[serde(default)]
     pub components: Vec<ComponentSpec>,
     pub visual: VisualSection,
     pub motion: MotionSection,
     pub behavior: BehaviorSection,
+    #[serde(default)]
+    pub model: ModelSection,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -36,6 +43,23 @@ pub struct AuthoringArtboard {
     pub height: Quantity,
 }
 
+/// An additional artboard, typically a reusable widget that other artboards
+/// instance through `nested_artboard` nodes. File assets, parameters and
+/// components are shared with the primary artboard; the view model is not.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ArtboardDefinitionSpec {
+    pub id: String,
+    pub width: Quantity,
+    pub height: Quantity,
+    #[serde(default)]
+    pub visual: VisualSection,
+    #[serde(default)]
+    pub motion: MotionSection,
+    #[serde(default)]
+    pub behavior: BehaviorSection,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct Quantity {
@@ -78,6 +102,46 @@ pub enum ScalarExpr {
         value: Box<ScalarExpr>,
         divisor: f64,
     },
+    Min {
+        left: Box<ScalarExpr>,
+        right: Box<ScalarExpr>,
+    },
+    Max {
+        left: Box<ScalarExpr>,
+        right: Box<ScalarExpr>,
+    },
+    Clamp {
+        value: Box<ScalarExpr>,
+        min: Box<ScalarExpr>,
+        max: Box<ScalarExpr>,
+    },
+    /// Multiplies two expressions; at least one side must be scalar.
+    Product {
+        left: Box<ScalarExpr>,
+        right: Box<ScalarExpr>,
+    },
+    /// Divides two expressions. Like units cancel to scalar; otherwise the
+    /// divisor must be scalar.
+    Quotient {
+        left: Box<ScalarExpr>,
+        right: Box<ScalarExpr>,
+    },
+    Sin {
+        angle: Box<ScalarExpr>,
+    },
+    Cos {
+        angle: Box<ScalarExpr>,
+    },
+    /// Rounds a pixel or scalar value to the nearest whole unit, halves away
+    /// from zero.
+    Round {
+        value: Box<ScalarExpr>,
+    },
+    Lerp {
+        from: Box<ScalarExpr>,
+        to: Box<ScalarExpr>,
+        t: Box<ScalarExpr>,
+    },
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
@@ -146,7 +210,7 @@ pub enum GradientKind {
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct GradientStopSpec {
-    pub color: String,
+    pub color: ParameterizedString,
     pub position: ScalarExpr,
 }
 
@@ -166,6 +230,35 @@ pub struct GradientPaintSpec {
 pub enum PaintSpec {
     Solid(String),
     Gradient(GradientPaintSpec),
+    /// A `color` or `paint` component parameter.
+    Parameter(ParameterRef),
+}
+
+/// Reads a typed component parameter in place of a literal field value.
+/// References resolve only inside component visuals, when an instance
+/// expands.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ParameterRef {
+    pub parameter: String,
+}
+
+/// A colour, text string or asset id, either literal or read from a
+/// component parameter.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub enum ParameterizedString {
+    Literal(String),
+    Parameter(ParameterRef),
+}
+
+impl ParameterizedString {
+    pub(crate) fn literal(&self) -> Option<&str> {
+        match self {
+            Self::Literal(value) => Some(value),
+            Self::Parameter(_) => None,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
@@ -221,10 +314,74 @@ pub enum TextOverflow {
 pub struct ComponentSpec {
     pub id: String,
     #[serde(default)]
-    pub parameters: BTreeMap<String, Quantity>,
+    pub parameters: BTreeMap<String, ComponentParameter>,
     pub visual: Vec<VisualNode>,
 }
 
+/// A component parameter default or instance override. Quantities feed
+/// scalar expressions; typed values substitute into the visual fields that
+/// reference them.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub enum ComponentParameter {
+    Quantity(Quantity),
+    Typed(TypedParameter),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "kind", rename_all = "snake_case", deny_unknown_fields)]
+pub enum TypedParameter {
+    Color { value: String },
+    Paint { value: PaintSpec },
+    String { value: String },
+    FontAsset { value: String },
+    ImageAsset { value: String },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ParameterKind {
+    Quantity,
+    Color,
+    Paint,
+    String,
+    FontAsset,
+    ImageAsset,
+}
+
+impl ParameterKind {
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            Self::Quantity => "quantity",
+            Self::Color => "color",
+            Self::Paint => "paint",
+            Self::String => "string",
+            Self::FontAsset => "font_asset",
+            Self::ImageAsset => "image_asset",
+        }
+    }
+}
+
+impl ComponentParameter {
+    pub(crate) fn kind(&self) -> ParameterKind {
+        match self {
+            Self::Quantity(_) => ParameterKind::Quantity,
+            Self::Typed(typed) => typed.kind(),
+        }
+    }
+}
+
+impl TypedParameter {
+    pub(crate) fn kind(&self) -> ParameterKind {
+        match self {
+            Self::Color { .. } => ParameterKind::Color,
+            Self::Paint { .. } => ParameterKind::Paint,
+            Self::String { .. } => ParameterKind::String,
+            Self::FontAsset { .. } => ParameterKind::FontAsset,
+            Self::ImageAsset { .. } => ParameterKind::ImageAsset,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct VisualSection {
@@ -259,16 +416,65 @@ pub enum MotionEasingSpec {
         x2: ScalarExpr,
         y2: ScalarExpr,
     },
+    /// A named cubic curve; lowers exactly like the equivalent `cubic`.
+    Preset { id: String, preset: EasingPreset },
+    Elastic {
+        id: String,
+        #[serde(default)]
+        easing: ElasticEasing,
+        amplitude: ScalarExpr,
+        period: ScalarExpr,
+    },
+    /// A damped spring, baked into linear keyframes that stay within
+    /// `tolerance` of the simulated curve. Each spring-eased segment must be
+    /// long enough for the spring to settle within `tolerance` of its target.
+    Spring {
+        id: String,
+        stiffness: ScalarExpr,
+        damping: ScalarExpr,
+        mass: ScalarExpr,
+        #[serde(default)]
+        tolerance: Option<ScalarExpr>,
+    },
 }
 
 impl MotionEasingSpec {
     pub(crate) fn id(&self) -> &str {
         match self {
-            Self::Cubic { id, .. } => id,
+            Self::Cubic { id, .. }
+            | Self::Preset { id, .. }
+            | Self::Elastic { id, .. }
+            | Self::Spring { id, .. } => id,
         }
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum EasingPreset {
+    Ease,
+    EaseIn,
+    EaseOut,
+    EaseInOut,
+    EaseInBack,
+    EaseOutBack,
+    EaseInOutBack,
+    MaterialStandard,
+    MaterialStandardAccelerate,
+    MaterialStandardDecelerate,
+    MaterialEmphasizedAccelerate,
+    MaterialEmphasizedDecelerate,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ElasticEasing {
+    In,
+    #[default]
+    Out,
+    InOut,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct PoseTargetSpec {
@@ -281,6 +487,97 @@ pub struct PoseTargetSpec {
     pub width: Option<ScalarExpr>,
     #[serde(default)]
     pub height: Option<ScalarExpr>,
+    #[serde(default)]
+    pub corner_radius: Option<ScalarExpr>,
+    /// Colour of the target's solid fill.
+    #[serde(default)]
+    pub fill: Option<String>,
+    #[serde(default)]
+    pub stroke: Option<PoseStrokeSpec>,
+    #[serde(default)]
+    pub gradient: Option<PoseGradientSpec>,
+    /// Outline a `path` target morphs to.
+    #[serde(default)]
+    pub outline: Option<OutlineSpec>,
+}
+
+/// Path data, or a parametric shape centred on the target path's origin.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub enum OutlineSpec {
+    Path(PathDataSpec),
+    Shape(ParametricOutlineSpec),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "kind", rename_all = "snake_case", deny_unknown_fields)]
+pub enum ParametricOutlineSpec {
+    Ellipse {
+        width: ScalarExpr,
+        height: ScalarExpr,
+    },
+    Rectangle {
+        width: ScalarExpr,
+        height: ScalarExpr,
+        #[serde(default)]
+        corner_radius: Option<ScalarExpr>,
+    },
+    Triangle {
+        width: ScalarExpr,
+        height: ScalarExpr,
+    },
+    Polygon {
+        width: ScalarExpr,
+        height: ScalarExpr,
+        points: u64,
+    },
+    Star {
+        width: ScalarExpr,
+        height: ScalarExpr,
+        points: u64,
+        inner_radius: ScalarExpr,
+    },
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct PoseStrokeSpec {
+    /// Colour of the target's solid stroke.
+    #[serde(default)]
+    pub paint: Option<String>,
+    #[serde(default)]
+    pub width: Option<ScalarExpr>,
+    #[serde(default)]
+    pub trim: Option<PoseTrimSpec>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct PoseTrimSpec {
+    #[serde(default)]
+    pub start: Option<ScalarExpr>,
+    #[serde(default)]
+    pub end: Option<ScalarExpr>,
+    #[serde(default)]
+    pub offset: Option<ScalarExpr>,
+}
+
+/// Stops are matched by index against the target's single gradient paint,
+/// whether it fills or strokes the shape.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct PoseGradientSpec {
+    #[schemars(length(max = 64))]
+    pub stops: Vec<PoseGradientStopSpec>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct PoseGradientStopSpec {
+    #[serde(default)]
+    pub color: Option<String>,
+    #[serde(default)]
+    pub position: Option<ScalarExpr>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -313,6 +610,48 @@ pub struct MotionTrackSpec {
     pub loop_type: MotionLoop,
     #[schemars(length(min = 2, max = 1000))]
     pub keyframes: Vec<PoseKeyframeSpec>,
+    #[serde(default)]
+    pub stagger: Option<StaggerSpec>,
+    #[serde(default)]
+    #[schemars(length(max = 1000))]
+    pub events: Vec<TrackEventSpec>,
+}
+
+/// Fires a `behavior.events` entry when playback crosses `frame`. Staggered
+/// tracks fire it once, not once per copy.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct TrackEventSpec {
+    pub frame: ScalarExpr,
+    pub event: String,
+}
+
+/// Replays a track on every copy a pattern node generates. Pose targets then
+/// name the pattern's item, or nodes inside it, relative to each copy.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct StaggerSpec {
+    pub pattern: String,
+    pub offset: StaggerOffsetSpec,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "kind", rename_all = "snake_case", deny_unknown_fields)]
+pub enum StaggerOffsetSpec {
+    Linear {
+        step_frames: ScalarExpr,
+    },
+    Reverse {
+        step_frames: ScalarExpr,
+    },
+    FromCenter {
+        step_frames: ScalarExpr,
+    },
+    /// Evaluated once per copy with `copy_index` and `copy_count` bound as
+    /// scalar parameters; authored parameters must not use either name.
+    Expression {
+        frames: ScalarExpr,
+    },
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
@@ -334,10 +673,221 @@ pub struct MotionSection {
 #[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct BehaviorSection {
+    #[serde(default)]
+    #[schemars(length(max = 1000))]
+    pub events: Vec<EventSpec>,
     #[serde(default)]
     pub raw_state_machines: Vec<RawSceneFragment>,
 }
 
+/// A Rive event the host observes. Its runtime name is the authored id so
+/// hosts can match on it; property keys are likewise reported unmangled.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "kind", rename_all = "snake_case", deny_unknown_fields)]
+pub enum EventSpec {
+    General {
+        id: String,
+        #[serde(default)]
+        properties: BTreeMap<String, EventPropertySpec>,
+    },
+    OpenUrl {
+        id: String,
+        url: String,
+        #[serde(default)]
+        target: OpenUrlTarget,
+        #[serde(default)]
+        properties: BTreeMap<String, EventPropertySpec>,
+    },
+    Audio {
+        id: String,
+        asset: String,
+        #[serde(default)]
+        properties: BTreeMap<String, EventPropertySpec>,
+    },
+}
+
+impl EventSpec {
+    pub(crate) fn id(&self) -> &str {
+        match self {
+            Self::General { id, .. } | Self::OpenUrl { id, .. } | Self::Audio { id, .. } => id,
+        }
+    }
+
+    pub(crate) fn properties(&self) -> &BTreeMap<String, EventPropertySpec> {
+        match self {
+            Self::General { properties, .. }
+            | Self::OpenUrl { properties, .. }
+            | Self::Audio { properties, .. } => properties,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum OpenUrlTarget {
+    #[default]
+    Blank,
+    Parent,
+    #[serde(rename = "self")]
+    SelfTarget,
+    Top,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "kind", rename_all = "snake_case", deny_unknown_fields)]
+pub enum EventPropertySpec {
+    Number { value: f64 },
+    Boolean { value: bool },
+    String { value: String },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "kind", rename_all = "snake_case", deny_unknown_fields)]
+pub enum ModelPropertySpec {
+    Number {
+        id: String,
+        #[serde(default)]
+        default: f64,
+    },
+    String {
+        id: String,
+        #[serde(default)]
+        default: String,
+    },
+    Color {
+        id: String,
+        default: String,
+    },
+    Boolean {
+        id: String,
+        #[serde(default)]
+        default: bool,
+    },
+    Enum {
+        id: String,
+        #[schemars(length(min = 1, max = 1000))]
+        values: Vec<String>,
+        #[serde(default)]
+        default: Option<String>,
+    },
+    Trigger {
+        id: String,
+    },
+    List {
+        id: String,
+    },
+}
+
+impl ModelPropertySpec {
+    pub(crate) fn id(&self) -> &str {
+        match self {
+            Self::Number { id, .. }
+            | Self::String { id, .. }
+            | Self::Color { id, .. }
+            | Self::Boolean { id, .. }
+            | Self::Enum { id, .. }
+            | Self::Trigger { id }
+            | Self::List { id } => id,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub enum ModelBindingField {
+    #[serde(rename = "transform.x")]
+    TransformX,
+    #[serde(rename = "transform.y")]
+    TransformY,
+    #[serde(rename = "transform.rotation")]
+    TransformRotation,
+    #[serde(rename = "transform.scale_x")]
+    TransformScaleX,
+    #[serde(rename = "transform.scale_y")]
+    TransformScaleY,
+    #[serde(rename = "opacity")]
+    Opacity,
+    #[serde(rename = "width")]
+    Width,
+    #[serde(rename = "height")]
+    Height,
+    #[serde(rename = "fill")]
+    Fill,
+    #[serde(rename = "stroke")]
+    Stroke,
+    #[serde(rename = "stroke.width")]
+    StrokeWidth,
+    #[serde(rename = "text")]
+    Text,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ModelBindingDirection {
+    #[default]
+    ToTarget,
+    ToSource,
+    TwoWay,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "kind", rename_all = "snake_case", deny_unknown_fields)]
+pub enum ModelConverterSpec {
+    Add {
+        value: f64,
+    },
+    Subtract {
+        value: f64,
+    },
+    Multiply {
+        value: f64,
+    },
+    Divide {
+        value: f64,
+    },
+    Round {
+        #[schemars(range(max = 10))]
+        decimals: u64,
+    },
+    RangeMap {
+        min_input: f64,
+        max_input: f64,
+        min_output: f64,
+        max_output: f64,
+    },
+    DegreesToRadians,
+    Negate,
+    ToString {
+        #[serde(default)]
+        #[schemars(range(max = 10))]
+        decimals: Option<u64>,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ModelBindingSpec {
+    pub id: String,
+    pub property: String,
+    pub target: String,
+    pub field: ModelBindingField,
+    #[serde(default)]
+    pub direction: ModelBindingDirection,
+    #[serde(default)]
+    #[schemars(length(max = 16))]
+    pub converters: Vec<ModelConverterSpec>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ModelSection {
+    #[serde(default)]
+    #[schemars(length(max = 1000))]
+    pub properties: Vec<ModelPropertySpec>,
+    #[serde(default)]
+    #[schemars(length(max = 1000))]
+    pub bind: Vec<ModelBindingSpec>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct RawSceneFragment {
@@ -2,10 +2,12 @@ use std::collections::BTreeMap;
 
 use super::expression::validate_scene_number;
 use super::spec::{
-    AuthoringDiagnostic, AuthoringSpec, ConstraintSpec, PaintSpec, Quantity, ScalarExpr,
-    TransformSpec,
+    AuthoringDiagnostic, AuthoringSpec, ComponentParameter, ConstraintSpec, PaintSpec, Quantity,
+    ScalarExpr, StrokeSpec, TransformSpec,
+};
+use super::visual::{
+    BoneBindingSpec, LayoutSizeSpec, PathDataSpec, PathSegmentSpec, PatternNodeRef, VisualNode,
 };
-use super::visual::{PatternNodeRef, VisualNode};
 
 pub(super) fn validate_numeric_values(spec: &AuthoringSpec) -> Vec<AuthoringDiagnostic> {
     let mut diagnostics = Vec::new();
@@ -15,7 +17,7 @@ pub(super) fn validate_numeric_values(spec: &AuthoringSpec) -> Vec<AuthoringDiag
 
     for (component_index, component) in spec.components.iter().enumerate() {
         let component_path = format!("$.components[{component_index}]");
-        validate_quantity_map(
+        validate_parameter_map(
             &component.parameters,
             &format!("{component_path}.parameters"),
             &mut diagnostics,
@@ -27,6 +29,24 @@ pub(super) fn validate_numeric_values(spec: &AuthoringSpec) -> Vec<AuthoringDiag
         );
     }
     validate_nodes(&spec.visual.nodes, "$.visual.nodes", &mut diagnostics);
+    for (artboard_index, artboard) in spec.artboards.iter().enumerate() {
+        let artboard_path = format!("$.artboards[{artboard_index}]");
+        validate_quantity(
+            artboard.width,
+            &format!("{artboard_path}.width"),
+            &mut diagnostics,
+        );
+        validate_quantity(
+            artboard.height,
+            &format!("{artboard_path}.height"),
+            &mut diagnostics,
+        );
+        validate_nodes(
+            &artboard.visual.nodes,
+            &format!("{artboard_path}.visual.nodes"),
+            &mut diagnostics,
+        );
+    }
     diagnostics
 }
 
@@ -40,6 +60,18 @@ fn validate_quantity_map(
     }
 }
 
+fn validate_parameter_map(
+    parameters: &BTreeMap<String, ComponentParameter>,
+    path: &str,
+    diagnostics: &mut Vec<AuthoringDiagnostic>,
+) {
+    for (name, parameter) in parameters {
+        if let ComponentParameter::Quantity(quantity) = parameter {
+            validate_quantity(*quantity, &format!("{path}.{name}"), diagnostics);
+        }
+    }
+}
+
 fn validate_quantity(quantity: Quantity, path: &str, diagnostics: &mut Vec<AuthoringDiagnostic>) {
     if let Err(diagnostic) = validate_scene_number(quantity.value, &format!("{path}.value")) {
         diagnostics.push(diagnostic);
@@ -68,21 +100,30 @@ fn validate_node(node: &VisualNode, path: &str, diagnostics: &mut Vec<AuthoringD
         }
         validate_paint(shape.fill, &format!("{path}.fill"), diagnostics);
         if let Some(stroke) = shape.stroke {
-            validate_paint(&stroke.paint, &format!("{path}.stroke.paint"), diagnostics);
-            validate_expression(&stroke.width, &format!("{path}.stroke.width"), diagnostics);
-            if let Some(trim) = &stroke.trim {
-                validate_expression(
-                    &trim.start,
-                    &format!("{path}.stroke.trim.start"),
-                    diagnostics,
-                );
-                validate_expression(&trim.end, &format!("{path}.stroke.trim.end"), diagnostics);
-                if let Some(offset) = &trim.offset {
-                    validate_expression(offset, &format!("{path}.stroke.trim.offset"), diagnostics);
+            validate_stroke(stroke, &format!("{path}.stroke"), diagnostics);
+        }
+        validate_transform(shape.transform, &format!("{path}.transform"), diagnostics);
+        return;
+    }
+
+    if let Some(path_node) = node.path_node() {
+        if let PathDataSpec::Segments(segments) = path_node.path {
+            for (index, segment) in segments.iter().enumerate() {
+                let segment_path = format!("{path}.path[{index}]");
+                for (name, expression) in segment_expressions(segment) {
+                    validate_expression(expression, &format!("{segment_path}.{name}"), diagnostics);
                 }
             }
         }
-        validate_transform(shape.transform, &format!("{path}.transform"), diagnostics);
+        validate_paint(path_node.fill, &format!("{path}.fill"), diagnostics);
+        if let Some(stroke) = path_node.stroke {
+            validate_stroke(stroke, &format!("{path}.stroke"), diagnostics);
+        }
+        validate_transform(
+            path_node.transform,
+            &format!("{path}.transform"),
+            diagnostics,
+        );
         return;
     }
 
@@ -111,6 +152,24 @@ fn validate_node(node: &VisualNode, path: &str, diagnostics: &mut Vec<AuthoringD
         return;
     }
 
+    if let Some(nine_slice) = node.nine_slice_node() {
+        let insets = nine_slice.insets;
+        for (name, expression) in [
+            ("left", &insets.left),
+            ("top", &insets.top),
+            ("right", &insets.right),
+            ("bottom", &insets.bottom),
+        ] {
+            validate_expression(expression, &format!("{path}.insets.{name}"), diagnostics);
+        }
+        validate_transform(
+            nine_slice.transform,
+            &format!("{path}.transform"),
+            diagnostics,
+        );
+        return;
+    }
+
     if let Some(pattern) = node.pattern() {
         match pattern {
             PatternNodeRef::Grid(grid) => {
@@ -201,12 +260,91 @@ fn validate_node(node: &VisualNode, path: &str, diagnostics: &mut Vec<AuthoringD
             }
             validate_nodes(children, &format!("{path}.children"), diagnostics);
         }
+        VisualNode::Layout {
+            gap,
+            padding,
+            width,
+            height,
+            children,
+            ..
+        } => {
+            if let Some(gap) = gap {
+                validate_expression(gap, &format!("{path}.gap"), diagnostics);
+            }
+            for (side, value) in [
+                ("top", &padding.top),
+                ("right", &padding.right),
+                ("bottom", &padding.bottom),
+                ("left", &padding.left),
+            ] {
+                if let Some(value) = value {
+                    validate_expression(value, &format!("{path}.padding.{side}"), diagnostics);
+                }
+            }
+            for (axis, size) in [("width", width), ("height", height)] {
+                if let LayoutSizeSpec::Fixed { value } = size {
+                    validate_expression(value, &format!("{path}.{axis}.value"), diagnostics);
+                }
+            }
+            validate_nodes(children, &format!("{path}.children"), diagnostics);
+        }
+        VisualNode::Skeleton {
+            transform,
+            chains,
+            bindings,
+            ik,
+            children,
+            ..
+        } => {
+            validate_transform(transform, &format!("{path}.transform"), diagnostics);
+            for (chain_index, chain) in chains.iter().enumerate() {
+                let chain_path = format!("{path}.chains[{chain_index}]");
+                for (axis, value) in [("x", &chain.x), ("y", &chain.y)] {
+                    if let Some(value) = value {
+                        validate_expression(value, &format!("{chain_path}.{axis}"), diagnostics);
+                    }
+                }
+                for (bone_index, bone) in chain.bones.iter().enumerate() {
+                    let bone_path = format!("{chain_path}.bones[{bone_index}]");
+                    validate_expression(&bone.length, &format!("{bone_path}.length"), diagnostics);
+                    if let Some(angle) = &bone.angle {
+                        validate_expression(angle, &format!("{bone_path}.angle"), diagnostics);
+                    }
+                }
+            }
+            for (index, binding) in bindings.iter().enumerate() {
+                if let BoneBindingSpec::Skinned {
+                    radius: Some(radius),
+                    ..
+                } = binding
+                {
+                    validate_expression(
+                        radius,
+                        &format!("{path}.bindings[{index}].radius"),
+                        diagnostics,
+                    );
+                }
+            }
+            for (index, ik) in ik.iter().enumerate() {
+                if let Some(strength) = &ik.strength {
+                    validate_expression(
+                        strength,
+                        &format!("{path}.ik[{index}].strength"),
+                        diagnostics,
+                    );
+                }
+            }
+            validate_nodes(children, &format!("{path}.children"), diagnostics);
+        }
         VisualNode::Instance {
             overrides,
             transform,
             ..
         } => {
-            validate_quantity_map(overrides, &format!("{path}.overrides"), diagnostics);
+            validate_parameter_map(overrides, &format!("{path}.overrides"), diagnostics);
+            validate_transform(transform, &format!("{path}.transform"), diagnostics);
+        }
+        VisualNode::NestedArtboard { transform, .. } => {
             validate_transform(transform, &format!("{path}.transform"), diagnostics);
         }
         VisualNode::RawSceneObject { .. } => {}
@@ -215,8 +353,10 @@ fn validate_node(node: &VisualNode, path: &str, diagnostics: &mut Vec<AuthoringD
         | VisualNode::Triangle { .. }
         | VisualNode::Polygon { .. }
         | VisualNode::Star { .. }
+        | VisualNode::Path { .. }
         | VisualNode::Text { .. }
         | VisualNode::Image { .. }
+        | VisualNode::NineSlice { .. }
         | VisualNode::Grid { .. }
         | VisualNode::Radial { .. }
         | VisualNode::Mirror { .. }
@@ -227,6 +367,58 @@ fn validate_node(node: &VisualNode, path: &str, diagnostics: &mut Vec<AuthoringD
     }
 }
 
+fn validate_stroke(stroke: &StrokeSpec, path: &str, diagnostics: &mut Vec<AuthoringDiagnostic>) {
+    validate_paint(&stroke.paint, &format!("{path}.paint"), diagnostics);
+    validate_expression(&stroke.width, &format!("{path}.width"), diagnostics);
+    if let Some(trim) = &stroke.trim {
+        validate_expression(&trim.start, &format!("{path}.trim.start"), diagnostics);
+        validate_expression(&trim.end, &format!("{path}.trim.end"), diagnostics);
+        if let Some(offset) = &trim.offset {
+            validate_expression(offset, &format!("{path}.trim.offset"), diagnostics);
+        }
+    }
+}
+
+fn segment_expressions(segment: &PathSegmentSpec) -> Vec<(&'static str, &ScalarExpr)> {
+    match segment {
+        PathSegmentSpec::Move { x, y } | PathSegmentSpec::Line { x, y } => {
+            vec![("x", x), ("y", y)]
+        }
+        PathSegmentSpec::Cubic {
+            x1,
+            y1,
+            x2,
+            y2,
+            x,
+            y,
+        } => vec![
+            ("x1", x1),
+            ("y1", y1),
+            ("x2", x2),
+            ("y2", y2),
+            ("x", x),
+            ("y", y),
+        ],
+        PathSegmentSpec::Quad { x1, y1, x, y } => vec![("x1", x1), ("y1", y1), ("x", x), ("y", y)],
+        PathSegmentSpec::Arc {
+            rx,
+            ry,
+            rotation,
+            x,
+            y,
+            ..
+        } => {
+            let mut expressions = vec![("rx", rx), ("ry", ry)];
+            if let Some(rotation) = rotation {
+                expressions.push(("rotation", rotation));
+            }
+            expressions.extend([("x", x), ("y", y)]);
+            expressions
+        }
+        PathSegmentSpec::Close => Vec::new(),
+    }
+}
+
 fn validate_paint(paint: &PaintSpec, path: &str, diagnostics: &mut Vec<AuthoringDiagnostic>) {
     let PaintSpec::Gradient(gradient) = paint else {
         return;
@@ -287,7 +479,12 @@ fn validate_expression(
             }
         }
         ScalarExpr::Parameter { .. } => {}
-        ScalarExpr::Add { left, right } | ScalarExpr::Subtract { left, right } => {
+        ScalarExpr::Add { left, right }
+        | ScalarExpr::Subtract { left, right }
+        | ScalarExpr::Min { left, right }
+        | ScalarExpr::Max { left, right }
+        | ScalarExpr::Product { left, right }
+        | ScalarExpr::Quotient { left, right } => {
             validate_expression(left, &format!("{path}.left"), diagnostics);
             validate_expression(right, &format!("{path}.right"), diagnostics);
         }
@@ -303,5 +500,21 @@ fn validate_expression(
                 diagnostics.push(diagnostic);
             }
         }
+        ScalarExpr::Clamp { value, min, max } => {
+            validate_expression(value, &format!("{path}.value"), diagnostics);
+            validate_expression(min, &format!("{path}.min"), diagnostics);
+            validate_expression(max, &format!("{path}.max"), diagnostics);
+        }
+        ScalarExpr::Sin { angle } | ScalarExpr::Cos { angle } => {
+            validate_expression(angle, &format!("{path}.angle"), diagnostics);
+        }
+        ScalarExpr::Round { value } => {
+            validate_expression(value, &format!("{path}.value"), diagnostics);
+        }
+        ScalarExpr::Lerp { from, to, t } => {
+            validate_expression(from, &format!("{path}.from"), diagnostics);
+            validate_expression(to, &format!("{path}.to"), diagnostics);
+            validate_expression(t, &format!("{path}.t"), diagnostics);
+        }
     }
 }
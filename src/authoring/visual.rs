@@ -5,8 +5,8 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
 use super::spec::{
-    ConstraintSpec, PaintSpec, Quantity, ScalarExpr, StrokeSpec, TextAlign, TextOverflow,
-    TransformSpec,
+    ComponentParameter, ConstraintSpec, PaintSpec, ParameterizedString, ScalarExpr, StrokeSpec,
+    TextAlign, TextOverflow, TransformSpec,
 };
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
@@ -23,6 +23,272 @@ pub struct PathPointSpec {
     pub y: ScalarExpr,
 }
 
+/// Clips a group's content to an earlier sibling shape or path.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ClipSpec {
+    pub shape: String,
+    /// Keeps only what lies outside the shape.
+    #[serde(default)]
+    pub inverse: bool,
+    /// Draws the clip shape as well; by default its paints are hidden.
+    #[serde(default)]
+    pub visible: bool,
+}
+
+/// Whether a group draws before or after its draw target's drawable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DrawPlacement {
+    Before,
+    After,
+}
+
+/// Draws a group's content next to an earlier sibling drawable instead of in
+/// sibling order.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ZOrderSpec {
+    pub drawable: String,
+    pub placement: DrawPlacement,
+}
+
+/// Named draw-order layers for a group; one is active at a time.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct DrawRulesSpec {
+    #[schemars(length(min = 1, max = 100))]
+    pub layers: Vec<DrawLayerSpec>,
+    /// The active layer; defaults to the first.
+    #[serde(default)]
+    pub active: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct DrawLayerSpec {
+    pub id: String,
+    pub drawable: String,
+    pub placement: DrawPlacement,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum LayoutDirection {
+    #[default]
+    Row,
+    Column,
+}
+
+/// Cross-axis alignment of a layout's children.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum LayoutAlign {
+    #[default]
+    Start,
+    Center,
+    End,
+    Stretch,
+}
+
+/// Main-axis distribution of a layout's children.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum LayoutJustify {
+    #[default]
+    Start,
+    Center,
+    End,
+    SpaceBetween,
+    SpaceAround,
+    SpaceEvenly,
+}
+
+/// How a layout sizes itself along one axis: a fixed pixel length, hugging
+/// its content, or filling the space its parent layout leaves.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "kind", rename_all = "snake_case", deny_unknown_fields)]
+pub enum LayoutSizeSpec {
+    Fixed {
+        value: ScalarExpr,
+    },
+    #[default]
+    Hug,
+    Fill,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct LayoutPaddingSpec {
+    #[serde(default)]
+    pub top: Option<ScalarExpr>,
+    #[serde(default)]
+    pub right: Option<ScalarExpr>,
+    #[serde(default)]
+    pub bottom: Option<ScalarExpr>,
+    #[serde(default)]
+    pub left: Option<ScalarExpr>,
+}
+
+/// Pixel insets from each edge of a nine-slice image, measured against its
+/// intrinsic size.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct NineSliceInsetsSpec {
+    pub left: ScalarExpr,
+    pub top: ScalarExpr,
+    pub right: ScalarExpr,
+    pub bottom: ScalarExpr,
+}
+
+/// How a nine-slice fills the patches that grow with the frame.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum NineSliceTileMode {
+    #[default]
+    Stretch,
+    Repeat,
+    Hidden,
+}
+
+impl NineSliceTileMode {
+    pub(crate) fn scene_name(self) -> &'static str {
+        match self {
+            Self::Stretch => "stretch",
+            Self::Repeat => "repeat",
+            Self::Hidden => "hidden",
+        }
+    }
+}
+
+/// One bone of a chain. Its rest `angle` is relative to the bone before it,
+/// or to the skeleton for the first bone of a chain.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct BoneSpec {
+    pub id: String,
+    pub length: ScalarExpr,
+    #[serde(default)]
+    pub angle: Option<ScalarExpr>,
+}
+
+/// Bones joined base to tip. A chain starts at `x`/`y` in the skeleton, or
+/// at the tip of `parent`, a bone of an earlier chain.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct BoneChainSpec {
+    #[serde(default)]
+    pub x: Option<ScalarExpr>,
+    #[serde(default)]
+    pub y: Option<ScalarExpr>,
+    #[serde(default)]
+    pub parent: Option<String>,
+    #[schemars(length(min = 1, max = 100))]
+    pub bones: Vec<BoneSpec>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SkinFalloff {
+    #[default]
+    Distance,
+    Heat,
+}
+
+/// Attaches one of a skeleton's children to its bones.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "kind", rename_all = "snake_case", deny_unknown_fields)]
+pub enum BoneBindingSpec {
+    /// Moves the node under the bone. Its transform becomes relative to the
+    /// bone: the origin is the bone's base and +x points along it.
+    Rigid { node: String, bone: String },
+    /// Deforms a path node's vertices by the listed bones, weighted by
+    /// their distance to each vertex.
+    Skinned {
+        node: String,
+        #[schemars(length(min = 1, max = 255))]
+        bones: Vec<String>,
+        #[serde(default)]
+        falloff: SkinFalloff,
+        /// Heat falloff radius; defaults to the mean bone length.
+        #[serde(default)]
+        radius: Option<ScalarExpr>,
+        #[serde(default)]
+        #[schemars(range(min = 1, max = 4))]
+        max_influences: Option<u8>,
+    },
+}
+
+/// Turns the bones ending at `bone` so its tip reaches `target`, the
+/// authored id of a node declared before the skeleton.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct IkSpec {
+    pub id: String,
+    pub bone: String,
+    pub target: String,
+    /// Bones the constraint turns, `bone` included; defaults to every bone
+    /// back to the root of its chain.
+    #[serde(default)]
+    #[schemars(range(min = 1))]
+    pub bone_count: Option<u64>,
+    #[serde(default)]
+    pub strength: Option<ScalarExpr>,
+    /// Bends the chain the other way.
+    #[serde(default)]
+    pub invert: bool,
+}
+
+/// Free-form path geometry: an SVG path `d` string, or typed segments with
+/// absolute pixel coordinates.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub enum PathDataSpec {
+    Svg(String),
+    Segments(Vec<PathSegmentSpec>),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "kind", rename_all = "snake_case", deny_unknown_fields)]
+pub enum PathSegmentSpec {
+    Move {
+        x: ScalarExpr,
+        y: ScalarExpr,
+    },
+    Line {
+        x: ScalarExpr,
+        y: ScalarExpr,
+    },
+    Cubic {
+        x1: ScalarExpr,
+        y1: ScalarExpr,
+        x2: ScalarExpr,
+        y2: ScalarExpr,
+        x: ScalarExpr,
+        y: ScalarExpr,
+    },
+    Quad {
+        x1: ScalarExpr,
+        y1: ScalarExpr,
+        x: ScalarExpr,
+        y: ScalarExpr,
+    },
+    /// An elliptical arc with SVG semantics, approximated by cubic segments.
+    Arc {
+        rx: ScalarExpr,
+        ry: ScalarExpr,
+        #[serde(default)]
+        rotation: Option<ScalarExpr>,
+        #[serde(default)]
+        large_arc: bool,
+        #[serde(default)]
+        sweep: bool,
+        x: ScalarExpr,
+        y: ScalarExpr,
+    },
+    Close,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(tag = "kind", rename_all = "snake_case", deny_unknown_fields)]
 pub enum VisualNode {
@@ -87,11 +353,20 @@ pub enum VisualNode {
         #[serde(default)]
         transform: TransformSpec,
     },
+    Path {
+        id: String,
+        path: PathDataSpec,
+        fill: PaintSpec,
+        #[serde(default)]
+        stroke: Option<StrokeSpec>,
+        #[serde(default)]
+        transform: TransformSpec,
+    },
     Text {
         id: String,
-        text: String,
+        text: ParameterizedString,
         #[serde(default)]
-        font: Option<String>,
+        font: Option<ParameterizedString>,
         font_size: ScalarExpr,
         fill: PaintSpec,
         #[serde(default)]
@@ -117,7 +392,20 @@ pub enum VisualNode {
     },
     Image {
         id: String,
-        asset: String,
+        asset: ParameterizedString,
+        #[serde(default)]
+        transform: TransformSpec,
+    },
+    /// An image whose corners keep their size while `transform.scale_x` and
+    /// `transform.scale_y`, relative to the intrinsic size, resize the frame.
+    NineSlice {
+        id: String,
+        asset: ParameterizedString,
+        insets: NineSliceInsetsSpec,
+        #[serde(default)]
+        edges: NineSliceTileMode,
+        #[serde(default)]
+        center: NineSliceTileMode,
         #[serde(default)]
         transform: TransformSpec,
     },
@@ -185,13 +473,71 @@ pub enum VisualNode {
         #[schemars(length(max = 100))]
         constraints: Vec<ConstraintSpec>,
         #[serde(default)]
+        clip: Option<ClipSpec>,
+        #[serde(default)]
+        z_order: Option<ZOrderSpec>,
+        #[serde(default)]
+        draw_rules: Option<DrawRulesSpec>,
+        #[serde(default)]
+        children: Vec<VisualNode>,
+    },
+    /// A flex container. Child layouts are arranged along `direction`;
+    /// other children keep their own transforms inside it.
+    Layout {
+        id: String,
+        #[serde(default)]
+        direction: LayoutDirection,
+        #[serde(default)]
+        gap: Option<ScalarExpr>,
+        #[serde(default)]
+        padding: LayoutPaddingSpec,
+        #[serde(default)]
+        align: LayoutAlign,
+        #[serde(default)]
+        justify: LayoutJustify,
+        #[serde(default)]
+        wrap: bool,
+        #[serde(default)]
+        width: LayoutSizeSpec,
+        #[serde(default)]
+        height: LayoutSizeSpec,
+        #[serde(default)]
+        clip: bool,
+        #[serde(default)]
+        children: Vec<VisualNode>,
+    },
+    /// Bone chains, with the children bound to them and the IK constraints
+    /// that pose them. Bones are posed by their authored id.
+    Skeleton {
+        id: String,
+        #[serde(default)]
+        transform: TransformSpec,
+        #[schemars(length(min = 1, max = 100))]
+        chains: Vec<BoneChainSpec>,
+        #[serde(default)]
+        #[schemars(length(max = 100))]
+        bindings: Vec<BoneBindingSpec>,
+        #[serde(default)]
+        #[schemars(length(max = 100))]
+        ik: Vec<IkSpec>,
+        #[serde(default)]
         children: Vec<VisualNode>,
     },
     Instance {
         id: String,
         component: String,
         #[serde(default)]
-        overrides: BTreeMap<String, Quantity>,
+        overrides: BTreeMap<String, ComponentParameter>,
+        #[serde(default)]
+        transform: TransformSpec,
+    },
+    NestedArtboard {
+        id: String,
+        artboard: String,
+        #[serde(default)]
+        animation: Option<String>,
+        #[serde(default)]
+        state_machine: Option<String>,
         #[serde(default)]
         transform: TransformSpec,
     },
@@ -214,10 +560,18 @@ pub(crate) struct ShapeNodeRef<'a> {
     pub transform: &'a TransformSpec,
 }
 
+#[derive(Clone, Copy)]
+pub(crate) struct PathNodeRef<'a> {
+    pub path: &'a PathDataSpec,
+    pub fill: &'a PaintSpec,
+    pub stroke: Option<&'a StrokeSpec>,
+    pub transform: &'a TransformSpec,
+}
+
 #[derive(Clone, Copy)]
 pub(crate) struct TextNodeRef<'a> {
-    pub content: &'a str,
-    pub font: Option<&'a str>,
+    pub content: &'a ParameterizedString,
+    pub font: Option<&'a ParameterizedString>,
     pub font_size: &'a ScalarExpr,
     pub fill: &'a PaintSpec,
     pub width: Option<&'a ScalarExpr>,
@@ -234,10 +588,50 @@ pub(crate) struct TextNodeRef<'a> {
 
 #[derive(Clone, Copy)]
 pub(crate) struct ImageNodeRef<'a> {
-    pub asset: &'a str,
+    pub asset: &'a ParameterizedString,
+    pub transform: &'a TransformSpec,
+}
+
+#[derive(Clone, Copy)]
+pub(crate) struct NineSliceNodeRef<'a> {
+    pub asset: &'a ParameterizedString,
+    pub insets: &'a NineSliceInsetsSpec,
+    pub edges: NineSliceTileMode,
+    pub center: NineSliceTileMode,
     pub transform: &'a TransformSpec,
 }
 
+#[derive(Clone, Copy)]
+pub(crate) struct NestedArtboardNodeRef<'a> {
+    pub artboard: &'a str,
+    pub animation: Option<&'a str>,
+    pub state_machine: Option<&'a str>,
+    pub transform: &'a TransformSpec,
+}
+
+#[derive(Clone, Copy)]
+pub(crate) struct LayoutNodeRef<'a> {
+    pub direction: LayoutDirection,
+    pub gap: Option<&'a ScalarExpr>,
+    pub padding: &'a LayoutPaddingSpec,
+    pub align: LayoutAlign,
+    pub justify: LayoutJustify,
+    pub wrap: bool,
+    pub width: &'a LayoutSizeSpec,
+    pub height: &'a LayoutSizeSpec,
+    pub clip: bool,
+    pub children: &'a [VisualNode],
+}
+
+#[derive(Clone, Copy)]
+pub(crate) struct SkeletonNodeRef<'a> {
+    pub transform: &'a TransformSpec,
+    pub chains: &'a [BoneChainSpec],
+    pub bindings: &'a [BoneBindingSpec],
+    pub ik: &'a [IkSpec],
+    pub children: &'a [VisualNode],
+}
+
 #[derive(Clone, Copy)]
 pub(crate) struct GridNodeRef<'a> {
     pub columns: u64,
@@ -315,15 +709,20 @@ impl VisualNode {
             | Self::Triangle { id, .. }
             | Self::Polygon { id, .. }
             | Self::Star { id, .. }
+            | Self::Path { id, .. }
             | Self::Text { id, .. }
             | Self::Image { id, .. }
+            | Self::NineSlice { id, .. }
             | Self::Grid { id, .. }
             | Self::Radial { id, .. }
             | Self::Mirror { id, .. }
             | Self::Distribute { id, .. }
             | Self::AlongPath { id, .. }
             | Self::Group { id, .. }
+            | Self::Layout { id, .. }
+            | Self::Skeleton { id, .. }
             | Self::Instance { id, .. }
+            | Self::NestedArtboard { id, .. }
             | Self::RawSceneObject { id, .. } => id,
         }
     }
@@ -426,15 +825,20 @@ impl VisualNode {
                 stroke: stroke.as_ref(),
                 transform,
             },
-            Self::Text { .. }
+            Self::Path { .. }
+            | Self::Text { .. }
             | Self::Image { .. }
+            | Self::NineSlice { .. }
             | Self::Grid { .. }
             | Self::Radial { .. }
             | Self::Mirror { .. }
             | Self::Distribute { .. }
             | Self::AlongPath { .. }
             | Self::Group { .. }
+            | Self::Layout { .. }
+            | Self::Skeleton { .. }
             | Self::Instance { .. }
+            | Self::NestedArtboard { .. }
             | Self::RawSceneObject { .. } => {
                 return None;
             }
@@ -442,6 +846,24 @@ impl VisualNode {
         Some(shape)
     }
 
+    pub(crate) fn path_node(&self) -> Option<PathNodeRef<'_>> {
+        match self {
+            Self::Path {
+                path,
+                fill,
+                stroke,
+                transform,
+                ..
+            } => Some(PathNodeRef {
+                path,
+                fill,
+                stroke: stroke.as_ref(),
+                transform,
+            }),
+            _ => None,
+        }
+    }
+
     pub(crate) fn text_node(&self) -> Option<TextNodeRef<'_>> {
         match self {
             Self::Text {
@@ -462,7 +884,7 @@ impl VisualNode {
                 ..
             } => Some(TextNodeRef {
                 content: text,
-                font: font.as_deref(),
+                font: font.as_ref(),
                 font_size,
                 fill,
                 width: width.as_ref(),
@@ -489,6 +911,94 @@ impl VisualNode {
         }
     }
 
+    pub(crate) fn nine_slice_node(&self) -> Option<NineSliceNodeRef<'_>> {
+        match self {
+            Self::NineSlice {
+                asset,
+                insets,
+                edges,
+                center,
+                transform,
+                ..
+            } => Some(NineSliceNodeRef {
+                asset,
+                insets,
+                edges: *edges,
+                center: *center,
+                transform,
+            }),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn nested_artboard_node(&self) -> Option<NestedArtboardNodeRef<'_>> {
+        match self {
+            Self::NestedArtboard {
+                artboard,
+                animation,
+                state_machine,
+                transform,
+                ..
+            } => Some(NestedArtboardNodeRef {
+                artboard,
+                animation: animation.as_deref(),
+                state_machine: state_machine.as_deref(),
+                transform,
+            }),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn layout_node(&self) -> Option<LayoutNodeRef<'_>> {
+        match self {
+            Self::Layout {
+                direction,
+                gap,
+                padding,
+                align,
+                justify,
+                wrap,
+                width,
+                height,
+                clip,
+                children,
+                ..
+            } => Some(LayoutNodeRef {
+                direction: *direction,
+                gap: gap.as_ref(),
+                padding,
+                align: *align,
+                justify: *justify,
+                wrap: *wrap,
+                width,
+                height,
+                clip: *clip,
+                children,
+            }),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn skeleton_node(&self) -> Option<SkeletonNodeRef<'_>> {
+        match self {
+            Self::Skeleton {
+                transform,
+                chains,
+                bindings,
+                ik,
+                children,
+                ..
+            } => Some(SkeletonNodeRef {
+                transform,
+                chains,
+                bindings,
+                ik,
+                children,
+            }),
+            _ => None,
+        }
+    }
+
     pub(crate) fn pattern(&self) -> Option<PatternNodeRef<'_>> {
         match self {
             Self::Grid {
@@ -573,7 +1083,9 @@ impl VisualNode {
 
     pub(crate) fn children(&self) -> Option<&[VisualNode]> {
         match self {
-            Self::Group { children, .. } => Some(children),
+            Self::Group { children, .. }
+            | Self::Layout { children, .. }
+            | Self::Skeleton { children, .. } => Some(children),
             _ => None,
         }
     }
@@ -0,0 +1,453 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::luau;
+use super::objects::FileAssetKind;
+use super::paths::{AssetPolicy, RootViolation, asset_root, canonical_root, within_roots};
+use super::spec::ObjectSpec;
+
+const ASSET_LIBRARY_VERSION: u32 = 1;
+const EXTERNAL_ASSETS_VERSION: u32 = 1;
+/// Written beside the external asset files by [`write_external_assets`].
+pub const EXTERNAL_ASSET_MANIFEST: &str = "manifest.json";
+
+/// A project-level manifest of named assets that several scenes share. A
+/// scene opts in with `asset_library`, and its file assets name an entry with
+/// `library` instead of setting `source`.
+#[derive(Debug, Deserialize)]
+pub(crate) struct AssetLibrary {
+    asset_library_version: u32,
+    assets: BTreeMap<String, LibraryAsset>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LibraryAsset {
+    kind: LibraryAssetKind,
+    /// Relative to the directory holding the library manifest.
+    source: String,
+    /// Lowercase hex SHA-256 of the source bytes, so a changed file fails
+    /// every scene that embeds it instead of silently shipping.
+    sha256: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum LibraryAssetKind {
+    Image,
+    Font,
+    Audio,
+    Script,
+}
+
+impl LibraryAssetKind {
+    fn file_asset_kind(self) -> FileAssetKind {
+        match self {
+            LibraryAssetKind::Image => FileAssetKind::Image,
+            LibraryAssetKind::Font => FileAssetKind::Font,
+            LibraryAssetKind::Audio => FileAssetKind::Audio,
+            LibraryAssetKind::Script => FileAssetKind::Script,
+        }
+    }
+}
+
+pub(crate) struct LoadedLibrary {
+    path: String,
+    dir: PathBuf,
+    library: AssetLibrary,
+}
+
+pub(crate) fn sha256_hex(bytes: &[u8]) -> String {
+    Sha256::digest(bytes)
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+/// Reads the scene's `asset_library` manifest. It is resolved like an asset
+/// `source`, so it must sit inside the scene's project.
+pub(crate) fn load_library(
+    path: &str,
+    base_dir: Option<&Path>,
+    policy: &AssetPolicy,
+) -> Result<LoadedLibrary, String> {
+    let Some(base_dir) = base_dir else {
+        return Err(
+            "scene sets 'asset_library', but asset libraries are only supported when generating from a scene file on disk"
+                .to_string(),
+        );
+    };
+    let subject = format!("asset library '{path}'");
+    let (bytes, resolved) = read_project_file(
+        &subject,
+        path,
+        base_dir,
+        "the scene file's directory",
+        base_dir,
+        policy,
+    )?;
+    let library: AssetLibrary = serde_json::from_slice(&bytes)
+        .map_err(|error| format!("{subject} is not a valid asset library: {error}"))?;
+    if library.asset_library_version != ASSET_LIBRARY_VERSION {
+        return Err(format!(
+            "{subject} has asset_library_version {}; this version of rive-cli reads version {}",
+            library.asset_library_version, ASSET_LIBRARY_VERSION
+        ));
+    }
+    let dir = resolved
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| base_dir.to_path_buf());
+    Ok(LoadedLibrary {
+        path: path.to_string(),
+        dir,
+        library,
+    })
+}
+
+/// The bytes a file asset embeds, read from its `source` or from its
+/// `library` entry. `None` when the asset is loaded out of band.
+pub(crate) fn asset_contents(
+    spec: &ObjectSpec,
+    base_dir: Option<&Path>,
+    library: Option<&LoadedLibrary>,
+    policy: &AssetPolicy,
+) -> Result<Option<Vec<u8>>, String> {
+    let (name, kind, source, library_name) = match spec {
+        ObjectSpec::ImageAsset {
+            name,
+            source,
+            library,
+            ..
+        } => (name, FileAssetKind::Image, source, library),
+        ObjectSpec::FontAsset {
+            name,
+            source,
+            library,
+            ..
+        } => (name, FileAssetKind::Font, source, library),
+        ObjectSpec::AudioAsset {
+            name,
+            source,
+            library,
+            ..
+        } => (name, FileAssetKind::Audio, source, library),
+        ObjectSpec::ScriptAsset {
+            name,
+            source,
+            library,
+            ..
+        } => (name, FileAssetKind::Script, source, library),
+        _ => return Ok(None),
+    };
+    let (bytes, subject) = match (source, library_name) {
+        (None, None) => return Ok(None),
+        (Some(_), Some(_)) => {
+            return Err(format!(
+                "asset '{name}' sets both 'source' and 'library'; use one"
+            ));
+        }
+        (Some(source), None) => {
+            let Some(base_dir) = base_dir else {
+                return Err(format!(
+                    "asset '{name}' sets 'source', but embedding asset files is only supported when generating from a scene file on disk"
+                ));
+            };
+            let subject = format!("asset '{name}' source '{source}'");
+            let (bytes, _) = read_project_file(
+                &subject,
+                source,
+                base_dir,
+                "the scene file's directory",
+                base_dir,
+                policy,
+            )?;
+            (bytes, subject)
+        }
+        (None, Some(entry_name)) => {
+            let Some(loaded) = library else {
+                return Err(format!(
+                    "asset '{name}' references library asset '{entry_name}', but the scene does not set 'asset_library'"
+                ));
+            };
+            let Some(entry) = loaded.library.assets.get(entry_name) else {
+                return Err(format!(
+                    "asset '{name}' references library asset '{entry_name}', which asset library '{}' does not declare",
+                    loaded.path
+                ));
+            };
+            let entry_kind = entry.kind.file_asset_kind();
+            if entry_kind != kind {
+                return Err(format!(
+                    "asset '{name}' references library asset '{entry_name}', which is a {}; it must be a {}",
+                    entry_kind.label(),
+                    kind.label()
+                ));
+            }
+            let subject = format!("library asset '{entry_name}' source '{}'", entry.source);
+            let (bytes, _) = read_project_file(
+                &subject,
+                &entry.source,
+                &loaded.dir,
+                "the asset library's directory",
+                base_dir.unwrap_or(&loaded.dir),
+                policy,
+            )?;
+            let actual = sha256_hex(&bytes);
+            if !actual.eq_ignore_ascii_case(&entry.sha256) {
+                return Err(format!(
+                    "{subject} has sha256 {actual}, but asset library '{}' declares {}; update the library if the change is intended",
+                    loaded.path, entry.sha256
+                ));
+            }
+            (bytes, subject)
+        }
+    };
+    // The runtime decodes audio lazily, so a bad clip would only fail when an
+    // event first plays it.
+    if kind == FileAssetKind::Audio
+        && let Err(error) = crate::validator::sniff_audio(&bytes)
+    {
+        return Err(format!("{subject} is not decodable audio: {error}"));
+    }
+    if kind == FileAssetKind::Script {
+        let source = std::str::from_utf8(&bytes)
+            .map_err(|error| format!("{subject} is not UTF-8 text: {error}"))?;
+        luau::check_syntax(source).map_err(|error| format!("{subject}: {error}"))?;
+        luau::declared_inputs(source).map_err(|error| format!("{subject}: {error}"))?;
+    }
+    Ok(Some(bytes))
+}
+
+/// Reads `relative` from `dir`, refusing paths that leave the project found
+/// from the scene's `base_dir` or the policy's allowed roots. Returns the
+/// bytes and the resolved path.
+fn read_project_file(
+    subject: &str,
+    relative: &str,
+    dir: &Path,
+    anchor: &str,
+    base_dir: &Path,
+    policy: &AssetPolicy,
+) -> Result<(Vec<u8>, PathBuf), String> {
+    let relative_path = Path::new(relative);
+    if relative_path.is_absolute() {
+        return Err(format!(
+            "{subject} must be relative to {anchor} so the scene stays portable"
+        ));
+    }
+    let path = dir.join(relative_path);
+    let resolved = std::path::absolute(&path).unwrap_or_else(|_| path.clone());
+    let root = asset_root(base_dir);
+    let canonical_path = path.canonicalize().map_err(|error| {
+        format!(
+            "{} could not be read from {}: {}",
+            subject,
+            resolved.display(),
+            error
+        )
+    })?;
+    let canonical_root = canonical_root(&root);
+    if !canonical_path.starts_with(&canonical_root) {
+        return Err(format!(
+            "{} resolves to {}, outside the project rooted at {}",
+            subject,
+            canonical_path.display(),
+            canonical_root.display()
+        ));
+    }
+    if !policy.allowed_roots.is_empty()
+        && let Err(violation) = within_roots(&resolved, &policy.allowed_roots)
+    {
+        let roots = policy
+            .allowed_roots
+            .iter()
+            .map(|root| root.display().to_string())
+            .collect::<Vec<_>>();
+        let reason = match violation {
+            RootViolation::Outside => "is outside",
+            RootViolation::SymlinkEscape => "follows a symlink out of",
+        };
+        return Err(format!(
+            "{} resolves to {}, which {} the allowed roots: {}",
+            subject,
+            canonical_path.display(),
+            reason,
+            roots.join(", ")
+        ));
+    }
+    if let Some(limit) = policy.max_file_bytes {
+        let size = std::fs::metadata(&canonical_path)
+            .map(|metadata| metadata.len())
+            .unwrap_or(0);
+        if size > limit {
+            return Err(format!(
+                "{} is {} bytes, over the {} byte limit",
+                subject, size, limit
+            ));
+        }
+    }
+    let bytes = std::fs::read(&canonical_path).map_err(|error| {
+        format!(
+            "{} could not be read from {}: {}",
+            subject,
+            resolved.display(),
+            error
+        )
+    })?;
+    if bytes.is_empty() {
+        return Err(format!("{} is empty at {}", subject, resolved.display()));
+    }
+    Ok((bytes, canonical_path))
+}
+
+/// One asset that `generate --assets external` left out of the `.riv`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExternalAssetEntry {
+    pub asset_id: u64,
+    pub name: String,
+    pub kind: String,
+    /// The runtime's unique file name for the asset, which asset loaders
+    /// receive as `uniqueFilename`.
+    pub file: String,
+    pub sha256: String,
+    pub bytes: usize,
+}
+
+/// Maps the asset ids of one `.riv` to the files beside this manifest.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExternalAssetManifest {
+    pub external_assets_version: u32,
+    pub riv: String,
+    pub assets: Vec<ExternalAssetEntry>,
+}
+
+pub struct ExternalAsset {
+    pub entry: ExternalAssetEntry,
+    pub contents: Vec<u8>,
+}
+
+/// `FileAsset::uniqueFilename()` from the runtime: the asset name without its
+/// final extension, a dash, the asset id, and the extension the runtime
+/// assigns to the asset type.
+fn unique_filename(name: &str, asset_id: u64, kind: FileAssetKind) -> String {
+    let stem = name.rfind('.').map_or(name, |dot| &name[..dot]);
+    let extension = match kind {
+        FileAssetKind::Image => "png",
+        FileAssetKind::Font => "ttf",
+        FileAssetKind::Audio => "wav",
+        FileAssetKind::Script => "lua",
+    };
+    format!("{stem}-{asset_id}.{extension}")
+}
+
+pub(crate) fn external_asset(
+    spec: &ObjectSpec,
+    kind: FileAssetKind,
+    contents: Vec<u8>,
+) -> Result<ExternalAsset, String> {
+    let (name, asset_id) = match spec {
+        ObjectSpec::ImageAsset { name, asset_id, .. }
+        | ObjectSpec::FontAsset { name, asset_id, .. }
+        | ObjectSpec::AudioAsset { name, asset_id, .. }
+        | ObjectSpec::ScriptAsset { name, asset_id, .. } => (name, asset_id.unwrap_or(0)),
+        _ => return Err("only file assets can be external".to_string()),
+    };
+    if name.is_empty() || name.contains(['/', '\\', '\0']) || name.starts_with('.') {
+        return Err(format!(
+            "asset '{name}' cannot be written as an external file; external asset names must not be empty, start with '.', or contain path separators"
+        ));
+    }
+    Ok(ExternalAsset {
+        entry: ExternalAssetEntry {
+            asset_id,
+            name: name.clone(),
+            kind: match kind {
+                FileAssetKind::Image => "image",
+                FileAssetKind::Font => "font",
+                FileAssetKind::Audio => "audio",
+                FileAssetKind::Script => "script",
+            }
+            .to_string(),
+            file: unique_filename(name, asset_id, kind),
+            sha256: sha256_hex(&contents),
+            bytes: contents.len(),
+        },
+        contents,
+    })
+}
+
+/// Writes each external asset into `dir` under its unique file name, then a
+/// [`EXTERNAL_ASSET_MANIFEST`] naming `riv`. Returns the manifest path.
+pub fn write_external_assets(
+    dir: &Path,
+    riv: &str,
+    assets: &[ExternalAsset],
+) -> std::io::Result<PathBuf> {
+    std::fs::create_dir_all(dir)?;
+    for asset in assets {
+        std::fs::write(dir.join(&asset.entry.file), &asset.contents)?;
+    }
+    let manifest = ExternalAssetManifest {
+        external_assets_version: EXTERNAL_ASSETS_VERSION,
+        riv: riv.to_string(),
+        assets: assets.iter().map(|asset| asset.entry.clone()).collect(),
+    };
+    let path = dir.join(EXTERNAL_ASSET_MANIFEST);
+    let mut text = serde_json::to_string_pretty(&manifest)?;
+    text.push('\n');
+    std::fs::write(&path, text)?;
+    Ok(path)
+}
+
+/// Reads the manifest [`write_external_assets`] left in `dir` and each file it
+/// lists, refusing files that are missing or no longer match their recorded
+/// sha256.
+pub fn read_external_assets(dir: &Path) -> Result<Vec<ExternalAsset>, String> {
+    let path = dir.join(EXTERNAL_ASSET_MANIFEST);
+    let text = std::fs::read_to_string(&path).map_err(|e| {
+        format!(
+            "error reading external asset manifest {}: {e}",
+            path.display()
+        )
+    })?;
+    let manifest: ExternalAssetManifest = serde_json::from_str(&text)
+        .map_err(|e| format!("invalid external asset manifest {}: {e}", path.display()))?;
+    if manifest.external_assets_version != EXTERNAL_ASSETS_VERSION {
+        return Err(format!(
+            "external asset manifest {} has external_assets_version {}; this build reads version {}",
+            path.display(),
+            manifest.external_assets_version,
+            EXTERNAL_ASSETS_VERSION
+        ));
+    }
+    manifest
+        .assets
+        .into_iter()
+        .map(|entry| {
+            if entry.file.contains(['/', '\\']) || entry.file.starts_with('.') {
+                return Err(format!(
+                    "external asset manifest {} lists '{}', which is not a plain file name",
+                    path.display(),
+                    entry.file
+                ));
+            }
+            let file = dir.join(&entry.file);
+            let contents = std::fs::read(&file).map_err(|e| {
+                format!("error reading external asset '{}' at {}: {e}", entry.name, file.display())
+            })?;
+            let sha256 = sha256_hex(&contents);
+            if sha256 != entry.sha256 {
+                return Err(format!(
+                    "external asset '{}' at {} has sha256 {sha256}, but the manifest records {}; regenerate the scene with --assets external",
+                    entry.name,
+                    file.display(),
+                    entry.sha256
+                ));
+            }
+            Ok(ExternalAsset { entry, contents })
+        })
+        .collect()
+}
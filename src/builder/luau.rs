@@ -0,0 +1,482 @@
+//! A structural check of Luau script sources and the input declarations in
+//! their header comment. It is not a parser: it tokenizes strings, comments
+//! and brackets, and matches block keywords, which catches the mistakes that
+//! otherwise only surface when the runtime loads the script.
+
+/// The kinds a script can declare with `-- @input NAME KIND`, one for each
+/// `script_input_*` object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ScriptInputKind {
+    Number,
+    Boolean,
+    String,
+    Color,
+    Trigger,
+    Artboard,
+    ViewModelProperty,
+}
+
+const INPUT_KINDS: [(&str, ScriptInputKind); 7] = [
+    ("number", ScriptInputKind::Number),
+    ("boolean", ScriptInputKind::Boolean),
+    ("string", ScriptInputKind::String),
+    ("color", ScriptInputKind::Color),
+    ("trigger", ScriptInputKind::Trigger),
+    ("artboard", ScriptInputKind::Artboard),
+    ("view_model_property", ScriptInputKind::ViewModelProperty),
+];
+
+impl ScriptInputKind {
+    pub(crate) fn label(self) -> &'static str {
+        INPUT_KINDS
+            .iter()
+            .find(|(_, kind)| *kind == self)
+            .map_or("number", |(label, _)| label)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ScriptInput {
+    pub name: String,
+    pub kind: ScriptInputKind,
+}
+
+/// Reads `-- @input NAME KIND` lines from the comment block that opens the
+/// script. Declarations after the first line of code are ignored.
+pub(crate) fn declared_inputs(source: &str) -> Result<Vec<ScriptInput>, String> {
+    let mut inputs: Vec<ScriptInput> = Vec::new();
+    for (number, line) in source.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let Some(comment) = line.strip_prefix("--") else {
+            break;
+        };
+        let Some(declaration) = comment.trim().strip_prefix("@input") else {
+            continue;
+        };
+        let line = number + 1;
+        let fields: Vec<&str> = declaration.split_whitespace().collect();
+        let [name, kind] = fields[..] else {
+            return Err(format!(
+                "line {line}: '@input' takes a name and a kind, e.g. '-- @input speed number'"
+            ));
+        };
+        let Some((_, kind)) = INPUT_KINDS.iter().find(|(label, _)| *label == kind) else {
+            return Err(format!(
+                "line {line}: unknown input kind '{kind}'; expected one of {}",
+                INPUT_KINDS.map(|(label, _)| label).join(", ")
+            ));
+        };
+        if inputs.iter().any(|input| input.name == name) {
+            return Err(format!("line {line}: input '{name}' is declared twice"));
+        }
+        inputs.push(ScriptInput {
+            name: name.to_string(),
+            kind: *kind,
+        });
+    }
+    Ok(inputs)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Block {
+    Function,
+    /// An `if` statement; `true` once its `then` has been seen.
+    If(bool),
+    /// An `if` expression, which closes at its `else` instead of an `end`.
+    IfExpression(bool),
+    /// A `while` or `for`; `true` once its `do` has been seen.
+    Loop(bool),
+    Do,
+    Repeat,
+    Bracket(u8),
+}
+
+impl Block {
+    fn opener(self) -> &'static str {
+        match self {
+            Block::Function => "function",
+            Block::If(_) | Block::IfExpression(_) => "if",
+            Block::Loop(_) => "while/for",
+            Block::Do => "do",
+            Block::Repeat => "repeat",
+            Block::Bracket(b'(') => "(",
+            Block::Bracket(b'[') => "[",
+            Block::Bracket(_) => "{",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Previous {
+    Start,
+    Word,
+    Keyword,
+    Value,
+    Operator,
+    Closer,
+}
+
+/// Checks that strings and comments terminate and that brackets and block
+/// keywords pair up.
+pub(crate) fn check_syntax(source: &str) -> Result<(), String> {
+    let bytes = source.as_bytes();
+    let mut stack: Vec<(Block, usize)> = Vec::new();
+    let mut previous = Previous::Start;
+    let mut previous_word = "";
+    let mut line = 1;
+    let mut at = 0;
+    while at < bytes.len() {
+        let byte = bytes[at];
+        match byte {
+            b'\n' => {
+                line += 1;
+                at += 1;
+            }
+            _ if byte.is_ascii_whitespace() => at += 1,
+            b'-' if bytes.get(at + 1) == Some(&b'-') => {
+                at += 2;
+                if let Some(level) = long_bracket_level(bytes, at) {
+                    at = skip_long_bracket(bytes, at, level, &mut line, "comment")?;
+                } else {
+                    while at < bytes.len() && bytes[at] != b'\n' {
+                        at += 1;
+                    }
+                }
+            }
+            b'"' | b'\'' | b'`' => {
+                at = skip_quoted(bytes, at, &mut line)?;
+                previous = Previous::Value;
+            }
+            b'[' if long_bracket_level(bytes, at).is_some() => {
+                let level = long_bracket_level(bytes, at).unwrap_or(0);
+                at = skip_long_bracket(bytes, at, level, &mut line, "string")?;
+                previous = Previous::Value;
+            }
+            b'(' | b'[' | b'{' => {
+                stack.push((Block::Bracket(byte), line));
+                previous = Previous::Operator;
+                at += 1;
+            }
+            b')' | b']' | b'}' => {
+                let opener = match byte {
+                    b')' => b'(',
+                    b']' => b'[',
+                    _ => b'{',
+                };
+                match stack.pop() {
+                    Some((Block::Bracket(open), _)) if open == opener => {}
+                    Some((block, opened)) => {
+                        return Err(format!(
+                            "line {line}: '{}' closes '{}' from line {opened}",
+                            byte as char,
+                            block.opener()
+                        ));
+                    }
+                    None => {
+                        return Err(format!(
+                            "line {line}: '{}' has nothing to close",
+                            byte as char
+                        ));
+                    }
+                }
+                previous = Previous::Closer;
+                at += 1;
+            }
+            _ if byte.is_ascii_digit()
+                || (byte == b'.' && bytes.get(at + 1).is_some_and(u8::is_ascii_digit)) =>
+            {
+                at += 1;
+                while at < bytes.len()
+                    && (bytes[at].is_ascii_alphanumeric()
+                        || bytes[at] == b'_'
+                        || (bytes[at] == b'.' && bytes.get(at + 1) != Some(&b'.')))
+                {
+                    at += 1;
+                }
+                previous = Previous::Value;
+            }
+            _ if byte.is_ascii_alphabetic() || byte == b'_' => {
+                let start = at;
+                while at < bytes.len() && (bytes[at].is_ascii_alphanumeric() || bytes[at] == b'_') {
+                    at += 1;
+                }
+                let word = &source[start..at];
+                previous = keyword(word, previous, previous_word, &mut stack, line)?;
+                previous_word = word;
+            }
+            b';' => {
+                // A statement separator starts a statement, not an expression.
+                previous = Previous::Start;
+                at += 1;
+            }
+            _ => {
+                previous = Previous::Operator;
+                at += 1;
+            }
+        }
+    }
+    match stack.last() {
+        Some((block, opened)) => Err(format!(
+            "line {opened}: '{}' is never closed",
+            block.opener()
+        )),
+        None => Ok(()),
+    }
+}
+
+fn keyword(
+    word: &str,
+    previous: Previous,
+    previous_word: &str,
+    stack: &mut Vec<(Block, usize)>,
+    line: usize,
+) -> Result<Previous, String> {
+    let top = stack.last().map(|(block, _)| *block);
+    let unexpected = |word: &str| {
+        Err(format!(
+            "line {line}: '{word}' does not continue an open block"
+        ))
+    };
+    match word {
+        "function" => stack.push((Block::Function, line)),
+        "if" => {
+            // `x = if a then b else c` is an expression: it follows an
+            // operator or a keyword that expects a value.
+            let expression = previous == Previous::Operator
+                || (previous == Previous::Keyword
+                    && matches!(
+                        previous_word,
+                        "return" | "and" | "or" | "not" | "in" | "until" | "while" | "elseif"
+                    ));
+            stack.push((
+                if expression {
+                    Block::IfExpression(false)
+                } else {
+                    Block::If(false)
+                },
+                line,
+            ));
+        }
+        "then" => match top {
+            Some(Block::If(false)) => replace_top(stack, Block::If(true)),
+            Some(Block::IfExpression(false)) => {
+                replace_top(stack, Block::IfExpression(true));
+                return Ok(Previous::Operator);
+            }
+            _ => return unexpected(word),
+        },
+        "elseif" => match top {
+            Some(Block::If(true)) => replace_top(stack, Block::If(false)),
+            Some(Block::IfExpression(true)) => replace_top(stack, Block::IfExpression(false)),
+            _ => return unexpected(word),
+        },
+        "else" => match top {
+            Some(Block::If(true)) => {}
+            Some(Block::IfExpression(true)) => {
+                stack.pop();
+                return Ok(Previous::Operator);
+            }
+            _ => return unexpected(word),
+        },
+        "while" | "for" => stack.push((Block::Loop(false), line)),
+        "do" => match top {
+            Some(Block::Loop(false)) => replace_top(stack, Block::Loop(true)),
+            _ => stack.push((Block::Do, line)),
+        },
+        "repeat" => stack.push((Block::Repeat, line)),
+        "until" => match top {
+            Some(Block::Repeat) => {
+                stack.pop();
+            }
+            _ => return unexpected(word),
+        },
+        "end" => match top {
+            Some(Block::Function | Block::If(true) | Block::Loop(true) | Block::Do) => {
+                stack.pop();
+            }
+            Some(block @ (Block::If(false) | Block::Loop(false))) => {
+                return Err(format!(
+                    "line {line}: 'end' closes '{}' before its '{}'",
+                    block.opener(),
+                    if block == Block::If(false) {
+                        "then"
+                    } else {
+                        "do"
+                    }
+                ));
+            }
+            _ => return unexpected(word),
+        },
+        "return" | "and" | "or" | "not" | "in" | "local" | "break" | "continue" => {}
+        "nil" | "true" | "false" => return Ok(Previous::Value),
+        _ => return Ok(Previous::Word),
+    }
+    Ok(Previous::Keyword)
+}
+
+fn replace_top(stack: &mut [(Block, usize)], block: Block) {
+    if let Some(top) = stack.last_mut() {
+        top.0 = block;
+    }
+}
+
+/// The number of `=` in a `[[`, `[=[`, ... opener at `at`.
+fn long_bracket_level(bytes: &[u8], at: usize) -> Option<usize> {
+    if bytes.get(at) != Some(&b'[') {
+        return None;
+    }
+    let mut level = 0;
+    while bytes.get(at + 1 + level) == Some(&b'=') {
+        level += 1;
+    }
+    (bytes.get(at + 1 + level) == Some(&b'[')).then_some(level)
+}
+
+fn skip_long_bracket(
+    bytes: &[u8],
+    at: usize,
+    level: usize,
+    line: &mut usize,
+    what: &str,
+) -> Result<usize, String> {
+    let opened = *line;
+    let mut closer = vec![b']'];
+    closer.extend(std::iter::repeat_n(b'=', level));
+    closer.push(b']');
+    let mut cursor = at + level + 2;
+    while cursor < bytes.len() {
+        if bytes[cursor..].starts_with(&closer) {
+            return Ok(cursor + closer.len());
+        }
+        if bytes[cursor] == b'\n' {
+            *line += 1;
+        }
+        cursor += 1;
+    }
+    Err(format!("line {opened}: long {what} is never closed"))
+}
+
+fn skip_quoted(bytes: &[u8], at: usize, line: &mut usize) -> Result<usize, String> {
+    let quote = bytes[at];
+    let mut cursor = at + 1;
+    while cursor < bytes.len() {
+        match bytes[cursor] {
+            byte if byte == quote => return Ok(cursor + 1),
+            b'\\' => {
+                if bytes.get(cursor + 1) == Some(&b'\n') {
+                    *line += 1;
+                }
+                cursor += 2;
+            }
+            b'\n' if quote != b'`' => break,
+            b'\n' => {
+                *line += 1;
+                cursor += 1;
+            }
+            _ => cursor += 1,
+        }
+    }
+    Err(format!("line {line}: string is never closed"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WOBBLE: &str = "\
+--!strict
+-- Wobbles the drawable it is attached to.
+-- @input amplitude number
+-- @input tint color
+
+local function wobble(t: number): number
+  return math.sin(t) * if t > 1 then 2 else if t > 0 then 1 else 0
+end
+
+function init(self)
+  for i = 1, 3 do
+    if i == 2 then
+      print(`step {i}`)
+    elseif i == 3 then
+      print([[done]])
+    else
+      repeat i += 1 until i > 4
+    end
+  end
+  return { speed = 1, label = \"a -- b\" } -- trailing
+end
+";
+
+    #[test]
+    fn accepts_balanced_luau_with_if_expressions_and_strings() {
+        check_syntax(WOBBLE).unwrap();
+        check_syntax("--[==[ a ]] still a comment ]==]\nlocal s = [=[ end ]=]").unwrap();
+    }
+
+    #[test]
+    fn semicolons_start_statements_rather_than_expressions() {
+        check_syntax("local a = 1; if a then print(a) end").unwrap();
+        check_syntax("local b = 1; local c = if b then 2 else 3").unwrap();
+    }
+
+    #[test]
+    fn reports_unbalanced_blocks_with_their_lines() {
+        assert_eq!(
+            check_syntax("function f()\n  if x then\n    y()\nend").unwrap_err(),
+            "line 1: 'function' is never closed"
+        );
+        assert_eq!(
+            check_syntax("local x = 1\nend").unwrap_err(),
+            "line 2: 'end' does not continue an open block"
+        );
+        assert_eq!(
+            check_syntax("if x\n  y()\nend").unwrap_err(),
+            "line 3: 'end' closes 'if' before its 'then'"
+        );
+        assert_eq!(
+            check_syntax("print((1)]").unwrap_err(),
+            "line 1: ']' closes '(' from line 1"
+        );
+        assert_eq!(
+            check_syntax("local s = \"open\nprint(s)").unwrap_err(),
+            "line 1: string is never closed"
+        );
+        assert_eq!(
+            check_syntax("--[[ note").unwrap_err(),
+            "line 1: long comment is never closed"
+        );
+    }
+
+    #[test]
+    fn reads_input_declarations_from_the_header_only() {
+        let inputs = declared_inputs(&format!("{WOBBLE}-- @input late number\n")).unwrap();
+        assert_eq!(
+            inputs,
+            vec![
+                ScriptInput {
+                    name: "amplitude".to_string(),
+                    kind: ScriptInputKind::Number,
+                },
+                ScriptInput {
+                    name: "tint".to_string(),
+                    kind: ScriptInputKind::Color,
+                },
+            ]
+        );
+        assert_eq!(
+            declared_inputs("-- @input speed float").unwrap_err(),
+            "line 1: unknown input kind 'float'; expected one of number, boolean, string, color, trigger, artboard, view_model_property"
+        );
+        assert_eq!(
+            declared_inputs("-- @input speed").unwrap_err(),
+            "line 1: '@input' takes a name and a kind, e.g. '-- @input speed number'"
+        );
+        assert_eq!(
+            declared_inputs("-- @input a number\n-- @input a trigger").unwrap_err(),
+            "line 2: input 'a' is declared twice"
+        );
+    }
+}
@@ -0,0 +1,549 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// The runtime reads triangle indices as `uint16_t`, and triangulation is
+/// quadratic in the vertex count.
+pub(crate) const MAX_MESH_VERTICES: usize = 4096;
+
+const MAX_EDGE_FLIPS: usize = 1_000_000;
+
+/// One `mesh_vertex` or `contour_mesh_vertex` child, in child order.
+pub(super) struct MeshPoint<'s> {
+    pub(super) name: &'s str,
+    pub(super) x: f64,
+    pub(super) y: f64,
+    pub(super) contour: bool,
+}
+
+/// The vertex a `mesh_grid` generates at `row` and `column`.
+pub(crate) fn grid_vertex_name(mesh: &str, row: u32, column: u32) -> String {
+    format!("{}_{}_{}", mesh, row, column)
+}
+
+/// Two triangles per cell of a `columns` x `rows` grid whose vertices are
+/// laid out row by row.
+pub(super) fn grid_triangle_indices(columns: u32, rows: u32) -> Vec<u16> {
+    let stride = columns + 1;
+    let mut indices = Vec::with_capacity((columns * rows * 6) as usize);
+    for row in 0..rows {
+        for column in 0..columns {
+            let top_left = (row * stride + column) as u16;
+            let top_right = top_left + 1;
+            let bottom_left = top_left + stride as u16;
+            let bottom_right = bottom_left + 1;
+            indices.extend_from_slice(&[top_left, top_right, bottom_right]);
+            indices.extend_from_slice(&[top_left, bottom_right, bottom_left]);
+        }
+    }
+    indices
+}
+
+/// Triangulates a mesh's vertices with a constrained Delaunay triangulation.
+/// Contour vertices, in child order, close the outline the triangles must
+/// fill; without them the convex hull is filled. Forced edges, given as
+/// vertex index pairs, always appear as triangle edges.
+pub(super) fn triangulate(
+    mesh: &str,
+    points: &[MeshPoint<'_>],
+    forced: &[(usize, usize)],
+) -> Result<Vec<u16>, String> {
+    if points.is_empty() {
+        return Ok(Vec::new());
+    }
+    if points.len() < 3 {
+        return Err(format!(
+            "mesh '{}' needs at least three vertices to triangulate",
+            mesh
+        ));
+    }
+    if points.len() > MAX_MESH_VERTICES {
+        return Err(format!(
+            "mesh '{}' has {} vertices; at most {} are supported",
+            mesh,
+            points.len(),
+            MAX_MESH_VERTICES
+        ));
+    }
+    let mut seen = HashMap::new();
+    for point in points {
+        if let Some(other) = seen.insert((point.x.to_bits(), point.y.to_bits()), point.name) {
+            return Err(format!(
+                "mesh '{}' vertices '{}' and '{}' share a position",
+                mesh, other, point.name
+            ));
+        }
+    }
+    let coordinates = points
+        .iter()
+        .map(|point| [point.x, point.y])
+        .collect::<Vec<_>>();
+    if coordinates
+        .iter()
+        .all(|c| orient(coordinates[0], coordinates[1], *c) == 0.0)
+    {
+        return Err(format!("mesh '{}' vertices are collinear", mesh));
+    }
+
+    let contour = points
+        .iter()
+        .enumerate()
+        .filter(|(_, point)| point.contour)
+        .map(|(index, _)| index)
+        .collect::<Vec<_>>();
+    if !contour.is_empty() && contour.len() < 3 {
+        return Err(format!(
+            "mesh '{}' needs at least three contour vertices to close its outline",
+            mesh
+        ));
+    }
+    let outline = if contour.is_empty() {
+        convex_hull(&coordinates)
+    } else {
+        contour.clone()
+    };
+
+    let mut triangulation = Triangulation::delaunay(coordinates);
+    let outline_edges =
+        (0..outline.len()).map(|index| (outline[index], outline[(index + 1) % outline.len()]));
+    for (from, to) in outline_edges.chain(forced.iter().copied()) {
+        if from == to {
+            return Err(format!(
+                "mesh '{}' forced edge joins vertex '{}' to itself",
+                mesh, points[from].name
+            ));
+        }
+        for (a, b) in triangulation.split_at_vertices(from, to) {
+            triangulation.insert_constraint(a, b).map_err(|crossed| {
+                format!(
+                    "mesh '{}' edge from '{}' to '{}' crosses another forced or contour edge{}",
+                    mesh,
+                    points[from].name,
+                    points[to].name,
+                    crossed
+                        .map(|(u, v)| format!(" at '{}'-'{}'", points[u].name, points[v].name))
+                        .unwrap_or_default()
+                )
+            })?;
+        }
+    }
+
+    let outline_points = outline
+        .iter()
+        .map(|&index| triangulation.points[index])
+        .collect::<Vec<_>>();
+    let mut indices = Vec::new();
+    let mut covered = vec![false; points.len()];
+    for triangle in triangulation.triangles.iter().flatten() {
+        if triangle.iter().any(|&vertex| vertex >= points.len()) {
+            continue;
+        }
+        let [a, b, c] = triangle.map(|vertex| triangulation.points[vertex]);
+        let centroid = [(a[0] + b[0] + c[0]) / 3.0, (a[1] + b[1] + c[1]) / 3.0];
+        if !contour.is_empty() && !inside_polygon(&outline_points, centroid) {
+            continue;
+        }
+        for &vertex in triangle {
+            covered[vertex] = true;
+            indices.push(vertex as u16);
+        }
+    }
+    if let Some(index) = covered.iter().position(|covered| !covered) {
+        return Err(format!(
+            "mesh '{}' vertex '{}' lies outside the mesh contour",
+            mesh, points[index].name
+        ));
+    }
+    Ok(indices)
+}
+
+/// Counter-clockwise triangles over the mesh points plus three super
+/// triangle vertices appended after them.
+struct Triangulation {
+    points: Vec<[f64; 2]>,
+    triangles: Vec<Option<[usize; 3]>>,
+    edges: HashMap<(usize, usize), usize>,
+    constrained: HashSet<(usize, usize)>,
+}
+
+impl Triangulation {
+    fn delaunay(mut points: Vec<[f64; 2]>) -> Self {
+        let count = points.len();
+        let (mut min, mut max) = (points[0], points[0]);
+        for point in &points {
+            min = [min[0].min(point[0]), min[1].min(point[1])];
+            max = [max[0].max(point[0]), max[1].max(point[1])];
+        }
+        let span = (max[0] - min[0]).max(max[1] - min[1]).max(1.0) * 64.0;
+        let center = [(min[0] + max[0]) / 2.0, (min[1] + max[1]) / 2.0];
+        points.push([center[0] - span, center[1] - span]);
+        points.push([center[0] + span, center[1] - span]);
+        points.push([center[0], center[1] + span]);
+
+        let mut triangulation = Self {
+            points,
+            triangles: Vec::new(),
+            edges: HashMap::new(),
+            constrained: HashSet::new(),
+        };
+        triangulation.add([count, count + 1, count + 2]);
+        for index in 0..count {
+            triangulation.insert_point(index);
+        }
+        triangulation
+    }
+
+    fn add(&mut self, triangle: [usize; 3]) {
+        let slot = self.triangles.len();
+        for i in 0..3 {
+            self.edges
+                .insert((triangle[i], triangle[(i + 1) % 3]), slot);
+        }
+        self.triangles.push(Some(triangle));
+    }
+
+    fn remove(&mut self, slot: usize) {
+        let triangle = self.triangles[slot].take().expect("live triangle");
+        for i in 0..3 {
+            self.edges.remove(&(triangle[i], triangle[(i + 1) % 3]));
+        }
+    }
+
+    fn point(&self, index: usize) -> [f64; 2] {
+        self.points[index]
+    }
+
+    /// Bowyer-Watson insertion: the cavity grows from the triangle holding
+    /// the point across every edge whose far triangle's circumcircle
+    /// contains it.
+    fn insert_point(&mut self, index: usize) {
+        let point = self.point(index);
+        let start = self
+            .triangles
+            .iter()
+            .position(|triangle| {
+                triangle.is_some_and(|[a, b, c]| {
+                    orient(self.point(a), self.point(b), point) >= 0.0
+                        && orient(self.point(b), self.point(c), point) >= 0.0
+                        && orient(self.point(c), self.point(a), point) >= 0.0
+                })
+            })
+            .expect("the super triangle contains every mesh vertex");
+
+        let mut cavity = HashSet::from([start]);
+        let mut pending = vec![start];
+        while let Some(slot) = pending.pop() {
+            let triangle = self.triangles[slot].expect("live triangle");
+            for i in 0..3 {
+                let (a, b) = (triangle[i], triangle[(i + 1) % 3]);
+                if let Some(&neighbour) = self.edges.get(&(b, a))
+                    && !cavity.contains(&neighbour)
+                {
+                    let [p, q, r] = self.triangles[neighbour].expect("live triangle");
+                    if in_circle(self.point(p), self.point(q), self.point(r), point) > 0.0 {
+                        cavity.insert(neighbour);
+                        pending.push(neighbour);
+                    }
+                }
+            }
+        }
+
+        let mut boundary = Vec::new();
+        let mut slots = cavity.iter().copied().collect::<Vec<_>>();
+        slots.sort_unstable();
+        for &slot in &slots {
+            let triangle = self.triangles[slot].expect("live triangle");
+            for i in 0..3 {
+                let (a, b) = (triangle[i], triangle[(i + 1) % 3]);
+                if !self
+                    .edges
+                    .get(&(b, a))
+                    .is_some_and(|neighbour| cavity.contains(neighbour))
+                {
+                    boundary.push((a, b));
+                }
+            }
+        }
+        for slot in slots {
+            self.remove(slot);
+        }
+        for (a, b) in boundary {
+            self.add([a, b, index]);
+        }
+    }
+
+    /// Splits the edge `from`-`to` at every vertex lying on it.
+    fn split_at_vertices(&self, from: usize, to: usize) -> Vec<(usize, usize)> {
+        let (a, b) = (self.point(from), self.point(to));
+        let length = (b[0] - a[0]).powi(2) + (b[1] - a[1]).powi(2);
+        let mut between = (0..self.points.len() - 3)
+            .filter(|&index| index != from && index != to)
+            .filter_map(|index| {
+                let c = self.point(index);
+                let along = (c[0] - a[0]) * (b[0] - a[0]) + (c[1] - a[1]) * (b[1] - a[1]);
+                (orient(a, b, c) == 0.0 && along > 0.0 && along < length).then_some((along, index))
+            })
+            .collect::<Vec<_>>();
+        between.sort_by(|left, right| left.0.total_cmp(&right.0));
+        let mut chain = vec![from];
+        chain.extend(between.into_iter().map(|(_, index)| index));
+        chain.push(to);
+        chain.windows(2).map(|pair| (pair[0], pair[1])).collect()
+    }
+
+    /// Flips edges until `a`-`b` is an edge, then restores the Delaunay
+    /// property around the flipped edges. Fails with the crossed edge when
+    /// another constraint is in the way.
+    fn insert_constraint(&mut self, a: usize, b: usize) -> Result<(), Option<(usize, usize)>> {
+        let key = (a.min(b), a.max(b));
+        if self.edges.contains_key(&(a, b)) || self.edges.contains_key(&(b, a)) {
+            self.constrained.insert(key);
+            return Ok(());
+        }
+        let (pa, pb) = (self.point(a), self.point(b));
+        let crosses = |this: &Self, (u, v): (usize, usize)| {
+            let (pu, pv) = (this.point(u), this.point(v));
+            orient(pa, pb, pu) * orient(pa, pb, pv) < 0.0
+                && orient(pu, pv, pa) * orient(pu, pv, pb) < 0.0
+        };
+        let mut queue = VecDeque::new();
+        let mut keys = self.edges.keys().copied().collect::<Vec<_>>();
+        keys.sort_unstable();
+        for (u, v) in keys {
+            if u < v && crosses(self, (u, v)) {
+                if self.constrained.contains(&(u, v)) {
+                    return Err(Some((u, v)));
+                }
+                queue.push_back((u, v));
+            }
+        }
+
+        let mut created = Vec::new();
+        let mut flips = 0;
+        while let Some((u, v)) = queue.pop_front() {
+            flips += 1;
+            if flips > MAX_EDGE_FLIPS {
+                return Err(None);
+            }
+            let Some((p, q)) = self.flip(u, v, false) else {
+                queue.push_back((u, v));
+                continue;
+            };
+            if crosses(self, (p, q)) {
+                queue.push_back((p, q));
+            } else {
+                created.push((p, q));
+            }
+        }
+        self.constrained.insert(key);
+
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for edge in &mut created {
+                let (u, v) = *edge;
+                if self.constrained.contains(&(u.min(v), u.max(v))) {
+                    continue;
+                }
+                if let Some(flipped) = self.flip(u, v, true) {
+                    *edge = flipped;
+                    changed = true;
+                    flips += 1;
+                    if flips > MAX_EDGE_FLIPS {
+                        return Err(None);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Replaces the edge `u`-`v` with the other diagonal of its quad when the
+    /// quad is convex, and when `only_if_illegal` also only when the edge
+    /// fails the empty-circumcircle test.
+    fn flip(&mut self, u: usize, v: usize, only_if_illegal: bool) -> Option<(usize, usize)> {
+        let (&first, &second) = self.edges.get(&(u, v)).zip(self.edges.get(&(v, u)))?;
+        let p = third(self.triangles[first]?, u, v);
+        let q = third(self.triangles[second]?, v, u);
+        let (pu, pv, pp, pq) = (self.point(u), self.point(v), self.point(p), self.point(q));
+        if orient(pp, pq, pu) * orient(pp, pq, pv) >= 0.0 {
+            return None;
+        }
+        if only_if_illegal && in_circle(pu, pv, pp, pq) <= 0.0 {
+            return None;
+        }
+        self.remove(first);
+        self.remove(second);
+        self.add([u, q, p]);
+        self.add([v, p, q]);
+        Some((p, q))
+    }
+}
+
+/// The vertex of `triangle` opposite its directed edge `u`-`v`.
+fn third(triangle: [usize; 3], u: usize, v: usize) -> usize {
+    *triangle
+        .iter()
+        .find(|&&vertex| vertex != u && vertex != v)
+        .expect("triangle has a third vertex")
+}
+
+fn orient(a: [f64; 2], b: [f64; 2], c: [f64; 2]) -> f64 {
+    (b[0] - a[0]) * (c[1] - a[1]) - (b[1] - a[1]) * (c[0] - a[0])
+}
+
+/// Positive when `d` lies inside the circumcircle of counter-clockwise
+/// `a`, `b`, `c`.
+fn in_circle(a: [f64; 2], b: [f64; 2], c: [f64; 2], d: [f64; 2]) -> f64 {
+    let [adx, ady] = [a[0] - d[0], a[1] - d[1]];
+    let [bdx, bdy] = [b[0] - d[0], b[1] - d[1]];
+    let [cdx, cdy] = [c[0] - d[0], c[1] - d[1]];
+    (adx * adx + ady * ady) * (bdx * cdy - cdx * bdy)
+        + (bdx * bdx + bdy * bdy) * (cdx * ady - adx * cdy)
+        + (cdx * cdx + cdy * cdy) * (adx * bdy - bdx * ady)
+}
+
+/// Hull vertex indices in counter-clockwise order (Andrew's monotone chain).
+fn convex_hull(points: &[[f64; 2]]) -> Vec<usize> {
+    let mut order = (0..points.len()).collect::<Vec<_>>();
+    order.sort_by(|&a, &b| {
+        points[a][0]
+            .total_cmp(&points[b][0])
+            .then(points[a][1].total_cmp(&points[b][1]))
+    });
+    let mut hull: Vec<usize> = Vec::with_capacity(points.len() * 2);
+    for pass in [order.clone(), order.into_iter().rev().collect()] {
+        let floor = hull.len();
+        for index in pass {
+            while hull.len() >= floor + 2
+                && orient(
+                    points[hull[hull.len() - 2]],
+                    points[hull[hull.len() - 1]],
+                    points[index],
+                ) <= 0.0
+            {
+                hull.pop();
+            }
+            hull.push(index);
+        }
+        hull.pop();
+    }
+    hull
+}
+
+/// Even-odd point-in-polygon test.
+fn inside_polygon(polygon: &[[f64; 2]], point: [f64; 2]) -> bool {
+    let mut inside = false;
+    let mut previous = polygon[polygon.len() - 1];
+    for &current in polygon {
+        if (current[1] > point[1]) != (previous[1] > point[1]) {
+            let x = previous[0]
+                + (point[1] - previous[1]) / (current[1] - previous[1])
+                    * (current[0] - previous[0]);
+            if point[0] < x {
+                inside = !inside;
+            }
+        }
+        previous = current;
+    }
+    inside
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scattered(count: usize) -> Vec<(String, f64, f64)> {
+        // A deterministic low-discrepancy scatter with no repeated positions.
+        (0..count)
+            .map(|index| {
+                let t = index as f64;
+                let x = (t * 0.618_033_988_75).fract() * 400.0;
+                let y = (t * 0.754_877_666_25).fract() * 300.0;
+                (format!("v{index}"), x.round(), y.round())
+            })
+            .collect()
+    }
+
+    fn points(coordinates: &[(String, f64, f64)]) -> Vec<MeshPoint<'_>> {
+        coordinates
+            .iter()
+            .map(|(name, x, y)| MeshPoint {
+                name,
+                x: *x,
+                y: *y,
+                contour: false,
+            })
+            .collect()
+    }
+
+    fn area(points: &[MeshPoint<'_>], triangle: &[u16]) -> f64 {
+        let [a, b, c] = [0, 1, 2].map(|i| {
+            let point = &points[triangle[i] as usize];
+            [point.x, point.y]
+        });
+        orient(a, b, c) / 2.0
+    }
+
+    #[test]
+    fn test_unconstrained_triangulation_fills_the_hull_and_is_delaunay() {
+        let coordinates = scattered(60);
+        let points = points(&coordinates);
+        let indices = triangulate("m", &points, &[]).unwrap();
+        assert_eq!(indices.len() % 3, 0);
+
+        let flat = points.iter().map(|p| [p.x, p.y]).collect::<Vec<_>>();
+        let hull = convex_hull(&flat);
+        let hull_area = (0..hull.len())
+            .map(|i| {
+                let (a, b) = (flat[hull[i]], flat[hull[(i + 1) % hull.len()]]);
+                a[0] * b[1] - b[0] * a[1]
+            })
+            .sum::<f64>()
+            / 2.0;
+        let covered = indices
+            .chunks(3)
+            .map(|triangle| {
+                let area = area(&points, triangle);
+                assert!(
+                    area > 0.0,
+                    "triangles are counter-clockwise and not degenerate"
+                );
+                area
+            })
+            .sum::<f64>();
+        assert!((covered - hull_area).abs() < 1e-6);
+
+        for triangle in indices.chunks(3) {
+            let [a, b, c] = [0, 1, 2].map(|i| flat[triangle[i] as usize]);
+            for (index, point) in flat.iter().enumerate() {
+                if !triangle.contains(&(index as u16)) {
+                    assert!(in_circle(a, b, c, *point) <= 1e-6);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_forced_edges_survive_in_the_triangulation() {
+        let coordinates = scattered(40);
+        let points = points(&coordinates);
+        let unconstrained = triangulate("m", &points, &[]).unwrap();
+        let has_edge = |indices: &[u16], a: u16, b: u16| {
+            indices
+                .chunks(3)
+                .any(|triangle| triangle.contains(&a) && triangle.contains(&b))
+        };
+        let far = (1..40)
+            .rev()
+            .find(|&b| !has_edge(&unconstrained, 0, b))
+            .unwrap();
+
+        let indices = triangulate("m", &points, &[(0, usize::from(far))]).unwrap();
+        assert!(has_edge(&indices, 0, far));
+        assert_eq!(indices.len(), unconstrained.len());
+        assert!(
+            indices
+                .chunks(3)
+                .all(|triangle| area(&points, triangle) > 0.0)
+        );
+    }
+}
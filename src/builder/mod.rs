@@ -1,13 +1,29 @@
 mod animations;
+mod assets;
+mod luau;
+mod mesh;
+mod nine_slice;
 mod objects;
 mod parsers;
+mod paths;
 mod references;
 pub mod scene;
+mod skin;
 pub(crate) mod spec;
 mod state_machines;
 mod validation;
 
-pub use scene::{artboard_presets, build_scene};
+pub use assets::{
+    EXTERNAL_ASSET_MANIFEST, ExternalAsset, ExternalAssetEntry, ExternalAssetManifest,
+    read_external_assets, write_external_assets,
+};
+pub(crate) use parsers::parse_color;
+pub use paths::AssetPolicy;
+#[cfg(feature = "mcp")]
+pub(crate) use paths::{RootViolation, canonicalise_existing, within_roots};
+pub use scene::{
+    artboard_presets, build_scene, build_scene_with_external_assets, build_scene_with_policy,
+};
 pub use spec::SceneSpec;
 pub fn animatable_properties_for(type_name: &str) -> Vec<&'static str> {
     parsers::animatable_properties_for_object_type(type_name)
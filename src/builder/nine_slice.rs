@@ -0,0 +1,86 @@
+use crate::objects::core::RiveObject;
+use crate::objects::nslicer::{AxisX, AxisY, NSlicer, NSlicerTileMode};
+
+use super::parsers::parse_nslicer_tile_mode;
+
+/// Pixel insets from each edge of the image.
+#[derive(Debug, Clone, Copy)]
+pub(super) struct Insets {
+    pub(super) left: f32,
+    pub(super) top: f32,
+    pub(super) right: f32,
+    pub(super) bottom: f32,
+}
+
+/// The runtime numbers the nine patches row by row; the corners (0, 2, 6
+/// and 8) never scale, so only the edges and the centre take a tile mode.
+const EDGE_PATCHES: [u64; 4] = [1, 3, 5, 7];
+const CENTER_PATCH: u64 = 4;
+
+/// The axis offsets, as fractions of the intrinsic size, that cut the image
+/// `insets` in from each edge.
+pub(super) fn normalized_axes(
+    name: &str,
+    insets: Insets,
+    (width, height): (u32, u32),
+) -> Result<([f32; 2], [f32; 2]), String> {
+    let (width, height) = (width as f32, height as f32);
+    if insets.left + insets.right >= width || insets.top + insets.bottom >= height {
+        return Err(format!(
+            "nine_slice '{}' insets leave no centre in its {}x{} image; left + right must be below the width and top + bottom below the height",
+            name, width, height
+        ));
+    }
+    Ok((
+        [insets.left / width, (width - insets.right) / width],
+        [insets.top / height, (height - insets.bottom) / height],
+    ))
+}
+
+/// Pushes the `nslicer` under the image at `image_id`, then its axes and the
+/// tile modes that differ from stretching.
+pub(super) fn append_slicer(
+    name: &str,
+    image_id: u64,
+    artboard_start: usize,
+    (xs, ys): ([f32; 2], [f32; 2]),
+    edges: Option<&str>,
+    center: Option<&str>,
+    objects: &mut Vec<Box<dyn RiveObject>>,
+) -> Result<(), String> {
+    let slicer_id = (objects.len() - artboard_start) as u64;
+    objects.push(Box::new(NSlicer::new(
+        format!("{}_nslicer", name),
+        image_id,
+    )));
+    for offset in xs {
+        let mut axis = AxisX::new(String::new(), slicer_id, offset);
+        axis.normalized = true;
+        objects.push(Box::new(axis));
+    }
+    for offset in ys {
+        let mut axis = AxisY::new(String::new(), slicer_id, offset);
+        axis.normalized = true;
+        objects.push(Box::new(axis));
+    }
+    let patches = [
+        (edges, EDGE_PATCHES.as_slice()),
+        (center, [CENTER_PATCH].as_slice()),
+    ];
+    for (mode, patch_indices) in patches {
+        let style = mode
+            .map(parse_nslicer_tile_mode)
+            .transpose()
+            .map_err(|error| format!("nine_slice '{}': {}", name, error))?
+            .unwrap_or(0);
+        if style == 0 {
+            continue;
+        }
+        for &patch_index in patch_indices {
+            let mut tile = NSlicerTileMode::new(String::new(), slicer_id, patch_index);
+            tile.style = style;
+            objects.push(Box::new(tile));
+        }
+    }
+    Ok(())
+}
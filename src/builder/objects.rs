@@ -1,8 +1,9 @@
 use std::collections::HashMap;
-use std::path::{Component, Path, PathBuf};
 
 use crate::objects::artboard::NestedArtboard;
-use crate::objects::assets::{self, AudioAsset, FileAssetContents, FontAsset, ImageAsset};
+use crate::objects::assets::{
+    self, AudioAsset, FileAssetContents, FontAsset, ImageAsset, ScriptAsset,
+};
 use crate::objects::bones::{Bone, CubicWeight, RootBone, Skin, Tendon, Weight};
 use crate::objects::constraints::{
     DistanceConstraint, FollowPathConstraint, IKConstraint, RotationConstraint, ScaleConstraint,
@@ -13,8 +14,8 @@ use crate::objects::data_binding::{
     self, BindablePropertyArtboard, BindablePropertyBoolean, BindablePropertyColor,
     BindablePropertyEnum, BindablePropertyId, BindablePropertyInteger, BindablePropertyList,
     BindablePropertyNumber, BindablePropertyString, BindablePropertyTrigger, DataBind,
-    DataBindPath, DataEnum, DataEnumCustom, DataEnumSystem, DataEnumValue, ViewModel,
-    ViewModelInstance, ViewModelInstanceArtboard, ViewModelInstanceAssetImage,
+    DataBindContext, DataBindPath, DataEnum, DataEnumCustom, DataEnumSystem, DataEnumValue,
+    ViewModel, ViewModelInstance, ViewModelInstanceArtboard, ViewModelInstanceAssetImage,
     ViewModelInstanceBoolean, ViewModelInstanceColor, ViewModelInstanceEnum, ViewModelInstanceList,
     ViewModelInstanceListItem, ViewModelInstanceNumber, ViewModelInstanceString,
     ViewModelInstanceSymbol, ViewModelInstanceSymbolListIndex, ViewModelInstanceTrigger,
@@ -37,26 +38,32 @@ use crate::objects::text::{
     TextStyleAxis, TextStyleFeature, TextTargetModifier, TextValueRun, TextVariationModifier,
 };
 
+use super::luau::{ScriptInput, ScriptInputKind};
+use super::mesh::{self, MeshPoint};
+use super::nine_slice::{self, Insets};
 use super::parsers::{
-    parse_color, parse_fill_rule, parse_stroke_cap, parse_stroke_join, parse_trim_mode,
-    required_u64_field,
+    parse_color, parse_draggable_direction, parse_fill_rule, parse_stroke_cap, parse_stroke_join,
+    parse_trim_mode, required_u64_field,
 };
 use super::references::{self, Namespace};
-use super::spec::{ObjectSpec, TextModifierGroupChildSpec};
+use super::skin;
+use super::spec::{ObjectSpec, ScrollPhysicsSpec, TextModifierGroupChildSpec};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub(crate) enum FileAssetKind {
     Image,
     Font,
     Audio,
+    Script,
 }
 
 impl FileAssetKind {
-    fn label(self) -> &'static str {
+    pub(super) fn label(self) -> &'static str {
         match self {
             FileAssetKind::Image => "image_asset",
             FileAssetKind::Font => "font_asset",
             FileAssetKind::Audio => "audio_asset",
+            FileAssetKind::Script => "script_asset",
         }
     }
 }
@@ -64,6 +71,20 @@ impl FileAssetKind {
 pub(crate) struct SceneContext<'a> {
     pub asset_ids: &'a HashMap<String, (u64, FileAssetKind)>,
     pub asset_kinds: &'a [FileAssetKind],
+    pub nested_playback: &'a HashMap<String, NestedPlayback>,
+    /// Inputs declared by embedded scripts, by asset index.
+    pub script_inputs: &'a HashMap<u64, Vec<ScriptInput>>,
+    /// Intrinsic pixel sizes of embedded images, by asset index.
+    pub image_sizes: &'a HashMap<u64, (u32, u32)>,
+}
+
+/// Animation and state machine indices of one artboard. Playback objects
+/// inside a `nested_artboard` name animations of the artboard it instances,
+/// not of the artboard that hosts it.
+#[derive(Default)]
+pub(crate) struct NestedPlayback {
+    pub animations: HashMap<String, usize>,
+    pub state_machines: HashMap<String, usize>,
 }
 
 fn resolve_asset_ordinal(
@@ -107,11 +128,114 @@ fn resolve_asset_ordinal(
     )
 }
 
+fn scroll_physics(spec: &ScrollPhysicsSpec) -> Box<dyn RiveObject> {
+    match spec {
+        ScrollPhysicsSpec::Clamped {
+            friction,
+            speed_multiplier,
+        } => {
+            let mut physics = layout::ClampedScrollPhysics::new();
+            physics.friction = friction.unwrap_or(physics.friction);
+            physics.speed_multiplier = speed_multiplier.unwrap_or(physics.speed_multiplier);
+            Box::new(physics)
+        }
+        ScrollPhysicsSpec::Elastic {
+            friction,
+            speed_multiplier,
+            elastic_factor,
+        } => {
+            let mut physics = layout::ElasticScrollPhysics::new();
+            physics.friction = friction.unwrap_or(physics.friction);
+            physics.speed_multiplier = speed_multiplier.unwrap_or(physics.speed_multiplier);
+            physics.elastic_factor = elastic_factor.unwrap_or(physics.elastic_factor);
+            Box::new(physics)
+        }
+    }
+}
+
+/// A bare `script_asset_id` is written as given, since it may index a script
+/// the host supplies; `script` names one the scene declares.
+fn resolve_script(
+    owner: &str,
+    script: &Option<String>,
+    script_asset_id: Option<u64>,
+    ctx: &SceneContext<'_>,
+) -> Result<u64, String> {
+    if script.is_none() {
+        return Ok(script_asset_id.unwrap_or(0));
+    }
+    Ok(resolve_asset_ordinal(
+        owner,
+        script.as_deref(),
+        script_asset_id,
+        FileAssetKind::Script,
+        ctx,
+        ("script", "script_asset_id"),
+    )?
+    .unwrap_or(0))
+}
+
+fn script_input(spec: &ObjectSpec) -> Option<(&str, ScriptInputKind)> {
+    match spec {
+        ObjectSpec::ScriptInputNumber { name } => Some((name, ScriptInputKind::Number)),
+        ObjectSpec::ScriptInputBoolean { name } => Some((name, ScriptInputKind::Boolean)),
+        ObjectSpec::ScriptInputString { name } => Some((name, ScriptInputKind::String)),
+        ObjectSpec::ScriptInputColor { name } => Some((name, ScriptInputKind::Color)),
+        ObjectSpec::ScriptInputTrigger { name } => Some((name, ScriptInputKind::Trigger)),
+        ObjectSpec::ScriptInputArtboard { name, .. } => Some((name, ScriptInputKind::Artboard)),
+        ObjectSpec::ScriptInputViewModelProperty { name, .. } => {
+            Some((name, ScriptInputKind::ViewModelProperty))
+        }
+        _ => None,
+    }
+}
+
+/// Checks a scripted object's `script_input_*` children against the inputs
+/// its embedded script declares.
+fn check_script_inputs(
+    owner: &str,
+    script_asset_id: u64,
+    children: Option<&[ObjectSpec]>,
+    ctx: &SceneContext<'_>,
+) -> Result<(), String> {
+    let Some(declared) = ctx.script_inputs.get(&script_asset_id) else {
+        return Ok(());
+    };
+    for (name, kind) in children.unwrap_or_default().iter().filter_map(script_input) {
+        match declared.iter().find(|input| input.name == name) {
+            Some(input) if input.kind == kind => {}
+            Some(input) => {
+                return Err(format!(
+                    "'{owner}' gives its script input '{name}' as a {}, but the script declares it as {}",
+                    kind.label(),
+                    input.kind.label()
+                ));
+            }
+            None => {
+                let names = declared
+                    .iter()
+                    .map(|input| format!("{} {}", input.name, input.kind.label()))
+                    .collect::<Vec<_>>();
+                return Err(format!(
+                    "'{owner}' gives input '{name}', which its script does not declare; declared: {}",
+                    if names.is_empty() {
+                        "(none)".to_string()
+                    } else {
+                        names.join(", ")
+                    }
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
 pub(crate) fn file_asset(spec: &ObjectSpec) -> Option<(&str, FileAssetKind)> {
     match spec {
         ObjectSpec::ImageAsset { name, .. } => Some((name, FileAssetKind::Image)),
         ObjectSpec::FontAsset { name, .. } => Some((name, FileAssetKind::Font)),
         ObjectSpec::AudioAsset { name, .. } => Some((name, FileAssetKind::Audio)),
+        ObjectSpec::ScriptAsset { name, .. } => Some((name, FileAssetKind::Script)),
         _ => None,
     }
 }
@@ -120,17 +244,19 @@ pub(crate) fn is_file_asset(spec: &ObjectSpec) -> bool {
     file_asset(spec).is_some()
 }
 
+/// Pushes a file asset followed by the `FileAssetContents` that embed it,
+/// when there are any.
 pub(crate) fn append_file_asset(
     spec: &ObjectSpec,
+    contents: Option<Vec<u8>>,
     objects: &mut Vec<Box<dyn RiveObject>>,
-    base_dir: Option<&Path>,
-) -> Result<(), String> {
+) {
     match spec {
         ObjectSpec::ImageAsset {
             name,
             asset_id,
             cdn_base_url,
-            source,
+            ..
         } => {
             let mut asset = ImageAsset::new(name.clone());
             if let Some(v) = asset_id {
@@ -140,13 +266,12 @@ pub(crate) fn append_file_asset(
                 asset.cdn_base_url = v.clone();
             }
             objects.push(Box::new(asset));
-            append_asset_contents(name, source.as_deref(), base_dir, objects)
         }
         ObjectSpec::FontAsset {
             name,
             asset_id,
             cdn_base_url,
-            source,
+            ..
         } => {
             let mut asset = FontAsset::new(name.clone());
             if let Some(v) = asset_id {
@@ -156,12 +281,12 @@ pub(crate) fn append_file_asset(
                 asset.cdn_base_url = v.clone();
             }
             objects.push(Box::new(asset));
-            append_asset_contents(name, source.as_deref(), base_dir, objects)
         }
         ObjectSpec::AudioAsset {
             name,
             asset_id,
             cdn_base_url,
+            ..
         } => {
             let mut asset = AudioAsset::new(name.clone());
             if let Some(v) = asset_id {
@@ -171,105 +296,31 @@ pub(crate) fn append_file_asset(
                 asset.cdn_base_url = v.clone();
             }
             objects.push(Box::new(asset));
-            Ok(())
-        }
-        _ => Ok(()),
-    }
-}
-
-const PROJECT_MARKERS: [&str; 3] = ["Cargo.toml", ".git", "package.json"];
-
-fn asset_root(base_dir: &Path) -> PathBuf {
-    let start = std::path::absolute(base_dir).unwrap_or_else(|_| base_dir.to_path_buf());
-    let mut cursor = normalise(&start);
-    loop {
-        if PROJECT_MARKERS
-            .iter()
-            .any(|marker| cursor.join(marker).exists())
-        {
-            return cursor;
-        }
-        if !cursor.pop() {
-            return normalise(&start);
         }
-    }
-}
-
-fn normalise(path: &Path) -> PathBuf {
-    let mut out = PathBuf::new();
-    for component in path.components() {
-        match component {
-            Component::ParentDir => {
-                out.pop();
+        ObjectSpec::ScriptAsset {
+            name,
+            asset_id,
+            cdn_base_url,
+            is_module,
+            ..
+        } => {
+            let mut asset = ScriptAsset::new(name.clone());
+            if let Some(v) = asset_id {
+                asset.asset_id = *v;
+            }
+            if let Some(v) = cdn_base_url {
+                asset.cdn_base_url = v.clone();
+            }
+            if let Some(v) = is_module {
+                asset.is_module = *v;
             }
-            Component::CurDir => {}
-            other => out.push(other),
+            objects.push(Box::new(asset));
         }
+        _ => return,
     }
-    out
-}
-
-fn append_asset_contents(
-    asset_name: &str,
-    source: Option<&str>,
-    base_dir: Option<&Path>,
-    objects: &mut Vec<Box<dyn RiveObject>>,
-) -> Result<(), String> {
-    let Some(source) = source else {
-        return Ok(());
-    };
-    let Some(base_dir) = base_dir else {
-        return Err(format!(
-            "asset '{asset_name}' sets 'source', but embedding asset files is only supported when generating from a scene file on disk"
-        ));
-    };
-    let relative = Path::new(source);
-    if relative.is_absolute() {
-        return Err(format!(
-            "asset '{asset_name}' source '{source}' must be relative to the scene file's directory so the scene stays portable"
-        ));
+    if let Some(bytes) = contents {
+        objects.push(Box::new(FileAssetContents::new(bytes)));
     }
-    let path = base_dir.join(relative);
-    let resolved = std::path::absolute(&path).unwrap_or_else(|_| path.clone());
-    let root = asset_root(base_dir);
-    let canonical_path = path.canonicalize().map_err(|error| {
-        format!(
-            "asset '{}' source '{}' could not be read from {}: {}",
-            asset_name,
-            source,
-            resolved.display(),
-            error
-        )
-    })?;
-    let canonical_root = root.canonicalize().unwrap_or_else(|_| normalise(&root));
-    if !canonical_path.starts_with(&canonical_root) {
-        return Err(format!(
-            "asset '{}' source '{}' resolves to {}, outside the project rooted at {}",
-            asset_name,
-            source,
-            canonical_path.display(),
-            canonical_root.display()
-        ));
-    }
-    let bytes = std::fs::read(&canonical_path).map_err(|error| {
-        format!(
-            "asset '{}' source '{}' could not be read from {}: {}",
-            asset_name,
-            source,
-            resolved.display(),
-            error
-        )
-    })?;
-    if bytes.is_empty() {
-        return Err(format!(
-            "asset '{}' source '{}' is empty at {}",
-            asset_name,
-            source,
-            resolved.display()
-        ));
-    }
-    objects.push(Box::new(FileAssetContents::new(bytes)));
-    Ok(())
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -849,6 +900,76 @@ pub(crate) fn append_object(
                 }
             }
         }
+        ObjectSpec::NineSlice {
+            name,
+            asset,
+            left,
+            top,
+            right,
+            bottom,
+            x,
+            y,
+            rotation,
+            width,
+            height,
+            scale_x,
+            scale_y,
+            edges,
+            center,
+            image_width,
+            image_height,
+        } => {
+            let asset_id = resolve_asset_ordinal(
+                name,
+                Some(asset),
+                None,
+                FileAssetKind::Image,
+                ctx,
+                ("asset", "asset_id"),
+            )?
+            .unwrap_or(0);
+            let embedded = ctx.image_sizes.get(&asset_id).copied();
+            let declared = image_width.zip(*image_height);
+            let size = match (embedded, declared) {
+                (Some(embedded), Some(declared)) if embedded != declared => {
+                    return Err(format!(
+                        "nine_slice '{}' declares a {}x{} image, but asset '{}' embeds a {}x{} image",
+                        name, declared.0, declared.1, asset, embedded.0, embedded.1
+                    ));
+                }
+                (Some(size), _) | (None, Some(size)) => size,
+                (None, None) => {
+                    return Err(format!(
+                        "nine_slice '{}' needs the size of image asset '{}'; give the asset a PNG, JPEG or WebP source, or set image_width and image_height",
+                        name, asset
+                    ));
+                }
+            };
+            let insets = Insets {
+                left: *left,
+                top: *top,
+                right: *right,
+                bottom: *bottom,
+            };
+            let axes = nine_slice::normalized_axes(name, insets, size)?;
+            let mut image = Image::new(name.clone(), parent_id, asset_id);
+            image.x = x.unwrap_or(0.0);
+            image.y = y.unwrap_or(0.0);
+            image.rotation = rotation.unwrap_or(0.0);
+            image.scale_x = width.map_or(scale_x.unwrap_or(1.0), |width| width / size.0 as f32);
+            image.scale_y = height.map_or(scale_y.unwrap_or(1.0), |height| height / size.1 as f32);
+            objects.push(Box::new(image));
+            name_to_index.insert(name.clone(), object_index);
+            nine_slice::append_slicer(
+                name,
+                (object_index - artboard_start) as u64,
+                artboard_start,
+                axes,
+                edges.as_deref(),
+                center.as_deref(),
+                objects,
+            )?;
+        }
         ObjectSpec::Path { name, path_flags } => {
             objects.push(Box::new(PathObject {
                 name: name.clone(),
@@ -863,6 +984,7 @@ pub(crate) fn append_object(
             y,
             is_closed,
             path_flags,
+            skin,
             children,
         } => {
             objects.push(Box::new(PointsPathObject {
@@ -889,6 +1011,9 @@ pub(crate) fn append_object(
                     )?;
                 }
             }
+            if let Some(skin) = skin {
+                skin::append_skin(skin, object_index, artboard_start, objects, name_to_index)?;
+            }
         }
         ObjectSpec::StraightVertex { name, x, y, radius } => {
             objects.push(Box::new(StraightVertexObject {
@@ -1012,7 +1137,54 @@ pub(crate) fn append_object(
             }));
             name_to_index.insert(name.clone(), object_index);
             if let Some(children) = children {
+                let playback = ctx.nested_playback.get(source_artboard);
                 for child in children {
+                    let playback_names = match (child, playback) {
+                        (ObjectSpec::NestedSimpleAnimation { .. }, Some(playback)) => {
+                            &playback.animations
+                        }
+                        (ObjectSpec::NestedStateMachine { .. }, Some(playback)) => {
+                            &playback.state_machines
+                        }
+                        _ => animation_name_to_index,
+                    };
+                    let playback_reference = match child {
+                        ObjectSpec::NestedSimpleAnimation {
+                            name: child_name,
+                            animation,
+                            ..
+                        } => Some((
+                            "nested_simple_animation",
+                            child_name,
+                            "animation",
+                            animation,
+                        )),
+                        ObjectSpec::NestedStateMachine {
+                            name: child_name,
+                            animation,
+                        } => Some((
+                            "nested_state_machine",
+                            child_name,
+                            "state machine",
+                            animation,
+                        )),
+                        _ => None,
+                    };
+                    if let Some((kind, child_name, playback_kind, playback_name)) =
+                        playback_reference
+                        && !playback_names.contains_key(playback_name)
+                    {
+                        let mut available = playback_names.keys().cloned().collect::<Vec<_>>();
+                        available.sort();
+                        return Err(format!(
+                            "{kind} '{child_name}' names {playback_kind} '{playback_name}', which source artboard '{source_artboard}' does not define; nested playback names the instanced artboard's {playback_kind}s, not those of host artboard '{current_artboard_name}' (available: {})",
+                            if available.is_empty() {
+                                "none".to_string()
+                            } else {
+                                available.join(", ")
+                            }
+                        ));
+                    }
                     append_object(
                         child,
                         object_index,
@@ -1021,7 +1193,7 @@ pub(crate) fn append_object(
                         name_to_index,
                         artboard_name_to_index,
                         current_artboard_name,
-                        animation_name_to_index,
+                        playback_names,
                         ctx,
                     )?;
                 }
@@ -1089,12 +1261,16 @@ pub(crate) fn append_object(
         ObjectSpec::Bone {
             name,
             length,
+            rotation,
             children,
         } => {
             let mut bone = Bone::new(name.clone(), parent_id);
             if let Some(length) = length {
                 bone.length = *length;
             }
+            if let Some(rotation) = rotation {
+                bone.rotation = *rotation;
+            }
             objects.push(Box::new(bone));
             name_to_index.insert(name.clone(), object_index);
             if let Some(children) = children {
@@ -1118,6 +1294,7 @@ pub(crate) fn append_object(
             x,
             y,
             length,
+            rotation,
             children,
         } => {
             let mut root_bone = RootBone::new(name.clone(), parent_id);
@@ -1130,6 +1307,9 @@ pub(crate) fn append_object(
             if let Some(length) = length {
                 root_bone.length = *length;
             }
+            if let Some(rotation) = rotation {
+                root_bone.rotation = *rotation;
+            }
             objects.push(Box::new(root_bone));
             name_to_index.insert(name.clone(), object_index);
             if let Some(children) = children {
@@ -1779,6 +1959,8 @@ pub(crate) fn append_object(
             y,
             x_id,
             y_id,
+            x_animation,
+            y_animation,
             pos_x,
             pos_y,
             width,
@@ -1787,6 +1969,7 @@ pub(crate) fn append_object(
             origin_y,
             flags,
             handle_source_id,
+            handle,
         } => {
             let mut js = Joystick::new(name.clone(), parent_id);
             if let Some(v) = x {
@@ -1795,11 +1978,29 @@ pub(crate) fn append_object(
             if let Some(v) = y {
                 js.y = *v;
             }
-            if let Some(v) = x_id {
-                js.x_id = *v;
-            }
-            if let Some(v) = y_id {
-                js.y_id = *v;
+            let animation_lookup = |animation: &str| {
+                animation_name_to_index
+                    .get(animation)
+                    .map(|index| *index as u64)
+            };
+            for (fields, animation, id, target) in [
+                (("x_animation", "x_id"), x_animation, x_id, &mut js.x_id),
+                (("y_animation", "y_id"), y_animation, y_id, &mut js.y_id),
+            ] {
+                if let Some(resolved) = references::resolve(
+                    name,
+                    &Namespace {
+                        kind: "animation",
+                        name_field: fields.0,
+                        index_field: fields.1,
+                        lookup: &animation_lookup,
+                        check: None,
+                    },
+                    animation.as_deref(),
+                    *id,
+                )? {
+                    *target = resolved;
+                }
             }
             if let Some(v) = pos_x {
                 js.pos_x = *v;
@@ -1822,8 +2023,27 @@ pub(crate) fn append_object(
             if let Some(v) = flags {
                 js.flags = *v;
             }
-            if let Some(v) = handle_source_id {
-                js.handle_source_id = *v;
+            // The handle is resolved among the objects already written, so
+            // it must come before the joystick.
+            let handle_lookup = |handle: &str| {
+                name_to_index
+                    .get(handle)
+                    .and_then(|index| index.checked_sub(artboard_start))
+                    .map(|local| local as u64)
+            };
+            if let Some(resolved) = references::resolve(
+                name,
+                &Namespace {
+                    kind: "earlier object",
+                    name_field: "handle",
+                    index_field: "handle_source_id",
+                    lookup: &handle_lookup,
+                    check: None,
+                },
+                handle.as_deref(),
+                *handle_source_id,
+            )? {
+                js.handle_source_id = resolved;
             }
             objects.push(Box::new(js));
             name_to_index.insert(name.clone(), object_index);
@@ -1990,7 +2210,8 @@ pub(crate) fn append_object(
         }
         ObjectSpec::ImageAsset { name, .. }
         | ObjectSpec::FontAsset { name, .. }
-        | ObjectSpec::AudioAsset { name, .. } => {
+        | ObjectSpec::AudioAsset { name, .. }
+        | ObjectSpec::ScriptAsset { name, .. } => {
             return Err(format!(
                 "asset '{name}' must be a direct child of an artboard; Rive stores assets at file scope, not inside the object tree"
             ));
@@ -2001,30 +2222,32 @@ pub(crate) fn append_object(
             width,
             height,
             style_id,
+            style,
             fractional_width,
             fractional_height,
             children,
         } => {
-            let mut lc = LayoutComponent::new(name.clone(), parent_id);
-            if let Some(v) = clip {
-                lc.clip = *v;
-            }
-            if let Some(v) = width {
-                lc.width = *v;
-            }
-            if let Some(v) = height {
-                lc.height = *v;
-            }
-            if let Some(v) = style_id {
-                lc.style_id = *v;
-            }
-            if let Some(v) = fractional_width {
-                lc.fractional_width = *v;
-            }
-            if let Some(v) = fractional_height {
-                lc.fractional_height = *v;
-            }
-            objects.push(Box::new(lc));
+            let layout_component = |style_id: u64| {
+                let mut lc = LayoutComponent::new(name.clone(), parent_id);
+                if let Some(v) = clip {
+                    lc.clip = *v;
+                }
+                if let Some(v) = width {
+                    lc.width = *v;
+                }
+                if let Some(v) = height {
+                    lc.height = *v;
+                }
+                lc.style_id = style_id;
+                if let Some(v) = fractional_width {
+                    lc.fractional_width = *v;
+                }
+                if let Some(v) = fractional_height {
+                    lc.fractional_height = *v;
+                }
+                lc
+            };
+            objects.push(Box::new(layout_component(0)));
             name_to_index.insert(name.clone(), object_index);
             if let Some(children) = children {
                 for child in children {
@@ -2041,6 +2264,45 @@ pub(crate) fn append_object(
                     )?;
                 }
             }
+            // The style is usually the component's own child, so a named
+            // style resolves once the children are in place.
+            let lookup = |style_name: &str| {
+                name_to_index
+                    .get(style_name)
+                    .and_then(|index| index.checked_sub(artboard_start))
+                    .map(|local| local as u64)
+            };
+            let check = |local: u64| {
+                let subject = match style.as_deref() {
+                    Some(style_name) => format!("'{style_name}'"),
+                    None => format!("artboard object {local}"),
+                };
+                match objects
+                    .get(artboard_start + local as usize)
+                    .map(|object| object.type_key())
+                {
+                    Some(type_keys::LAYOUT_COMPONENT_STYLE) => Ok(()),
+                    Some(_) => Err(format!(
+                        "references {subject}, which is not a layout_component_style"
+                    )),
+                    None => Err(format!("references {subject}, which is not defined")),
+                }
+            };
+            let resolved = references::resolve(
+                name,
+                &Namespace {
+                    kind: "layout style",
+                    name_field: "style",
+                    index_field: "style_id",
+                    lookup: &lookup,
+                    check: style.is_some().then_some(&check),
+                },
+                style.as_deref(),
+                *style_id,
+            )?;
+            if let Some(resolved) = resolved {
+                objects[object_index] = Box::new(layout_component(resolved));
+            }
         }
         ObjectSpec::LayoutComponentStyle {
             name,
@@ -2230,13 +2492,23 @@ pub(crate) fn append_object(
             property_key,
             flags,
             converter_id,
-        } => {
-            let mut db = DataBind::new(*property_key, *flags);
-            if let Some(v) = converter_id {
-                db.converter_id = *v;
+            source_path_ids,
+        } => match source_path_ids {
+            Some(source_path_ids) => {
+                let mut db = DataBindContext::new(*property_key, *flags, source_path_ids.clone());
+                if let Some(v) = converter_id {
+                    db.converter_id = *v;
+                }
+                objects.push(Box::new(db));
             }
-            objects.push(Box::new(db));
-        }
+            None => {
+                let mut db = DataBind::new(*property_key, *flags);
+                if let Some(v) = converter_id {
+                    db.converter_id = *v;
+                }
+                objects.push(Box::new(db));
+            }
+        },
         ObjectSpec::ViewModelInstance { view_model_id } => {
             objects.push(Box::new(ViewModelInstance {
                 view_model_id: required_u64_field(
@@ -2544,25 +2816,6 @@ pub(crate) fn append_object(
             objects.push(Box::new(ea));
             name_to_index.insert(name.clone(), object_index);
         }
-        ObjectSpec::ScriptAsset {
-            name,
-            asset_id,
-            cdn_base_url,
-            is_module,
-        } => {
-            let mut asset = assets::ScriptAsset::new(name.clone());
-            if let Some(v) = asset_id {
-                asset.asset_id = *v;
-            }
-            if let Some(v) = cdn_base_url {
-                asset.cdn_base_url = v.clone();
-            }
-            if let Some(v) = is_module {
-                asset.is_module = *v;
-            }
-            objects.push(Box::new(asset));
-            name_to_index.insert(name.clone(), object_index);
-        }
         ObjectSpec::BlobAsset {
             name,
             asset_id,
@@ -2686,11 +2939,25 @@ pub(crate) fn append_object(
         ObjectSpec::AudioEvent {
             name,
             asset_id,
+            asset,
             children,
         } => {
             let mut evt = state_machine::AudioEvent::new(name.clone(), parent_id);
-            if let Some(v) = asset_id {
-                evt.asset_id = *v;
+            // A bare `asset_id` stays unchecked: it may name an asset the host
+            // supplies out of band rather than one this scene declares.
+            let resolved_asset_id = match asset {
+                Some(_) => resolve_asset_ordinal(
+                    name,
+                    asset.as_deref(),
+                    *asset_id,
+                    FileAssetKind::Audio,
+                    ctx,
+                    ("asset", "asset_id"),
+                )?,
+                None => *asset_id,
+            };
+            if let Some(v) = resolved_asset_id {
+                evt.asset_id = v;
             }
             objects.push(Box::new(evt));
             name_to_index.insert(name.clone(), object_index);
@@ -3015,27 +3282,108 @@ pub(crate) fn append_object(
             }
             objects.push(Box::new(esp));
         }
-        ObjectSpec::Mesh { name, children } => {
+        ObjectSpec::Mesh {
+            name,
+            skin,
+            children,
+        } => {
             objects.push(Box::new(crate::objects::mesh::Mesh::new(
                 name.clone(),
                 parent_id,
             )));
             name_to_index.insert(name.clone(), object_index);
-            if let Some(children) = children {
-                for child in children {
-                    append_object(
-                        child,
-                        object_index,
-                        artboard_start,
-                        objects,
-                        name_to_index,
-                        artboard_name_to_index,
-                        current_artboard_name,
-                        animation_name_to_index,
-                        ctx,
-                    )?;
+            let children = children.as_deref().unwrap_or_default();
+            for child in children {
+                append_object(
+                    child,
+                    object_index,
+                    artboard_start,
+                    objects,
+                    name_to_index,
+                    artboard_name_to_index,
+                    current_artboard_name,
+                    animation_name_to_index,
+                    ctx,
+                )?;
+            }
+
+            let mut points = Vec::new();
+            let mut forced = Vec::new();
+            for child in children {
+                match child {
+                    ObjectSpec::MeshVertex { name, x, y, .. } => points.push(MeshPoint {
+                        name,
+                        x: f64::from(x.unwrap_or(0.0)),
+                        y: f64::from(y.unwrap_or(0.0)),
+                        contour: false,
+                    }),
+                    ObjectSpec::ContourMeshVertex { name, x, y, .. } => points.push(MeshPoint {
+                        name,
+                        x: f64::from(x.unwrap_or(0.0)),
+                        y: f64::from(y.unwrap_or(0.0)),
+                        contour: true,
+                    }),
+                    ObjectSpec::ForcedEdge {
+                        name: edge_name,
+                        from_vertex: Some(from_vertex),
+                        to_vertex: Some(to_vertex),
+                    } => forced.push((edge_name, from_vertex, to_vertex)),
+                    _ => {}
                 }
             }
+            let vertex_index = |edge: &str, vertex: &str| {
+                points
+                    .iter()
+                    .position(|point| point.name == vertex)
+                    .ok_or_else(|| {
+                        format!(
+                            "forced_edge '{}' vertex '{}' is not a vertex of mesh '{}'",
+                            edge, vertex, name
+                        )
+                    })
+            };
+            let forced = forced
+                .into_iter()
+                .map(|(edge, from, to)| Ok((vertex_index(edge, from)?, vertex_index(edge, to)?)))
+                .collect::<Result<Vec<_>, String>>()?;
+            let mut mesh = crate::objects::mesh::Mesh::new(name.clone(), parent_id);
+            mesh.triangle_indices = mesh::triangulate(name, &points, &forced)?;
+            objects[object_index] = Box::new(mesh);
+            if let Some(skin) = skin {
+                skin::append_skin(skin, object_index, artboard_start, objects, name_to_index)?;
+            }
+        }
+        ObjectSpec::MeshGrid {
+            name,
+            columns,
+            rows,
+            width,
+            height,
+            skin,
+        } => {
+            let mut mesh = crate::objects::mesh::Mesh::new(name.clone(), parent_id);
+            mesh.triangle_indices = mesh::grid_triangle_indices(*columns, *rows);
+            objects.push(Box::new(mesh));
+            name_to_index.insert(name.clone(), object_index);
+            let mesh_id = (object_index - artboard_start) as u64;
+            for row in 0..=*rows {
+                for column in 0..=*columns {
+                    let u = column as f32 / *columns as f32;
+                    let v = row as f32 / *rows as f32;
+                    let vertex_name = mesh::grid_vertex_name(name, row, column);
+                    let mut vertex =
+                        crate::objects::mesh::MeshVertex::new(vertex_name.clone(), mesh_id);
+                    vertex.x = (u - 0.5) * width;
+                    vertex.y = (v - 0.5) * height;
+                    vertex.u = u;
+                    vertex.v = v;
+                    name_to_index.insert(vertex_name, objects.len());
+                    objects.push(Box::new(vertex));
+                }
+            }
+            if let Some(skin) = skin {
+                skin::append_skin(skin, object_index, artboard_start, objects, name_to_index)?;
+            }
         }
         ObjectSpec::MeshVertex { name, x, y, u, v } => {
             let mut mv = crate::objects::mesh::MeshVertex::new(name.clone(), parent_id);
@@ -3308,8 +3656,10 @@ pub(crate) fn append_object(
             name,
             strength,
             direction_value,
+            direction,
             snap,
             physics_id,
+            physics,
             scroll_offset_x,
             scroll_offset_y,
             scroll_percent_x,
@@ -3322,8 +3672,19 @@ pub(crate) fn append_object(
             if let Some(v) = strength {
                 sc.strength = *v;
             }
-            if let Some(v) = direction_value {
-                sc.direction_value = *v;
+            match (direction, direction_value) {
+                (Some(_), Some(_)) => {
+                    return Err(format!(
+                        "'{}' sets both 'direction' and 'direction_value'; use one or the other",
+                        name
+                    ));
+                }
+                (Some(direction), None) => {
+                    sc.direction_value = parse_draggable_direction(direction)
+                        .map_err(|error| format!("scroll_constraint '{}': {}", name, error))?;
+                }
+                (None, Some(v)) => sc.direction_value = *v,
+                (None, None) => {}
             }
             if let Some(v) = snap {
                 sc.snap = *v;
@@ -3331,6 +3692,21 @@ pub(crate) fn append_object(
             if let Some(v) = physics_id {
                 sc.physics_id = *v;
             }
+            // Inline physics is written straight after the constraint,
+            // ahead of the constraint's children.
+            let physics = match physics {
+                Some(_) if physics_id.is_some() => {
+                    return Err(format!(
+                        "'{}' sets both 'physics' and 'physics_id'; use one or the other",
+                        name
+                    ));
+                }
+                Some(physics) => {
+                    sc.physics_id = (object_index + 1 - artboard_start) as u64;
+                    Some(scroll_physics(physics))
+                }
+                None => None,
+            };
             if let Some(v) = scroll_offset_x {
                 sc.scroll_offset_x = *v;
             }
@@ -3347,6 +3723,7 @@ pub(crate) fn append_object(
                 sc.scroll_index = *v;
             }
             objects.push(Box::new(sc));
+            objects.extend(physics);
             name_to_index.insert(name.clone(), object_index);
             if let Some(children) = children {
                 for child in children {
@@ -3368,6 +3745,7 @@ pub(crate) fn append_object(
             name,
             strength,
             scroll_constraint_id,
+            scroll,
             auto_size,
         } => {
             let mut sbc =
@@ -3375,8 +3753,39 @@ pub(crate) fn append_object(
             if let Some(v) = strength {
                 sbc.strength = *v;
             }
-            if let Some(v) = scroll_constraint_id {
-                sbc.scroll_constraint_id = *v;
+            let lookup = |scroll_name: &str| {
+                name_to_index
+                    .get(scroll_name)
+                    .and_then(|index| index.checked_sub(artboard_start))
+                    .map(|local| local as u64)
+            };
+            let check = |local: u64| match objects
+                .get(artboard_start + local as usize)
+                .map(|object| object.type_key())
+            {
+                Some(type_keys::SCROLL_CONSTRAINT) => Ok(()),
+                Some(_) => Err(format!(
+                    "references '{}', which is not a scroll_constraint",
+                    scroll.as_deref().unwrap_or_default()
+                )),
+                None => Err(format!(
+                    "references '{}', which is not defined before it",
+                    scroll.as_deref().unwrap_or_default()
+                )),
+            };
+            if let Some(resolved) = references::resolve(
+                name,
+                &Namespace {
+                    kind: "scroll constraint",
+                    name_field: "scroll",
+                    index_field: "scroll_constraint_id",
+                    lookup: &lookup,
+                    check: scroll.is_some().then_some(&check),
+                },
+                scroll.as_deref(),
+                *scroll_constraint_id,
+            )? {
+                sbc.scroll_constraint_id = resolved;
             }
             if let Some(v) = auto_size {
                 sbc.auto_size = *v;
@@ -4537,6 +4946,7 @@ pub(crate) fn append_object(
         ObjectSpec::ScriptedDrawable {
             name,
             script_asset_id,
+            script,
             generator_function_ref,
             threshold,
             is_paused,
@@ -4545,10 +4955,12 @@ pub(crate) fn append_object(
             interactive,
             children,
         } => {
+            let script_asset_id = resolve_script(name, script, *script_asset_id, ctx)?;
+            check_script_inputs(name, script_asset_id, children.as_deref(), ctx)?;
             objects.push(Box::new(scripting::ScriptedDrawable {
                 name: name.clone(),
                 parent_id,
-                script_asset_id: script_asset_id.unwrap_or(0),
+                script_asset_id,
                 generator_function_ref: generator_function_ref.unwrap_or(0),
                 threshold: threshold.unwrap_or(0.0),
                 is_paused: is_paused.unwrap_or(false),
@@ -4576,22 +4988,26 @@ pub(crate) fn append_object(
         ObjectSpec::ScriptedDataConverter {
             name,
             script_asset_id,
+            script,
         } => {
             objects.push(Box::new(scripting::ScriptedDataConverter {
                 name: name.clone(),
-                script_asset_id: script_asset_id.unwrap_or(0),
+                script_asset_id: resolve_script(name, script, *script_asset_id, ctx)?,
             }));
             name_to_index.insert(name.clone(), object_index);
         }
         ObjectSpec::ScriptedLayout {
             name,
             script_asset_id,
+            script,
             children,
         } => {
+            let script_asset_id = resolve_script(name, script, *script_asset_id, ctx)?;
+            check_script_inputs(name, script_asset_id, children.as_deref(), ctx)?;
             objects.push(Box::new(scripting::ScriptedLayout {
                 name: name.clone(),
                 parent_id,
-                script_asset_id: script_asset_id.unwrap_or(0),
+                script_asset_id,
             }));
             name_to_index.insert(name.clone(), object_index);
             if let Some(children) = children {
@@ -4625,19 +5041,31 @@ pub(crate) fn append_object(
         }
         ObjectSpec::ScriptedListenerAction {
             script_asset_id,
+            script,
             is_stateful,
         } => {
             objects.push(Box::new(scripting::ScriptedListenerAction {
-                script_asset_id: script_asset_id.unwrap_or(0),
+                script_asset_id: resolve_script(
+                    "scripted_listener_action",
+                    script,
+                    *script_asset_id,
+                    ctx,
+                )?,
                 is_stateful: is_stateful.unwrap_or(false),
             }));
         }
         ObjectSpec::ScriptedTransitionCondition {
             script_asset_id,
+            script,
             is_stateful,
         } => {
             objects.push(Box::new(scripting::ScriptedTransitionCondition {
-                script_asset_id: script_asset_id.unwrap_or(0),
+                script_asset_id: resolve_script(
+                    "scripted_transition_condition",
+                    script,
+                    *script_asset_id,
+                    ctx,
+                )?,
                 is_stateful: is_stateful.unwrap_or(false),
             }));
         }
@@ -153,6 +153,32 @@ pub(crate) fn parse_trim_mode(v: &serde_json::Value) -> Result<u64, String> {
     }
 }
 
+/// The `DraggableConstraintDirection` a scroll constraint moves along.
+pub(crate) fn parse_draggable_direction(direction: &str) -> Result<u64, String> {
+    match direction {
+        "horizontal" => Ok(0),
+        "vertical" => Ok(1),
+        "all" => Ok(2),
+        _ => Err(format!(
+            "unknown direction '{}' (expected horizontal, vertical, or all)",
+            direction
+        )),
+    }
+}
+
+/// The `NSlicerTileModeType` of a 9-slice patch.
+pub(crate) fn parse_nslicer_tile_mode(mode: &str) -> Result<u64, String> {
+    match mode {
+        "stretch" => Ok(0),
+        "repeat" => Ok(1),
+        "hidden" => Ok(2),
+        _ => Err(format!(
+            "unknown tile mode '{}' (expected stretch, repeat, or hidden)",
+            mode
+        )),
+    }
+}
+
 pub(crate) fn json_value_to_f32(value: &serde_json::Value) -> Option<f32> {
     match value {
         serde_json::Value::Number(number) => number.as_f64().map(|v| v as f32),
@@ -236,6 +262,23 @@ const TRANSFORM_ANIMATABLE_PROPERTIES: &[(&str, u16)] = &[
     ("opacity", property_keys::WORLD_TRANSFORM_OPACITY),
 ];
 
+/// A bone starts at its parent bone's tip, so it has no position of its own.
+const BONE_ANIMATABLE_PROPERTIES: &[(&str, u16)] = &[
+    ("rotation", property_keys::TRANSFORM_ROTATION),
+    ("scale_x", property_keys::TRANSFORM_SCALE_X),
+    ("scale_y", property_keys::TRANSFORM_SCALE_Y),
+    ("length", property_keys::BONE_LENGTH),
+];
+
+const ROOT_BONE_ANIMATABLE_PROPERTIES: &[(&str, u16)] = &[
+    ("x", property_keys::ROOT_BONE_X),
+    ("y", property_keys::ROOT_BONE_Y),
+    ("rotation", property_keys::TRANSFORM_ROTATION),
+    ("scale_x", property_keys::TRANSFORM_SCALE_X),
+    ("scale_y", property_keys::TRANSFORM_SCALE_Y),
+    ("length", property_keys::BONE_LENGTH),
+];
+
 const PARAMETRIC_ANIMATABLE_PROPERTIES: &[(&str, u16)] = &[
     ("width", property_keys::PARAMETRIC_PATH_WIDTH),
     ("height", property_keys::PARAMETRIC_PATH_HEIGHT),
@@ -289,6 +332,17 @@ const CUBIC_ASYMMETRIC_VERTEX_ANIMATABLE_PROPERTIES: &[(&str, u16)] = &[
     ),
 ];
 
+const STROKE_ANIMATABLE_PROPERTIES: &[(&str, u16)] = &[
+    ("is_visible", property_keys::SHAPE_PAINT_IS_VISIBLE),
+    ("thickness", property_keys::STROKE_THICKNESS),
+];
+
+/// Rectangles keep their corners linked, so the top-left radius drives all four.
+const RECTANGLE_CORNER_ANIMATABLE_PROPERTIES: &[(&str, u16)] =
+    &[("corner_radius", property_keys::RECTANGLE_CORNER_RADIUS_TL)];
+const POLYGON_CORNER_ANIMATABLE_PROPERTIES: &[(&str, u16)] =
+    &[("corner_radius", property_keys::POLYGON_CORNER_RADIUS)];
+
 const SOLID_COLOR_ANIMATABLE_PROPERTIES: &[(&str, u16)] =
     &[("color", property_keys::SOLID_COLOR_VALUE)];
 const GRADIENT_STOP_ANIMATABLE_PROPERTIES: &[(&str, u16)] =
@@ -345,6 +399,14 @@ const TEXT_MODIFIER_GROUP_ANIMATABLE_PROPERTIES: &[(&str, u16)] = &[
 const TEXT_VALUE_RUN_ANIMATABLE_PROPERTIES: &[&str] = &["text"];
 const VISIBILITY_ANIMATABLE_PROPERTIES: &[&str] = &["is_visible"];
 
+fn corner_animatable_properties(type_name: &str) -> Option<&'static [(&'static str, u16)]> {
+    match type_name {
+        "rectangle" => Some(RECTANGLE_CORNER_ANIMATABLE_PROPERTIES),
+        "polygon" | "star" => Some(POLYGON_CORNER_ANIMATABLE_PROPERTIES),
+        _ => None,
+    }
+}
+
 fn property_key_from(properties: &[(&str, u16)], name: &str) -> Option<u16> {
     properties
         .iter()
@@ -430,7 +492,8 @@ pub(crate) fn animatable_properties_for_object_type(type_name: &str) -> Vec<&'st
         }
         "text_value_run" => TEXT_VALUE_RUN_ANIMATABLE_PROPERTIES.to_vec(),
         "clipping_shape" => VISIBILITY_ANIMATABLE_PROPERTIES.to_vec(),
-        "fill" | "stroke" => VISIBILITY_ANIMATABLE_PROPERTIES.to_vec(),
+        "fill" => VISIBILITY_ANIMATABLE_PROPERTIES.to_vec(),
+        "stroke" => property_names(STROKE_ANIMATABLE_PROPERTIES),
         "solid_color" => property_names(SOLID_COLOR_ANIMATABLE_PROPERTIES),
         "linear_gradient" | "radial_gradient" => property_names(GRADIENT_ANIMATABLE_PROPERTIES),
         "gradient_stop" => {
@@ -439,11 +502,16 @@ pub(crate) fn animatable_properties_for_object_type(type_name: &str) -> Vec<&'st
             properties
         }
         "trim_path" => property_names(TRIM_ANIMATABLE_PROPERTIES),
-        "event" => property_names(EVENT_ANIMATABLE_PROPERTIES),
+        "event" | "open_url_event" | "audio_event" => property_names(EVENT_ANIMATABLE_PROPERTIES),
         "solo" => property_names(SOLO_ANIMATABLE_PROPERTIES),
+        "bone" => property_names(BONE_ANIMATABLE_PROPERTIES),
+        "root_bone" => property_names(ROOT_BONE_ANIMATABLE_PROPERTIES),
         _ if is_parametric_type(type_name) => {
             let mut properties = transform_property_names();
             extend_property_names(&mut properties, PARAMETRIC_ANIMATABLE_PROPERTIES);
+            if let Some(corner_properties) = corner_animatable_properties(type_name) {
+                extend_property_names(&mut properties, corner_properties);
+            }
             properties
         }
         "shape"
@@ -452,8 +520,8 @@ pub(crate) fn animatable_properties_for_object_type(type_name: &str) -> Vec<&'st
         | "nested_artboard"
         | "nested_artboard_leaf"
         | "nested_artboard_layout"
-        | "root_bone"
-        | "n_sliced_node" => transform_property_names(),
+        | "n_sliced_node"
+        | "nine_slice" => transform_property_names(),
         _ => vertex_animatable_properties(type_name)
             .map(property_names)
             .unwrap_or_default(),
@@ -484,7 +552,8 @@ pub(crate) fn animatable_property_key_for_object_type(
         }
         "text_value_run" => Some(property_keys::TEXT_VALUE_RUN_TEXT),
         "clipping_shape" => Some(property_keys::CLIPPING_SHAPE_IS_VISIBLE),
-        "fill" | "stroke" => Some(property_keys::SHAPE_PAINT_IS_VISIBLE),
+        "fill" => Some(property_keys::SHAPE_PAINT_IS_VISIBLE),
+        "stroke" => property_key_from(STROKE_ANIMATABLE_PROPERTIES, property_name),
         "solid_color" => property_key_from(SOLID_COLOR_ANIMATABLE_PROPERTIES, property_name),
         "linear_gradient" | "radial_gradient" => {
             property_key_from(GRADIENT_ANIMATABLE_PROPERTIES, property_name)
@@ -494,10 +563,18 @@ pub(crate) fn animatable_property_key_for_object_type(
             _ => property_key_from(GRADIENT_STOP_ANIMATABLE_PROPERTIES, property_name),
         },
         "trim_path" => property_key_from(TRIM_ANIMATABLE_PROPERTIES, property_name),
-        "event" => property_key_from(EVENT_ANIMATABLE_PROPERTIES, property_name),
+        "event" | "open_url_event" | "audio_event" => {
+            property_key_from(EVENT_ANIMATABLE_PROPERTIES, property_name)
+        }
         "solo" => property_key_from(SOLO_ANIMATABLE_PROPERTIES, property_name),
+        "bone" => property_key_from(BONE_ANIMATABLE_PROPERTIES, property_name),
+        "root_bone" => property_key_from(ROOT_BONE_ANIMATABLE_PROPERTIES, property_name),
         _ if is_parametric_type(type_name) => {
             property_key_from(PARAMETRIC_ANIMATABLE_PROPERTIES, property_name)
+                .or_else(|| {
+                    corner_animatable_properties(type_name)
+                        .and_then(|properties| property_key_from(properties, property_name))
+                })
                 .or_else(|| property_key_from(TRANSFORM_ANIMATABLE_PROPERTIES, property_name))
         }
         _ => match vertex_animatable_properties(type_name) {
@@ -522,6 +599,8 @@ pub(crate) fn object_type_name_for_key(object_type_key: u16) -> &'static str {
         type_keys::GRADIENT_STOP => "gradient_stop",
         type_keys::TRIM_PATH => "trim_path",
         type_keys::EVENT => "event",
+        type_keys::OPEN_URL_EVENT => "open_url_event",
+        type_keys::AUDIO_EVENT => "audio_event",
         type_keys::SOLO => "solo",
         type_keys::ELLIPSE => "ellipse",
         type_keys::RECTANGLE => "rectangle",
@@ -531,6 +610,8 @@ pub(crate) fn object_type_name_for_key(object_type_key: u16) -> &'static str {
         type_keys::SHAPE => "shape",
         type_keys::NODE => "node",
         type_keys::IMAGE => "image",
+        type_keys::BONE => "bone",
+        type_keys::ROOT_BONE => "root_bone",
         type_keys::STRAIGHT_VERTEX => "straight_vertex",
         type_keys::CUBIC_MIRRORED_VERTEX => "cubic_mirrored_vertex",
         type_keys::CUBIC_DETACHED_VERTEX => "cubic_detached_vertex",
@@ -0,0 +1,160 @@
+use std::path::{Component, Path, PathBuf};
+
+const PROJECT_MARKERS: [&str; 3] = ["Cargo.toml", ".git", "package.json"];
+
+/// Limits applied when asset `source` files are embedded. The project root
+/// found from the scene directory always applies; `allowed_roots`, when
+/// non-empty, must additionally contain the resolved file.
+#[derive(Debug, Clone, Default)]
+pub struct AssetPolicy {
+    pub allowed_roots: Vec<PathBuf>,
+    pub max_file_bytes: Option<u64>,
+}
+
+/// Why a path was refused by [`within_roots`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum RootViolation {
+    /// The path lies outside every root, even before symlinks are followed.
+    Outside,
+    /// The path is lexically under a root, but a symlink takes it out.
+    SymlinkEscape,
+}
+
+#[cfg(feature = "mcp")]
+impl RootViolation {
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            Self::Outside => "outside_allowed_roots",
+            Self::SymlinkEscape => "symlink_escape",
+        }
+    }
+}
+
+pub(crate) fn asset_root(base_dir: &Path) -> PathBuf {
+    let start = std::path::absolute(base_dir).unwrap_or_else(|_| base_dir.to_path_buf());
+    let mut cursor = normalise(&start);
+    loop {
+        if PROJECT_MARKERS
+            .iter()
+            .any(|marker| cursor.join(marker).exists())
+        {
+            return cursor;
+        }
+        if !cursor.pop() {
+            return normalise(&start);
+        }
+    }
+}
+
+pub(crate) fn normalise(path: &Path) -> PathBuf {
+    let mut out = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::ParentDir => {
+                out.pop();
+            }
+            Component::CurDir => {}
+            other => out.push(other),
+        }
+    }
+    out
+}
+
+/// Canonical form of a root directory, falling back to its lexical form when
+/// it does not exist yet.
+pub(crate) fn canonical_root(root: &Path) -> PathBuf {
+    root.canonicalize().unwrap_or_else(|_| normalise(root))
+}
+
+/// Canonicalises the longest existing ancestor of `path` and re-appends the
+/// missing tail, so a file that is about to be written still resolves through
+/// every symlink on its way. A dangling symlink counts as existing, so it
+/// fails to canonicalise instead of being re-appended and followed by the
+/// eventual write.
+pub(crate) fn canonicalise_existing(path: &Path) -> Option<PathBuf> {
+    let mut existing = path;
+    let mut missing = Vec::new();
+    while std::fs::symlink_metadata(existing).is_err() {
+        missing.push(existing.file_name()?);
+        existing = existing.parent()?;
+    }
+    let mut resolved = existing.canonicalize().ok()?;
+    for name in missing.into_iter().rev() {
+        resolved.push(name);
+    }
+    Some(resolved)
+}
+
+/// Resolves `path` and requires it to sit under one of the canonical `roots`.
+pub(crate) fn within_roots(path: &Path, roots: &[PathBuf]) -> Result<PathBuf, RootViolation> {
+    let absolute = normalise(&std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf()));
+    let lexically_inside = roots.iter().any(|root| absolute.starts_with(root));
+    let violation = if lexically_inside {
+        RootViolation::SymlinkEscape
+    } else {
+        RootViolation::Outside
+    };
+    let resolved = canonicalise_existing(&absolute).ok_or(violation)?;
+    if roots.iter().any(|root| resolved.starts_with(root)) {
+        Ok(resolved)
+    } else {
+        Err(violation)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scratch(name: &str) -> PathBuf {
+        let path =
+            std::env::temp_dir().join(format!("rive_builder_paths_{name}_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&path);
+        std::fs::create_dir_all(path.join("inside")).expect("scratch dirs");
+        path.canonicalize().expect("canonical scratch")
+    }
+
+    #[test]
+    fn paths_resolve_under_their_root_even_before_they_exist() {
+        let root = scratch("within");
+        let roots = [root.join("inside")];
+        assert_eq!(
+            within_roots(&root.join("inside/new/out.riv"), &roots),
+            Ok(root.join("inside/new/out.riv"))
+        );
+        assert_eq!(
+            within_roots(&root.join("inside/../other.riv"), &roots),
+            Err(RootViolation::Outside)
+        );
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlinks_leaving_the_root_are_reported_as_escapes() {
+        let root = scratch("escape");
+        let outside = scratch("escape_outside");
+        std::os::unix::fs::symlink(&outside, root.join("inside/link")).expect("symlink");
+        assert_eq!(
+            within_roots(&root.join("inside/link/secret.riv"), &[root.join("inside")]),
+            Err(RootViolation::SymlinkEscape)
+        );
+        let _ = std::fs::remove_dir_all(&root);
+        let _ = std::fs::remove_dir_all(&outside);
+    }
+    #[cfg(unix)]
+    #[test]
+    fn dangling_symlinks_are_refused_rather_than_treated_as_missing() {
+        let root = scratch("dangling");
+        let outside = scratch("dangling_outside");
+        std::os::unix::fs::symlink(outside.join("new.riv"), root.join("inside/link"))
+            .expect("symlink");
+        assert_eq!(
+            within_roots(&root.join("inside/link"), &[root.join("inside")]),
+            Err(RootViolation::SymlinkEscape)
+        );
+        assert!(!outside.join("new.riv").exists());
+        let _ = std::fs::remove_dir_all(&root);
+        let _ = std::fs::remove_dir_all(&outside);
+    }
+}
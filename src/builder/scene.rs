@@ -7,9 +7,13 @@ use crate::objects::artboard::{Artboard, Backboard};
 use crate::objects::core::RiveObject;
 
 use super::animations::{build_animations, register_interpolators};
+use super::assets::{ExternalAsset, asset_contents, external_asset, load_library, sha256_hex};
+use super::luau::{ScriptInput, declared_inputs};
 use super::objects::{
-    FileAssetKind, SceneContext, append_file_asset, append_object, file_asset, is_file_asset,
+    FileAssetKind, NestedPlayback, SceneContext, append_file_asset, append_object, file_asset,
+    is_file_asset,
 };
+use super::paths::AssetPolicy;
 use super::spec::{InterpolatorDef, SceneSpec};
 use super::state_machines::build_state_machines;
 use super::validation::validate_scene_spec;
@@ -153,6 +157,39 @@ pub(crate) fn resolve_artboards(spec: &SceneSpec) -> Result<Vec<&ArtboardSpec>,
 pub fn build_scene(
     spec: &SceneSpec,
     base_dir: Option<&Path>,
+) -> Result<Vec<Box<dyn RiveObject>>, String> {
+    build_scene_with_policy(spec, base_dir, &AssetPolicy::default())
+}
+
+/// Like [`build_scene`], but asset `source` files must also satisfy `policy`.
+pub fn build_scene_with_policy(
+    spec: &SceneSpec,
+    base_dir: Option<&Path>,
+    policy: &AssetPolicy,
+) -> Result<Vec<Box<dyn RiveObject>>, String> {
+    build(spec, base_dir, policy, None)
+}
+
+type SceneObjects = Vec<Box<dyn RiveObject>>;
+
+/// Like [`build_scene_with_policy`], but the bytes of sourced assets are
+/// returned beside the objects instead of embedded as `FileAssetContents`,
+/// so a host can load them out of band.
+pub fn build_scene_with_external_assets(
+    spec: &SceneSpec,
+    base_dir: Option<&Path>,
+    policy: &AssetPolicy,
+) -> Result<(SceneObjects, Vec<ExternalAsset>), String> {
+    let mut external = Vec::new();
+    let objects = build(spec, base_dir, policy, Some(&mut external))?;
+    Ok((objects, external))
+}
+
+fn build(
+    spec: &SceneSpec,
+    base_dir: Option<&Path>,
+    policy: &AssetPolicy,
+    mut external: Option<&mut Vec<ExternalAsset>>,
 ) -> Result<Vec<Box<dyn RiveObject>>, String> {
     let indexes = validate_scene_spec(spec)?;
 
@@ -165,9 +202,18 @@ pub fn build_scene(
 
     objects.push(Box::new(Backboard));
 
+    let library = spec
+        .asset_library
+        .as_deref()
+        .map(|path| load_library(path, base_dir, policy))
+        .transpose()?;
     let mut asset_ids: HashMap<String, (u64, FileAssetKind)> = HashMap::new();
     let mut asset_kinds: Vec<FileAssetKind> = Vec::new();
-    let mut next_asset_ordinal: u64 = 0;
+    // Embedded assets of one kind with identical bytes share the first
+    // declaration's ordinal, so the file carries each payload once.
+    let mut embedded: HashMap<(FileAssetKind, String), u64> = HashMap::new();
+    let mut script_inputs: HashMap<u64, Vec<ScriptInput>> = HashMap::new();
+    let mut image_sizes: HashMap<u64, (u32, u32)> = HashMap::new();
     for (artboard_spec, index) in artboard_specs.iter().zip(&indexes) {
         for (asset_name, asset_kind) in &index.assets {
             if asset_ids.contains_key(asset_name) {
@@ -175,20 +221,86 @@ pub fn build_scene(
                     "asset '{asset_name}' is declared more than once; Rive stores assets at file scope, so their names must be unique across every artboard"
                 ));
             }
-            asset_ids.insert(asset_name.clone(), (next_asset_ordinal, *asset_kind));
+            let child = artboard_spec
+                .children
+                .iter()
+                .find(|child| file_asset(child).is_some_and(|(name, _)| name == asset_name));
+            let contents = match child {
+                Some(child) => asset_contents(child, base_dir, library.as_ref(), policy)?,
+                None => None,
+            };
+            let digest = contents
+                .as_deref()
+                .map(|bytes| (*asset_kind, sha256_hex(bytes)));
+            if let Some(&ordinal) = digest.as_ref().and_then(|digest| embedded.get(digest)) {
+                asset_ids.insert(asset_name.clone(), (ordinal, *asset_kind));
+                continue;
+            }
+            let ordinal = asset_kinds.len() as u64;
+            if let Some(digest) = digest {
+                embedded.insert(digest, ordinal);
+            }
+            asset_ids.insert(asset_name.clone(), (ordinal, *asset_kind));
             asset_kinds.push(*asset_kind);
-            next_asset_ordinal += 1;
-        }
-        for child in &artboard_spec.children {
-            if file_asset(child).is_some() {
-                append_file_asset(child, &mut objects, base_dir)?;
+            let Some(child) = child else {
+                continue;
+            };
+            if let Some(source) = contents
+                .as_deref()
+                .filter(|_| *asset_kind == FileAssetKind::Script)
+                .and_then(|bytes| std::str::from_utf8(bytes).ok())
+            {
+                script_inputs.insert(ordinal, declared_inputs(source)?);
+            }
+            if let Some(Ok(info)) = contents
+                .as_deref()
+                .filter(|_| *asset_kind == FileAssetKind::Image)
+                .map(crate::validator::sniff_image)
+            {
+                image_sizes.insert(ordinal, (info.width, info.height));
+            }
+            match (contents, external.as_deref_mut()) {
+                (Some(bytes), Some(external)) => {
+                    let asset = external_asset(child, *asset_kind, bytes)?;
+                    if let Some(clash) = external
+                        .iter()
+                        .find(|other| other.entry.file == asset.entry.file)
+                    {
+                        return Err(format!(
+                            "assets '{}' and '{}' would both be written as external file '{}'; give one a different asset_id",
+                            clash.entry.name, asset.entry.name, asset.entry.file
+                        ));
+                    }
+                    external.push(asset);
+                    append_file_asset(child, None, &mut objects);
+                }
+                (contents, _) => append_file_asset(child, contents, &mut objects),
             }
         }
     }
+    let nested_playback = artboard_specs
+        .iter()
+        .map(|artboard_spec| {
+            let mut playback = NestedPlayback::default();
+            for (index, animation) in artboard_spec.animations.iter().flatten().enumerate() {
+                playback.animations.insert(animation.name.clone(), index);
+            }
+            for (index, state_machine) in artboard_spec.state_machines.iter().flatten().enumerate()
+            {
+                playback
+                    .state_machines
+                    .insert(state_machine.name.clone(), index);
+            }
+            (artboard_spec.name.clone(), playback)
+        })
+        .collect::<HashMap<_, _>>();
     for artboard_spec in &artboard_specs {
         let ctx = SceneContext {
             asset_ids: &asset_ids,
             asset_kinds: &asset_kinds,
+            nested_playback: &nested_playback,
+            script_inputs: &script_inputs,
+            image_sizes: &image_sizes,
         };
         let artboard_start = objects.len();
         let (artboard_width, artboard_height) = resolve_artboard_dimensions(artboard_spec)?;
@@ -322,6 +434,7 @@ mod tests {
     fn test_build_scene_zero_dimensions() {
         let spec = SceneSpec {
             scene_format_version: 1,
+            asset_library: None,
             artboard: Some(ArtboardSpec {
                 name: "Main".to_string(),
                 preset: None,
@@ -349,6 +462,7 @@ mod tests {
     fn test_build_scene_empty_children() {
         let spec = SceneSpec {
             scene_format_version: 1,
+            asset_library: None,
             artboard: Some(ArtboardSpec {
                 name: "Main".to_string(),
                 preset: None,
@@ -375,6 +489,7 @@ mod tests {
     fn test_build_scene_duplicate_names() {
         let spec = SceneSpec {
             scene_format_version: 1,
+            asset_library: None,
             artboard: None,
             artboards: Some(vec![
                 ArtboardSpec {
@@ -436,6 +551,7 @@ mod tests {
     fn test_reject_unsupported_scene_format_version() {
         let spec = SceneSpec {
             scene_format_version: 2,
+            asset_library: None,
             artboard: Some(ArtboardSpec {
                 name: "Main".to_string(),
                 preset: None,
@@ -511,6 +627,7 @@ mod tests {
     fn test_build_minimal_scene() {
         let spec = SceneSpec {
             scene_format_version: 1,
+            asset_library: None,
             artboard: Some(ArtboardSpec {
                 name: "Main".to_string(),
                 preset: None,
@@ -544,6 +661,7 @@ mod tests {
     fn test_build_scene_with_shape() {
         let spec = SceneSpec {
             scene_format_version: 1,
+            asset_library: None,
             artboard: Some(ArtboardSpec {
                 name: "Main".to_string(),
                 preset: None,
@@ -633,6 +751,7 @@ mod tests {
     fn test_build_scene_with_animation() {
         let spec = SceneSpec {
             scene_format_version: 1,
+            asset_library: None,
             artboard: Some(ArtboardSpec {
                 name: "Main".to_string(),
                 preset: None,
@@ -721,6 +840,7 @@ mod tests {
     fn test_build_scene_with_solo_and_key_frame_id() {
         let spec = SceneSpec {
             scene_format_version: 1,
+            asset_library: None,
             artboard: Some(ArtboardSpec {
                 name: "Main".to_string(),
                 preset: None,
@@ -805,6 +925,7 @@ mod tests {
     fn test_build_scene_with_state_machine_listener() {
         let spec = SceneSpec {
             scene_format_version: 1,
+            asset_library: None,
             artboard: Some(ArtboardSpec {
                 name: "Main".to_string(),
                 preset: None,
@@ -874,10 +995,702 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_nested_playback_resolves_against_the_source_artboard() {
+        let spec: SceneSpec = serde_json::from_str(
+            r#"{
+                "scene_format_version": 1,
+                "artboards": [
+                    {
+                        "name": "Host",
+                        "width": 200,
+                        "height": 200,
+                        "children": [{
+                            "type": "nested_artboard",
+                            "name": "widget",
+                            "source_artboard": "Widget",
+                            "children": [
+                                { "type": "nested_simple_animation", "name": "spin", "animation": "spin" },
+                                { "type": "nested_state_machine", "name": "logic", "animation": "logic" }
+                            ]
+                        }],
+                        "animations": [{ "name": "host_idle", "fps": 60, "duration": 10, "keyframes": [] }]
+                    },
+                    {
+                        "name": "Widget",
+                        "width": 100,
+                        "height": 100,
+                        "children": [],
+                        "animations": [
+                            { "name": "idle", "fps": 60, "duration": 10, "keyframes": [] },
+                            { "name": "spin", "fps": 60, "duration": 10, "keyframes": [] }
+                        ],
+                        "state_machines": [
+                            { "name": "unused", "layers": [] },
+                            { "name": "logic", "layers": [] }
+                        ]
+                    }
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        let objects = build_scene(&spec, None).unwrap();
+        let animation_id = |type_key: u16| {
+            objects
+                .iter()
+                .find(|object| object.type_key() == type_key)
+                .unwrap()
+                .properties()
+                .into_iter()
+                .find(|property| property.key == property_keys::NESTED_ANIMATION_ID)
+                .unwrap()
+                .value
+        };
+        assert_eq!(
+            animation_id(type_keys::NESTED_SIMPLE_ANIMATION),
+            PropertyValue::UInt(1)
+        );
+        assert_eq!(
+            animation_id(type_keys::NESTED_STATE_MACHINE),
+            PropertyValue::UInt(1)
+        );
+
+        let host_only = spec_json_with_nested_animation("host_idle");
+        let error = match build_scene(&host_only, None) {
+            Ok(_) => panic!("host animations must not satisfy nested playback"),
+            Err(error) => error,
+        };
+        assert!(
+            error.contains(
+                "names animation 'host_idle', which source artboard 'Widget' does not define"
+            ),
+            "{error}"
+        );
+    }
+
+    fn spec_json_with_nested_animation(animation: &str) -> SceneSpec {
+        serde_json::from_value(serde_json::json!({
+            "scene_format_version": 1,
+            "artboards": [
+                {
+                    "name": "Host",
+                    "width": 200,
+                    "height": 200,
+                    "children": [{
+                        "type": "nested_artboard",
+                        "name": "widget",
+                        "source_artboard": "Widget",
+                        "children": [
+                            { "type": "nested_simple_animation", "name": "play", "animation": animation }
+                        ]
+                    }],
+                    "animations": [{ "name": "host_idle", "fps": 60, "duration": 10, "keyframes": [] }]
+                },
+                { "name": "Widget", "width": 100, "height": 100, "children": [] }
+            ]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_layout_component_resolves_a_named_child_style() {
+        let layout = |style: &str| -> SceneSpec {
+            serde_json::from_value(serde_json::json!({
+                "scene_format_version": 1,
+                "artboard": {
+                    "name": "Main",
+                    "width": 200,
+                    "height": 200,
+                    "children": [{
+                        "type": "layout_component",
+                        "name": "Row",
+                        "style": style,
+                        "children": [
+                            { "type": "layout_component_style", "name": "RowStyle", "flex_direction": 2 },
+                            { "type": "layout_component", "name": "Cell" }
+                        ]
+                    }]
+                }
+            }))
+            .unwrap()
+        };
+
+        let objects = build_scene(&layout("RowStyle"), None).unwrap();
+        let artboard = objects
+            .iter()
+            .position(|object| object.type_key() == type_keys::ARTBOARD)
+            .unwrap();
+        let style_index = objects
+            .iter()
+            .position(|object| object.type_key() == type_keys::LAYOUT_COMPONENT_STYLE)
+            .unwrap();
+        let row = objects
+            .iter()
+            .find(|object| object.type_key() == type_keys::LAYOUT_COMPONENT)
+            .unwrap()
+            .properties();
+        assert!(row.iter().any(|property| {
+            property.key == property_keys::LAYOUT_COMPONENT_STYLE_ID
+                && property.value == PropertyValue::UInt((style_index - artboard) as u64)
+        }));
+
+        let error = match build_scene(&layout("Cell"), None) {
+            Ok(_) => panic!("a layout style must be a layout_component_style"),
+            Err(error) => error,
+        };
+        assert!(error.contains("not a layout_component_style"), "{error}");
+    }
+
+    fn event_scene(event: &str) -> SceneSpec {
+        serde_json::from_value(serde_json::json!({
+            "scene_format_version": 1,
+            "artboard": {
+                "name": "Main",
+                "width": 200,
+                "height": 200,
+                "children": [
+                    { "type": "image_asset", "name": "Cover" },
+                    { "type": "audio_asset", "name": "Click" },
+                    { "type": "shape", "name": "Button" },
+                    { "type": "event", "name": "Tapped" },
+                    { "type": "audio_event", "name": "Chime", "asset": "Click" }
+                ],
+                "animations": [{ "name": "idle", "fps": 60, "duration": 10, "keyframes": [] }],
+                "state_machines": [{
+                    "name": "SM",
+                    "listeners": [{
+                        "target": "Button",
+                        "listener_type": "down",
+                        "actions": [{ "type": "fire_event", "event": event }]
+                    }],
+                    "layers": [{
+                        "states": [{ "type": "entry" }, { "type": "exit" }, { "type": "animation", "animation": "idle" }],
+                        "transitions": [{
+                            "from": 0,
+                            "to": 2,
+                            "events": [{ "event": "Chime", "occurs_value": 1 }]
+                        }]
+                    }]
+                }]
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_fire_events_resolve_event_names_to_local_ids() {
+        let objects = build_scene(&event_scene("Tapped"), None).unwrap();
+        let local_id = |type_key: u16| {
+            let artboard = objects
+                .iter()
+                .position(|object| object.type_key() == type_keys::ARTBOARD)
+                .unwrap();
+            let index = objects
+                .iter()
+                .position(|object| object.type_key() == type_key)
+                .unwrap();
+            PropertyValue::UInt((index - artboard) as u64)
+        };
+        let property = |type_key: u16, key: u16| {
+            objects
+                .iter()
+                .find(|object| object.type_key() == type_key)
+                .unwrap()
+                .properties()
+                .into_iter()
+                .find(|property| property.key == key)
+                .map(|property| property.value)
+        };
+
+        assert_eq!(
+            property(
+                type_keys::LISTENER_FIRE_EVENT,
+                property_keys::LISTENER_FIRE_EVENT_EVENT_ID
+            ),
+            Some(local_id(type_keys::EVENT))
+        );
+        assert_eq!(
+            property(
+                type_keys::STATE_MACHINE_FIRE_EVENT,
+                property_keys::STATE_MACHINE_FIRE_EVENT_EVENT_ID
+            ),
+            Some(local_id(type_keys::AUDIO_EVENT))
+        );
+        assert_eq!(
+            property(
+                type_keys::STATE_MACHINE_FIRE_EVENT,
+                property_keys::STATE_MACHINE_FIRE_EVENT_OCCURS_VALUE
+            ),
+            Some(PropertyValue::UInt(1))
+        );
+        // The fire event follows its transition so the importer attaches it there.
+        let transition = objects
+            .iter()
+            .position(|object| object.type_key() == type_keys::STATE_TRANSITION)
+            .unwrap();
+        assert_eq!(
+            objects[transition + 1].type_key(),
+            type_keys::STATE_MACHINE_FIRE_EVENT
+        );
+        // Audio events reference their asset by name like images do.
+        assert_eq!(
+            property(type_keys::AUDIO_EVENT, property_keys::AUDIO_EVENT_ASSET_ID),
+            Some(PropertyValue::UInt(1))
+        );
+
+        let error = match build_scene(&event_scene("Button"), None) {
+            Ok(_) => panic!("a fire_event must reference an event"),
+            Err(error) => error,
+        };
+        assert!(error.contains("it must be an event"), "{error}");
+        let error = match build_scene(&event_scene("Missing"), None) {
+            Ok(_) => panic!("a fire_event must reference a declared event"),
+            Err(error) => error,
+        };
+        assert!(
+            error.contains("event 'Missing', which is not declared"),
+            "{error}"
+        );
+    }
+
+    fn mesh_scene(mesh: serde_json::Value) -> SceneSpec {
+        serde_json::from_value(serde_json::json!({
+            "scene_format_version": 1,
+            "artboard": {
+                "name": "Main",
+                "width": 200,
+                "height": 200,
+                "children": [
+                    { "type": "image_asset", "name": "Photo", "asset_id": 0 },
+                    { "type": "image", "name": "Warped", "asset_id": 0, "children": [mesh] }
+                ]
+            }
+        }))
+        .unwrap()
+    }
+
+    fn mesh_triangles(objects: &[Box<dyn RiveObject>]) -> Vec<[u64; 3]> {
+        let mesh = objects
+            .iter()
+            .find(|object| object.type_key() == type_keys::MESH)
+            .unwrap()
+            .properties();
+        let Some(PropertyValue::Bytes(bytes)) = mesh
+            .iter()
+            .find(|property| property.key == property_keys::MESH_TRIANGLE_INDEX_BYTES)
+            .map(|property| property.value.clone())
+        else {
+            panic!("mesh must emit its triangle index bytes");
+        };
+        let mut reader = crate::validator::BinaryReader::new(&bytes);
+        let mut indices = Vec::new();
+        while let Some(index) = reader.read_varuint() {
+            indices.push(index);
+        }
+        assert_eq!(indices.len() % 3, 0);
+        indices
+            .chunks(3)
+            .map(|triangle| [triangle[0], triangle[1], triangle[2]])
+            .collect()
+    }
+
+    #[test]
+    fn test_mesh_triangulates_inside_its_contour_and_keeps_forced_edges() {
+        let vertex = |kind: &str, name: &str, x: f32, y: f32| {
+            serde_json::json!({
+                "type": kind, "name": name, "x": x, "y": y, "u": x / 200.0, "v": y / 200.0
+            })
+        };
+        // An L-shaped contour whose notch the convex hull would fill.
+        let mesh = |forced: serde_json::Value| {
+            serde_json::json!({
+                "type": "mesh",
+                "name": "Grid",
+                "children": [
+                    vertex("contour_mesh_vertex", "A", 0.0, 0.0),
+                    vertex("contour_mesh_vertex", "B", 200.0, 0.0),
+                    vertex("contour_mesh_vertex", "C", 200.0, 100.0),
+                    vertex("contour_mesh_vertex", "D", 100.0, 100.0),
+                    vertex("contour_mesh_vertex", "E", 100.0, 200.0),
+                    vertex("contour_mesh_vertex", "F", 0.0, 200.0),
+                    vertex("mesh_vertex", "Mid", 50.0, 50.0),
+                    forced
+                ]
+            })
+        };
+
+        let objects = build_scene(
+            &mesh_scene(mesh(serde_json::json!({
+                "type": "forced_edge", "name": "Cut", "from_vertex": "A", "to_vertex": "D"
+            }))),
+            None,
+        )
+        .unwrap();
+        let triangles = mesh_triangles(&objects);
+        // Seven vertices on a six-sided contour with one interior vertex.
+        assert_eq!(triangles.len(), 6);
+        let has_edge = |a: u64, b: u64| {
+            triangles
+                .iter()
+                .any(|triangle| triangle.contains(&a) && triangle.contains(&b))
+        };
+        // The cut runs through Mid, so it is kept as two edges.
+        assert!(has_edge(0, 6) && has_edge(6, 3));
+        assert!(!triangles.iter().any(|triangle| {
+            triangle.contains(&2) && triangle.contains(&3) && triangle.contains(&4)
+        }));
+
+        let crossing = mesh(serde_json::json!({
+            "type": "forced_edge", "name": "Cut", "from_vertex": "B", "to_vertex": "E"
+        }));
+        let error = match build_scene(&mesh_scene(crossing), None) {
+            Ok(_) => panic!("a forced edge may not cross the contour"),
+            Err(error) => error,
+        };
+        assert!(
+            error.contains("crosses another forced or contour edge"),
+            "{error}"
+        );
+
+        let mut outside = mesh(serde_json::json!({ "type": "forced_edge", "name": "Cut" }));
+        outside["children"][6]["x"] = serde_json::json!(150.0);
+        outside["children"][6]["y"] = serde_json::json!(150.0);
+        let error = match build_scene(&mesh_scene(outside), None) {
+            Ok(_) => panic!("every vertex must lie inside the contour"),
+            Err(error) => error,
+        };
+        assert!(
+            error.contains("'Mid' lies outside the mesh contour"),
+            "{error}"
+        );
+
+        let mut stretched = mesh(serde_json::json!({ "type": "forced_edge", "name": "Cut" }));
+        stretched["children"][1]["u"] = serde_json::json!(1.5);
+        let error = match build_scene(&mesh_scene(stretched), None) {
+            Ok(_) => panic!("uv coordinates must lie in [0, 1]"),
+            Err(error) => error,
+        };
+        assert!(error.contains("'B' u must be between 0 and 1"), "{error}");
+    }
+
+    #[test]
+    fn test_mesh_grid_generates_named_vertices_and_two_triangles_per_cell() {
+        let grid = |columns: u32| {
+            mesh_scene(serde_json::json!({
+                "type": "mesh_grid",
+                "name": "Warp",
+                "columns": columns,
+                "rows": 2,
+                "width": 300,
+                "height": 200
+            }))
+        };
+
+        let objects = build_scene(&grid(3), None).unwrap();
+        let mesh = objects
+            .iter()
+            .position(|object| object.type_key() == type_keys::MESH)
+            .unwrap();
+        let vertices = objects
+            .iter()
+            .filter(|object| object.type_key() == type_keys::MESH_VERTEX)
+            .collect::<Vec<_>>();
+        assert_eq!(vertices.len(), 12);
+        let corner = vertices[11].properties();
+        assert_eq!(
+            corner[0].value,
+            PropertyValue::String("Warp_2_3".to_string())
+        );
+        let artboard = objects
+            .iter()
+            .position(|object| object.type_key() == type_keys::ARTBOARD)
+            .unwrap();
+        assert_eq!(
+            corner[1].value,
+            PropertyValue::UInt((mesh - artboard) as u64)
+        );
+        let value = |key: u16| {
+            corner
+                .iter()
+                .find(|property| property.key == key)
+                .map(|property| property.value.clone())
+        };
+        assert_eq!(
+            value(property_keys::VERTEX_X),
+            Some(PropertyValue::Float(150.0))
+        );
+        assert_eq!(
+            value(property_keys::VERTEX_Y),
+            Some(PropertyValue::Float(100.0))
+        );
+        assert_eq!(
+            value(property_keys::MESH_VERTEX_U),
+            Some(PropertyValue::Float(1.0))
+        );
+
+        let triangles = mesh_triangles(&objects);
+        assert_eq!(triangles.len(), 12);
+        assert_eq!(triangles[0], [0, 1, 5]);
+        assert_eq!(triangles[1], [0, 5, 4]);
+
+        let error = match build_scene(&grid(0), None) {
+            Ok(_) => panic!("a grid needs at least one cell"),
+            Err(error) => error,
+        };
+        assert!(error.contains("at least one column and one row"), "{error}");
+    }
+
+    fn skinned_scene(skin: serde_json::Value) -> SceneSpec {
+        serde_json::from_value(serde_json::json!({
+            "scene_format_version": 1,
+            "artboard": {
+                "name": "Main",
+                "width": 400,
+                "height": 400,
+                "children": [
+                    {
+                        "type": "root_bone",
+                        "name": "Upper",
+                        "x": 100,
+                        "y": 50,
+                        "length": 100,
+                        "children": [{ "type": "bone", "name": "Lower", "length": 100 }]
+                    },
+                    {
+                        "type": "shape",
+                        "name": "Arm",
+                        "x": 100,
+                        "y": 50,
+                        "children": [{
+                            "type": "points_path",
+                            "name": "Outline",
+                            "skin": skin,
+                            "children": [
+                                { "type": "straight_vertex", "name": "Shoulder", "x": 50, "y": 0 },
+                                { "type": "straight_vertex", "name": "Elbow", "x": 100, "y": 0 },
+                                { "type": "straight_vertex", "name": "Wrist", "x": 190, "y": 10 }
+                            ]
+                        }]
+                    }
+                ]
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_skin_binds_bones_and_packs_the_strongest_influences() {
+        let objects = build_scene(
+            &skinned_scene(serde_json::json!({ "bones": ["Upper", "Lower"] })),
+            None,
+        )
+        .unwrap();
+        let artboard = objects
+            .iter()
+            .position(|object| object.type_key() == type_keys::ARTBOARD)
+            .unwrap();
+        let local = |name: &str| {
+            let index = objects
+                .iter()
+                .position(|object| {
+                    object.properties().first().is_some_and(|property| {
+                        property.value == PropertyValue::String(name.to_string())
+                    })
+                })
+                .unwrap_or_else(|| panic!("missing {name}"));
+            (index - artboard) as u64
+        };
+        let value = |name: &str, key: u16| {
+            objects[artboard + local(name) as usize]
+                .properties()
+                .into_iter()
+                .find(|property| property.key == key)
+                .map(|property| property.value)
+        };
+
+        assert_eq!(
+            value("Outline_skin", property_keys::COMPONENT_PARENT_ID),
+            Some(PropertyValue::UInt(local("Outline")))
+        );
+        assert_eq!(
+            value("Outline_skin", property_keys::SKIN_TX),
+            Some(PropertyValue::Float(100.0))
+        );
+        // Lower sits at the tip of Upper, so its bind transform adds both.
+        assert_eq!(
+            value("Outline_tendon_Lower", property_keys::TENDON_BONE_ID),
+            Some(PropertyValue::UInt(local("Lower")))
+        );
+        assert_eq!(
+            value("Outline_tendon_Lower", property_keys::TENDON_TX),
+            Some(PropertyValue::Float(200.0))
+        );
+        assert_eq!(
+            value("Outline_tendon_Lower", property_keys::TENDON_TY),
+            Some(PropertyValue::Float(50.0))
+        );
+
+        // At rest the runtime's bone transform, bone world times the inverse
+        // of the tendon's bind, is the identity: every vertex stays put.
+        let matrix = |name: &str, keys: [u16; 6]| {
+            let mut matrix = [1.0, 0.0, 0.0, 1.0, 0.0, 0.0];
+            for (slot, key) in keys.into_iter().enumerate() {
+                if let Some(PropertyValue::Float(stored)) = value(name, key) {
+                    matrix[slot] = f64::from(stored);
+                }
+            }
+            matrix
+        };
+        let apply = |[m0, m1, m2, m3, m4, m5]: [f64; 6], [x, y]: [f64; 2]| {
+            [m0 * x + m2 * y + m4, m1 * x + m3 * y + m5]
+        };
+        let invert = |[m0, m1, m2, m3, m4, m5]: [f64; 6]| {
+            let det = m0 * m3 - m1 * m2;
+            [
+                m3 / det,
+                -m1 / det,
+                -m2 / det,
+                m0 / det,
+                (m2 * m5 - m3 * m4) / det,
+                (m1 * m4 - m0 * m5) / det,
+            ]
+        };
+        let tendon_keys = [
+            property_keys::TENDON_XX,
+            property_keys::TENDON_YX,
+            property_keys::TENDON_XY,
+            property_keys::TENDON_YY,
+            property_keys::TENDON_TX,
+            property_keys::TENDON_TY,
+        ];
+        let bones = [
+            (
+                [1.0, 0.0, 0.0, 1.0, 100.0, 50.0],
+                invert(matrix("Outline_tendon_Upper", tendon_keys)),
+            ),
+            (
+                [1.0, 0.0, 0.0, 1.0, 200.0, 50.0],
+                invert(matrix("Outline_tendon_Lower", tendon_keys)),
+            ),
+        ];
+        let skin = matrix(
+            "Outline_skin",
+            [
+                property_keys::SKIN_XX,
+                property_keys::SKIN_YX,
+                property_keys::SKIN_XY,
+                property_keys::SKIN_YY,
+                property_keys::SKIN_TX,
+                property_keys::SKIN_TY,
+            ],
+        );
+        for (vertex, local_position) in [
+            ("Shoulder", [50.0, 0.0]),
+            ("Elbow", [100.0, 0.0]),
+            ("Wrist", [190.0, 10.0]),
+        ] {
+            let weight = format!("{vertex}_weight");
+            let packed = |key, default| match value(&weight, key) {
+                Some(PropertyValue::UInt(packed)) => packed,
+                _ => default,
+            };
+            let (values, indices) = (
+                packed(property_keys::WEIGHT_VALUES, 255),
+                packed(property_keys::WEIGHT_INDICES, 1),
+            );
+            let bind = apply(skin, local_position);
+            let mut deformed = [0.0, 0.0];
+            for slot in 0..4 {
+                let influence = ((values >> (slot * 8)) & 0xff) as f64 / 255.0;
+                let tendon = ((indices >> (slot * 8)) & 0xff) as usize;
+                if influence == 0.0 || tendon == 0 {
+                    continue;
+                }
+                let (world, inverse_bind) = bones[tendon - 1];
+                let moved = apply(world, apply(inverse_bind, bind));
+                deformed[0] += moved[0] * influence;
+                deformed[1] += moved[1] * influence;
+            }
+            assert!(
+                (deformed[0] - bind[0]).abs() < 1e-3 && (deformed[1] - bind[1]).abs() < 1e-3,
+                "{vertex} moved from {bind:?} to {deformed:?} at rest"
+            );
+        }
+
+        // On Upper only: the runtime defaults already say so.
+        assert_eq!(value("Shoulder_weight", property_keys::WEIGHT_VALUES), None);
+        assert_eq!(
+            value("Shoulder_weight", property_keys::COMPONENT_PARENT_ID),
+            Some(PropertyValue::UInt(local("Shoulder")))
+        );
+        // Exactly on the joint: split evenly, quantised to sum to 255.
+        assert_eq!(
+            value("Elbow_weight", property_keys::WEIGHT_VALUES),
+            Some(PropertyValue::UInt(128 | 127 << 8))
+        );
+        assert_eq!(
+            value("Elbow_weight", property_keys::WEIGHT_INDICES),
+            Some(PropertyValue::UInt(1 | 2 << 8))
+        );
+
+        let single = build_scene(
+            &skinned_scene(serde_json::json!({
+                "bones": ["Upper", "Lower"], "falloff": "heat", "max_influences": 1
+            })),
+            None,
+        )
+        .unwrap();
+        let wrist = single
+            .iter()
+            .find(|object| {
+                object.properties().first().is_some_and(|property| {
+                    property.value == PropertyValue::String("Wrist_weight".to_string())
+                })
+            })
+            .unwrap()
+            .properties();
+        assert!(wrist.iter().any(|property| {
+            property.key == property_keys::WEIGHT_INDICES
+                && property.value == PropertyValue::UInt(2)
+        }));
+        assert!(
+            !wrist
+                .iter()
+                .any(|property| property.key == property_keys::WEIGHT_VALUES)
+        );
+
+        for (skin, message) in [
+            (
+                serde_json::json!({ "bones": ["Upper", "Hand"] }),
+                "unknown bone 'Hand'",
+            ),
+            (
+                serde_json::json!({ "bones": ["Upper", "Upper"] }),
+                "lists bone 'Upper' twice",
+            ),
+            (
+                serde_json::json!({ "bones": ["Upper"], "max_influences": 5 }),
+                "max_influences must be between 1 and 4",
+            ),
+            (
+                serde_json::json!({ "bones": ["Upper"], "radius": 10 }),
+                "radius only applies to the heat falloff",
+            ),
+        ] {
+            let error = match build_scene(&skinned_scene(skin), None) {
+                Ok(_) => panic!("expected {message}"),
+                Err(error) => error,
+            };
+            assert!(error.contains(message), "{error}");
+        }
+    }
+
     #[test]
     fn test_build_scene_with_nested_state_machine_object() {
         let spec = SceneSpec {
             scene_format_version: 1,
+            asset_library: None,
             artboard: Some(ArtboardSpec {
                 name: "Main".to_string(),
                 preset: None,
@@ -921,6 +1734,7 @@ mod tests {
     fn test_build_scene_rejects_invalid_color() {
         let spec = SceneSpec {
             scene_format_version: 1,
+            asset_library: None,
             artboard: Some(ArtboardSpec {
                 name: "Main".to_string(),
                 preset: None,
@@ -987,6 +1801,7 @@ mod tests {
     fn test_build_scene_rejects_oob_transition_index() {
         let spec = SceneSpec {
             scene_format_version: 1,
+            asset_library: None,
             artboard: Some(ArtboardSpec {
                 name: "Main".to_string(),
                 preset: None,
@@ -1007,6 +1822,7 @@ mod tests {
                             duration: None,
                             conditions: None,
                             children: None,
+                            events: None,
                         }]),
                     }],
                 }]),
@@ -1029,6 +1845,7 @@ mod tests {
     fn test_build_scene_rejects_non_number_blend_input() {
         let spec = SceneSpec {
             scene_format_version: 1,
+            asset_library: None,
             artboard: Some(ArtboardSpec {
                 name: "Main".to_string(),
                 preset: None,
@@ -1092,6 +1909,7 @@ mod tests {
     fn test_build_scene_rejects_invalid_transition_view_model_condition_op_value() {
         let spec = SceneSpec {
             scene_format_version: 1,
+            asset_library: None,
             artboard: Some(ArtboardSpec {
                 name: "Main".to_string(),
                 preset: None,
@@ -1116,6 +1934,7 @@ mod tests {
                                     op_value: Some(6),
                                 },
                             ]),
+                            events: None,
                         }]),
                     }],
                 }]),
@@ -1138,6 +1957,7 @@ mod tests {
     fn test_build_scene_rejects_invalid_condition_operator() {
         let spec = SceneSpec {
             scene_format_version: 1,
+            asset_library: None,
             artboard: Some(ArtboardSpec {
                 name: "Main".to_string(),
                 preset: None,
@@ -1165,6 +1985,7 @@ mod tests {
                                 value: Some(serde_json::json!(true)),
                             }]),
                             children: None,
+                            events: None,
                         }]),
                     }],
                 }]),
@@ -1187,6 +2008,7 @@ mod tests {
     fn test_build_scene_rejects_missing_view_model_instance_reference() {
         let spec = SceneSpec {
             scene_format_version: 1,
+            asset_library: None,
             artboard: Some(ArtboardSpec {
                 name: "Main".to_string(),
                 preset: None,
@@ -1216,6 +2038,7 @@ mod tests {
     fn test_build_scene_rejects_bool_keyframe_interpolation() {
         let spec = SceneSpec {
             scene_format_version: 1,
+            asset_library: None,
             artboard: Some(ArtboardSpec {
                 name: "Main".to_string(),
                 preset: None,
@@ -1269,6 +2092,7 @@ mod tests {
     fn test_build_scene_rejects_string_keyframe_interpolation() {
         let spec = SceneSpec {
             scene_format_version: 1,
+            asset_library: None,
             artboard: Some(ArtboardSpec {
                 name: "Main".to_string(),
                 preset: None,
@@ -1412,6 +2236,7 @@ mod tests {
     fn test_build_scene_rejects_standalone_text_leaf_variants() {
         let text_modifier_range = SceneSpec {
             scene_format_version: 1,
+            asset_library: None,
             artboard: Some(ArtboardSpec {
                 name: "Main".to_string(),
                 preset: None,
@@ -1447,6 +2272,7 @@ mod tests {
 
         let text_variation_modifier = SceneSpec {
             scene_format_version: 1,
+            asset_library: None,
             artboard: Some(ArtboardSpec {
                 name: "Main".to_string(),
                 preset: None,
@@ -1473,6 +2299,7 @@ mod tests {
 
         let text_style_feature = SceneSpec {
             scene_format_version: 1,
+            asset_library: None,
             artboard: Some(ArtboardSpec {
                 name: "Main".to_string(),
                 preset: None,
@@ -1502,6 +2329,7 @@ mod tests {
     fn test_build_scene_with_path_object() {
         let spec = SceneSpec {
             scene_format_version: 1,
+            asset_library: None,
             artboard: Some(ArtboardSpec {
                 name: "Main".to_string(),
                 preset: None,
@@ -1535,6 +2363,7 @@ mod tests {
     fn test_build_scene_with_points_path_object() {
         let spec = SceneSpec {
             scene_format_version: 1,
+            asset_library: None,
             artboard: Some(ArtboardSpec {
                 name: "Main".to_string(),
                 preset: None,
@@ -1554,6 +2383,7 @@ mod tests {
                         y: None,
                         is_closed: Some(true),
                         path_flags: Some(3),
+                        skin: None,
                         children: Some(vec![
                             ObjectSpec::StraightVertex {
                                 name: "v1".to_string(),
@@ -1590,6 +2420,7 @@ mod tests {
     fn test_gradient_stop_generated_name_alignment() {
         let spec = SceneSpec {
             scene_format_version: 1,
+            asset_library: None,
             artboard: Some(ArtboardSpec {
                 name: "Main".to_string(),
                 preset: None,
@@ -1773,6 +2604,7 @@ mod tests {
     fn test_trim_path_rejects_shape_parent() {
         let spec = SceneSpec {
             scene_format_version: 1,
+            asset_library: None,
             artboard: Some(ArtboardSpec {
                 name: "Main".to_string(),
                 preset: None,
@@ -1818,6 +2650,7 @@ mod tests {
     fn test_trim_path_accepts_stroke_parent() {
         let spec = SceneSpec {
             scene_format_version: 1,
+            asset_library: None,
             artboard: Some(ArtboardSpec {
                 name: "Main".to_string(),
                 preset: None,
@@ -1870,6 +2703,7 @@ mod tests {
     fn test_dash_path_rejects_shape_parent() {
         let spec = SceneSpec {
             scene_format_version: 1,
+            asset_library: None,
             artboard: Some(ArtboardSpec {
                 name: "Main".to_string(),
                 preset: None,
@@ -1914,6 +2748,7 @@ mod tests {
     fn test_dash_path_accepts_stroke_parent() {
         let spec = SceneSpec {
             scene_format_version: 1,
+            asset_library: None,
             artboard: Some(ArtboardSpec {
                 name: "Main".to_string(),
                 preset: None,
@@ -1963,6 +2798,7 @@ mod tests {
     fn test_dash_rejects_artboard_parent() {
         let spec = SceneSpec {
             scene_format_version: 1,
+            asset_library: None,
             artboard: Some(ArtboardSpec {
                 name: "Main".to_string(),
                 preset: None,
@@ -1997,6 +2833,7 @@ mod tests {
     fn test_feather_rejects_shape_parent() {
         let spec = SceneSpec {
             scene_format_version: 1,
+            asset_library: None,
             artboard: Some(ArtboardSpec {
                 name: "Main".to_string(),
                 preset: None,
@@ -2043,6 +2880,7 @@ mod tests {
     fn test_feather_accepts_fill_parent() {
         let spec = SceneSpec {
             scene_format_version: 1,
+            asset_library: None,
             artboard: Some(ArtboardSpec {
                 name: "Main".to_string(),
                 preset: None,
@@ -2083,10 +2921,331 @@ mod tests {
         build_scene(&spec, None).unwrap();
     }
 
+    fn nine_slice_scene(nine_slice: serde_json::Value) -> SceneSpec {
+        serde_json::from_value(serde_json::json!({
+            "scene_format_version": 1,
+            "artboard": {
+                "name": "Main",
+                "width": 200.0,
+                "height": 100.0,
+                "children": [{"type": "image_asset", "name": "Panel"}, nine_slice]
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_nine_slice_scales_to_its_width_and_normalises_its_axes() {
+        let spec = nine_slice_scene(serde_json::json!({
+            "type": "nine_slice",
+            "name": "frame",
+            "asset": "Panel",
+            "left": 8,
+            "top": 4,
+            "right": 8,
+            "bottom": 8,
+            "width": 160,
+            "center": "hidden",
+            "image_width": 32,
+            "image_height": 16
+        }));
+        let objects = build_scene(&spec, None).unwrap();
+        let float = |object: &dyn RiveObject, key: u16| {
+            object.properties().into_iter().find_map(|property| {
+                (property.key == key)
+                    .then_some(property.value)
+                    .and_then(|value| match value {
+                        PropertyValue::Float(value) => Some(value),
+                        _ => None,
+                    })
+            })
+        };
+        let image = objects
+            .iter()
+            .find(|object| object.type_key() == type_keys::IMAGE)
+            .unwrap();
+        assert_eq!(
+            float(image.as_ref(), property_keys::TRANSFORM_SCALE_X),
+            Some(5.0)
+        );
+        assert_eq!(
+            float(image.as_ref(), property_keys::TRANSFORM_SCALE_Y),
+            None
+        );
+        let offsets = objects
+            .iter()
+            .filter(|object| matches!(object.type_key(), type_keys::AXIS_X | type_keys::AXIS_Y))
+            .map(|object| float(object.as_ref(), property_keys::AXIS_OFFSET).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(offsets, vec![0.25, 0.75, 0.25, 0.5]);
+        let tiles = objects
+            .iter()
+            .filter(|object| object.type_key() == type_keys::NSLICER_TILE_MODE)
+            .count();
+        assert_eq!(tiles, 1, "only the hidden centre needs a tile mode");
+    }
+
+    #[test]
+    fn test_nine_slice_without_a_known_image_size_is_rejected() {
+        let frame = serde_json::json!({
+            "type": "nine_slice",
+            "name": "frame",
+            "asset": "Panel",
+            "left": 8,
+            "top": 8,
+            "right": 8,
+            "bottom": 8
+        });
+        let err = build_scene(&nine_slice_scene(frame.clone()), None)
+            .err()
+            .expect("a nine-slice without an image size should fail");
+        assert!(
+            err.contains("needs the size of image asset 'Panel'"),
+            "unexpected error: {err}"
+        );
+
+        let mut tight = frame;
+        tight["image_width"] = serde_json::json!(16);
+        tight["image_height"] = serde_json::json!(32);
+        let err = build_scene(&nine_slice_scene(tight), None)
+            .err()
+            .expect("insets wider than the image should fail");
+        assert!(
+            err.contains("insets leave no centre in its 16x32 image"),
+            "unexpected error: {err}"
+        );
+    }
+
+    fn uint_property(object: &dyn RiveObject, key: u16) -> Option<u64> {
+        object
+            .properties()
+            .into_iter()
+            .find_map(|property| match property.value {
+                PropertyValue::UInt(value) if property.key == key => Some(value),
+                _ => None,
+            })
+    }
+
+    fn scroll_scene(scroll: serde_json::Value, bar: serde_json::Value) -> SceneSpec {
+        serde_json::from_value(serde_json::json!({
+            "scene_format_version": 1,
+            "artboard": {
+                "name": "Main",
+                "width": 200.0,
+                "height": 200.0,
+                "children": [{
+                    "type": "layout_component",
+                    "name": "Viewport",
+                    "children": [{
+                        "type": "layout_component",
+                        "name": "Content",
+                        "children": [scroll]
+                    }]
+                }, bar]
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_scroll_constraint_writes_its_physics_and_scroll_bars_resolve_it_by_name() {
+        let spec = scroll_scene(
+            serde_json::json!({
+                "type": "scroll_constraint",
+                "name": "List",
+                "direction": "vertical",
+                "physics": {"type": "clamped", "friction": 4}
+            }),
+            serde_json::json!({
+                "type": "scroll_bar_constraint",
+                "name": "Bar",
+                "scroll": "List"
+            }),
+        );
+        let objects = build_scene(&spec, None).unwrap();
+        let artboard = objects
+            .iter()
+            .position(|object| object.type_key() == type_keys::ARTBOARD)
+            .unwrap();
+        let scroll = objects
+            .iter()
+            .position(|object| object.type_key() == type_keys::SCROLL_CONSTRAINT)
+            .unwrap();
+        assert_eq!(
+            objects[scroll + 1].type_key(),
+            type_keys::CLAMPED_SCROLL_PHYSICS
+        );
+        assert_eq!(
+            uint_property(
+                objects[scroll].as_ref(),
+                property_keys::SCROLL_CONSTRAINT_PHYSICS_ID
+            ),
+            Some((scroll + 1 - artboard) as u64)
+        );
+        assert_eq!(
+            uint_property(
+                objects[scroll].as_ref(),
+                property_keys::DRAGGABLE_CONSTRAINT_DIRECTION_VALUE
+            ),
+            Some(1)
+        );
+        let bar = objects
+            .iter()
+            .find(|object| object.type_key() == type_keys::SCROLL_BAR_CONSTRAINT)
+            .unwrap();
+        assert_eq!(
+            uint_property(
+                bar.as_ref(),
+                property_keys::SCROLL_BAR_CONSTRAINT_SCROLL_CONSTRAINT_ID
+            ),
+            Some((scroll - artboard) as u64)
+        );
+    }
+
+    #[test]
+    fn test_scroll_helpers_reject_conflicting_and_mistyped_references() {
+        let bar = serde_json::json!({"type": "scroll_bar_constraint", "name": "Bar"});
+        let cases = [
+            (
+                serde_json::json!({
+                    "type": "scroll_constraint",
+                    "name": "List",
+                    "direction": "all",
+                    "direction_value": 2
+                }),
+                bar.clone(),
+                "'List' sets both 'direction' and 'direction_value'",
+            ),
+            (
+                serde_json::json!({
+                    "type": "scroll_constraint",
+                    "name": "List",
+                    "physics": {"type": "elastic"},
+                    "physics_id": 0
+                }),
+                bar.clone(),
+                "'List' sets both 'physics' and 'physics_id'",
+            ),
+            (
+                serde_json::json!({
+                    "type": "scroll_constraint",
+                    "name": "List",
+                    "direction": "diagonal"
+                }),
+                bar.clone(),
+                "unknown direction 'diagonal'",
+            ),
+            (
+                serde_json::json!({"type": "scroll_constraint", "name": "List"}),
+                serde_json::json!({
+                    "type": "scroll_bar_constraint",
+                    "name": "Bar",
+                    "scroll": "Content"
+                }),
+                "references 'Content', which is not a scroll_constraint",
+            ),
+            (
+                serde_json::json!({"type": "scroll_constraint", "name": "List"}),
+                serde_json::json!({
+                    "type": "scroll_bar_constraint",
+                    "name": "Bar",
+                    "scroll": "Missing"
+                }),
+                "references scroll constraint 'Missing', which is not declared",
+            ),
+        ];
+        for (scroll, bar, expected) in cases {
+            let err = build_scene(&scroll_scene(scroll, bar), None)
+                .err()
+                .expect("the scroll helper should be rejected");
+            assert!(err.contains(expected), "unexpected error: {err}");
+        }
+    }
+
+    fn joystick_scene(joystick: serde_json::Value) -> SceneSpec {
+        let animation = |name: &str| {
+            serde_json::json!({
+                "name": name,
+                "fps": 60,
+                "duration": 60,
+                "keyframes": [{
+                    "object": "Arrow",
+                    "property": "rotation",
+                    "frames": [{"frame": 0, "value": 0.0}, {"frame": 60, "value": 1.0}]
+                }]
+            })
+        };
+        serde_json::from_value(serde_json::json!({
+            "scene_format_version": 1,
+            "artboard": {
+                "name": "Main",
+                "width": 200.0,
+                "height": 200.0,
+                "children": [
+                    {"type": "shape", "name": "Arrow"},
+                    {"type": "shape", "name": "Handle"},
+                    joystick
+                ],
+                "animations": [animation("idle"), animation("tilt_x"), animation("tilt_y")]
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_joystick_resolves_its_animations_and_handle_by_name() {
+        let spec = joystick_scene(serde_json::json!({
+            "type": "joystick",
+            "name": "Stick",
+            "x_animation": "tilt_x",
+            "y_animation": "tilt_y",
+            "handle": "Handle"
+        }));
+        let objects = build_scene(&spec, None).unwrap();
+        let joystick = objects
+            .iter()
+            .find(|object| object.type_key() == type_keys::JOYSTICK)
+            .unwrap();
+        let uint = |key| uint_property(joystick.as_ref(), key);
+        assert_eq!(uint(property_keys::JOYSTICK_X_ID), Some(1));
+        assert_eq!(uint(property_keys::JOYSTICK_Y_ID), Some(2));
+        assert_eq!(uint(property_keys::JOYSTICK_HANDLE_SOURCE_ID), Some(2));
+    }
+
+    #[test]
+    fn test_joystick_rejects_unknown_animations_and_conflicting_ids() {
+        let cases = [
+            (
+                serde_json::json!({"type": "joystick", "name": "Stick", "x_animation": "spin"}),
+                "'Stick' references animation 'spin', which is not declared",
+            ),
+            (
+                serde_json::json!({
+                    "type": "joystick",
+                    "name": "Stick",
+                    "y_animation": "tilt_y",
+                    "y_id": 2
+                }),
+                "'Stick' sets both 'y_animation' and 'y_id'",
+            ),
+            (
+                serde_json::json!({"type": "joystick", "name": "Stick", "handle": "Stick"}),
+                "'Stick' references earlier object 'Stick', which is not declared",
+            ),
+        ];
+        for (joystick, expected) in cases {
+            let err = build_scene(&joystick_scene(joystick), None)
+                .err()
+                .expect("the joystick should be rejected");
+            assert!(err.contains(expected), "unexpected error: {err}");
+        }
+    }
+
     #[test]
     fn test_image_reference_requires_declared_asset() {
         let spec = SceneSpec {
             scene_format_version: 1,
+            asset_library: None,
             artboard: Some(ArtboardSpec {
                 name: "Main".to_string(),
                 preset: None,
@@ -2124,6 +3283,7 @@ mod tests {
     fn test_image_reference_accepts_declared_asset() {
         let spec = SceneSpec {
             scene_format_version: 1,
+            asset_library: None,
             artboard: Some(ArtboardSpec {
                 name: "Main".to_string(),
                 preset: None,
@@ -2135,6 +3295,7 @@ mod tests {
                         asset_id: Some(100),
                         cdn_base_url: None,
                         source: None,
+                        library: None,
                     },
                     ObjectSpec::Image {
                         name: "sprite_1".to_string(),
@@ -2162,6 +3323,7 @@ mod tests {
     fn test_build_multi_artboard_scene() {
         let spec = SceneSpec {
             scene_format_version: 1,
+            asset_library: None,
             artboard: None,
             artboards: Some(vec![
                 ArtboardSpec {
@@ -2277,6 +3439,7 @@ mod tests {
     fn test_build_scene_with_nested_artboard() {
         let spec = SceneSpec {
             scene_format_version: 1,
+            asset_library: None,
             artboard: None,
             artboards: Some(vec![
                 ArtboardSpec {
@@ -2338,6 +3501,7 @@ mod tests {
     fn test_nested_artboard_rejects_unknown_source() {
         let spec = SceneSpec {
             scene_format_version: 1,
+            asset_library: None,
             artboard: Some(ArtboardSpec {
                 name: "Main".to_string(),
                 preset: None,
@@ -2373,6 +3537,7 @@ mod tests {
     fn test_nested_artboard_rejects_self_reference() {
         let spec = SceneSpec {
             scene_format_version: 1,
+            asset_library: None,
             artboard: Some(ArtboardSpec {
                 name: "Main".to_string(),
                 preset: None,
@@ -2406,6 +3571,7 @@ mod tests {
     fn test_nested_artboard_rejects_indirect_cycle() {
         let spec = SceneSpec {
             scene_format_version: 1,
+            asset_library: None,
             artboard: None,
             artboards: Some(vec![
                 ArtboardSpec {
@@ -2460,6 +3626,7 @@ mod tests {
     fn test_reject_both_artboard_and_artboards() {
         let spec = SceneSpec {
             scene_format_version: 1,
+            asset_library: None,
             artboard: Some(ArtboardSpec {
                 name: "A".to_string(),
                 preset: None,
@@ -2499,6 +3666,7 @@ mod tests {
     fn test_reject_neither_artboard_nor_artboards() {
         let spec = SceneSpec {
             scene_format_version: 1,
+            asset_library: None,
             artboard: None,
             artboards: None,
         };
@@ -2514,6 +3682,7 @@ mod tests {
     fn test_reject_empty_artboards_array() {
         let spec = SceneSpec {
             scene_format_version: 1,
+            asset_library: None,
             artboard: None,
             artboards: Some(vec![]),
         };
@@ -2529,6 +3698,7 @@ mod tests {
     fn test_reject_duplicate_artboard_names() {
         let spec = SceneSpec {
             scene_format_version: 1,
+            asset_library: None,
             artboard: None,
             artboards: Some(vec![
                 ArtboardSpec {
@@ -2571,6 +3741,7 @@ mod tests {
     fn test_multi_artboard_with_animation() {
         let spec = SceneSpec {
             scene_format_version: 1,
+            asset_library: None,
             artboard: None,
             artboards: Some(vec![
                 ArtboardSpec {
@@ -2701,6 +3872,7 @@ mod tests {
     fn test_multi_artboard_keyed_object_id_resets_per_artboard() {
         let spec = SceneSpec {
             scene_format_version: 1,
+            asset_library: None,
             artboard: None,
             artboards: Some(vec![
                 ArtboardSpec {
@@ -2912,6 +4084,7 @@ mod tests {
     fn test_artboard_preset_resolves_dimensions() {
         let spec = SceneSpec {
             scene_format_version: 1,
+            asset_library: None,
             artboard: Some(ArtboardSpec {
                 name: "Main".to_string(),
                 preset: Some("mobile".to_string()),
@@ -2953,6 +4126,7 @@ mod tests {
     fn test_artboard_preset_applies_per_dimension_overrides() {
         let spec = SceneSpec {
             scene_format_version: 1,
+            asset_library: None,
             artboard: Some(ArtboardSpec {
                 name: "Main".to_string(),
                 preset: Some("mobile".to_string()),
@@ -2991,6 +4165,7 @@ mod tests {
     fn test_artboard_unknown_preset_rejected() {
         let spec = SceneSpec {
             scene_format_version: 1,
+            asset_library: None,
             artboard: Some(ArtboardSpec {
                 name: "Main".to_string(),
                 preset: Some("watch".to_string()),
@@ -3018,6 +4193,7 @@ mod tests {
     fn test_artboard_requires_dimensions_or_preset() {
         let spec = SceneSpec {
             scene_format_version: 1,
+            asset_library: None,
             artboard: Some(ArtboardSpec {
                 name: "Main".to_string(),
                 preset: None,
@@ -3045,6 +4221,7 @@ mod tests {
     fn test_multi_artboard_names_can_overlap_object_names() {
         let spec = SceneSpec {
             scene_format_version: 1,
+            asset_library: None,
             artboard: None,
             artboards: Some(vec![
                 ArtboardSpec {
@@ -3100,6 +4277,7 @@ mod tests {
     fn test_clipping_shape_visibility_keyframes_use_clipping_property() {
         let spec = SceneSpec {
             scene_format_version: 1,
+            asset_library: None,
             artboard: Some(ArtboardSpec {
                 name: "Main".to_string(),
                 preset: None,
@@ -0,0 +1,442 @@
+use std::collections::HashMap;
+
+use crate::objects::bones::{CubicWeight, Skin, Tendon, Weight};
+use crate::objects::core::{PropertyValue, RiveObject, property_keys, type_keys};
+
+use super::spec::{SkinFalloff, SkinSpec};
+
+/// The runtime packs one byte per influence into a 32-bit value.
+pub(crate) const MAX_INFLUENCES: u8 = 4;
+
+/// Tendon indices are one byte, and index 0 is the identity transform.
+pub(crate) const MAX_SKIN_BONES: usize = 255;
+
+/// A 2D affine transform in the runtime's `[xx, xy, yx, yy, tx, ty]` order.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Transform([f64; 6]);
+
+impl Transform {
+    const IDENTITY: Self = Self([1.0, 0.0, 0.0, 1.0, 0.0, 0.0]);
+
+    fn local(x: f64, y: f64, rotation: f64, scale_x: f64, scale_y: f64) -> Self {
+        let (sin, cos) = rotation.sin_cos();
+        Self([
+            cos * scale_x,
+            sin * scale_x,
+            -sin * scale_y,
+            cos * scale_y,
+            x,
+            y,
+        ])
+    }
+
+    fn multiply(self, other: Self) -> Self {
+        let [a0, a1, a2, a3, a4, a5] = self.0;
+        let [b0, b1, b2, b3, b4, b5] = other.0;
+        Self([
+            a0 * b0 + a2 * b1,
+            a1 * b0 + a3 * b1,
+            a0 * b2 + a2 * b3,
+            a1 * b2 + a3 * b3,
+            a0 * b4 + a2 * b5 + a4,
+            a1 * b4 + a3 * b5 + a5,
+        ])
+    }
+
+    fn invert(self) -> Option<Self> {
+        let [m0, m1, m2, m3, m4, m5] = self.0;
+        let det = m0 * m3 - m1 * m2;
+        if det == 0.0 || !det.is_finite() {
+            return None;
+        }
+        Some(Self([
+            m3 / det,
+            -m1 / det,
+            -m2 / det,
+            m0 / det,
+            (m2 * m5 - m3 * m4) / det,
+            (m1 * m4 - m0 * m5) / det,
+        ]))
+    }
+
+    fn apply(self, [x, y]: [f64; 2]) -> [f64; 2] {
+        let [m0, m1, m2, m3, m4, m5] = self.0;
+        [m0 * x + m2 * y + m4, m1 * x + m3 * y + m5]
+    }
+}
+
+/// The `skin` a skinned object generates.
+pub(crate) fn skin_name(owner: &str) -> String {
+    format!("{}_skin", owner)
+}
+
+/// The `tendon` a skin generates for one of its bones.
+pub(crate) fn tendon_name(owner: &str, bone: &str) -> String {
+    format!("{}_tendon_{}", owner, bone)
+}
+
+/// The `weight` or `cubic_weight` a skin generates under one vertex.
+pub(crate) fn weight_name(vertex: &str) -> String {
+    format!("{}_weight", vertex)
+}
+
+struct SkinBone {
+    id: u64,
+    start: [f64; 2],
+    end: [f64; 2],
+    length: f64,
+}
+
+/// Appends the skin for the points path or mesh at `owner_index`, once its
+/// vertices are in place: a `skin` holding the owner's bind transform, one
+/// `tendon` per bone holding the bone's bind (world) transform, which the
+/// runtime inverts on load, and a weight under every vertex.
+pub(super) fn append_skin(
+    spec: &SkinSpec,
+    owner_index: usize,
+    artboard_start: usize,
+    objects: &mut Vec<Box<dyn RiveObject>>,
+    name_to_index: &mut HashMap<String, usize>,
+) -> Result<(), String> {
+    let owner = string_property(objects[owner_index].as_ref(), property_keys::COMPONENT_NAME);
+    let owner_id = (owner_index - artboard_start) as u64;
+    let max_influences = spec.max_influences.unwrap_or(MAX_INFLUENCES);
+
+    let mut bones = Vec::with_capacity(spec.bones.len());
+    let mut tendons = Vec::with_capacity(spec.bones.len());
+    for bone_name in &spec.bones {
+        let bone_index = name_to_index
+            .get(bone_name)
+            .copied()
+            .filter(|index| *index >= artboard_start)
+            .ok_or_else(|| {
+                format!(
+                    "skin on '{}' references unknown bone '{}'; bones must be declared before the objects they skin",
+                    owner, bone_name
+                )
+            })?;
+        let bone = objects[bone_index].as_ref();
+        if bone.type_key() != type_keys::BONE && bone.type_key() != type_keys::ROOT_BONE {
+            return Err(format!(
+                "skin on '{}' bone '{}' is not a bone or root_bone",
+                owner, bone_name
+            ));
+        }
+        let length = f64::from(float_property(bone, property_keys::BONE_LENGTH, 0.0));
+        let world = world_transform(objects, bone_index, artboard_start);
+        // The runtime refuses a tendon whose bind transform cannot be inverted.
+        if world.invert().is_none() {
+            return Err(format!(
+                "skin on '{}' bone '{}' has a degenerate world transform",
+                owner, bone_name
+            ));
+        }
+        bones.push(SkinBone {
+            id: (bone_index - artboard_start) as u64,
+            start: world.apply([0.0, 0.0]),
+            end: world.apply([length, 0.0]),
+            length,
+        });
+        tendons.push((bone_name, world));
+    }
+    let radius = match spec.radius {
+        Some(radius) => f64::from(radius),
+        None => (bones.iter().map(|bone| bone.length).sum::<f64>() / bones.len() as f64).max(1.0),
+    };
+    let falloff = Falloff {
+        kind: spec.falloff,
+        radius,
+        max_influences: usize::from(max_influences),
+    };
+
+    let owner_world = world_transform(objects, owner_index, artboard_start);
+    let vertices = (owner_index + 1..objects.len())
+        .filter(|&index| {
+            let object = objects[index].as_ref();
+            VERTEX_TYPES.contains(&object.type_key())
+                && uint_property(object, property_keys::COMPONENT_PARENT_ID) == Some(owner_id)
+        })
+        .collect::<Vec<_>>();
+
+    let skin_index = objects.len();
+    let [xx, xy, yx, yy, tx, ty] = owner_world.0.map(|value| value as f32);
+    name_to_index.insert(skin_name(&owner), skin_index);
+    objects.push(Box::new(Skin {
+        name: skin_name(&owner),
+        parent_id: owner_id,
+        xx,
+        yx,
+        xy,
+        yy,
+        tx,
+        ty,
+    }));
+    let skin_id = (skin_index - artboard_start) as u64;
+    for ((bone_name, bind), bone) in tendons.into_iter().zip(&bones) {
+        let [xx, xy, yx, yy, tx, ty] = bind.0.map(|value| value as f32);
+        name_to_index.insert(tendon_name(&owner, bone_name), objects.len());
+        objects.push(Box::new(Tendon {
+            name: tendon_name(&owner, bone_name),
+            parent_id: skin_id,
+            bone_id: bone.id,
+            xx,
+            yx,
+            xy,
+            yy,
+            tx,
+            ty,
+        }));
+    }
+
+    for vertex_index in vertices {
+        let vertex = objects[vertex_index].as_ref();
+        let vertex_name = string_property(vertex, property_keys::COMPONENT_NAME);
+        let vertex_id = (vertex_index - artboard_start) as u64;
+        let handles = vertex_handles(vertex);
+        let [position, handles @ ..] =
+            handles.map(|point| point.map(|point| falloff.pack(&bones, owner_world.apply(point))));
+        let (values, indices) = position.expect("every vertex has a position");
+        let weight: Box<dyn RiveObject> = match handles {
+            [
+                Some((in_values, in_indices)),
+                Some((out_values, out_indices)),
+            ] => {
+                let mut weight = CubicWeight::new(weight_name(&vertex_name), vertex_id);
+                weight.values = values;
+                weight.indices = indices;
+                weight.in_values = in_values;
+                weight.in_indices = in_indices;
+                weight.out_values = out_values;
+                weight.out_indices = out_indices;
+                Box::new(weight)
+            }
+            _ => {
+                let mut weight = Weight::new(weight_name(&vertex_name), vertex_id);
+                weight.values = values;
+                weight.indices = indices;
+                Box::new(weight)
+            }
+        };
+        name_to_index.insert(weight_name(&vertex_name), objects.len());
+        objects.push(weight);
+    }
+    Ok(())
+}
+
+const VERTEX_TYPES: [u16; 6] = [
+    type_keys::STRAIGHT_VERTEX,
+    type_keys::CUBIC_MIRRORED_VERTEX,
+    type_keys::CUBIC_DETACHED_VERTEX,
+    type_keys::CUBIC_ASYMMETRIC_VERTEX,
+    type_keys::MESH_VERTEX,
+    type_keys::CONTOUR_MESH_VERTEX,
+];
+
+struct Falloff {
+    kind: SkinFalloff,
+    radius: f64,
+    max_influences: usize,
+}
+
+impl Falloff {
+    /// Packs the strongest influences on `point` into the runtime's weight
+    /// layout: byte `i` of `values` is influence `i` out of 255, and byte `i`
+    /// of `indices` is its tendon, counted from 1.
+    fn pack(&self, bones: &[SkinBone], point: [f64; 2]) -> (u64, u64) {
+        let mut influences = bones
+            .iter()
+            .enumerate()
+            .map(|(index, bone)| {
+                let distance = segment_distance(point, bone.start, bone.end);
+                let weight = match self.kind {
+                    SkinFalloff::Distance => 1.0 / (distance * distance + 1e-6),
+                    SkinFalloff::Heat => (-(distance / self.radius).powi(2) / 2.0).exp(),
+                };
+                (index, weight, distance)
+            })
+            .collect::<Vec<_>>();
+        influences.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.2.total_cmp(&b.2)));
+        influences.truncate(self.max_influences);
+        let total = influences.iter().map(|influence| influence.1).sum::<f64>();
+        if total <= 0.0 {
+            // Far beyond every heat radius: follow the nearest bone.
+            influences.sort_by(|a, b| a.2.total_cmp(&b.2));
+            influences.truncate(1);
+            influences[0].1 = 1.0;
+        }
+        let total = influences.iter().map(|influence| influence.1).sum::<f64>();
+
+        // Largest remainder keeps the quantised weights summing to 255.
+        let scaled = influences
+            .iter()
+            .map(|influence| influence.1 / total * 255.0)
+            .collect::<Vec<_>>();
+        let mut bytes = scaled
+            .iter()
+            .map(|value| value.floor() as u64)
+            .collect::<Vec<_>>();
+        let mut order = (0..scaled.len()).collect::<Vec<_>>();
+        order.sort_by(|&a, &b| {
+            (scaled[b] - scaled[b].floor()).total_cmp(&(scaled[a] - scaled[a].floor()))
+        });
+        let missing = 255 - bytes.iter().sum::<u64>();
+        for &slot in order.iter().take(missing as usize) {
+            bytes[slot] += 1;
+        }
+
+        let mut values = 0;
+        let mut indices = 0;
+        for (slot, (influence, byte)) in influences
+            .iter()
+            .zip(bytes)
+            .filter(|(_, byte)| *byte > 0)
+            .enumerate()
+        {
+            values |= byte << (slot * 8);
+            indices |= (influence.0 as u64 + 1) << (slot * 8);
+        }
+        (values, indices)
+    }
+}
+
+fn segment_distance(point: [f64; 2], start: [f64; 2], end: [f64; 2]) -> f64 {
+    let along = [end[0] - start[0], end[1] - start[1]];
+    let length_squared = along[0] * along[0] + along[1] * along[1];
+    let t = if length_squared == 0.0 {
+        0.0
+    } else {
+        (((point[0] - start[0]) * along[0] + (point[1] - start[1]) * along[1]) / length_squared)
+            .clamp(0.0, 1.0)
+    };
+    let closest = [start[0] + along[0] * t, start[1] + along[1] * t];
+    ((point[0] - closest[0]).powi(2) + (point[1] - closest[1]).powi(2)).sqrt()
+}
+
+/// A vertex's position and, for cubic vertices, its in and out handles, in
+/// the owner's space.
+fn vertex_handles(vertex: &dyn RiveObject) -> [Option<[f64; 2]>; 3] {
+    let read = |key| f64::from(float_property(vertex, key, 0.0));
+    let position = [read(property_keys::VERTEX_X), read(property_keys::VERTEX_Y)];
+    let handle = |rotation: f64, distance: f64| {
+        let (sin, cos) = rotation.sin_cos();
+        Some([position[0] + cos * distance, position[1] + sin * distance])
+    };
+    let (in_handle, out_handle) = match vertex.type_key() {
+        type_keys::CUBIC_MIRRORED_VERTEX => {
+            let rotation = read(property_keys::CUBIC_MIRRORED_VERTEX_ROTATION);
+            let distance = read(property_keys::CUBIC_MIRRORED_VERTEX_DISTANCE);
+            (handle(rotation, -distance), handle(rotation, distance))
+        }
+        type_keys::CUBIC_ASYMMETRIC_VERTEX => {
+            let rotation = read(property_keys::CUBIC_ASYMMETRIC_VERTEX_ROTATION);
+            (
+                handle(
+                    rotation,
+                    -read(property_keys::CUBIC_ASYMMETRIC_VERTEX_IN_DISTANCE),
+                ),
+                handle(
+                    rotation,
+                    read(property_keys::CUBIC_ASYMMETRIC_VERTEX_OUT_DISTANCE),
+                ),
+            )
+        }
+        type_keys::CUBIC_DETACHED_VERTEX => (
+            handle(
+                read(property_keys::CUBIC_DETACHED_VERTEX_IN_ROTATION),
+                read(property_keys::CUBIC_DETACHED_VERTEX_IN_DISTANCE),
+            ),
+            handle(
+                read(property_keys::CUBIC_DETACHED_VERTEX_OUT_ROTATION),
+                read(property_keys::CUBIC_DETACHED_VERTEX_OUT_DISTANCE),
+            ),
+        ),
+        _ => (None, None),
+    };
+    [Some(position), in_handle, out_handle]
+}
+
+/// The object's world transform at bind time, composed from the transform
+/// properties of it and its ancestors. A bone sits at the tip of its parent
+/// bone.
+fn world_transform(
+    objects: &[Box<dyn RiveObject>],
+    index: usize,
+    artboard_start: usize,
+) -> Transform {
+    let mut world = Transform::IDENTITY;
+    let mut cursor = Some(index);
+    while let Some(index) = cursor {
+        let object = objects[index].as_ref();
+        if object.type_key() == type_keys::ARTBOARD {
+            break;
+        }
+        let parent = uint_property(object, property_keys::COMPONENT_PARENT_ID)
+            .map(|id| artboard_start + id as usize)
+            .filter(|parent| *parent < index);
+        let read = |key, default| f64::from(float_property(object, key, default));
+        let (x, y) = match object.type_key() {
+            type_keys::BONE => (
+                parent
+                    .map(|parent| {
+                        f64::from(float_property(
+                            objects[parent].as_ref(),
+                            property_keys::BONE_LENGTH,
+                            0.0,
+                        ))
+                    })
+                    .unwrap_or(0.0),
+                0.0,
+            ),
+            type_keys::ROOT_BONE => (
+                read(property_keys::ROOT_BONE_X, 0.0),
+                read(property_keys::ROOT_BONE_Y, 0.0),
+            ),
+            _ => (
+                read(property_keys::NODE_X, 0.0),
+                read(property_keys::NODE_Y, 0.0),
+            ),
+        };
+        let local = Transform::local(
+            x,
+            y,
+            read(property_keys::TRANSFORM_ROTATION, 0.0),
+            read(property_keys::TRANSFORM_SCALE_X, 1.0),
+            read(property_keys::TRANSFORM_SCALE_Y, 1.0),
+        );
+        world = local.multiply(world);
+        cursor = parent;
+    }
+    world
+}
+
+fn float_property(object: &dyn RiveObject, key: u16, default: f32) -> f32 {
+    object
+        .properties()
+        .into_iter()
+        .find_map(|property| match property.value {
+            PropertyValue::Float(value) if property.key == key => Some(value),
+            _ => None,
+        })
+        .unwrap_or(default)
+}
+
+fn uint_property(object: &dyn RiveObject, key: u16) -> Option<u64> {
+    object
+        .properties()
+        .into_iter()
+        .find_map(|property| match property.value {
+            PropertyValue::UInt(value) if property.key == key => Some(value),
+            _ => None,
+        })
+}
+
+fn string_property(object: &dyn RiveObject, key: u16) -> String {
+    object
+        .properties()
+        .into_iter()
+        .find_map(|property| match property.value {
+            PropertyValue::String(value) if property.key == key => Some(value),
+            _ => None,
+        })
+        .unwrap_or_default()
+}
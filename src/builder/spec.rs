@@ -10,6 +10,10 @@ pub struct SceneSpec {
     pub artboard: Option<ArtboardSpec>,
     #[serde(default)]
     pub artboards: Option<Vec<ArtboardSpec>>,
+    /// Path, relative to the scene file, of an asset library manifest whose
+    /// entries file assets can reference by `library` name.
+    #[serde(default)]
+    pub asset_library: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Default, JsonSchema)]
@@ -195,6 +199,8 @@ pub enum ObjectSpec {
         #[serde(default)]
         path_flags: Option<u64>,
         #[serde(default)]
+        skin: Option<SkinSpec>,
+        #[serde(default)]
         children: Option<Vec<ObjectSpec>>,
     },
     #[serde(rename = "straight_vertex")]
@@ -281,6 +287,7 @@ pub enum ObjectSpec {
     Bone {
         name: String,
         length: Option<f32>,
+        rotation: Option<f32>,
         children: Option<Vec<ObjectSpec>>,
     },
     RootBone {
@@ -288,6 +295,7 @@ pub enum ObjectSpec {
         x: Option<f32>,
         y: Option<f32>,
         length: Option<f32>,
+        rotation: Option<f32>,
         children: Option<Vec<ObjectSpec>>,
     },
     Skin {
@@ -432,12 +440,22 @@ pub enum ObjectSpec {
         drawable: Option<String>,
         placement_value: Option<u64>,
     },
+    /// Scrubs one animation with its horizontal position and another with
+    /// its vertical position, from -1 at one edge to 1 at the other.
     Joystick {
         name: String,
         x: Option<f32>,
         y: Option<f32>,
         x_id: Option<u64>,
         y_id: Option<u64>,
+        /// Names the animation the horizontal position scrubs instead of
+        /// setting `x_id`.
+        #[serde(default)]
+        x_animation: Option<String>,
+        /// Names the animation the vertical position scrubs instead of
+        /// setting `y_id`.
+        #[serde(default)]
+        y_animation: Option<String>,
         pos_x: Option<f32>,
         pos_y: Option<f32>,
         width: Option<f32>,
@@ -446,6 +464,11 @@ pub enum ObjectSpec {
         origin_y: Option<f32>,
         flags: Option<u64>,
         handle_source_id: Option<u64>,
+        /// Names an earlier object whose position drives the joystick
+        /// instead of setting `handle_source_id`; move it with an
+        /// `align_target` listener to drag the joystick.
+        #[serde(default)]
+        handle: Option<String>,
     },
     Text {
         name: String,
@@ -485,6 +508,9 @@ pub enum ObjectSpec {
         cdn_base_url: Option<String>,
         #[serde(default)]
         source: Option<String>,
+        /// Names an entry of the scene's `asset_library` instead of a `source`.
+        #[serde(default)]
+        library: Option<String>,
     },
     FontAsset {
         name: String,
@@ -492,11 +518,19 @@ pub enum ObjectSpec {
         cdn_base_url: Option<String>,
         #[serde(default)]
         source: Option<String>,
+        /// Names an entry of the scene's `asset_library` instead of a `source`.
+        #[serde(default)]
+        library: Option<String>,
     },
     AudioAsset {
         name: String,
         asset_id: Option<u64>,
         cdn_base_url: Option<String>,
+        #[serde(default)]
+        source: Option<String>,
+        /// Names an entry of the scene's `asset_library` instead of a `source`.
+        #[serde(default)]
+        library: Option<String>,
     },
     LayoutComponent {
         name: String,
@@ -504,6 +538,8 @@ pub enum ObjectSpec {
         width: Option<f32>,
         height: Option<f32>,
         style_id: Option<u64>,
+        #[serde(default)]
+        style: Option<String>,
         fractional_width: Option<f32>,
         fractional_height: Option<f32>,
         children: Option<Vec<ObjectSpec>>,
@@ -560,6 +596,10 @@ pub enum ObjectSpec {
         property_key: u64,
         flags: u64,
         converter_id: Option<u64>,
+        /// The view model's id followed by the bound property's index in it.
+        /// When set, the binding is written as a `DataBindContext`.
+        #[serde(default)]
+        source_path_ids: Option<Vec<u64>>,
     },
     ViewModelInstance {
         view_model_id: Option<u64>,
@@ -664,6 +704,14 @@ pub enum ObjectSpec {
         asset_id: Option<u64>,
         cdn_base_url: Option<String>,
         is_module: Option<bool>,
+        /// A Luau file to embed. `-- @input NAME KIND` lines in its opening
+        /// comment declare the `script_input_*` children scripted objects
+        /// may give it.
+        #[serde(default)]
+        source: Option<String>,
+        /// Names an entry of the scene's `asset_library` instead of a `source`.
+        #[serde(default)]
+        library: Option<String>,
     },
     BlobAsset {
         name: String,
@@ -701,6 +749,8 @@ pub enum ObjectSpec {
     AudioEvent {
         name: String,
         asset_id: Option<u64>,
+        #[serde(default)]
+        asset: Option<String>,
         children: Option<Vec<ObjectSpec>>,
     },
     #[serde(rename = "custom_property_number")]
@@ -805,8 +855,23 @@ pub enum ObjectSpec {
     },
     Mesh {
         name: String,
+        #[serde(default)]
+        skin: Option<SkinSpec>,
         children: Option<Vec<ObjectSpec>>,
     },
+    /// A mesh of `columns` x `rows` cells spanning `width` x `height`,
+    /// centred on the image origin. Its vertices are named
+    /// `{name}_{row}_{column}`.
+    #[serde(rename = "mesh_grid")]
+    MeshGrid {
+        name: String,
+        columns: u32,
+        rows: u32,
+        width: f32,
+        height: f32,
+        #[serde(default)]
+        skin: Option<SkinSpec>,
+    },
     #[serde(rename = "mesh_vertex")]
     MeshVertex {
         name: String,
@@ -883,13 +948,22 @@ pub enum ObjectSpec {
         strength: Option<f32>,
         direction_value: Option<u64>,
     },
+    /// Lets pointer drags scroll its parent layout's children inside the
+    /// layout above it.
     #[serde(rename = "scroll_constraint")]
     ScrollConstraint {
         name: String,
         strength: Option<f32>,
         direction_value: Option<u64>,
+        /// `horizontal`, `vertical` or `all` instead of `direction_value`.
+        #[serde(default)]
+        direction: Option<String>,
         snap: Option<bool>,
         physics_id: Option<u64>,
+        /// Physics written beside the constraint instead of setting
+        /// `physics_id`.
+        #[serde(default)]
+        physics: Option<ScrollPhysicsSpec>,
         scroll_offset_x: Option<f32>,
         scroll_offset_y: Option<f32>,
         scroll_percent_x: Option<f32>,
@@ -902,6 +976,10 @@ pub enum ObjectSpec {
         name: String,
         strength: Option<f32>,
         scroll_constraint_id: Option<u64>,
+        /// Names an earlier `scroll_constraint` instead of setting
+        /// `scroll_constraint_id`.
+        #[serde(default)]
+        scroll: Option<String>,
         auto_size: Option<bool>,
     },
     #[serde(rename = "list_follow_path_constraint")]
@@ -951,6 +1029,51 @@ pub enum ObjectSpec {
         height: Option<f32>,
         children: Option<Vec<ObjectSpec>>,
     },
+    /// An `image` drawn as a 9-slice frame. The `left`, `top`, `right` and
+    /// `bottom` pixel insets keep their size while the edges and centre
+    /// stretch or repeat, so scaling the image (or keying its `scale_x` and
+    /// `scale_y`) resizes the frame without distorting its corners. Writes
+    /// an `image` named `name` with an `nslicer`, its normalised axes and
+    /// tile modes.
+    #[serde(rename = "nine_slice")]
+    NineSlice {
+        name: String,
+        /// Names an `image_asset`; its embedded PNG, JPEG or WebP source
+        /// gives the intrinsic size the insets are measured against.
+        asset: String,
+        left: f32,
+        top: f32,
+        right: f32,
+        bottom: f32,
+        #[serde(default)]
+        x: Option<f32>,
+        #[serde(default)]
+        y: Option<f32>,
+        #[serde(default)]
+        rotation: Option<f32>,
+        /// The drawn size in pixels. Defaults to the intrinsic size.
+        #[serde(default)]
+        width: Option<f32>,
+        #[serde(default)]
+        height: Option<f32>,
+        /// The drawn size relative to the intrinsic size, instead of
+        /// `width` and `height`.
+        #[serde(default)]
+        scale_x: Option<f32>,
+        #[serde(default)]
+        scale_y: Option<f32>,
+        /// `stretch` (default), `repeat` or `hidden` for the four edges.
+        #[serde(default)]
+        edges: Option<String>,
+        /// `stretch` (default), `repeat` or `hidden` for the centre.
+        #[serde(default)]
+        center: Option<String>,
+        /// The intrinsic size of an asset that embeds no source.
+        #[serde(default)]
+        image_width: Option<u32>,
+        #[serde(default)]
+        image_height: Option<u32>,
+    },
     #[serde(rename = "view_model_property_number")]
     ViewModelPropertyNumber {
         name: String,
@@ -1299,6 +1422,9 @@ pub enum ObjectSpec {
     ScriptedDrawable {
         name: String,
         script_asset_id: Option<u64>,
+        /// Names a `script_asset` instead of setting `script_asset_id`.
+        #[serde(default)]
+        script: Option<String>,
         generator_function_ref: Option<u64>,
         threshold: Option<f32>,
         is_paused: Option<bool>,
@@ -1311,11 +1437,17 @@ pub enum ObjectSpec {
     ScriptedDataConverter {
         name: String,
         script_asset_id: Option<u64>,
+        /// Names a `script_asset` instead of setting `script_asset_id`.
+        #[serde(default)]
+        script: Option<String>,
     },
     #[serde(rename = "scripted_layout")]
     ScriptedLayout {
         name: String,
         script_asset_id: Option<u64>,
+        /// Names a `script_asset` instead of setting `script_asset_id`.
+        #[serde(default)]
+        script: Option<String>,
         children: Option<Vec<ObjectSpec>>,
     },
     #[serde(rename = "scripted_path_effect")]
@@ -1327,11 +1459,17 @@ pub enum ObjectSpec {
     #[serde(rename = "scripted_listener_action")]
     ScriptedListenerAction {
         script_asset_id: Option<u64>,
+        /// Names a `script_asset` instead of setting `script_asset_id`.
+        #[serde(default)]
+        script: Option<String>,
         is_stateful: Option<bool>,
     },
     #[serde(rename = "scripted_transition_condition")]
     ScriptedTransitionCondition {
         script_asset_id: Option<u64>,
+        /// Names a `script_asset` instead of setting `script_asset_id`.
+        #[serde(default)]
+        script: Option<String>,
         is_stateful: Option<bool>,
     },
     #[serde(rename = "script_input_number")]
@@ -1366,6 +1504,47 @@ pub enum ObjectSpec {
     },
 }
 
+/// How a `scroll_constraint` carries a drag on after the pointer lets go.
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+pub enum ScrollPhysicsSpec {
+    /// Stops at the content's edges.
+    Clamped {
+        friction: Option<f32>,
+        speed_multiplier: Option<f32>,
+    },
+    /// Overscrolls past the content's edges and springs back.
+    Elastic {
+        friction: Option<f32>,
+        speed_multiplier: Option<f32>,
+        elastic_factor: Option<f32>,
+    },
+}
+
+/// Binds a `points_path`, `mesh` or `mesh_grid` to bones declared earlier in
+/// the artboard. The builder derives the bind matrices from the current
+/// world transforms and weights every vertex by its distance to each bone.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct SkinSpec {
+    pub bones: Vec<String>,
+    #[serde(default)]
+    pub falloff: SkinFalloff,
+    /// The heat falloff's radius; defaults to the mean bone length.
+    pub radius: Option<f32>,
+    /// At most 4, the runtime's limit.
+    pub max_influences: Option<u8>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SkinFalloff {
+    /// Inverse-square distance to each bone segment.
+    #[default]
+    Distance,
+    /// Gaussian falloff with distance, scaled by `radius`.
+    Heat,
+}
+
 #[derive(Debug, Deserialize, JsonSchema)]
 pub struct InterpolatorSpec {
     pub name: String,
@@ -1425,6 +1604,8 @@ pub enum StateMachineComponentSpec {
     FireEvent {
         name: String,
         event_id: Option<u64>,
+        #[serde(default)]
+        event: Option<String>,
         occurs_value: Option<u64>,
     },
     FireTrigger {
@@ -1471,9 +1652,15 @@ pub enum ListenerActionSpec {
     },
     AlignTarget {
         target_id: Option<u64>,
+        /// Names the object moved to the pointer instead of setting
+        /// `target_id`.
+        #[serde(default)]
+        target: Option<String>,
     },
     FireEvent {
         event_id: Option<u64>,
+        #[serde(default)]
+        event: Option<String>,
     },
     ViewModelChange {
         view_model_property_id: Option<u64>,
@@ -1592,6 +1779,17 @@ pub struct TransitionSpec {
     pub duration: Option<u64>,
     pub conditions: Option<Vec<ConditionSpec>>,
     pub children: Option<Vec<TransitionChildSpec>>,
+    /// Events fired when the transition starts (`occurs_value` 0) or ends (1).
+    #[serde(default)]
+    pub events: Option<Vec<TransitionEventSpec>>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct TransitionEventSpec {
+    #[serde(default)]
+    pub event: Option<String>,
+    pub event_id: Option<u64>,
+    pub occurs_value: Option<u64>,
 }
 
 #[derive(Debug, Deserialize, JsonSchema)]
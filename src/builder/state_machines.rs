@@ -72,11 +72,19 @@ pub(crate) fn build_state_machines(
                     StateMachineComponentSpec::FireEvent {
                         name,
                         event_id,
+                        event,
                         occurs_value,
                     } => {
+                        let event_id = resolve_event_id(
+                            name,
+                            event.as_deref(),
+                            *event_id,
+                            artboard_start,
+                            object_name_to_index,
+                        )?;
                         objects.push(Box::new(StateMachineFireEvent {
                             name: name.clone(),
-                            event_id: event_id.unwrap_or(0),
+                            event_id,
                             occurs_value: occurs_value.unwrap_or(0),
                         }));
                     }
@@ -213,15 +221,38 @@ pub(crate) fn build_state_machines(
                                     value: number_value,
                                 }));
                             }
-                            ListenerActionSpec::AlignTarget { target_id } => {
+                            ListenerActionSpec::AlignTarget { target_id, target } => {
+                                let lookup = |name: &str| {
+                                    object_name_to_index
+                                        .get(name)
+                                        .and_then(|index| index.checked_sub(artboard_start))
+                                        .map(|index| index as u64)
+                                };
+                                let target_id = references::resolve(
+                                    "listener align_target",
+                                    &Namespace {
+                                        kind: "object",
+                                        name_field: "target",
+                                        index_field: "target_id",
+                                        lookup: &lookup,
+                                        check: None,
+                                    },
+                                    target.as_deref(),
+                                    *target_id,
+                                )?;
                                 objects.push(Box::new(ListenerAlignTarget {
                                     target_id: target_id.unwrap_or(0),
                                 }));
                             }
-                            ListenerActionSpec::FireEvent { event_id } => {
-                                objects.push(Box::new(ListenerFireEvent {
-                                    event_id: event_id.unwrap_or(0),
-                                }));
+                            ListenerActionSpec::FireEvent { event_id, event } => {
+                                let event_id = resolve_event_id(
+                                    "listener fire_event",
+                                    event.as_deref(),
+                                    *event_id,
+                                    artboard_start,
+                                    object_name_to_index,
+                                )?;
+                                objects.push(Box::new(ListenerFireEvent { event_id }));
                             }
                             ListenerActionSpec::ViewModelChange {
                                 view_model_property_id,
@@ -398,6 +429,24 @@ pub(crate) fn build_state_machines(
                                 append_transition_child(child, objects)?;
                             }
                         }
+
+                        // Fire events attach to the latest state or transition on import.
+                        if let Some(events) = &transition.events {
+                            for event in events {
+                                let event_id = resolve_event_id(
+                                    "transition fire_event",
+                                    event.event.as_deref(),
+                                    event.event_id,
+                                    artboard_start,
+                                    object_name_to_index,
+                                )?;
+                                objects.push(Box::new(StateMachineFireEvent {
+                                    name: String::new(),
+                                    event_id,
+                                    occurs_value: event.occurs_value.unwrap_or(0),
+                                }));
+                            }
+                        }
                     }
                 }
             }
@@ -409,6 +458,35 @@ pub(crate) fn build_state_machines(
     Ok(())
 }
 
+/// Resolves an event reference to its artboard-local object id.
+fn resolve_event_id(
+    owner: &str,
+    event: Option<&str>,
+    event_id: Option<u64>,
+    artboard_start: usize,
+    object_name_to_index: &HashMap<String, usize>,
+) -> Result<u64, String> {
+    let lookup = |name: &str| {
+        object_name_to_index
+            .get(name)
+            .and_then(|index| index.checked_sub(artboard_start))
+            .map(|index| index as u64)
+    };
+    let event_id = references::resolve(
+        owner,
+        &Namespace {
+            kind: "event",
+            name_field: "event",
+            index_field: "event_id",
+            lookup: &lookup,
+            check: None,
+        },
+        event,
+        event_id,
+    )?;
+    Ok(event_id.unwrap_or(0))
+}
+
 fn append_blend_state_child(spec: &BlendStateChildSpec, objects: &mut Vec<Box<dyn RiveObject>>) {
     let BlendStateChildSpec::BlendAnimation { animation_id } = spec;
     objects.push(Box::new(BlendAnimation {
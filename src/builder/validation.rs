@@ -2,19 +2,23 @@ use std::collections::{HashMap, HashSet};
 
 use crate::objects::core::{BackingType, is_bool_property, property_backing_type, type_keys};
 
+use super::mesh::{MAX_MESH_VERTICES, grid_vertex_name};
 use super::objects::FileAssetKind;
 use super::parsers::{
     animatable_properties_for_object_type, condition_op_is_valid, interpolation_type_from_name,
     invalid_animatable_property_error, json_value_to_color, json_value_to_f32,
     json_value_to_string, json_value_to_u64, object_type_name_for_key, parse_color,
-    parse_fill_rule, parse_loop_type, parse_stroke_cap, parse_stroke_join, parse_trim_mode,
-    property_key_for_object, required_u64_field, validate_discrete_keyframe_interpolation,
+    parse_draggable_direction, parse_fill_rule, parse_loop_type, parse_nslicer_tile_mode,
+    parse_stroke_cap, parse_stroke_join, parse_trim_mode, property_key_for_object,
+    required_u64_field, validate_discrete_keyframe_interpolation,
 };
 use super::scene::resolve_artboard_dimensions;
+use super::skin::{MAX_INFLUENCES, MAX_SKIN_BONES, skin_name, tendon_name, weight_name};
 use super::spec::{
     ArtboardSpec, BlendState1DChildSpec, BlendStateChildSpec, BlendStateDirectChildSpec, InputSpec,
-    ListenerActionSpec, ObjectSpec, ParentKind, SCENE_FORMAT_VERSION, SceneSpec, StateSpec,
-    TextModifierGroupChildSpec, TransitionChildSpec,
+    ListenerActionSpec, ObjectSpec, ParentKind, SCENE_FORMAT_VERSION, SceneSpec, SkinFalloff,
+    SkinSpec, StateMachineComponentSpec, StateSpec, TextModifierGroupChildSpec,
+    TransitionChildSpec,
 };
 
 pub(crate) fn validate_scene_spec(spec: &SceneSpec) -> Result<Vec<SpecIndex>, String> {
@@ -254,6 +258,20 @@ pub(crate) fn validate_artboard_spec(artboard_spec: &ArtboardSpec) -> Result<Spe
                 }
             }
 
+            if let Some(components) = &state_machine.components {
+                for component in components {
+                    if let StateMachineComponentSpec::FireEvent {
+                        name,
+                        event_id,
+                        event,
+                        ..
+                    } = component
+                    {
+                        validate_event_reference(name, event.as_deref(), *event_id, &spec_index)?;
+                    }
+                }
+            }
+
             if let Some(listeners) = &state_machine.listeners {
                 for listener in listeners {
                     if !object_names.contains(&listener.target) {
@@ -332,9 +350,22 @@ pub(crate) fn validate_artboard_spec(artboard_spec: &ArtboardSpec) -> Result<Spe
                                         ));
                                     }
                                 }
-                                ListenerActionSpec::AlignTarget { .. }
-                                | ListenerActionSpec::FireEvent { .. }
-                                | ListenerActionSpec::ViewModelChange { .. } => {}
+                                ListenerActionSpec::FireEvent { event_id, event } => {
+                                    validate_event_reference(
+                                        "listener fire_event",
+                                        event.as_deref(),
+                                        *event_id,
+                                        &spec_index,
+                                    )?;
+                                }
+                                ListenerActionSpec::AlignTarget { target_id, target } => {
+                                    validate_align_target_reference(
+                                        target.as_deref(),
+                                        *target_id,
+                                        &spec_index,
+                                    )?;
+                                }
+                                ListenerActionSpec::ViewModelChange { .. } => {}
                             }
                         }
                     }
@@ -474,6 +505,17 @@ pub(crate) fn validate_artboard_spec(artboard_spec: &ArtboardSpec) -> Result<Spe
                         if let Some(children) = &transition.children {
                             validate_transition_children(children, state_machine.name.as_str())?;
                         }
+
+                        if let Some(events) = &transition.events {
+                            for event in events {
+                                validate_event_reference(
+                                    "transition fire_event",
+                                    event.event.as_deref(),
+                                    event.event_id,
+                                    &spec_index,
+                                )?;
+                            }
+                        }
                     }
                 }
             }
@@ -483,6 +525,64 @@ pub(crate) fn validate_artboard_spec(artboard_spec: &ArtboardSpec) -> Result<Spe
     Ok(spec_index)
 }
 
+fn validate_event_reference(
+    owner: &str,
+    event: Option<&str>,
+    event_id: Option<u64>,
+    spec_index: &SpecIndex,
+) -> Result<(), String> {
+    let Some(name) = event else {
+        return Ok(());
+    };
+    if event_id.is_some() {
+        return Err(format!(
+            "'{owner}' sets both 'event' and 'event_id'; use one or the other"
+        ));
+    }
+    if spec_index.ambiguous.contains(name) {
+        return Err(format!(
+            "'{owner}' references event '{name}', but more than one object has that name; give each object a unique name"
+        ));
+    }
+    match spec_index.type_keys.get(name) {
+        Some(&(type_keys::EVENT | type_keys::OPEN_URL_EVENT | type_keys::AUDIO_EVENT)) => Ok(()),
+        Some(&type_key) => Err(format!(
+            "'{owner}' references '{name}', which is a {}; it must be an event",
+            object_type_name_for_key(type_key)
+        )),
+        None => Err(format!(
+            "'{owner}' references event '{name}', which is not declared"
+        )),
+    }
+}
+
+fn validate_align_target_reference(
+    target: Option<&str>,
+    target_id: Option<u64>,
+    spec_index: &SpecIndex,
+) -> Result<(), String> {
+    let Some(name) = target else {
+        return Ok(());
+    };
+    if target_id.is_some() {
+        return Err(
+            "'listener align_target' sets both 'target' and 'target_id'; use one or the other"
+                .to_string(),
+        );
+    }
+    if spec_index.ambiguous.contains(name) {
+        return Err(format!(
+            "'listener align_target' references object '{name}', but more than one object has that name; give each object a unique name"
+        ));
+    }
+    if !spec_index.type_keys.contains_key(name) {
+        return Err(format!(
+            "'listener align_target' references object '{name}', which is not declared"
+        ));
+    }
+    Ok(())
+}
+
 pub(crate) fn validate_object_spec(
     spec: &ObjectSpec,
     object_names: &mut HashSet<String>,
@@ -522,6 +622,7 @@ pub(crate) fn validate_object_spec(
                         | ObjectSpec::RadialGradient { name, .. }
                         | ObjectSpec::Node { name, .. }
                         | ObjectSpec::Image { name, .. }
+                        | ObjectSpec::NineSlice { name, .. }
                         | ObjectSpec::Path { name, .. }
                         | ObjectSpec::PointsPath { name, .. }
                         | ObjectSpec::StraightVertex { name, .. }
@@ -699,6 +800,7 @@ pub(crate) fn validate_object_spec(
                         | ObjectSpec::ScriptedListenerAction { .. }
                         | ObjectSpec::ScriptedTransitionCondition { .. } => {}
                         ObjectSpec::Mesh { name, .. }
+                        | ObjectSpec::MeshGrid { name, .. }
                         | ObjectSpec::MeshVertex { name, .. }
                         | ObjectSpec::ContourMeshVertex { name, .. }
                         | ObjectSpec::ForcedEdge { name, .. }
@@ -923,13 +1025,79 @@ pub(crate) fn validate_object_spec(
         ObjectSpec::Node { name, .. } => {
             ensure_unique_name(name, object_names)?;
         }
-        ObjectSpec::Image { name, .. } => {
+        ObjectSpec::Image { name, children, .. } => {
+            ensure_unique_name(name, object_names)?;
+            if let Some(children) = children {
+                for child in children {
+                    validate_object_spec(child, object_names, &ParentKind::Image)?;
+                }
+            }
+        }
+        ObjectSpec::NineSlice {
+            name,
+            left,
+            top,
+            right,
+            bottom,
+            width,
+            height,
+            scale_x,
+            scale_y,
+            edges,
+            center,
+            image_width,
+            image_height,
+            ..
+        } => {
             ensure_unique_name(name, object_names)?;
+            if [left, top, right, bottom]
+                .iter()
+                .any(|inset| !(**inset >= 0.0 && inset.is_finite()))
+            {
+                return Err(format!(
+                    "nine_slice '{}' insets must be finite and not negative",
+                    name
+                ));
+            }
+            if (width.is_some() && scale_x.is_some()) || (height.is_some() && scale_y.is_some()) {
+                return Err(format!(
+                    "nine_slice '{}' sets both a size and a scale on one axis; use width/height or scale_x/scale_y",
+                    name
+                ));
+            }
+            if [width, height]
+                .iter()
+                .flat_map(|v| v.iter())
+                .any(|v| *v <= 0.0)
+            {
+                return Err(format!(
+                    "nine_slice '{}' width and height must be positive",
+                    name
+                ));
+            }
+            if image_width.is_some() != image_height.is_some()
+                || image_width.is_some_and(|v| v == 0)
+                || image_height.is_some_and(|v| v == 0)
+            {
+                return Err(format!(
+                    "nine_slice '{}' image_width and image_height must both be set and positive",
+                    name
+                ));
+            }
+            for mode in [edges, center].into_iter().flatten() {
+                parse_nslicer_tile_mode(mode)
+                    .map_err(|error| format!("nine_slice '{}': {}", name, error))?;
+            }
         }
         ObjectSpec::Path { name, .. } => {
             ensure_unique_name(name, object_names)?;
         }
-        ObjectSpec::PointsPath { name, children, .. } => {
+        ObjectSpec::PointsPath {
+            name,
+            skin,
+            children,
+            ..
+        } => {
             ensure_unique_name(name, object_names)?;
             if !matches!(parent_kind, ParentKind::Shape) {
                 return Err(format!("points_path '{}' must be a child of a shape", name));
@@ -939,6 +1107,20 @@ pub(crate) fn validate_object_spec(
                     validate_object_spec(child, object_names, &ParentKind::PointsPath)?;
                 }
             }
+            if let Some(skin) = skin {
+                let vertices = children
+                    .iter()
+                    .flatten()
+                    .filter_map(|child| match child {
+                        ObjectSpec::StraightVertex { name, .. }
+                        | ObjectSpec::CubicMirroredVertex { name, .. }
+                        | ObjectSpec::CubicDetachedVertex { name, .. }
+                        | ObjectSpec::CubicAsymmetricVertex { name, .. } => Some(name.clone()),
+                        _ => None,
+                    })
+                    .collect::<Vec<_>>();
+                validate_skin(name, skin, &vertices, object_names)?;
+            }
         }
         ObjectSpec::StraightVertex { name, radius, .. } => {
             ensure_unique_name(name, object_names)?;
@@ -1170,7 +1352,8 @@ pub(crate) fn validate_object_spec(
         }
         ObjectSpec::ImageAsset { name, .. }
         | ObjectSpec::FontAsset { name, .. }
-        | ObjectSpec::AudioAsset { name, .. } => {
+        | ObjectSpec::AudioAsset { name, .. }
+        | ObjectSpec::ScriptAsset { name, .. } => {
             ensure_unique_name(name, object_names)?;
         }
         ObjectSpec::LayoutComponent { name, children, .. } => {
@@ -1312,7 +1495,6 @@ pub(crate) fn validate_object_spec(
         | ObjectSpec::SVGAsset { name, .. }
         | ObjectSpec::LottieAsset { name, .. }
         | ObjectSpec::ExportAudio { name, .. }
-        | ObjectSpec::ScriptAsset { name, .. }
         | ObjectSpec::BlobAsset { name, .. }
         | ObjectSpec::CustomPropertyNumber { name, .. }
         | ObjectSpec::CustomPropertyBoolean { name, .. }
@@ -1356,19 +1538,77 @@ pub(crate) fn validate_object_spec(
             }
         }
         ObjectSpec::ClampedScrollPhysics { .. } | ObjectSpec::ElasticScrollPhysics { .. } => {}
-        ObjectSpec::Mesh { name, children } => {
+        ObjectSpec::Mesh {
+            name,
+            skin,
+            children,
+        } => {
             ensure_unique_name(name, object_names)?;
             if let Some(children) = children {
                 for child in children {
                     validate_object_spec(child, object_names, &ParentKind::Artboard)?;
                 }
             }
+            if let Some(skin) = skin {
+                let vertices = children
+                    .iter()
+                    .flatten()
+                    .filter_map(|child| match child {
+                        ObjectSpec::MeshVertex { name, .. }
+                        | ObjectSpec::ContourMeshVertex { name, .. } => Some(name.clone()),
+                        _ => None,
+                    })
+                    .collect::<Vec<_>>();
+                validate_skin(name, skin, &vertices, object_names)?;
+            }
         }
-        ObjectSpec::MeshVertex { name, .. } => {
+        ObjectSpec::MeshGrid {
+            name,
+            columns,
+            rows,
+            width,
+            height,
+            skin,
+        } => {
             ensure_unique_name(name, object_names)?;
+            if *columns == 0 || *rows == 0 {
+                return Err(format!(
+                    "mesh_grid '{}' needs at least one column and one row",
+                    name
+                ));
+            }
+            let vertices = (*columns as u64 + 1) * (*rows as u64 + 1);
+            if vertices > MAX_MESH_VERTICES as u64 {
+                return Err(format!(
+                    "mesh_grid '{}' has {} vertices; at most {} are supported",
+                    name, vertices, MAX_MESH_VERTICES
+                ));
+            }
+            if !(*width > 0.0 && *height > 0.0) {
+                return Err(format!(
+                    "mesh_grid '{}' width and height must be positive",
+                    name
+                ));
+            }
+            let mut vertices = Vec::new();
+            for row in 0..=*rows {
+                for column in 0..=*columns {
+                    let vertex = grid_vertex_name(name, row, column);
+                    ensure_unique_name(&vertex, object_names)?;
+                    vertices.push(vertex);
+                }
+            }
+            if let Some(skin) = skin {
+                validate_skin(name, skin, &vertices, object_names)?;
+            }
         }
-        ObjectSpec::ContourMeshVertex { name, .. } => {
+        ObjectSpec::MeshVertex { name, u, v, .. } => {
+            ensure_unique_name(name, object_names)?;
+            validate_mesh_uv("mesh_vertex", name, *u, *v)?;
+        }
+        ObjectSpec::ContourMeshVertex { name, u, v, .. } => {
             ensure_unique_name(name, object_names)?;
+            validate_mesh_uv("contour_mesh_vertex", name, *u, *v)?;
         }
         ObjectSpec::ForcedEdge { name, .. } => {
             ensure_unique_name(name, object_names)?;
@@ -1407,8 +1647,17 @@ pub(crate) fn validate_object_spec(
         ObjectSpec::DraggableConstraint { name, .. } => {
             ensure_unique_name(name, object_names)?;
         }
-        ObjectSpec::ScrollConstraint { name, children, .. } => {
+        ObjectSpec::ScrollConstraint {
+            name,
+            direction,
+            children,
+            ..
+        } => {
             ensure_unique_name(name, object_names)?;
+            if let Some(direction) = direction {
+                parse_draggable_direction(direction)
+                    .map_err(|e| format!("scroll_constraint '{}': {}", name, e))?;
+            }
             if let Some(children) = children {
                 for child in children {
                     validate_object_spec(child, object_names, &ParentKind::Artboard)?;
@@ -1605,6 +1854,9 @@ impl SpecIndex {
                     type_keys::AUDIO_ASSET => {
                         index.assets.push((name.to_string(), FileAssetKind::Audio));
                     }
+                    type_keys::SCRIPT_ASSET => {
+                        index.assets.push((name.to_string(), FileAssetKind::Script));
+                    }
                     _ => {}
                 }
                 if index.type_keys.insert(name.to_string(), type_key).is_some() {
@@ -1697,6 +1949,9 @@ pub(crate) fn collect_object_type_key(spec: &ObjectSpec, visit: &mut dyn FnMut(&
                 }
             }
         }
+        ObjectSpec::NineSlice { name, .. } => {
+            visit(name, type_keys::IMAGE);
+        }
         ObjectSpec::Image { name, children, .. } => {
             visit(name, type_keys::IMAGE);
             if let Some(children) = children {
@@ -2023,6 +2278,19 @@ pub(crate) fn collect_object_type_key(spec: &ObjectSpec, visit: &mut dyn FnMut(&
                 }
             }
         }
+        ObjectSpec::MeshGrid {
+            name,
+            columns,
+            rows,
+            ..
+        } => {
+            visit(name, type_keys::MESH);
+            for row in 0..=*rows {
+                for column in 0..=*columns {
+                    visit(&grid_vertex_name(name, row, column), type_keys::MESH_VERTEX);
+                }
+            }
+        }
         ObjectSpec::MeshVertex { name, .. } => {
             visit(name, type_keys::MESH_VERTEX);
         }
@@ -2517,6 +2785,67 @@ fn validate_transition_children(
     Ok(())
 }
 
+fn validate_skin(
+    owner: &str,
+    skin: &SkinSpec,
+    vertices: &[String],
+    object_names: &mut HashSet<String>,
+) -> Result<(), String> {
+    if skin.bones.is_empty() || skin.bones.len() > MAX_SKIN_BONES {
+        return Err(format!(
+            "skin on '{}' needs between 1 and {} bones",
+            owner, MAX_SKIN_BONES
+        ));
+    }
+    let mut bones = HashSet::new();
+    for bone in &skin.bones {
+        if !bones.insert(bone) {
+            return Err(format!("skin on '{}' lists bone '{}' twice", owner, bone));
+        }
+    }
+    if let Some(max_influences) = skin.max_influences
+        && !(1..=MAX_INFLUENCES).contains(&max_influences)
+    {
+        return Err(format!(
+            "skin on '{}' max_influences must be between 1 and {}",
+            owner, MAX_INFLUENCES
+        ));
+    }
+    if let Some(radius) = skin.radius {
+        if skin.falloff != SkinFalloff::Heat {
+            return Err(format!(
+                "skin on '{}' radius only applies to the heat falloff",
+                owner
+            ));
+        }
+        if !(radius > 0.0 && radius.is_finite()) {
+            return Err(format!("skin on '{}' radius must be positive", owner));
+        }
+    }
+    ensure_unique_name(&skin_name(owner), object_names)?;
+    for bone in &skin.bones {
+        ensure_unique_name(&tendon_name(owner, bone), object_names)?;
+    }
+    for vertex in vertices {
+        ensure_unique_name(&weight_name(vertex), object_names)?;
+    }
+    Ok(())
+}
+
+fn validate_mesh_uv(kind: &str, name: &str, u: Option<f32>, v: Option<f32>) -> Result<(), String> {
+    for (axis, value) in [("u", u), ("v", v)] {
+        if let Some(value) = value
+            && !(0.0..=1.0).contains(&value)
+        {
+            return Err(format!(
+                "{} '{}' {} must be between 0 and 1",
+                kind, name, axis
+            ));
+        }
+    }
+    Ok(())
+}
+
 fn ensure_unique_name(name: &str, object_names: &mut HashSet<String>) -> Result<(), String> {
     if object_names.contains(name) {
         return Err(format!("duplicate object name '{}'", name));
@@ -2530,7 +2859,8 @@ fn validate_image_asset_references(children: &[ObjectSpec]) -> Result<(), String
         match spec {
             ObjectSpec::ImageAsset { .. }
             | ObjectSpec::FontAsset { .. }
-            | ObjectSpec::AudioAsset { .. } => {}
+            | ObjectSpec::AudioAsset { .. }
+            | ObjectSpec::ScriptAsset { .. } => {}
             ObjectSpec::Image {
                 name,
                 asset_id: Some(asset_id),
@@ -2572,7 +2902,8 @@ fn validate_image_asset_references(children: &[ObjectSpec]) -> Result<(), String
         match spec {
             ObjectSpec::ImageAsset { .. }
             | ObjectSpec::FontAsset { .. }
-            | ObjectSpec::AudioAsset { .. } => {
+            | ObjectSpec::AudioAsset { .. }
+            | ObjectSpec::ScriptAsset { .. } => {
                 *total += 1;
             }
             ObjectSpec::Shape { children, .. }
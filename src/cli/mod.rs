@@ -19,6 +19,42 @@ pub struct Cli {
     #[arg(long, help = "Run as MCP server over stdio")]
     pub mcp: bool,
 
+    #[cfg(feature = "mcp")]
+    #[arg(
+        long = "mcp-http",
+        value_name = "ADDR",
+        conflicts_with = "mcp",
+        help = "Run as MCP server over streamable HTTP on ADDR, e.g. 127.0.0.1:8931. Set RIVE_MCP_TOKEN to require a bearer token"
+    )]
+    pub mcp_http: Option<String>,
+
+    #[cfg(feature = "mcp")]
+    #[arg(
+        long = "mcp-root",
+        value_name = "DIR",
+        requires = "mcp_http",
+        help = "Directory HTTP sessions are confined to (default: the current directory)"
+    )]
+    pub mcp_root: Option<PathBuf>,
+
+    #[cfg(feature = "mcp")]
+    #[arg(
+        long = "mcp-allow-root",
+        value_name = "DIR",
+        requires = "mcp",
+        help = "Directory stdio MCP tools may read and write; repeat for several (default: unrestricted)"
+    )]
+    pub mcp_allow_root: Vec<PathBuf>,
+
+    #[cfg(feature = "mcp")]
+    #[arg(
+        long = "mcp-max-file-bytes",
+        value_name = "BYTES",
+        default_value_t = rive_cli::mcp::DEFAULT_MAX_FILE_BYTES,
+        help = "Largest file MCP tools read or embed as an asset source"
+    )]
+    pub mcp_max_file_bytes: u64,
+
     #[command(subcommand)]
     pub command: Option<Command>,
 }
@@ -27,7 +63,7 @@ pub struct Cli {
 pub enum Command {
     #[command(
         about = "Generate a .riv file from a JSON scene spec",
-        long_about = "Generate a .riv file from a JSON scene spec.\n\nExamples:\n  rive-cli generate scene.json\n  rive-cli generate scene.json -o my_animation.riv\n  rive-cli generate scene.json --file-id 42"
+        long_about = "Generate a .riv file from a JSON scene spec.\n\nExamples:\n  rive-cli generate scene.json\n  rive-cli generate scene.json -o my_animation.riv\n  rive-cli generate scene.json --file-id 42\n  rive-cli generate scene.json -o web/hero.riv --assets external"
     )]
     Generate {
         #[arg(help = "Path to the JSON scene input")]
@@ -36,6 +72,19 @@ pub enum Command {
         output: PathBuf,
         #[arg(long, default_value = "0", help = "Rive file id written in header")]
         file_id: u64,
+        #[arg(
+            long,
+            default_value = "embedded",
+            value_parser = ["embedded", "external"],
+            help = "How asset sources reach the runtime: embedded in the .riv, or external files beside it with a manifest"
+        )]
+        assets: String,
+        #[arg(
+            long = "assets-dir",
+            value_name = "DIR",
+            help = "Folder for --assets external files and manifest.json (defaults to <output stem>_assets beside the output)"
+        )]
+        assets_dir: Option<PathBuf>,
         #[arg(long, help = "Output as JSON")]
         json: bool,
     },
@@ -172,10 +221,24 @@ pub enum Command {
         #[arg(
             long = "pointer",
             value_name = "EVENT:X,Y@FRAME",
-            help = "Dispatch a pointer event in artboard coordinates at a frame, e.g. down:120,90@10. EVENT is down, up, move, enter or exit. Requires --state-machine. Repeatable."
+            help = "Dispatch a pointer event in artboard coordinates at a frame, e.g. down:120,90@10. EVENT is down, up, move, enter or exit. drag:X1,Y1->X2,Y2@FROM-TO presses at FROM, moves once per frame along the line and releases at TO, e.g. drag:200,300->200,100@10-40; a drag spans at most 10000 frames. Requires --state-machine. Repeatable."
         )]
         pointers: Vec<String>,
 
+        #[arg(
+            long = "view-model",
+            value_name = "PROP=VALUE[@FRAME]",
+            help = "Set a view model property: number, true/false, #RRGGBB color, 'trigger', or text for string and enum properties (quote it to force a string, e.g. title=\"42\"). Append @FRAME to apply it at that frame. Repeatable."
+        )]
+        view_model: Vec<String>,
+
+        #[arg(
+            long = "view-model-instance",
+            value_name = "NAME",
+            help = "Bind this named view model instance instead of the default instance"
+        )]
+        view_model_instance: Option<String>,
+
         #[arg(long, help = "Artboard name (defaults to the default artboard)")]
         artboard: Option<String>,
 
@@ -208,6 +271,13 @@ pub enum Command {
         )]
         browser: Option<PathBuf>,
 
+        #[arg(
+            long,
+            value_name = "DIR",
+            help = "Directory written by 'generate --assets external'; its assets are supplied to the runtime's asset loader"
+        )]
+        assets: Option<PathBuf>,
+
         #[arg(long, help = "Output as JSON")]
         json: bool,
     },
@@ -268,6 +338,20 @@ pub enum Command {
         )]
         candidate_state_machine: Option<String>,
 
+        #[arg(
+            long = "view-model",
+            value_name = "PROP=VALUE[@FRAME]",
+            help = "Set a view model property on both files before rendering. Repeatable."
+        )]
+        view_model: Vec<String>,
+
+        #[arg(
+            long = "view-model-instance",
+            value_name = "NAME",
+            help = "Bind this named view model instance in both files"
+        )]
+        view_model_instance: Option<String>,
+
         #[arg(
             long = "max-pixel-diff",
             value_name = "PCT",
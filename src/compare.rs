@@ -24,6 +24,8 @@ pub struct CompareOptions {
     pub candidate_animation: Option<String>,
     pub reference_state_machine: Option<String>,
     pub candidate_state_machine: Option<String>,
+    pub view_model: Vec<String>,
+    pub view_model_instance: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -208,6 +210,8 @@ fn render_side(
         state_machine,
         inputs: Vec::new(),
         pointers: Vec::new(),
+        view_model: options.view_model.clone(),
+        view_model_instance: options.view_model_instance.clone(),
         artboard: None,
         width: options.width,
         height: options.height,
@@ -216,6 +220,7 @@ fn render_side(
         contact_sheet: false,
         preview: false,
         browser: None,
+        assets_dir: None,
     };
     render(&render_options).map_err(|error| {
         RenderError::Message(format!(
@@ -265,10 +265,12 @@ fn category_for(name: &str) -> &'static str {
         | "joystick"
         | "guide"
         | "mesh"
+        | "mesh_grid"
         | "mesh_vertex"
         | "contour_mesh_vertex"
         | "forced_edge"
-        | "n_sliced_node" => "shape",
+        | "n_sliced_node"
+        | "nine_slice" => "shape",
         "path"
         | "points_path"
         | "straight_vertex"
@@ -384,6 +386,8 @@ fn summary_for(name: &str) -> String {
         "text_modifier_group" => "Groups modifiers that alter text layout or glyphs.",
         "bone" => "Defines a transformable bone in a skeletal hierarchy.",
         "root_bone" => "Defines the root of a skeletal hierarchy.",
+        "mesh_grid" => "Deforms an image with a generated grid of mesh vertices.",
+        "nine_slice" => "Draws an image as a 9-slice frame whose corners keep their size.",
         "skin" => "Binds mesh geometry to a bone hierarchy.",
         "tendon" => "Connects bones for coordinated deformation.",
         "weight" => "Assigns a bone influence to a mesh vertex.",
@@ -464,6 +468,8 @@ fn enum_values_for(type_name: &str, field_name: &str, schema: &Value) -> Vec<Str
         ("stroke", "join") => &["miter", "round", "bevel"][..],
         ("fill" | "clipping_shape", "fill_rule") => &["nonzero", "evenodd"][..],
         ("trim_path", "mode") => &["sequential", "synchronized"][..],
+        ("nine_slice", "edges" | "center") => &["stretch", "repeat", "hidden"][..],
+        ("scroll_constraint", "direction") => &["horizontal", "vertical", "all"][..],
         _ => &[],
     };
     values.iter().map(|value| (*value).to_owned()).collect()
@@ -637,6 +643,13 @@ fn example_for(name: &str, properties: &Map<String, Value>, required: &[&str]) -
         "image" => {
             serde_json::json!({"type":"shape", "name":"shape", "children":[{"type":"image_asset", "name":"asset"}, object]})
         }
+        "nine_slice" => {
+            let mut object = object;
+            object["asset"] = Value::String("asset".to_owned());
+            object["image_width"] = Value::from(32);
+            object["image_height"] = Value::from(32);
+            object
+        }
         "points_path" => serde_json::json!({"type":"shape", "name":"shape", "children":[object]}),
         "straight_vertex"
         | "cubic_mirrored_vertex"
@@ -660,6 +673,9 @@ fn example_for(name: &str, properties: &Map<String, Value>, required: &[&str]) -
     };
     let children = match bone_example(name) {
         Some(objects) => Value::Array(objects),
+        None if name == "nine_slice" => {
+            serde_json::json!([{"type":"image_asset", "name":"asset"}, context])
+        }
         None => serde_json::json!([context]),
     };
     let scene = serde_json::json!({
@@ -824,12 +840,22 @@ mod tests {
     }
 
     #[test]
-    fn stroke_thickness_is_not_animatable() {
+    fn stroke_thickness_and_corner_radius_are_animatable() {
+        assert_eq!(
+            describe("stroke").unwrap().animatable,
+            ["is_visible", "thickness"]
+        );
+        assert!(
+            describe("rectangle")
+                .unwrap()
+                .animatable
+                .contains(&"corner_radius".to_owned())
+        );
         assert!(
-            !describe("stroke")
+            !describe("ellipse")
                 .unwrap()
                 .animatable
-                .contains(&"thickness".to_owned())
+                .contains(&"corner_radius".to_owned())
         );
     }
 
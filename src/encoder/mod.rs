@@ -208,4 +208,57 @@ mod tests {
         writer.write_varuint(0);
         assert_eq!(result, writer.finish());
     }
+
+    #[test]
+    fn test_mesh_triangle_indices_round_trip_through_the_file() {
+        use crate::objects::core::property_keys;
+        use crate::objects::mesh::Mesh;
+        use crate::validator::{BinaryReader, PropertyValueRead};
+
+        let artboard = Artboard::new("Test".to_string(), 500.0, 500.0);
+        let mut mesh = Mesh::new("m".to_string(), 0);
+        mesh.triangle_indices = vec![0, 1, 2, 2, 127, 128, 300, 4095, 65535];
+
+        let encoded = encode_object(&mesh);
+        let mut reader = BinaryReader::new(&encoded);
+        assert_eq!(reader.read_varuint(), Some(109));
+        let mut payload = None;
+        while let Some(key) = reader.read_varuint().filter(|key| *key != 0) {
+            match key as u16 {
+                property_keys::MESH_TRIANGLE_INDEX_BYTES => {
+                    let length = reader.read_varuint().unwrap() as usize;
+                    payload = reader.read_bytes(length);
+                }
+                property_keys::COMPONENT_NAME => {
+                    reader.read_string().unwrap();
+                }
+                _ => {
+                    reader.read_varuint().unwrap();
+                }
+            }
+        }
+        let payload = payload.expect("mesh writes its triangle index bytes");
+        let mut indices = BinaryReader::new(payload);
+        let mut decoded = Vec::new();
+        while let Some(index) = indices.read_varuint() {
+            decoded.push(u16::try_from(index).unwrap());
+        }
+        assert_eq!(decoded, mesh.triangle_indices);
+
+        let data = encode_riv(&[&Backboard, &artboard, &mesh], 0);
+        let parsed =
+            crate::validator::parse_riv(&data, &crate::validator::InspectFilter::default())
+                .unwrap();
+        let property = parsed.objects[2]
+            .properties
+            .iter()
+            .find(|property| property.key == property_keys::MESH_TRIANGLE_INDEX_BYTES)
+            .unwrap();
+        assert_eq!(
+            property.value,
+            PropertyValueRead::Bytes {
+                length: payload.len()
+            }
+        );
+    }
 }
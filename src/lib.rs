@@ -1,4 +1,8 @@
-#![allow(clippy::len_without_is_empty, clippy::new_without_default)]
+#![allow(
+    clippy::collapsible_match,
+    clippy::len_without_is_empty,
+    clippy::new_without_default
+)]
 
 pub mod ai;
 pub mod authoring;
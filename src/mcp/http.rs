@@ -0,0 +1,238 @@
+use std::convert::Infallible;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use bytes::Bytes;
+use http_body_util::{BodyExt, Full, combinators::BoxBody};
+use hyper::{Request, Response, StatusCode, body::Incoming, header, service::service_fn};
+use hyper_util::rt::TokioIo;
+use rmcp::transport::streamable_http_server::{
+    StreamableHttpServerConfig, StreamableHttpService, session::local::LocalSessionManager,
+};
+use tokio::net::TcpListener;
+use tokio::task::JoinSet;
+use tokio_util::sync::CancellationToken;
+
+use super::{PathPolicy, RiveMcpServer};
+
+/// Environment variable holding the bearer token HTTP clients must present.
+pub const BEARER_TOKEN_VAR: &str = "RIVE_MCP_TOKEN";
+/// Path the streamable HTTP endpoint is served on.
+pub const ENDPOINT_PATH: &str = "/mcp";
+
+const DRAIN_TIMEOUT: Duration = Duration::from_secs(10);
+
+type HttpResponse = Response<BoxBody<Bytes, Infallible>>;
+
+pub struct HttpServerOptions {
+    /// Directory every session is confined to. Clients may narrow their own
+    /// session further with the `x-rive-workdir` header on `initialize`.
+    pub root: PathBuf,
+    /// When set, every request must carry `Authorization: Bearer <token>`.
+    pub bearer_token: Option<String>,
+    /// Largest file a tool reads or embeds as an asset.
+    pub max_file_bytes: u64,
+}
+
+/// Serves the MCP streamable HTTP transport on `listener` until `shutdown` is
+/// cancelled, then stops accepting connections and drains the open ones.
+pub async fn serve_http(
+    listener: TcpListener,
+    options: HttpServerOptions,
+    shutdown: CancellationToken,
+) -> std::io::Result<()> {
+    let policy = Arc::new(PathPolicy::new(
+        std::slice::from_ref(&options.root),
+        options.max_file_bytes,
+    )?);
+    let root = options.root.canonicalize()?;
+    let config = StreamableHttpServerConfig {
+        cancellation_token: shutdown.child_token(),
+        ..StreamableHttpServerConfig::default()
+    };
+    let service = StreamableHttpService::new(
+        move || Ok(RiveMcpServer::sandboxed(root.clone(), policy.clone())),
+        Arc::new(LocalSessionManager::default()),
+        config,
+    );
+    let bearer_token = options.bearer_token.map(Arc::<str>::from);
+
+    let mut connections = JoinSet::new();
+    loop {
+        let stream = tokio::select! {
+            _ = shutdown.cancelled() => break,
+            accepted = listener.accept() => match accepted {
+                Ok((stream, _)) => stream,
+                Err(error) => {
+                    eprintln!("MCP HTTP accept failed: {error}");
+                    continue;
+                }
+            },
+        };
+        let service = service.clone();
+        let bearer_token = bearer_token.clone();
+        let shutdown = shutdown.clone();
+        connections.spawn(async move {
+            let handler = service_fn(move |request: Request<Incoming>| {
+                let service = service.clone();
+                let bearer_token = bearer_token.clone();
+                async move {
+                    Ok::<_, Infallible>(route(&service, bearer_token.as_deref(), request).await)
+                }
+            });
+            let connection = hyper::server::conn::http1::Builder::new()
+                .serve_connection(TokioIo::new(stream), handler);
+            tokio::pin!(connection);
+            tokio::select! {
+                _ = connection.as_mut() => {}
+                _ = shutdown.cancelled() => {
+                    connection.as_mut().graceful_shutdown();
+                    let _ = connection.await;
+                }
+            }
+        });
+    }
+
+    drop(listener);
+    let drained = tokio::time::timeout(DRAIN_TIMEOUT, async {
+        while connections.join_next().await.is_some() {}
+    })
+    .await;
+    if drained.is_err() {
+        connections.abort_all();
+    }
+    Ok(())
+}
+
+async fn route(
+    service: &StreamableHttpService<RiveMcpServer, LocalSessionManager>,
+    bearer_token: Option<&str>,
+    request: Request<Incoming>,
+) -> HttpResponse {
+    if request.uri().path() != ENDPOINT_PATH {
+        return plain(StatusCode::NOT_FOUND, "not found; the MCP endpoint is /mcp");
+    }
+    if let Some(expected) = bearer_token
+        && !authorized(&request, expected)
+    {
+        let mut response = plain(StatusCode::UNAUTHORIZED, "missing or invalid bearer token");
+        response.headers_mut().insert(
+            header::WWW_AUTHENTICATE,
+            header::HeaderValue::from_static("Bearer"),
+        );
+        return response;
+    }
+    service.handle(request).await
+}
+
+fn authorized(request: &Request<Incoming>, expected: &str) -> bool {
+    let Some(presented) = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+    else {
+        return false;
+    };
+    presented.len() == expected.len()
+        && presented
+            .bytes()
+            .zip(expected.bytes())
+            .fold(0u8, |difference, (left, right)| difference | (left ^ right))
+            == 0
+}
+
+fn plain(status: StatusCode, message: &'static str) -> HttpResponse {
+    let mut response = Response::new(Full::new(Bytes::from_static(message.as_bytes())).boxed());
+    *response.status_mut() = status;
+    response
+}
+
+async fn shutdown_signal() {
+    #[cfg(unix)]
+    {
+        let mut terminate =
+            match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+                Ok(signal) => signal,
+                Err(_) => {
+                    let _ = tokio::signal::ctrl_c().await;
+                    return;
+                }
+            };
+        tokio::select! {
+            _ = tokio::signal::ctrl_c() => {}
+            _ = terminate.recv() => {}
+        }
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = tokio::signal::ctrl_c().await;
+    }
+}
+
+pub fn run_http_server(addr: &str, root: Option<PathBuf>, max_file_bytes: u64) {
+    let runtime = match tokio::runtime::Runtime::new() {
+        Ok(runtime) => runtime,
+        Err(e) => {
+            eprintln!("failed to create tokio runtime: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    runtime.block_on(async {
+        let root = match root {
+            Some(root) => root,
+            None => match std::env::current_dir() {
+                Ok(root) => root,
+                Err(e) => {
+                    eprintln!("failed to read the current directory: {}", e);
+                    std::process::exit(1);
+                }
+            },
+        };
+        let listener = match TcpListener::bind(addr).await {
+            Ok(listener) => listener,
+            Err(e) => {
+                eprintln!("failed to bind MCP HTTP server to {}: {}", addr, e);
+                std::process::exit(1);
+            }
+        };
+        let bearer_token = std::env::var(BEARER_TOKEN_VAR)
+            .ok()
+            .filter(|token| !token.is_empty());
+        match listener.local_addr() {
+            Ok(local) => eprintln!(
+                "MCP HTTP server listening on http://{}{} (root {}{})",
+                local,
+                ENDPOINT_PATH,
+                root.display(),
+                if bearer_token.is_some() {
+                    ", bearer token required"
+                } else {
+                    ""
+                }
+            ),
+            Err(e) => eprintln!("MCP HTTP server listening (address unavailable: {})", e),
+        }
+
+        let shutdown = CancellationToken::new();
+        tokio::spawn({
+            let shutdown = shutdown.clone();
+            async move {
+                shutdown_signal().await;
+                eprintln!("MCP HTTP server shutting down");
+                shutdown.cancel();
+            }
+        });
+        let options = HttpServerOptions {
+            root,
+            bearer_token,
+            max_file_bytes,
+        };
+        if let Err(e) = serve_http(listener, options, shutdown).await {
+            eprintln!("MCP HTTP server error: {}", e);
+            std::process::exit(1);
+        }
+    });
+}
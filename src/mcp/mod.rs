@@ -1,19 +1,40 @@
+mod http;
+mod policy;
+mod resources;
+mod sandbox;
+
+pub use http::{BEARER_TOKEN_VAR, ENDPOINT_PATH, HttpServerOptions, run_http_server, serve_http};
+pub use policy::{DEFAULT_MAX_FILE_BYTES, PathPolicy};
+pub use sandbox::WORKDIR_HEADER;
+
+use base64::Engine;
 use rmcp::{
     ErrorData as McpError, RoleServer,
     handler::server::{tool::ToolRouter, wrapper::Parameters},
     model::{
-        CallToolResult, Content, ErrorCode, Implementation, ListResourcesResult,
-        PaginatedRequestParams, ProtocolVersion, RawResource, ReadResourceRequestParams,
-        ReadResourceResult, Resource, ResourceContents, ServerCapabilities, ServerInfo,
+        CallToolResult, Content, ErrorCode, Implementation, InitializeRequestParams,
+        InitializeResult, ListResourcesResult, PaginatedRequestParams, ProtocolVersion,
+        ReadResourceRequestParams, ReadResourceResult, ResourceContents, ServerCapabilities,
+        ServerInfo,
     },
     service::ServiceExt,
     tool, tool_handler, tool_router,
 };
 use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+const DEFAULT_RENDER_FRAMES: &str = "0";
+const DEFAULT_RENDER_FPS: f64 = 60.0;
+const DEFAULT_RENDER_DIMENSION: u32 = 512;
+const DEFAULT_RENDER_SCALE: u32 = 2;
+
+static RENDER_SEQUENCE: AtomicU64 = AtomicU64::new(0);
 
 /// Shared helper for inspect and decompile: reads a .riv file, parses its
 /// object tree, and returns the result as pretty-printed JSON.
-async fn parse_and_serialize_riv(file_path: &str) -> Result<CallToolResult, McpError> {
+async fn parse_and_serialize_riv(file_path: &Path) -> Result<CallToolResult, McpError> {
     let bytes = tokio::fs::read(file_path).await.map_err(|e| {
         McpError::new(
             ErrorCode::INVALID_PARAMS,
@@ -36,19 +57,145 @@ async fn parse_and_serialize_riv(file_path: &str) -> Result<CallToolResult, McpE
     }
 }
 
+fn json_result<T: serde::Serialize>(value: &T) -> Result<CallToolResult, McpError> {
+    let json = serde_json::to_string_pretty(value)
+        .map_err(|e| McpError::new(ErrorCode::INTERNAL_ERROR, e.to_string(), None))?;
+    Ok(CallToolResult::success(vec![Content::text(json)]))
+}
+
+fn invalid_params(message: impl Into<String>) -> McpError {
+    McpError::new(ErrorCode::INVALID_PARAMS, message.into(), None)
+}
+
+fn encode_scene(
+    spec: &crate::builder::SceneSpec,
+    file_id: u64,
+    base_dir: Option<&Path>,
+    policy: &crate::builder::AssetPolicy,
+) -> Result<Vec<u8>, McpError> {
+    let scene = crate::builder::build_scene_with_policy(spec, base_dir, policy)
+        .map_err(|e| invalid_params(format!("invalid scene: {}", e)))?;
+    let refs: Vec<&dyn crate::objects::core::RiveObject> = scene.iter().map(|o| &**o).collect();
+    Ok(crate::encoder::encode_riv(&refs, file_id))
+}
+
+fn write_output(path: &Path, bytes: &[u8]) -> Result<(), McpError> {
+    std::fs::write(path, bytes).map_err(|e| {
+        McpError::new(
+            ErrorCode::INTERNAL_ERROR,
+            format!("write error: {}", e),
+            None,
+        )
+    })
+}
+
+fn frame_list(frames: Option<&str>) -> Result<Vec<u32>, McpError> {
+    crate::render::parse_frame_spec(frames.unwrap_or(DEFAULT_RENDER_FRAMES))
+        .map_err(|e| invalid_params(format!("invalid frames value: {}", e)))
+}
+
+/// A per-call render directory, removed once its frames have been read back.
+struct ScratchRenderDir(PathBuf);
+
+impl ScratchRenderDir {
+    fn new() -> Self {
+        Self(std::env::temp_dir().join(format!(
+            "rive-cli-mcp-render-{}-{}",
+            std::process::id(),
+            RENDER_SEQUENCE.fetch_add(1, Ordering::Relaxed)
+        )))
+    }
+}
+
+impl Drop for ScratchRenderDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+async fn run_blocking<T, F>(work: F) -> Result<T, McpError>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Result<T, crate::render::RenderError> + Send + 'static,
+{
+    tokio::task::spawn_blocking(work)
+        .await
+        .map_err(|e| McpError::new(ErrorCode::INTERNAL_ERROR, e.to_string(), None))?
+        .map_err(|e| McpError::new(ErrorCode::INTERNAL_ERROR, e.to_string(), None))
+}
+
 #[derive(Clone)]
 pub struct RiveMcpServer {
     tool_router: ToolRouter<Self>,
+    sandbox: Option<sandbox::SessionSandbox>,
+    policy: Arc<PathPolicy>,
 }
 
-#[tool_router]
 impl RiveMcpServer {
-    pub fn new() -> Self {
+    /// A server whose tools only touch files under `root`, which must already
+    /// be canonical. Used for every HTTP session.
+    fn sandboxed(root: PathBuf, policy: Arc<PathPolicy>) -> Self {
+        Self {
+            tool_router: Self::tool_router(),
+            sandbox: Some(sandbox::SessionSandbox::new(root)),
+            policy,
+        }
+    }
+
+    /// A server whose tools may only touch paths `policy` allows.
+    pub fn with_policy(policy: PathPolicy) -> Self {
         Self {
             tool_router: Self::tool_router(),
+            sandbox: None,
+            policy: Arc::new(policy),
         }
     }
 
+    /// Resolves a path a tool writes to or treats as a directory.
+    fn path(&self, raw: &str) -> Result<PathBuf, McpError> {
+        let path = match &self.sandbox {
+            Some(sandbox) => sandbox.resolve(raw).map_err(invalid_params)?,
+            None => PathBuf::from(raw),
+        };
+        self.policy.confine(path)
+    }
+
+    /// Resolves a file a tool reads, enforcing the size cap as well.
+    fn readable(&self, raw: &str) -> Result<PathBuf, McpError> {
+        let path = match &self.sandbox {
+            Some(sandbox) => sandbox.resolve(raw).map_err(invalid_params)?,
+            None => PathBuf::from(raw),
+        };
+        self.policy.readable(path)
+    }
+
+    /// Directory asset `source` paths resolve against when a tool builds a
+    /// scene: `base_dir` when given, else the session working directory.
+    fn asset_base(&self, base_dir: Option<&str>) -> Result<Option<PathBuf>, McpError> {
+        match base_dir {
+            Some(base_dir) => self.path(base_dir).map(Some),
+            None => Ok(self.sandbox.as_ref().map(sandbox::SessionSandbox::workdir)),
+        }
+    }
+
+    /// Limits for embedded asset `source` files. A session's working
+    /// directory sits inside the server root, so it replaces the root as the
+    /// only place assets may be read from.
+    fn asset_policy(&self) -> crate::builder::AssetPolicy {
+        let mut assets = self.policy.assets();
+        if let Some(sandbox) = &self.sandbox {
+            assets.allowed_roots = vec![sandbox.workdir()];
+        }
+        assets
+    }
+}
+
+#[tool_router]
+impl RiveMcpServer {
+    pub fn new() -> Self {
+        Self::with_policy(PathPolicy::default())
+    }
+
     #[tool(
         name = "generate",
         description = "Generate a .riv file from a SceneSpec JSON string. Returns hex-encoded bytes or writes to file."
@@ -65,29 +212,21 @@ impl RiveMcpServer {
                 None,
             )
         })?;
-        let scene = crate::builder::build_scene(&spec, None).map_err(|e| {
-            McpError::new(
-                ErrorCode::INVALID_PARAMS,
-                format!("invalid scene: {}", e),
-                None,
-            )
-        })?;
-        let refs: Vec<&dyn crate::objects::core::RiveObject> = scene.iter().map(|o| &**o).collect();
-        let file_id = params.0.file_id.unwrap_or(0);
-        let bytes = crate::encoder::encode_riv(&refs, file_id);
+        let base_dir = self.asset_base(params.0.base_dir.as_deref())?;
+        let bytes = encode_scene(
+            &spec,
+            params.0.file_id.unwrap_or(0),
+            base_dir.as_deref(),
+            &self.asset_policy(),
+        )?;
 
-        if let Some(path) = &params.0.output {
-            std::fs::write(path, &bytes).map_err(|e| {
-                McpError::new(
-                    ErrorCode::INTERNAL_ERROR,
-                    format!("write error: {}", e),
-                    None,
-                )
-            })?;
+        if let Some(output) = &params.0.output {
+            let path = self.path(output)?;
+            write_output(&path, &bytes)?;
             Ok(CallToolResult::success(vec![Content::text(format!(
                 "wrote {} bytes to {}",
                 bytes.len(),
-                path
+                path.display()
             ))]))
         } else {
             let encoded: String = bytes.iter().map(|byte| format!("{byte:02x}")).collect();
@@ -103,13 +242,15 @@ impl RiveMcpServer {
         &self,
         params: Parameters<ValidateParams>,
     ) -> Result<CallToolResult, McpError> {
-        let bytes = tokio::fs::read(&params.0.file).await.map_err(|e| {
-            McpError::new(
-                ErrorCode::INVALID_PARAMS,
-                format!("read error: {}", e),
-                None,
-            )
-        })?;
+        let bytes = tokio::fs::read(self.readable(&params.0.file)?)
+            .await
+            .map_err(|e| {
+                McpError::new(
+                    ErrorCode::INVALID_PARAMS,
+                    format!("read error: {}", e),
+                    None,
+                )
+            })?;
         match crate::validator::validate_riv(&bytes) {
             Ok(report) => {
                 let json = serde_json::to_string_pretty(&report)
@@ -131,7 +272,7 @@ impl RiveMcpServer {
         &self,
         params: Parameters<FilePathParams>,
     ) -> Result<CallToolResult, McpError> {
-        parse_and_serialize_riv(&params.0.file).await
+        parse_and_serialize_riv(&self.readable(&params.0.file)?).await
     }
 
     #[tool(
@@ -142,7 +283,7 @@ impl RiveMcpServer {
         &self,
         params: Parameters<FilePathParams>,
     ) -> Result<CallToolResult, McpError> {
-        parse_and_serialize_riv(&params.0.file).await
+        parse_and_serialize_riv(&self.readable(&params.0.file)?).await
     }
 
     #[tool(
@@ -150,10 +291,219 @@ impl RiveMcpServer {
         description = "List available animation templates."
     )]
     async fn list_templates(&self) -> Result<CallToolResult, McpError> {
-        let templates = crate::ai::templates::list_templates();
-        let json = serde_json::to_string_pretty(&templates)
+        json_result(&crate::ai::templates::list_templates())
+    }
+
+    #[tool(
+        name = "types",
+        description = "List the object types usable in a SceneSpec, optionally filtered by category."
+    )]
+    async fn types(&self, params: Parameters<TypesParams>) -> Result<CallToolResult, McpError> {
+        let category = params.0.category.as_deref();
+        let types = crate::discovery::list_types(category);
+        if types.is_empty() {
+            return Err(invalid_params(format!(
+                "unknown category: {}; valid categories: {}",
+                category.unwrap_or_default(),
+                crate::discovery::categories().join(", ")
+            )));
+        }
+        json_result(&types)
+    }
+
+    #[tool(
+        name = "describe",
+        description = "Describe one SceneSpec object type: valid parents, fields, enum values and animatable properties."
+    )]
+    async fn describe(
+        &self,
+        params: Parameters<DescribeParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let type_name = &params.0.type_name;
+        let Some(description) = crate::discovery::describe(type_name) else {
+            let mut message = format!("unknown object type: '{}'", type_name);
+            if let Some(closest) = crate::discovery::closest_type(type_name) {
+                message.push_str(&format!("; did you mean '{}'?", closest));
+            }
+            return Err(invalid_params(message));
+        };
+        json_result(&description)
+    }
+
+    #[tool(
+        name = "new",
+        description = "Return a starter SceneSpec from a scaffold template, or write it to a file."
+    )]
+    async fn new_scene(&self, params: Parameters<NewParams>) -> Result<CallToolResult, McpError> {
+        let template = &params.0.template;
+        let scene = crate::scaffold::template_json(template).map_err(|_| {
+            let names = crate::scaffold::templates()
+                .iter()
+                .map(|info| info.name)
+                .collect::<Vec<_>>();
+            invalid_params(format!(
+                "unknown template '{}'; available: {}",
+                template,
+                names.join(", ")
+            ))
+        })?;
+        match &params.0.output {
+            Some(output) => {
+                let path = self.path(output)?;
+                write_output(&path, scene.as_bytes())?;
+                Ok(CallToolResult::success(vec![Content::text(format!(
+                    "wrote {} template to {}",
+                    template,
+                    path.display()
+                ))]))
+            }
+            None => Ok(CallToolResult::success(vec![Content::text(scene)])),
+        }
+    }
+
+    #[tool(
+        name = "compile_authoring",
+        description = "Compile an AuthoringSpec v0 JSON document to SceneSpec plus its source map. Failures return every diagnostic with its authored path. Optionally writes the .riv."
+    )]
+    async fn compile_authoring(
+        &self,
+        params: Parameters<CompileAuthoringParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let lowered = match crate::authoring::lower_authoring_json(&params.0.authoring_json) {
+            Ok(lowered) => lowered,
+            Err(error) => {
+                let json = serde_json::to_string_pretty(
+                    &serde_json::json!({ "diagnostics": error.diagnostics }),
+                )
+                .map_err(|e| McpError::new(ErrorCode::INTERNAL_ERROR, e.to_string(), None))?;
+                return Ok(CallToolResult::error(vec![Content::text(json)]));
+            }
+        };
+        let mut contents = vec![Content::text(
+            serde_json::to_string_pretty(&lowered)
+                .map_err(|e| McpError::new(ErrorCode::INTERNAL_ERROR, e.to_string(), None))?,
+        )];
+        if let Some(output) = &params.0.output {
+            let path = self.path(output)?;
+            let spec: crate::builder::SceneSpec = serde_json::from_value(lowered.scene)
+                .map_err(|e| invalid_params(format!("invalid lowered scene: {}", e)))?;
+            let base_dir = self.asset_base(None)?;
+            let bytes = encode_scene(&spec, 0, base_dir.as_deref(), &self.asset_policy())?;
+            write_output(&path, &bytes)?;
+            contents.push(Content::text(format!(
+                "wrote {} bytes to {}",
+                bytes.len(),
+                path.display()
+            )));
+        }
+        Ok(CallToolResult::success(contents))
+    }
+
+    #[tool(
+        name = "render",
+        description = "Render frames of a .riv file through headless Chromium. Returns the render manifest followed by every frame as a PNG image."
+    )]
+    async fn render(&self, params: Parameters<RenderParams>) -> Result<CallToolResult, McpError> {
+        let params = params.0;
+        if self.sandbox.is_some() && params.browser.is_some() {
+            return Err(invalid_params(
+                "browser overrides are not accepted by a sandboxed server",
+            ));
+        }
+        let browser = self.policy.browser(params.browser)?;
+        let source_path = self.readable(&params.file)?;
+        let riv = tokio::fs::read(&source_path)
+            .await
+            .map_err(|e| invalid_params(format!("read error: {}", e)))?;
+        let mut scratch = None;
+        let output_dir = match &params.output_dir {
+            Some(output_dir) => self.path(output_dir)?,
+            None => scratch.insert(ScratchRenderDir::new()).0.clone(),
+        };
+        let options = crate::render::RenderOptions {
+            riv,
+            source_path,
+            output_dir: output_dir.clone(),
+            frames: frame_list(params.frames.as_deref())?,
+            fps: params.fps.unwrap_or(DEFAULT_RENDER_FPS),
+            animation: params.animation,
+            state_machine: params.state_machine,
+            inputs: params.inputs,
+            pointers: params.pointers,
+            view_model: params.view_model,
+            view_model_instance: params.view_model_instance,
+            artboard: params.artboard,
+            width: params.width.unwrap_or(DEFAULT_RENDER_DIMENSION),
+            height: params.height.unwrap_or(DEFAULT_RENDER_DIMENSION),
+            scale: params.scale.unwrap_or(DEFAULT_RENDER_SCALE),
+            background: params.background,
+            contact_sheet: false,
+            preview: false,
+            browser,
+            assets_dir: None,
+        };
+        let manifest = run_blocking(move || crate::render::render(&options)).await?;
+        let mut contents = vec![Content::text(
+            serde_json::to_string_pretty(&manifest)
+                .map_err(|e| McpError::new(ErrorCode::INTERNAL_ERROR, e.to_string(), None))?,
+        )];
+        for frame in &manifest.frames {
+            let bytes = tokio::fs::read(output_dir.join(&frame.filename))
+                .await
+                .map_err(|e| {
+                    McpError::new(
+                        ErrorCode::INTERNAL_ERROR,
+                        format!("could not read rendered frame {}: {}", frame.filename, e),
+                        None,
+                    )
+                })?;
+            contents.push(Content::image(
+                base64::engine::general_purpose::STANDARD.encode(bytes),
+                "image/png",
+            ));
+        }
+        Ok(CallToolResult::success(contents))
+    }
+
+    #[tool(
+        name = "compare",
+        description = "Compare a reference .riv against a candidate: per-type object deltas and per-frame pixel difference. With max_pixel_diff, a worse frame marks the result as an error."
+    )]
+    async fn compare(&self, params: Parameters<CompareParams>) -> Result<CallToolResult, McpError> {
+        let params = params.0;
+        if let Some(threshold) = params.max_pixel_diff
+            && (!threshold.is_finite() || !(0.0..=100.0).contains(&threshold))
+        {
+            return Err(invalid_params(format!(
+                "max_pixel_diff must be a finite percentage between 0 and 100, got {threshold}"
+            )));
+        }
+        let options = crate::compare::CompareOptions {
+            reference: self.readable(&params.reference)?,
+            candidate: self.readable(&params.candidate)?,
+            frames: frame_list(params.frames.as_deref())?,
+            width: params.width.unwrap_or(DEFAULT_RENDER_DIMENSION),
+            height: params.height.unwrap_or(DEFAULT_RENDER_DIMENSION),
+            scale: params.scale.unwrap_or(DEFAULT_RENDER_SCALE),
+            background: params.background,
+            reference_animation: params.reference_animation,
+            candidate_animation: params.candidate_animation,
+            reference_state_machine: params.reference_state_machine,
+            candidate_state_machine: params.candidate_state_machine,
+            view_model: params.view_model,
+            view_model_instance: params.view_model_instance,
+        };
+        let report = run_blocking(move || crate::compare::compare(&options)).await?;
+        let json = serde_json::to_string_pretty(&report)
             .map_err(|e| McpError::new(ErrorCode::INTERNAL_ERROR, e.to_string(), None))?;
-        Ok(CallToolResult::success(vec![Content::text(json)]))
+        let exceeded = params
+            .max_pixel_diff
+            .is_some_and(|threshold| report.max_pixel_difference > threshold);
+        if exceeded {
+            Ok(CallToolResult::error(vec![Content::text(json)]))
+        } else {
+            Ok(CallToolResult::success(vec![Content::text(json)]))
+        }
     }
 }
 
@@ -174,8 +524,31 @@ impl rmcp::handler::server::ServerHandler for RiveMcpServer {
                 icons: None,
                 website_url: None,
             },
-            instructions: Some("Rive CLI MCP server. Use 'generate' to create .riv files from SceneSpec JSON, 'validate' to check .riv files, 'inspect' to dump object trees, 'decompile' to return inspect-format JSON for a .riv file, and 'list_templates' for animation templates.".into()),
+            instructions: Some("Rive CLI MCP server. Use 'types' and 'describe' to discover SceneSpec object types, 'new' for a starter scene, 'compile_authoring' to lower an AuthoringSpec document with diagnostics, 'generate' to create .riv files from SceneSpec JSON, 'validate' to check .riv files, 'inspect' to dump object trees, 'decompile' to return inspect-format JSON for a .riv file, 'render' to capture frames, 'compare' to measure a candidate against a reference, and 'list_templates' for animation templates. Resources expose the SceneSpec and AuthoringSpec schemas, every scaffold template and every showcase scene.".into()),
+        }
+    }
+
+    fn initialize(
+        &self,
+        request: InitializeRequestParams,
+        context: rmcp::service::RequestContext<RoleServer>,
+    ) -> impl Future<Output = Result<InitializeResult, McpError>> + Send + '_ {
+        if let Some(sandbox) = &self.sandbox
+            && let Some(parts) = context.extensions.get::<hyper::http::request::Parts>()
+            && let Some(requested) = parts.headers.get(sandbox::WORKDIR_HEADER)
+        {
+            let entered = requested
+                .to_str()
+                .map_err(|_| format!("{} must be valid UTF-8", sandbox::WORKDIR_HEADER))
+                .and_then(|requested| sandbox.enter(requested));
+            if let Err(message) = entered {
+                return std::future::ready(Err(invalid_params(message)));
+            }
         }
+        if context.peer.peer_info().is_none() {
+            context.peer.set_peer_info(request);
+        }
+        std::future::ready(Ok(self.get_info()))
     }
 
     fn list_resources(
@@ -184,22 +557,7 @@ impl rmcp::handler::server::ServerHandler for RiveMcpServer {
         _context: rmcp::service::RequestContext<RoleServer>,
     ) -> impl Future<Output = Result<ListResourcesResult, McpError>> + Send + '_ {
         std::future::ready(Ok(ListResourcesResult {
-            resources: vec![Resource {
-                raw: RawResource {
-                    uri: "schema://scene/v1".into(),
-                    name: "SceneSpec JSON Schema v1".into(),
-                    title: None,
-                    description: Some(
-                        "Complete JSON Schema for rive-cli scene input format, generated from the Rust SceneSpec types"
-                            .into(),
-                    ),
-                    mime_type: Some("application/json".into()),
-                    size: None,
-                    icons: None,
-                    meta: None,
-                },
-                annotations: None,
-            }],
+            resources: resources::list(),
             next_cursor: None,
             meta: None,
         }))
@@ -210,10 +568,9 @@ impl rmcp::handler::server::ServerHandler for RiveMcpServer {
         request: ReadResourceRequestParams,
         _context: rmcp::service::RequestContext<RoleServer>,
     ) -> impl Future<Output = Result<ReadResourceResult, McpError>> + Send + '_ {
-        if request.uri.as_str() == "schema://scene/v1" {
-            let schema = crate::builder::scene_schema_json();
+        if let Some(text) = resources::read(request.uri.as_str()) {
             std::future::ready(Ok(ReadResourceResult {
-                contents: vec![ResourceContents::text(schema, "schema://scene/v1")],
+                contents: vec![ResourceContents::text(text, request.uri.as_str())],
             }))
         } else {
             std::future::ready(Err(McpError::new(
@@ -233,6 +590,10 @@ pub struct GenerateParams {
     pub output: Option<String>,
     #[schemars(description = "Optional Rive file ID (default: 0)")]
     pub file_id: Option<u64>,
+    #[schemars(
+        description = "Directory asset 'source' paths resolve against (default: the session working directory over HTTP)"
+    )]
+    pub base_dir: Option<String>,
 }
 
 #[derive(serde::Deserialize, schemars::JsonSchema)]
@@ -247,7 +608,125 @@ pub struct FilePathParams {
     pub file: String,
 }
 
-pub fn run_server() {
+#[derive(serde::Deserialize, schemars::JsonSchema)]
+pub struct TypesParams {
+    #[schemars(
+        description = "Optional category filter (shape, paint, animation, state-machine, ...)"
+    )]
+    pub category: Option<String>,
+}
+
+#[derive(serde::Deserialize, schemars::JsonSchema)]
+pub struct DescribeParams {
+    #[schemars(description = "SceneSpec object type name, e.g. rectangle")]
+    pub type_name: String,
+}
+
+#[derive(serde::Deserialize, schemars::JsonSchema)]
+pub struct NewParams {
+    #[schemars(
+        description = "Scaffold template name: shape, animated, gradient, spinner, button or multi"
+    )]
+    pub template: String,
+    #[schemars(description = "Optional output file path for the scene JSON")]
+    pub output: Option<String>,
+}
+
+#[derive(serde::Deserialize, schemars::JsonSchema)]
+pub struct CompileAuthoringParams {
+    #[schemars(description = "AuthoringSpec v0 JSON string")]
+    pub authoring_json: String,
+    #[schemars(description = "Optional output .riv path for the compiled scene")]
+    pub output: Option<String>,
+}
+
+#[derive(serde::Deserialize, schemars::JsonSchema)]
+pub struct RenderParams {
+    #[schemars(description = "Path to the .riv file to render")]
+    pub file: String,
+    #[schemars(
+        description = "Frames to capture: list (0,15,30) or range (start..end:step); default 0"
+    )]
+    pub frames: Option<String>,
+    #[schemars(
+        description = "Frames per second used to convert frame index to seconds (default: 60)"
+    )]
+    pub fps: Option<f64>,
+    #[schemars(description = "Animation name to scrub")]
+    pub animation: Option<String>,
+    #[schemars(description = "State machine to advance instead of an animation")]
+    pub state_machine: Option<String>,
+    #[serde(default)]
+    #[schemars(description = "State machine inputs as NAME=VALUE[@FRAME]")]
+    pub inputs: Vec<String>,
+    #[serde(default)]
+    #[schemars(description = "Pointer events as EVENT:X,Y@FRAME")]
+    pub pointers: Vec<String>,
+    #[serde(default)]
+    #[schemars(description = "View model property values as PROP=VALUE[@FRAME]")]
+    pub view_model: Vec<String>,
+    #[schemars(description = "Named view model instance to bind instead of the default")]
+    pub view_model_instance: Option<String>,
+    #[schemars(description = "Artboard name (defaults to the default artboard)")]
+    pub artboard: Option<String>,
+    #[schemars(description = "Logical render width in pixels (default: 512)")]
+    pub width: Option<u32>,
+    #[schemars(description = "Logical render height in pixels (default: 512)")]
+    pub height: Option<u32>,
+    #[schemars(description = "Device pixel ratio multiplier (default: 2)")]
+    pub scale: Option<u32>,
+    #[schemars(description = "Background color, e.g. #202024 (default transparent)")]
+    pub background: Option<String>,
+    #[schemars(
+        description = "Directory for PNG frames and manifest.json (default: a fresh temporary directory)"
+    )]
+    pub output_dir: Option<String>,
+    #[schemars(
+        description = "Path to a Chrome/Chromium binary (overrides auto-discovery; refused when the server confines paths to allowed roots)"
+    )]
+    pub browser: Option<String>,
+}
+
+#[derive(serde::Deserialize, schemars::JsonSchema)]
+pub struct CompareParams {
+    #[schemars(description = "Path to the reference .riv file")]
+    pub reference: String,
+    #[schemars(description = "Path to the candidate .riv file")]
+    pub candidate: String,
+    #[schemars(
+        description = "Frames to compare: list (0,15,30) or range (start..end:step); default 0"
+    )]
+    pub frames: Option<String>,
+    #[schemars(description = "Logical render width in pixels (default: 512)")]
+    pub width: Option<u32>,
+    #[schemars(description = "Logical render height in pixels (default: 512)")]
+    pub height: Option<u32>,
+    #[schemars(description = "Device pixel ratio multiplier (default: 2)")]
+    pub scale: Option<u32>,
+    #[schemars(description = "Background color behind both artboards")]
+    pub background: Option<String>,
+    #[schemars(description = "Animation to scrub in the reference file")]
+    pub reference_animation: Option<String>,
+    #[schemars(description = "Animation to scrub in the candidate file")]
+    pub candidate_animation: Option<String>,
+    #[schemars(description = "State machine to advance in the reference file")]
+    pub reference_state_machine: Option<String>,
+    #[schemars(description = "State machine to advance in the candidate file")]
+    pub candidate_state_machine: Option<String>,
+    #[serde(default)]
+    #[schemars(
+        description = "View model property values applied to both files as PROP=VALUE[@FRAME]"
+    )]
+    pub view_model: Vec<String>,
+    #[schemars(description = "Named view model instance bound in both files")]
+    pub view_model_instance: Option<String>,
+    #[schemars(
+        description = "Mark the result as an error when the worst frame exceeds this percentage (0-100)"
+    )]
+    pub max_pixel_diff: Option<f64>,
+}
+
+pub fn run_server(policy: PathPolicy) {
     let runtime = match tokio::runtime::Runtime::new() {
         Ok(runtime) => runtime,
         Err(e) => {
@@ -257,7 +736,7 @@ pub fn run_server() {
     };
 
     runtime.block_on(async {
-        let server = RiveMcpServer::new();
+        let server = RiveMcpServer::with_policy(policy);
         let service = match server.serve(rmcp::transport::io::stdio()).await {
             Ok(service) => service,
             Err(e) => {
@@ -276,9 +755,144 @@ pub fn run_server() {
 mod tests {
     use super::*;
 
+    #[test]
+    fn scratch_render_dirs_are_removed_on_drop() {
+        let scratch = ScratchRenderDir::new();
+        let path = scratch.0.clone();
+        std::fs::create_dir_all(&path).unwrap();
+        std::fs::write(path.join("frame_00000.png"), b"png").unwrap();
+        drop(scratch);
+        assert!(!path.exists());
+    }
+
     #[test]
     fn test_server_creation() {
         let server = RiveMcpServer::new();
         drop(server);
     }
+
+    fn call<F: Future<Output = Result<CallToolResult, McpError>>>(future: F) -> CallToolResult {
+        tokio::runtime::Runtime::new()
+            .expect("tokio runtime")
+            .block_on(future)
+            .expect("tool call")
+    }
+
+    fn text(result: &CallToolResult) -> &str {
+        result.content[0]
+            .as_text()
+            .map(|text| text.text.as_str())
+            .expect("text content")
+    }
+
+    #[test]
+    fn compile_authoring_returns_scene_and_source_map() {
+        let server = RiveMcpServer::new();
+        let document = serde_json::json!({
+            "authoring_format_version": 0,
+            "artboard": {
+                "id": "stage",
+                "width": { "value": 320.0, "unit": "px" },
+                "height": { "value": 240.0, "unit": "px" }
+            },
+            "visual": { "nodes": [] },
+            "motion": {},
+            "behavior": {}
+        });
+        let result = call(server.compile_authoring(Parameters(CompileAuthoringParams {
+            authoring_json: document.to_string(),
+            output: None,
+        })));
+        assert_ne!(result.is_error, Some(true));
+        let lowered: serde_json::Value = serde_json::from_str(text(&result)).expect("json");
+        assert!(lowered["scene"]["artboard"].is_object());
+        assert!(lowered["source_map"]["entries"].is_array());
+    }
+
+    #[test]
+    fn sandboxed_assets_cannot_be_read_from_another_session() {
+        let root = std::env::temp_dir().join(format!("rive_mcp_assets_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(root.join("mine")).unwrap();
+        std::fs::create_dir_all(root.join("other")).unwrap();
+        std::fs::write(root.join("package.json"), b"{}").unwrap();
+        std::fs::write(root.join("mine/logo.png"), b"png").unwrap();
+        std::fs::write(root.join("other/secret.png"), b"png").unwrap();
+        let root = root.canonicalize().unwrap();
+        let policy = Arc::new(PathPolicy::new(std::slice::from_ref(&root), 1024).unwrap());
+        let server = RiveMcpServer::sandboxed(root.clone(), policy);
+        server.sandbox.as_ref().unwrap().enter("mine").unwrap();
+
+        let generate = |source: &str| {
+            let scene_json = format!(
+                r#"{{"scene_format_version":1,"artboard":{{"name":"A","width":10,"height":10,
+                   "children":[{{"type":"image_asset","name":"Logo","source":"{source}"}}]}}}}"#
+            );
+            tokio::runtime::Runtime::new()
+                .expect("tokio runtime")
+                .block_on(server.generate(Parameters(GenerateParams {
+                    scene_json,
+                    output: None,
+                    file_id: None,
+                    base_dir: None,
+                })))
+        };
+        assert!(generate("logo.png").is_ok());
+        let error = generate("../other/secret.png").expect_err("other session's file");
+        assert!(
+            error.message.contains("outside the allowed roots"),
+            "{}",
+            error.message
+        );
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn compile_authoring_reports_diagnostics_as_a_tool_error() {
+        let server = RiveMcpServer::new();
+        let result = call(server.compile_authoring(Parameters(CompileAuthoringParams {
+            authoring_json: "{}".into(),
+            output: None,
+        })));
+        assert_eq!(result.is_error, Some(true));
+        let body: serde_json::Value = serde_json::from_str(text(&result)).expect("json");
+        assert_eq!(body["diagnostics"][0]["path"], "$");
+    }
+
+    #[test]
+    fn discovery_and_scaffold_tools_mirror_the_cli() {
+        let server = RiveMcpServer::new();
+        let described = call(server.describe(Parameters(DescribeParams {
+            type_name: "rectangle".into(),
+        })));
+        assert!(text(&described).contains("\"rectangle\""));
+        let types = call(server.types(Parameters(TypesParams {
+            category: Some("shape".into()),
+        })));
+        assert!(text(&types).contains("ellipse"));
+        let scene = call(server.new_scene(Parameters(NewParams {
+            template: "shape".into(),
+            output: None,
+        })));
+        assert_eq!(
+            text(&scene),
+            crate::scaffold::template_json("shape").unwrap()
+        );
+
+        let runtime = tokio::runtime::Runtime::new().expect("tokio runtime");
+        let unknown = runtime
+            .block_on(server.describe(Parameters(DescribeParams {
+                type_name: "rectangel".into(),
+            })))
+            .expect_err("unknown type must fail");
+        assert!(unknown.message.contains("did you mean 'rectangle'"));
+        assert!(
+            runtime
+                .block_on(server.new_scene(Parameters(NewParams {
+                    template: "missing".into(),
+                    output: None,
+                })))
+                .is_err()
+        );
+    }
 }
@@ -0,0 +1,229 @@
+use std::path::{Path, PathBuf};
+
+use rmcp::{ErrorData as McpError, model::ErrorCode};
+use serde_json::json;
+
+use crate::builder::{AssetPolicy, RootViolation};
+
+/// Largest file a tool reads, or embeds as an asset `source`, by default.
+pub const DEFAULT_MAX_FILE_BYTES: u64 = 64 * 1024 * 1024;
+
+/// Directories MCP tools may read and write, and the largest file they read.
+/// An empty root list leaves paths unrestricted; the size cap always applies.
+#[derive(Debug, Clone)]
+pub struct PathPolicy {
+    roots: Vec<PathBuf>,
+    max_file_bytes: u64,
+}
+
+impl Default for PathPolicy {
+    fn default() -> Self {
+        Self {
+            roots: Vec::new(),
+            max_file_bytes: DEFAULT_MAX_FILE_BYTES,
+        }
+    }
+}
+
+impl PathPolicy {
+    /// Every root must exist; it is canonicalised so symlinked spellings of
+    /// the same directory compare equal.
+    pub fn new(roots: &[PathBuf], max_file_bytes: u64) -> std::io::Result<Self> {
+        let roots = roots
+            .iter()
+            .map(|root| {
+                root.canonicalize().map_err(|error| {
+                    std::io::Error::new(
+                        error.kind(),
+                        format!("MCP root '{}' is not accessible: {error}", root.display()),
+                    )
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self {
+            roots,
+            max_file_bytes,
+        })
+    }
+
+    /// Requires `path` to resolve inside an allowed root, following every
+    /// symlink that already exists. Used for outputs and directories.
+    pub(super) fn confine(&self, path: PathBuf) -> Result<PathBuf, McpError> {
+        if self.roots.is_empty() {
+            return Ok(path);
+        }
+        crate::builder::within_roots(&path, &self.roots).map_err(|violation| {
+            let reason = match violation {
+                RootViolation::Outside => "is outside",
+                RootViolation::SymlinkEscape => "follows a symlink out of",
+            };
+            self.error(
+                format!(
+                    "path '{}' {} the allowed roots: {}",
+                    path.display(),
+                    reason,
+                    self.root_list().join(", ")
+                ),
+                violation.as_str(),
+                &path,
+            )
+        })
+    }
+
+    /// [`Self::confine`] plus the file size cap, for files a tool reads.
+    pub(super) fn readable(&self, path: PathBuf) -> Result<PathBuf, McpError> {
+        let path = self.confine(path)?;
+        if let Ok(metadata) = std::fs::metadata(&path)
+            && metadata.len() > self.max_file_bytes
+        {
+            return Err(self.error(
+                format!(
+                    "file '{}' is {} bytes, over the {} byte limit",
+                    path.display(),
+                    metadata.len(),
+                    self.max_file_bytes
+                ),
+                "file_too_large",
+                &path,
+            ));
+        }
+        Ok(path)
+    }
+
+    /// A `browser` override names an executable the server would spawn, so
+    /// it is refused whenever the roots confine what the tools may touch.
+    pub(super) fn browser(&self, browser: Option<String>) -> Result<Option<PathBuf>, McpError> {
+        let Some(browser) = browser.map(PathBuf::from) else {
+            return Ok(None);
+        };
+        if self.roots.is_empty() {
+            return Ok(Some(browser));
+        }
+        Err(self.error(
+            format!(
+                "browser override '{}' is not accepted while paths are confined to: {}",
+                browser.display(),
+                self.root_list().join(", ")
+            ),
+            "browser_override",
+            &browser,
+        ))
+    }
+
+    /// The same roots and cap, applied to asset `source` files by the builder.
+    pub(super) fn assets(&self) -> AssetPolicy {
+        AssetPolicy {
+            allowed_roots: self.roots.clone(),
+            max_file_bytes: Some(self.max_file_bytes),
+        }
+    }
+
+    fn root_list(&self) -> Vec<String> {
+        self.roots
+            .iter()
+            .map(|root| root.display().to_string())
+            .collect()
+    }
+
+    fn error(&self, message: String, violation: &str, path: &Path) -> McpError {
+        McpError::new(
+            ErrorCode::INVALID_PARAMS,
+            message,
+            Some(json!({
+                "violation": violation,
+                "path": path.display().to_string(),
+                "allowed_roots": self.root_list(),
+                "max_file_bytes": self.max_file_bytes,
+            })),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scratch(name: &str) -> PathBuf {
+        let path =
+            std::env::temp_dir().join(format!("rive_mcp_policy_{name}_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&path);
+        std::fs::create_dir_all(path.join("allowed")).expect("scratch dirs");
+        path.canonicalize().expect("canonical scratch")
+    }
+
+    #[test]
+    fn violations_name_the_allowed_roots() {
+        let root = scratch("roots");
+        let policy = PathPolicy::new(&[root.join("allowed")], 8).expect("policy");
+        let error = policy
+            .confine(root.join("elsewhere/out.riv"))
+            .expect_err("outside the root");
+        let data = error.data.expect("structured data");
+        assert_eq!(data["violation"], "outside_allowed_roots");
+        assert_eq!(
+            data["allowed_roots"][0],
+            root.join("allowed").display().to_string()
+        );
+        assert!(error.message.contains("is outside the allowed roots"));
+        assert!(policy.confine(root.join("allowed/out.riv")).is_ok());
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn reads_over_the_size_cap_are_rejected() {
+        let root = scratch("size");
+        let policy = PathPolicy::new(&[root.join("allowed")], 8).expect("policy");
+        std::fs::write(root.join("allowed/small.riv"), b"RIVE").expect("small");
+        std::fs::write(root.join("allowed/large.riv"), [0u8; 16]).expect("large");
+        assert!(policy.readable(root.join("allowed/small.riv")).is_ok());
+        let error = policy
+            .readable(root.join("allowed/large.riv"))
+            .expect_err("too large");
+        assert_eq!(
+            error.data.expect("structured data")["violation"],
+            "file_too_large"
+        );
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn browser_overrides_are_refused_inside_roots() {
+        let root = scratch("browser");
+        let confined = PathPolicy::new(&[root.join("allowed")], 1024).expect("policy");
+        let error = confined
+            .browser(Some("/tmp/not-a-browser".to_string()))
+            .expect_err("browser override inside roots");
+        assert_eq!(
+            error.data.expect("structured data")["violation"],
+            "browser_override"
+        );
+        assert_eq!(confined.browser(None).expect("no override"), None);
+        assert_eq!(
+            PathPolicy::default()
+                .browser(Some("/usr/bin/chromium".to_string()))
+                .expect("unrestricted policy"),
+            Some(PathBuf::from("/usr/bin/chromium"))
+        );
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlink_escapes_are_reported() {
+        let root = scratch("symlink");
+        let outside = scratch("symlink_outside");
+        std::fs::write(outside.join("allowed/secret.riv"), b"RIVE").expect("secret");
+        std::os::unix::fs::symlink(outside.join("allowed"), root.join("allowed/link"))
+            .expect("symlink");
+        let policy = PathPolicy::new(&[root.join("allowed")], 1024).expect("policy");
+        let error = policy
+            .readable(root.join("allowed/link/secret.riv"))
+            .expect_err("symlink escape");
+        assert_eq!(
+            error.data.expect("structured data")["violation"],
+            "symlink_escape"
+        );
+        let _ = std::fs::remove_dir_all(&root);
+        let _ = std::fs::remove_dir_all(&outside);
+    }
+}
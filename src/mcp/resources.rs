@@ -0,0 +1,151 @@
+use rmcp::model::{RawResource, Resource};
+
+pub(super) const SCENE_SCHEMA_URI: &str = "schema://scene/v1";
+pub(super) const AUTHORING_SCHEMA_URI: &str = "schema://authoring/v0";
+const TEMPLATE_PREFIX: &str = "template://scaffold/";
+const SHOWCASE_PREFIX: &str = "showcase://";
+
+/// Showcase scenes are embedded so the server answers without a checkout.
+/// `showcase_table_covers_every_showcase_scene` keeps this table in sync with
+/// the `showcase/` directory.
+const SHOWCASES: [(&str, &str); 10] = [
+    (
+        "audio_equaliser",
+        include_str!("../../showcase/audio_equaliser.json"),
+    ),
+    (
+        "control_panel",
+        include_str!("../../showcase/control_panel.json"),
+    ),
+    (
+        "day_night_toggle",
+        include_str!("../../showcase/day_night_toggle.json"),
+    ),
+    (
+        "liquid_loader",
+        include_str!("../../showcase/liquid_loader.json"),
+    ),
+    (
+        "orbital_loader",
+        include_str!("../../showcase/orbital_loader.json"),
+    ),
+    (
+        "pulse_button",
+        include_str!("../../showcase/pulse_button.json"),
+    ),
+    (
+        "radial_dashboard",
+        include_str!("../../showcase/radial_dashboard.json"),
+    ),
+    (
+        "rocket_launch",
+        include_str!("../../showcase/rocket_launch.json"),
+    ),
+    (
+        "textured_scene",
+        include_str!("../../showcase/textured_scene.json"),
+    ),
+    ("wordmark", include_str!("../../showcase/wordmark.json")),
+];
+
+fn resource(uri: String, name: String, description: String) -> Resource {
+    Resource {
+        raw: RawResource {
+            uri,
+            name,
+            title: None,
+            description: Some(description),
+            mime_type: Some("application/json".into()),
+            size: None,
+            icons: None,
+            meta: None,
+        },
+        annotations: None,
+    }
+}
+
+pub(super) fn list() -> Vec<Resource> {
+    let mut resources = vec![
+        resource(
+            SCENE_SCHEMA_URI.into(),
+            "SceneSpec JSON Schema v1".into(),
+            "Complete JSON Schema for rive-cli scene input format, generated from the Rust SceneSpec types".into(),
+        ),
+        resource(
+            AUTHORING_SCHEMA_URI.into(),
+            "AuthoringSpec JSON Schema v0".into(),
+            "JSON Schema for the semantic AuthoringSpec v0 document accepted by compile_authoring".into(),
+        ),
+    ];
+    for template in crate::scaffold::templates() {
+        resources.push(resource(
+            format!("{TEMPLATE_PREFIX}{}", template.name),
+            format!("Scaffold template '{}'", template.name),
+            template.description.into(),
+        ));
+    }
+    for (name, _) in SHOWCASES {
+        resources.push(resource(
+            format!("{SHOWCASE_PREFIX}{name}"),
+            format!("Showcase scene '{name}'"),
+            format!("SceneSpec JSON for showcase/{name}.json"),
+        ));
+    }
+    resources
+}
+
+pub(super) fn read(uri: &str) -> Option<String> {
+    match uri {
+        SCENE_SCHEMA_URI => return Some(crate::builder::scene_schema_json()),
+        AUTHORING_SCHEMA_URI => {
+            return serde_json::to_string_pretty(&crate::authoring::authoring_schema()).ok();
+        }
+        _ => {}
+    }
+    if let Some(name) = uri.strip_prefix(TEMPLATE_PREFIX) {
+        return crate::scaffold::template_json(name)
+            .ok()
+            .map(str::to_string);
+    }
+    let name = uri.strip_prefix(SHOWCASE_PREFIX)?;
+    SHOWCASES
+        .iter()
+        .find(|(showcase, _)| *showcase == name)
+        .map(|(_, scene)| scene.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_listed_resource_reads_as_json() {
+        for resource in list() {
+            let text = read(&resource.raw.uri)
+                .unwrap_or_else(|| panic!("{} must be readable", resource.raw.uri));
+            serde_json::from_str::<serde_json::Value>(&text)
+                .unwrap_or_else(|error| panic!("{} is not JSON: {error}", resource.raw.uri));
+        }
+        assert!(read("showcase://missing").is_none());
+        assert!(read("template://scaffold/missing").is_none());
+    }
+
+    #[test]
+    fn showcase_table_covers_every_showcase_scene() {
+        let directory = concat!(env!("CARGO_MANIFEST_DIR"), "/showcase");
+        let mut on_disk = std::fs::read_dir(directory)
+            .expect("showcase directory")
+            .filter_map(|entry| {
+                let path = entry.ok()?.path();
+                (path.extension()? == "json")
+                    .then(|| path.file_stem()?.to_str().map(String::from))?
+            })
+            .collect::<Vec<_>>();
+        on_disk.sort();
+        let embedded = SHOWCASES
+            .iter()
+            .map(|(name, _)| name.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(embedded, on_disk);
+    }
+}
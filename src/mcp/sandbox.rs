@@ -0,0 +1,149 @@
+use std::path::{Component, Path, PathBuf};
+use std::sync::{Arc, PoisonError, RwLock};
+
+/// Request header an HTTP client sends with `initialize` to narrow its
+/// session to a subdirectory of the server root.
+pub const WORKDIR_HEADER: &str = "x-rive-workdir";
+
+/// Working directory of one HTTP session. Every file path a tool receives is
+/// resolved against it and must stay inside it.
+#[derive(Clone)]
+pub(super) struct SessionSandbox {
+    root: PathBuf,
+    workdir: Arc<RwLock<PathBuf>>,
+}
+
+impl SessionSandbox {
+    pub(super) fn new(root: PathBuf) -> Self {
+        Self {
+            workdir: Arc::new(RwLock::new(root.clone())),
+            root,
+        }
+    }
+
+    pub(super) fn workdir(&self) -> PathBuf {
+        self.workdir
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    pub(super) fn enter(&self, requested: &str) -> Result<PathBuf, String> {
+        let workdir = confine(&self.root, requested, &self.root).map_err(|_| {
+            format!(
+                "working directory '{requested}' escapes the server root '{}'",
+                self.root.display()
+            )
+        })?;
+        if !workdir.is_dir() {
+            return Err(format!(
+                "working directory '{requested}' is not a directory under the server root '{}'",
+                self.root.display()
+            ));
+        }
+        *self.workdir.write().unwrap_or_else(PoisonError::into_inner) = workdir.clone();
+        Ok(workdir)
+    }
+
+    pub(super) fn resolve(&self, raw: &str) -> Result<PathBuf, String> {
+        let workdir = self.workdir();
+        confine(&workdir, raw, &workdir).map_err(|_| {
+            format!(
+                "path '{raw}' escapes the session working directory '{}'",
+                workdir.display()
+            )
+        })
+    }
+}
+
+/// Resolves `raw` against `base` and requires the result, after following
+/// every symlink that already exists, to stay under `boundary`.
+fn confine(base: &Path, raw: &str, boundary: &Path) -> Result<PathBuf, ()> {
+    let mut lexical = PathBuf::new();
+    for component in base.join(raw).components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !lexical.pop() {
+                    return Err(());
+                }
+            }
+            other => lexical.push(other),
+        }
+    }
+    let resolved = crate::builder::canonicalise_existing(&lexical).ok_or(())?;
+    if resolved.starts_with(boundary) {
+        Ok(resolved)
+    } else {
+        Err(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scratch(name: &str) -> PathBuf {
+        let path =
+            std::env::temp_dir().join(format!("rive_mcp_sandbox_{name}_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&path);
+        std::fs::create_dir_all(path.join("project/scenes")).expect("scratch dirs");
+        path.canonicalize().expect("canonical scratch")
+    }
+
+    #[test]
+    fn relative_paths_resolve_inside_the_working_directory() {
+        let root = scratch("relative");
+        let sandbox = SessionSandbox::new(root.clone());
+        sandbox.enter("project").expect("enter project");
+        assert_eq!(
+            sandbox.resolve("scenes/out.riv").expect("inside"),
+            root.join("project/scenes/out.riv")
+        );
+        assert_eq!(
+            sandbox
+                .resolve("scenes/../new/out.riv")
+                .expect("missing dirs"),
+            root.join("project/new/out.riv")
+        );
+        assert!(sandbox.resolve("../escape.riv").is_err());
+        assert!(sandbox.resolve("/etc/passwd").is_err());
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn working_directories_cannot_leave_the_root() {
+        let root = scratch("enter");
+        let sandbox = SessionSandbox::new(root.clone());
+        assert!(sandbox.enter("..").is_err());
+        assert!(sandbox.enter("missing").is_err());
+        assert_eq!(sandbox.workdir(), root);
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlinks_out_of_the_working_directory_are_rejected() {
+        let root = scratch("symlink");
+        let outside = scratch("symlink_outside");
+        std::os::unix::fs::symlink(&outside, root.join("project/link")).expect("symlink");
+        let sandbox = SessionSandbox::new(root.clone());
+        sandbox.enter("project").expect("enter project");
+        assert!(sandbox.resolve("link/out.riv").is_err());
+        let _ = std::fs::remove_dir_all(&root);
+        let _ = std::fs::remove_dir_all(&outside);
+    }
+    #[cfg(unix)]
+    #[test]
+    fn dangling_symlinks_cannot_redirect_outputs_out_of_the_working_directory() {
+        let root = scratch("dangling");
+        let outside = scratch("dangling_outside");
+        std::os::unix::fs::symlink(outside.join("out.riv"), root.join("project/out.riv"))
+            .expect("symlink");
+        let sandbox = SessionSandbox::new(root.clone());
+        sandbox.enter("project").expect("enter project");
+        assert!(sandbox.resolve("out.riv").is_err());
+        let _ = std::fs::remove_dir_all(&root);
+        let _ = std::fs::remove_dir_all(&outside);
+    }
+}
@@ -4,6 +4,7 @@ pub struct Bone {
     pub name: String,
     pub parent_id: u64,
     pub length: f32,
+    pub rotation: f32,
 }
 
 impl Bone {
@@ -12,6 +13,7 @@ impl Bone {
             name,
             parent_id,
             length: 0.0,
+            rotation: 0.0,
         }
     }
 }
@@ -38,6 +40,12 @@ impl RiveObject for Bone {
                 value: PropertyValue::Float(self.length),
             });
         }
+        if self.rotation != 0.0 {
+            props.push(Property {
+                key: property_keys::TRANSFORM_ROTATION,
+                value: PropertyValue::Float(self.rotation),
+            });
+        }
         props
     }
 }
@@ -48,6 +56,7 @@ pub struct RootBone {
     pub length: f32,
     pub x: f32,
     pub y: f32,
+    pub rotation: f32,
 }
 
 impl RootBone {
@@ -58,6 +67,7 @@ impl RootBone {
             length: 0.0,
             x: 0.0,
             y: 0.0,
+            rotation: 0.0,
         }
     }
 }
@@ -96,6 +106,12 @@ impl RiveObject for RootBone {
                 value: PropertyValue::Float(self.y),
             });
         }
+        if self.rotation != 0.0 {
+            props.push(Property {
+                key: property_keys::TRANSFORM_ROTATION,
+                value: PropertyValue::Float(self.rotation),
+            });
+        }
         props
     }
 }
@@ -325,6 +341,8 @@ impl RiveObject for Weight {
 pub struct CubicWeight {
     pub name: String,
     pub parent_id: u64,
+    pub values: u64,
+    pub indices: u64,
     pub in_values: u64,
     pub in_indices: u64,
     pub out_values: u64,
@@ -336,6 +354,8 @@ impl CubicWeight {
         Self {
             name,
             parent_id,
+            values: 255,
+            indices: 1,
             in_values: 255,
             in_indices: 1,
             out_values: 255,
@@ -360,6 +380,18 @@ impl RiveObject for CubicWeight {
                 value: PropertyValue::UInt(self.parent_id),
             },
         ];
+        if self.values != 255 {
+            props.push(Property {
+                key: property_keys::WEIGHT_VALUES,
+                value: PropertyValue::UInt(self.values),
+            });
+        }
+        if self.indices != 1 {
+            props.push(Property {
+                key: property_keys::WEIGHT_INDICES,
+                value: PropertyValue::UInt(self.indices),
+            });
+        }
         if self.in_values != 255 {
             props.push(Property {
                 key: property_keys::CUBIC_WEIGHT_IN_VALUES,
@@ -421,6 +453,18 @@ mod tests {
         assert_eq!(length_prop.value, PropertyValue::Float(50.0));
     }
 
+    #[test]
+    fn test_bone_with_rotation() {
+        let mut bone = Bone::new("bone1".to_string(), 0);
+        bone.rotation = 0.5;
+        let props = bone.properties();
+        let rotation_prop = props
+            .iter()
+            .find(|p| p.key == property_keys::TRANSFORM_ROTATION)
+            .unwrap();
+        assert_eq!(rotation_prop.value, PropertyValue::Float(0.5));
+    }
+
     #[test]
     fn test_root_bone_type_key() {
         let root = RootBone::new("root".to_string(), 0);
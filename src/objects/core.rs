@@ -146,6 +146,7 @@ pub mod type_keys {
     pub const VIEW_MODEL_COMPONENT: u16 = 436;
     pub const VIEW_MODEL_PROPERTY: u16 = 430;
     pub const DATA_BIND: u16 = 446;
+    pub const DATA_BIND_CONTEXT: u16 = 447;
     pub const TEXT: u16 = 134;
     pub const TEXT_VALUE_RUN: u16 = 135;
     pub const TEXT_STYLE: u16 = 573;
@@ -602,6 +603,7 @@ pub mod property_keys {
     pub const DATA_BIND_PROPERTY_KEY: u16 = 586;
     pub const DATA_BIND_FLAGS: u16 = 587;
     pub const DATA_BIND_CONVERTER_ID: u16 = 660;
+    pub const DATA_BIND_CONTEXT_SOURCE_PATH_IDS: u16 = 588;
     pub const BLEND_ANIMATION_ANIMATION_ID: u16 = 165;
     pub const BLEND_ANIMATION_1D_VALUE: u16 = 166;
     pub const BLEND_STATE_1D_INPUT_ID: u16 = 167;
@@ -861,6 +863,8 @@ pub fn is_bool_property(key: u16) -> bool {
 
 pub fn is_bytes_property(key: u16) -> bool {
     key == property_keys::FILE_ASSET_CONTENTS_BYTES
+        || key == property_keys::MESH_TRIANGLE_INDEX_BYTES
+        || key == property_keys::DATA_BIND_CONTEXT_SOURCE_PATH_IDS
 }
 
 pub fn property_backing_type(key: u16) -> Option<BackingType> {
@@ -886,6 +890,7 @@ pub fn property_backing_type(key: u16) -> Option<BackingType> {
         | property_keys::TEXT_INPUT_TEXT_VALUE
         | property_keys::FOLDER_PATH
         | property_keys::MESH_TRIANGLE_INDEX_BYTES
+        | property_keys::DATA_BIND_CONTEXT_SOURCE_PATH_IDS
         | property_keys::FILE_ASSET_CONTENTS_BYTES => Some(BackingType::String),
         property_keys::LAYOUT_COMPONENT_WIDTH
         | property_keys::LAYOUT_COMPONENT_HEIGHT
@@ -1,4 +1,5 @@
 use super::core::{Property, PropertyValue, RiveObject, property_keys, type_keys};
+use crate::encoder::binary_writer::BinaryWriter;
 
 pub struct ViewModel {
     pub name: String,
@@ -114,6 +115,60 @@ impl RiveObject for DataBind {
     }
 }
 
+/// A `DataBind` that resolves its source through a path of ids: the view
+/// model's id, then the bound property's index within it.
+pub struct DataBindContext {
+    pub property_key: u64,
+    pub flags: u64,
+    pub converter_id: u64,
+    pub source_path_ids: Vec<u64>,
+}
+
+impl DataBindContext {
+    pub fn new(property_key: u64, flags: u64, source_path_ids: Vec<u64>) -> Self {
+        Self {
+            property_key,
+            flags,
+            converter_id: u32::MAX as u64,
+            source_path_ids,
+        }
+    }
+}
+
+impl RiveObject for DataBindContext {
+    fn type_key(&self) -> u16 {
+        type_keys::DATA_BIND_CONTEXT
+    }
+
+    fn properties(&self) -> Vec<Property> {
+        let mut writer = BinaryWriter::new();
+        for id in &self.source_path_ids {
+            writer.write_varuint(*id);
+        }
+        let mut props = vec![
+            Property {
+                key: property_keys::DATA_BIND_PROPERTY_KEY,
+                value: PropertyValue::UInt(self.property_key),
+            },
+            Property {
+                key: property_keys::DATA_BIND_FLAGS,
+                value: PropertyValue::UInt(self.flags),
+            },
+        ];
+        if self.converter_id != u32::MAX as u64 {
+            props.push(Property {
+                key: property_keys::DATA_BIND_CONVERTER_ID,
+                value: PropertyValue::UInt(self.converter_id),
+            });
+        }
+        props.push(Property {
+            key: property_keys::DATA_BIND_CONTEXT_SOURCE_PATH_IDS,
+            value: PropertyValue::Bytes(writer.finish()),
+        });
+        props
+    }
+}
+
 pub struct ViewModelInstance {
     pub view_model_id: u64,
 }
@@ -1299,6 +1354,18 @@ mod tests {
         assert_eq!(conv_prop.value, PropertyValue::UInt(5));
     }
 
+    #[test]
+    fn test_data_bind_context_writes_its_source_path() {
+        let db = DataBindContext::new(42, 1, vec![0, 2]);
+        assert_eq!(db.type_key(), type_keys::DATA_BIND_CONTEXT);
+        let props = db.properties();
+        let path = props
+            .iter()
+            .find(|p| p.key == property_keys::DATA_BIND_CONTEXT_SOURCE_PATH_IDS)
+            .unwrap();
+        assert_eq!(path.value, PropertyValue::Bytes(vec![0, 2]));
+    }
+
     #[test]
     fn test_data_bind_no_name_or_parent() {
         let db = DataBind::new(10, 0);
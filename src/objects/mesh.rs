@@ -1,4 +1,5 @@
 use super::core::{Property, PropertyValue, RiveObject, property_keys, type_keys};
+use crate::encoder::binary_writer::BinaryWriter;
 
 pub struct MeshVertex {
     pub name: String,
@@ -69,12 +70,29 @@ impl RiveObject for MeshVertex {
 pub struct Mesh {
     pub name: String,
     pub parent_id: u64,
+    /// Three indices per triangle into the mesh's vertex children, in
+    /// child order.
+    pub triangle_indices: Vec<u16>,
 }
 
 impl Mesh {
     pub fn new(name: String, parent_id: u64) -> Self {
-        Self { name, parent_id }
+        Self {
+            name,
+            parent_id,
+            triangle_indices: Vec::new(),
+        }
+    }
+}
+
+/// Encodes triangle indices the way the runtime's `decodeTriangleIndexBytes`
+/// reads them: one varuint per index, with no count prefix.
+pub fn encode_triangle_indices(indices: &[u16]) -> Vec<u8> {
+    let mut writer = BinaryWriter::new();
+    for &index in indices {
+        writer.write_varuint(u64::from(index));
     }
+    writer.finish()
 }
 
 impl RiveObject for Mesh {
@@ -94,7 +112,7 @@ impl RiveObject for Mesh {
             },
             Property {
                 key: property_keys::MESH_TRIANGLE_INDEX_BYTES,
-                value: PropertyValue::String(String::new()),
+                value: PropertyValue::Bytes(encode_triangle_indices(&self.triangle_indices)),
             },
         ]
     }
@@ -258,11 +276,22 @@ mod tests {
         assert_eq!(props[2].key, property_keys::MESH_TRIANGLE_INDEX_BYTES);
         assert_eq!(
             props[2].value,
-            PropertyValue::String(String::new()),
+            PropertyValue::Bytes(Vec::new()),
             "the runtime allocates its index buffer from this property; omitting it leaves the buffer null and import returns InvalidObject"
         );
     }
 
+    #[test]
+    fn test_mesh_encodes_each_triangle_index_as_a_varuint() {
+        let mut m = Mesh::new("m".to_string(), 1);
+        m.triangle_indices = vec![0, 1, 2, 2, 127, 300];
+        let props = m.properties();
+        assert_eq!(
+            props[2].value,
+            PropertyValue::Bytes(vec![0, 1, 2, 2, 127, 0xAC, 0x02])
+        );
+    }
+
     #[test]
     fn test_contour_mesh_vertex_type_key() {
         let v = ContourMeshVertex::new("cv".to_string(), 1);
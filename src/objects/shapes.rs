@@ -691,6 +691,9 @@ pub struct Image {
     pub asset_id: u64,
     pub x: f32,
     pub y: f32,
+    pub rotation: f32,
+    pub scale_x: f32,
+    pub scale_y: f32,
 }
 
 impl Image {
@@ -701,6 +704,9 @@ impl Image {
             asset_id,
             x: 0.0,
             y: 0.0,
+            rotation: 0.0,
+            scale_x: 1.0,
+            scale_y: 1.0,
         }
     }
 }
@@ -737,6 +743,24 @@ impl RiveObject for Image {
                 value: PropertyValue::Float(self.y),
             });
         }
+        if self.rotation != 0.0 {
+            props.push(Property {
+                key: property_keys::TRANSFORM_ROTATION,
+                value: PropertyValue::Float(self.rotation),
+            });
+        }
+        if self.scale_x != 1.0 {
+            props.push(Property {
+                key: property_keys::TRANSFORM_SCALE_X,
+                value: PropertyValue::Float(self.scale_x),
+            });
+        }
+        if self.scale_y != 1.0 {
+            props.push(Property {
+                key: property_keys::TRANSFORM_SCALE_Y,
+                value: PropertyValue::Float(self.scale_y),
+            });
+        }
         props
     }
 }
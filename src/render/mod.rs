@@ -31,6 +31,8 @@ const READY_POLL_INTERVAL: Duration = Duration::from_millis(100);
 const TRANSPARENT_BACKGROUND_CHANNEL: u8 = 0;
 const TRANSPARENT_BACKGROUND_ALPHA: f64 = 0.0;
 const NON_POSITIVE_FPS: f64 = 0.0;
+/// Longest drag gesture, in frames; each frame expands to one move event.
+const MAX_DRAG_FRAMES: u32 = 10_000;
 
 #[derive(Debug, Error)]
 pub enum RenderError {
@@ -59,6 +61,8 @@ pub struct RenderOptions {
     pub state_machine: Option<String>,
     pub inputs: Vec<String>,
     pub pointers: Vec<String>,
+    pub view_model: Vec<String>,
+    pub view_model_instance: Option<String>,
     pub artboard: Option<String>,
     pub width: u32,
     pub height: u32,
@@ -67,6 +71,9 @@ pub struct RenderOptions {
     pub contact_sheet: bool,
     pub preview: bool,
     pub browser: Option<PathBuf>,
+    /// Directory written by `generate --assets external`; its files are
+    /// handed to the runtime's asset loader.
+    pub assets_dir: Option<PathBuf>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -80,6 +87,15 @@ pub struct RenderedFrame {
     pub preview: Option<image::CoveragePreview>,
 }
 
+/// A Rive event the state machine reported while stepping to `frame`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FiredEvent {
+    pub frame: u32,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "serde_json::Map::is_empty")]
+    pub properties: serde_json::Map<String, Value>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct RenderManifest {
     pub source: String,
@@ -97,6 +113,19 @@ pub struct RenderManifest {
     pub applied_inputs: Vec<Value>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub applied_pointers: Vec<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub view_model: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub view_model_instance: Option<String>,
+    /// The `--view-model` values the runtime accepted, each with the
+    /// `propertyType` the harness found; values scheduled after the last
+    /// rendered frame are not listed.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub applied_view_model: Vec<Value>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fired_events: Vec<FiredEvent>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub supplied_assets: Vec<String>,
     pub contact_sheet: Option<String>,
 }
 
@@ -178,6 +207,7 @@ pub fn render(options: &RenderOptions) -> Result<RenderManifest, RenderError> {
         ));
     }
     validate_fps(options.fps)?;
+    let events = scheduled_events(options)?;
     fs::create_dir_all(&options.output_dir)?;
 
     let background = options
@@ -185,7 +215,28 @@ pub fn render(options: &RenderOptions) -> Result<RenderManifest, RenderError> {
         .as_deref()
         .map(parse_background)
         .transpose()?;
-    let server = server::AssetServer::start(harness(), RIVE_JS, RIVE_WASM, options.riv.clone())?;
+    let external = match &options.assets_dir {
+        Some(dir) => crate::builder::read_external_assets(dir).map_err(RenderError::message)?,
+        None => Vec::new(),
+    };
+    let external_files: Vec<Value> = external
+        .iter()
+        .enumerate()
+        .map(|(index, asset)| {
+            json!({
+                "file": asset.entry.file,
+                "kind": asset.entry.kind,
+                "url": format!("/assets/{index}"),
+            })
+        })
+        .collect();
+    let server = server::AssetServer::start(
+        harness(),
+        RIVE_JS,
+        RIVE_WASM,
+        options.riv.clone(),
+        external.into_iter().map(|asset| asset.contents).collect(),
+    )?;
     let browser_path = chrome::discover(options.browser.as_deref())?;
     let mut browser = chrome::Chrome::launch(&browser_path, options.scale)?;
     let session = browser.session.clone();
@@ -209,7 +260,14 @@ pub fn render(options: &RenderOptions) -> Result<RenderManifest, RenderError> {
     )?;
     wait_for_document(&mut browser, &session)?;
 
-    let scene = load_scene(&mut browser, &session, options, background.as_deref())?;
+    let scene = load_scene(
+        &mut browser,
+        &session,
+        options,
+        background.as_deref(),
+        events,
+        &external_files,
+    )?;
     set_capture_background(&mut browser, &session, background.is_none())?;
 
     let mut frames = Vec::new();
@@ -272,6 +330,13 @@ pub fn render(options: &RenderOptions) -> Result<RenderManifest, RenderError> {
         written.push(path);
     }
 
+    let fired_events = fired_events(&mut browser, &session)?;
+    let applied_view_model = if scene.requested_view_model {
+        applied_view_model(&mut browser, &session)?
+    } else {
+        Vec::new()
+    };
+
     let contact_sheet = if options.contact_sheet {
         let path = options.output_dir.join("contact_sheet.png");
         image::contact_sheet(&written, &path)?;
@@ -306,6 +371,11 @@ pub fn render(options: &RenderOptions) -> Result<RenderManifest, RenderError> {
         frames,
         applied_inputs: scene.applied_inputs,
         applied_pointers: scene.applied_pointers,
+        view_model: scene.view_model,
+        view_model_instance: options.view_model_instance.clone(),
+        applied_view_model,
+        fired_events,
+        supplied_assets: scene.supplied_assets,
         contact_sheet,
     };
     fs::write(
@@ -324,6 +394,9 @@ struct LoadedScene {
     selected_state_machine: Option<String>,
     applied_inputs: Vec<Value>,
     applied_pointers: Vec<Value>,
+    view_model: Option<String>,
+    requested_view_model: bool,
+    supplied_assets: Vec<String>,
 }
 
 fn wait_for_document(browser: &mut chrome::Chrome, session: &str) -> Result<(), RenderError> {
@@ -445,6 +518,59 @@ fn parse_input(entry: &str) -> Result<Value, RenderError> {
     Ok(value)
 }
 
+fn parse_view_model_value(entry: &str) -> Result<Value, RenderError> {
+    let (name, raw) = entry.split_once('=').ok_or_else(|| {
+        RenderError::message(format!(
+            "invalid --view-model '{entry}': expected PROP=VALUE[@FRAME], e.g. score=42 or accent=#FF8800@30"
+        ))
+    })?;
+    let name = name.trim();
+    let (raw, frame) = split_frame_suffix(entry, raw.trim())?;
+    let raw = raw.trim();
+    if name.is_empty() {
+        return Err(RenderError::message(format!(
+            "invalid --view-model '{entry}': the property name is empty"
+        )));
+    }
+    let mut value = match raw {
+        "true" => json!({ "name": name, "kind": "bool", "value": true }),
+        "false" => json!({ "name": name, "kind": "bool", "value": false }),
+        "trigger" => json!({ "name": name, "kind": "trigger" }),
+        color if color.starts_with('#') => {
+            let color = parse_background(color).map_err(|_| {
+                RenderError::message(format!(
+                    "invalid --view-model '{entry}': '{color}' is not a #RRGGBB or #RRGGBBAA color"
+                ))
+            })?;
+            let color = if color.len() == 7 {
+                format!("{color}FF")
+            } else {
+                color
+            };
+            json!({ "name": name, "kind": "color", "value": color.to_ascii_uppercase() })
+        }
+        quoted if quoted.len() >= 2 && quoted.starts_with('"') && quoted.ends_with('"') => {
+            json!({ "name": name, "kind": "string", "value": &quoted[1..quoted.len() - 1] })
+        }
+        other => match other.parse::<f64>() {
+            Ok(number) if number.is_finite() => {
+                json!({ "name": name, "kind": "number", "value": number })
+            }
+            Ok(_) => {
+                return Err(RenderError::message(format!(
+                    "invalid --view-model '{entry}': '{other}' is not a finite number"
+                )));
+            }
+            Err(_) => json!({ "name": name, "kind": "string", "value": other }),
+        },
+    };
+    value["frame"] = match frame {
+        Some(frame) => json!(frame),
+        None => Value::Null,
+    };
+    Ok(value)
+}
+
 const POINTER_EVENTS: [&str; 5] = ["down", "up", "move", "enter", "exit"];
 
 fn parse_pointer(entry: &str) -> Result<Value, RenderError> {
@@ -466,6 +592,16 @@ fn parse_pointer(entry: &str) -> Result<Value, RenderError> {
             "invalid --pointer '{entry}': a frame is required, e.g. {event}:120,90@10"
         )));
     };
+    let (x, y) = parse_point(entry, &coords)?;
+    Ok(json!({
+        "event": event,
+        "x": x,
+        "y": y,
+        "frame": frame,
+    }))
+}
+
+fn parse_point(entry: &str, coords: &str) -> Result<(f64, f64), RenderError> {
     let (x, y) = coords.split_once(',').ok_or_else(|| {
         RenderError::message(format!(
             "invalid --pointer '{entry}': expected artboard coordinates X,Y"
@@ -485,20 +621,74 @@ fn parse_pointer(entry: &str) -> Result<Value, RenderError> {
         }
         Ok(value)
     };
-    Ok(json!({
-        "event": event,
-        "x": parse_coord(x, "x")?,
-        "y": parse_coord(y, "y")?,
-        "frame": frame,
-    }))
+    Ok((parse_coord(x, "x")?, parse_coord(y, "y")?))
 }
 
-fn load_scene(
-    browser: &mut chrome::Chrome,
-    session: &str,
-    options: &RenderOptions,
-    background: Option<&str>,
-) -> Result<LoadedScene, RenderError> {
+/// Expands `drag:X1,Y1->X2,Y2@A-B` into a press at A, one move per frame
+/// up to B interpolated linearly between the two points, and a release at B.
+fn parse_drag(entry: &str, rest: &str) -> Result<Vec<Value>, RenderError> {
+    let usage = || {
+        RenderError::message(format!(
+            "invalid --pointer '{entry}': expected drag:X1,Y1->X2,Y2@FROM-TO, e.g. drag:200,300->200,100@10-40"
+        ))
+    };
+    let (path, frames) = rest.rsplit_once('@').ok_or_else(usage)?;
+    let (from, to) = path.split_once("->").ok_or_else(usage)?;
+    let (start, end) = frames.split_once('-').ok_or_else(usage)?;
+    let parse_frame = |raw: &str| -> Result<u32, RenderError> {
+        raw.trim().parse().map_err(|_| {
+            RenderError::message(format!(
+                "invalid --pointer '{entry}': '{}' is not a non-negative frame index",
+                raw.trim()
+            ))
+        })
+    };
+    let (start, end) = (parse_frame(start)?, parse_frame(end)?);
+    if end <= start {
+        return Err(RenderError::message(format!(
+            "invalid --pointer '{entry}': the drag must end after it starts, e.g. @{start}-{}",
+            start.saturating_add(10)
+        )));
+    }
+    if end - start > MAX_DRAG_FRAMES {
+        return Err(RenderError::message(format!(
+            "invalid --pointer '{entry}': a drag may span at most {MAX_DRAG_FRAMES} frames, e.g. @{start}-{}",
+            start.saturating_add(MAX_DRAG_FRAMES)
+        )));
+    }
+    let (x1, y1) = parse_point(entry, from)?;
+    let (x2, y2) = parse_point(entry, to)?;
+    let span = f64::from(end - start);
+    let mut events = vec![json!({ "event": "down", "x": x1, "y": y1, "frame": start })];
+    for frame in start + 1..=end {
+        let t = f64::from(frame - start) / span;
+        events.push(json!({
+            "event": "move",
+            "x": x1 + (x2 - x1) * t,
+            "y": y1 + (y2 - y1) * t,
+            "frame": frame,
+        }));
+    }
+    events.push(json!({ "event": "up", "x": x2, "y": y2, "frame": end }));
+    Ok(events)
+}
+
+/// Parses one `--pointer` entry, which is a single event or a drag gesture
+/// that expands to a sequence of them.
+fn parse_pointer_entry(entry: &str) -> Result<Vec<Value>, RenderError> {
+    match entry.trim().strip_prefix("drag:") {
+        Some(rest) => parse_drag(entry, rest),
+        None => parse_pointer(entry).map(|pointer| vec![pointer]),
+    }
+}
+
+struct ScheduledEvents {
+    inputs: Vec<Value>,
+    pointers: Vec<Value>,
+    view_model: Vec<Value>,
+}
+
+fn scheduled_events(options: &RenderOptions) -> Result<ScheduledEvents, RenderError> {
     let inputs = options
         .inputs
         .iter()
@@ -507,7 +697,15 @@ fn load_scene(
     let pointers = options
         .pointers
         .iter()
-        .map(|entry| parse_pointer(entry))
+        .map(|entry| parse_pointer_entry(entry))
+        .collect::<Result<Vec<_>, _>>()?
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+    let view_model = options
+        .view_model
+        .iter()
+        .map(|entry| parse_view_model_value(entry))
         .collect::<Result<Vec<_>, _>>()?;
     if !inputs.is_empty() && options.state_machine.is_none() {
         return Err(RenderError::message(
@@ -519,6 +717,41 @@ fn load_scene(
             "--pointer only applies when --state-machine is given",
         ));
     }
+    if options.state_machine.is_none() && view_model.iter().any(|value| value["kind"] == "trigger")
+    {
+        return Err(RenderError::message(
+            "a --view-model trigger only applies when --state-machine is given",
+        ));
+    }
+    if options
+        .view_model_instance
+        .as_deref()
+        .is_some_and(|name| name.trim().is_empty())
+    {
+        return Err(RenderError::message(
+            "--view-model-instance needs a non-empty instance name",
+        ));
+    }
+    Ok(ScheduledEvents {
+        inputs,
+        pointers,
+        view_model,
+    })
+}
+
+fn load_scene(
+    browser: &mut chrome::Chrome,
+    session: &str,
+    options: &RenderOptions,
+    background: Option<&str>,
+    events: ScheduledEvents,
+    external_assets: &[Value],
+) -> Result<LoadedScene, RenderError> {
+    let ScheduledEvents {
+        inputs,
+        pointers,
+        view_model,
+    } = events;
     let request = json!({
         "width": options.width,
         "height": options.height,
@@ -529,7 +762,10 @@ fn load_scene(
         "stateMachine": options.state_machine,
         "inputs": inputs,
         "pointers": pointers,
+        "viewModel": view_model,
+        "viewModelInstance": options.view_model_instance,
         "background": background,
+        "externalAssets": external_assets,
     });
     let evaluated = browser.call(
         "Runtime.evaluate",
@@ -576,9 +812,69 @@ fn load_scene(
         selected_state_machine: if is_state_machine { selected } else { None },
         applied_inputs: inputs,
         applied_pointers: pointers,
+        view_model: value
+            .get("viewModel")
+            .and_then(Value::as_str)
+            .map(str::to_string),
+        requested_view_model: !view_model.is_empty(),
+        supplied_assets: string_list(value.get("suppliedAssets")),
+    })
+}
+
+fn fired_events(
+    browser: &mut chrome::Chrome,
+    session: &str,
+) -> Result<Vec<FiredEvent>, RenderError> {
+    let evaluated = browser.call(
+        "Runtime.evaluate",
+        json!({
+            "expression": "window.riveFiredEvents()",
+            "returnByValue": true,
+        }),
+        Some(session),
+    )?;
+    if let Some(details) = evaluated.get("exceptionDetails") {
+        return Err(RenderError::message(format!(
+            "the render harness could not report fired events: {}",
+            exception_text(details)
+        )));
+    }
+    let value = evaluated
+        .get("result")
+        .and_then(|result| result.get("value"))
+        .cloned()
+        .unwrap_or(Value::Array(Vec::new()));
+    serde_json::from_value(value).map_err(|error| {
+        RenderError::message(format!("render harness reported malformed events: {error}"))
     })
 }
 
+fn applied_view_model(
+    browser: &mut chrome::Chrome,
+    session: &str,
+) -> Result<Vec<Value>, RenderError> {
+    let evaluated = browser.call(
+        "Runtime.evaluate",
+        json!({
+            "expression": "window.riveAppliedViewModel()",
+            "returnByValue": true,
+        }),
+        Some(session),
+    )?;
+    if let Some(details) = evaluated.get("exceptionDetails") {
+        return Err(RenderError::message(format!(
+            "the render harness could not report applied view model values: {}",
+            exception_text(details)
+        )));
+    }
+    Ok(evaluated
+        .get("result")
+        .and_then(|result| result.get("value"))
+        .and_then(Value::as_array)
+        .cloned()
+        .unwrap_or_default())
+}
+
 fn exception_text(details: &Value) -> String {
     details
         .get("exception")
@@ -659,6 +955,35 @@ pub fn render_manifest_text(manifest: &RenderManifest) -> String {
             pointer["frame"]
         ));
     }
+    if let Some(view_model) = &manifest.view_model {
+        let instance = manifest
+            .view_model_instance
+            .as_deref()
+            .unwrap_or("default instance");
+        text.push_str(&format!("  view model '{view_model}' ({instance})\n"));
+    }
+    for property in &manifest.applied_view_model {
+        let name = property["name"].as_str().unwrap_or("?");
+        let when = match property["frame"].as_u64() {
+            Some(frame) => format!("frame {frame}"),
+            None => "before playback".to_string(),
+        };
+        let value = match property["kind"].as_str() {
+            Some("trigger") => "trigger".to_string(),
+            _ => property["value"].to_string(),
+        };
+        text.push_str(&format!("  view model {name} = {value} @ {when}\n"));
+    }
+    for event in &manifest.fired_events {
+        text.push_str(&format!("  event {} @ frame {}", event.name, event.frame));
+        for (name, value) in &event.properties {
+            text.push_str(&format!(" {name}={value}"));
+        }
+        text.push('\n');
+    }
+    for file in &manifest.supplied_assets {
+        text.push_str(&format!("  external asset {file}\n"));
+    }
     if let Some(sheet) = &manifest.contact_sheet {
         text.push_str(&format!("  contact sheet: {sheet}\n"));
     }
@@ -748,6 +1073,35 @@ mod tests {
         assert!(parse_input("x=true@abc").is_err());
     }
 
+    #[test]
+    fn parses_typed_view_model_values() {
+        assert_eq!(parse_view_model_value("on=true").unwrap()["kind"], "bool");
+        assert_eq!(parse_view_model_value("score=42").unwrap()["value"], 42.0);
+        assert_eq!(
+            parse_view_model_value("tap=trigger@12").unwrap()["frame"],
+            12
+        );
+        let color = parse_view_model_value("accent=#ff8800").unwrap();
+        assert_eq!(color["kind"], "color");
+        assert_eq!(color["value"], "#FF8800FF");
+        let mode = parse_view_model_value("mode=busy@3").unwrap();
+        assert_eq!(mode["kind"], "string");
+        assert_eq!(mode["value"], "busy");
+        let quoted = parse_view_model_value("title=\"42\"").unwrap();
+        assert_eq!(quoted["kind"], "string");
+        assert_eq!(quoted["value"], "42");
+        assert_eq!(parse_view_model_value("title=").unwrap()["value"], "");
+    }
+
+    #[test]
+    fn rejects_malformed_view_model_values() {
+        assert!(parse_view_model_value("score").is_err());
+        assert!(parse_view_model_value("=1").is_err());
+        assert!(parse_view_model_value("score=inf").is_err());
+        assert!(parse_view_model_value("accent=#FFF").is_err());
+        assert!(parse_view_model_value("score=1@x").is_err());
+    }
+
     #[test]
     fn parses_pointer_events() {
         let pointer = parse_pointer("down:120,90@10").unwrap();
@@ -771,6 +1125,58 @@ mod tests {
         assert!(parse_pointer("down:1,2@x").is_err());
     }
 
+    #[test]
+    fn expands_drag_gestures_into_interpolated_moves() {
+        let events = parse_pointer_entry("drag:0,100->40,20@10-14").unwrap();
+        let summary = events
+            .iter()
+            .map(|event| {
+                (
+                    event["event"].as_str().unwrap().to_string(),
+                    event["x"].as_f64().unwrap(),
+                    event["y"].as_f64().unwrap(),
+                    event["frame"].as_u64().unwrap(),
+                )
+            })
+            .collect::<Vec<_>>();
+        let expected = [
+            ("down", 0.0, 100.0, 10),
+            ("move", 10.0, 80.0, 11),
+            ("move", 20.0, 60.0, 12),
+            ("move", 30.0, 40.0, 13),
+            ("move", 40.0, 20.0, 14),
+            ("up", 40.0, 20.0, 14),
+        ]
+        .map(|(event, x, y, frame)| (event.to_string(), x, y, frame));
+        assert_eq!(summary, expected);
+        assert_eq!(parse_pointer_entry("down:1,2@0").unwrap().len(), 1);
+    }
+
+    #[test]
+    fn rejects_malformed_drag_gestures() {
+        assert!(parse_pointer_entry("drag:0,0->10,10@5").is_err());
+        assert!(parse_pointer_entry("drag:0,0->10,10@5-5").is_err());
+        assert!(parse_pointer_entry("drag:0,0->10,10@9-5").is_err());
+        assert!(parse_pointer_entry("drag:0,0,10,10@0-5").is_err());
+        assert!(parse_pointer_entry("drag:0,0->a,10@0-5").is_err());
+        assert!(parse_pointer_entry("drag:0,0->10,10").is_err());
+    }
+
+    #[test]
+    fn rejects_drag_gestures_longer_than_the_frame_cap() {
+        assert_eq!(
+            parse_pointer_entry(&format!("drag:0,0->1,1@5-{}", 5 + MAX_DRAG_FRAMES))
+                .expect("drag at the cap")
+                .len(),
+            MAX_DRAG_FRAMES as usize + 2
+        );
+        let error = parse_pointer_entry("drag:0,0->1,1@0-4000000000").expect_err("too long");
+        assert!(
+            error.to_string().contains("at most 10000 frames"),
+            "{error}"
+        );
+    }
+
     #[test]
     fn accepts_valid_backgrounds_and_rejects_others() {
         assert!(parse_background("#FF0000").is_ok());
@@ -779,4 +1185,36 @@ mod tests {
         assert!(parse_background("#FFF").is_err());
         assert!(parse_background("#GGGGGG").is_err());
     }
+
+    #[test]
+    fn manifest_text_lists_fired_events_with_their_properties() {
+        let manifest: RenderManifest = serde_json::from_value(json!({
+            "source": "scene.riv",
+            "artboard": "Main",
+            "animation": null,
+            "state_machine": "SM",
+            "available_animations": [],
+            "available_state_machines": ["SM"],
+            "width": 100,
+            "height": 100,
+            "scale": 1,
+            "fps": 60.0,
+            "frames": [],
+            "fired_events": [
+                { "frame": 10, "name": "pulsed", "properties": { "haptic": true } },
+                { "frame": 12, "name": "click" }
+            ],
+            "contact_sheet": null
+        }))
+        .unwrap();
+        let text = render_manifest_text(&manifest);
+        assert!(
+            text.contains("  event pulsed @ frame 10 haptic=true\n"),
+            "{text}"
+        );
+        assert!(text.contains("  event click @ frame 12\n"), "{text}");
+
+        let round_trip = serde_json::to_value(&manifest).unwrap();
+        assert!(round_trip["fired_events"][1].get("properties").is_none());
+    }
 }
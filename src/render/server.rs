@@ -24,6 +24,7 @@ struct Assets {
     js: &'static [u8],
     wasm: &'static [u8],
     scene: Vec<u8>,
+    external: Vec<Vec<u8>>,
 }
 
 impl AssetServer {
@@ -32,6 +33,7 @@ impl AssetServer {
         js: &'static [u8],
         wasm: &'static [u8],
         scene: Vec<u8>,
+        external: Vec<Vec<u8>>,
     ) -> std::io::Result<Self> {
         let listener = TcpListener::bind("127.0.0.1:0")?;
         listener.set_nonblocking(true)?;
@@ -43,6 +45,7 @@ impl AssetServer {
             js,
             wasm,
             scene,
+            external,
         });
         let thread = thread::spawn(move || {
             let mut workers: Vec<JoinHandle<()>> = Vec::new();
@@ -101,7 +104,14 @@ fn serve(mut stream: TcpStream, assets: &Assets) {
         "/rive.js" => ("200 OK", "application/javascript", assets.js),
         "/rive.wasm" => ("200 OK", "application/wasm", assets.wasm),
         "/scene.riv" => ("200 OK", "application/octet-stream", &assets.scene),
-        _ => ("404 Not Found", "text/plain; charset=utf-8", b"not found"),
+        _ => match route
+            .strip_prefix("/assets/")
+            .and_then(|index| index.parse::<usize>().ok())
+            .and_then(|index| assets.external.get(index))
+        {
+            Some(bytes) => ("200 OK", "application/octet-stream", bytes.as_slice()),
+            None => ("404 Not Found", "text/plain; charset=utf-8", b"not found"),
+        },
     };
     let header = format!(
         "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nCache-Control: no-store\r\nConnection: close\r\n\r\n",
@@ -0,0 +1,548 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::objects::core::{property_keys, type_keys};
+
+use super::parser::{PropertyValueRead, RivObject};
+
+/// The container formats the Rive runtime can decode for an `AudioAsset`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AudioFormat {
+    Wav,
+    Mp3,
+    Flac,
+}
+
+impl fmt::Display for AudioFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            AudioFormat::Wav => "WAV",
+            AudioFormat::Mp3 => "MP3",
+            AudioFormat::Flac => "FLAC",
+        })
+    }
+}
+
+/// What an audio file's headers say about its stream.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AudioInfo {
+    pub format: AudioFormat,
+    pub sample_rate: u32,
+    pub channels: u16,
+    pub duration_seconds: f64,
+}
+
+impl fmt::Display for AudioInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}, {} Hz, {} ch, {:.3}s",
+            self.format, self.sample_rate, self.channels, self.duration_seconds
+        )
+    }
+}
+
+/// Reads the headers of a WAV, MP3 or FLAC file without decoding any
+/// samples. Fails when the bytes are none of those, or when the headers are
+/// too damaged to give a sample rate, channel count and duration.
+pub fn sniff_audio(bytes: &[u8]) -> Result<AudioInfo, String> {
+    if bytes.starts_with(b"RIFF") {
+        sniff_wav(bytes)
+    } else if bytes.starts_with(b"fLaC") {
+        sniff_flac(bytes)
+    } else if bytes.starts_with(b"ID3") || frame_sync(bytes) {
+        sniff_mp3(bytes)
+    } else {
+        Err("not a WAV, MP3 or FLAC file".to_string())
+    }
+}
+
+fn u16_le(bytes: &[u8], at: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(bytes.get(at..at + 2)?.try_into().ok()?))
+}
+
+fn u32_le(bytes: &[u8], at: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(bytes.get(at..at + 4)?.try_into().ok()?))
+}
+
+fn u32_be(bytes: &[u8], at: usize) -> Option<u32> {
+    Some(u32::from_be_bytes(bytes.get(at..at + 4)?.try_into().ok()?))
+}
+
+fn sniff_wav(bytes: &[u8]) -> Result<AudioInfo, String> {
+    if bytes.get(8..12) != Some(b"WAVE") {
+        return Err("RIFF file is not a WAVE file".to_string());
+    }
+    let mut format = None;
+    let mut offset = 12;
+    while offset + 8 <= bytes.len() {
+        let id = &bytes[offset..offset + 4];
+        let size = u32_le(bytes, offset + 4).unwrap_or(0) as usize;
+        let body = offset + 8;
+        match id {
+            b"fmt " => {
+                let channels = u16_le(bytes, body + 2);
+                let sample_rate = u32_le(bytes, body + 4);
+                let byte_rate = u32_le(bytes, body + 8);
+                let (Some(channels), Some(sample_rate), Some(byte_rate)) =
+                    (channels, sample_rate, byte_rate)
+                else {
+                    return Err("WAV 'fmt ' chunk is truncated".to_string());
+                };
+                if channels == 0 || sample_rate == 0 || byte_rate == 0 {
+                    return Err(format!(
+                        "WAV 'fmt ' chunk declares {channels} channel(s) at {sample_rate} Hz and {byte_rate} bytes/s"
+                    ));
+                }
+                format = Some((channels, sample_rate, byte_rate));
+            }
+            b"data" => {
+                let Some((channels, sample_rate, byte_rate)) = format else {
+                    return Err("WAV 'data' chunk comes before its 'fmt ' chunk".to_string());
+                };
+                if body + size > bytes.len() {
+                    return Err(format!(
+                        "WAV 'data' chunk declares {size} bytes but only {} remain",
+                        bytes.len() - body
+                    ));
+                }
+                return Ok(AudioInfo {
+                    format: AudioFormat::Wav,
+                    sample_rate,
+                    channels,
+                    duration_seconds: size as f64 / byte_rate as f64,
+                });
+            }
+            _ => {}
+        }
+        // Chunks are padded to an even length.
+        offset = body + size + (size & 1);
+    }
+    Err(match format {
+        Some(_) => "WAV file has no 'data' chunk".to_string(),
+        None => "WAV file has no 'fmt ' chunk".to_string(),
+    })
+}
+
+fn sniff_flac(bytes: &[u8]) -> Result<AudioInfo, String> {
+    // The first metadata block must be STREAMINFO (type 0, 34 bytes).
+    let header = bytes.get(4..8).ok_or("FLAC file has no metadata blocks")?;
+    let length = u32::from_be_bytes([0, header[1], header[2], header[3]]);
+    if header[0] & 0x7F != 0 || length != 34 {
+        return Err("FLAC file does not start with a STREAMINFO block".to_string());
+    }
+    let info = bytes
+        .get(8..42)
+        .ok_or("FLAC STREAMINFO block is truncated")?;
+    let packed = u64::from_be_bytes(info[10..18].try_into().expect("8 bytes"));
+    let sample_rate = (packed >> 44) as u32;
+    let channels = ((packed >> 41) & 0x7) as u16 + 1;
+    let total_samples = packed & 0xF_FFFF_FFFF;
+    if sample_rate == 0 {
+        return Err("FLAC STREAMINFO declares a sample rate of 0 Hz".to_string());
+    }
+    if total_samples == 0 {
+        return Err("FLAC STREAMINFO does not record the stream length".to_string());
+    }
+    Ok(AudioInfo {
+        format: AudioFormat::Flac,
+        sample_rate,
+        channels,
+        duration_seconds: total_samples as f64 / sample_rate as f64,
+    })
+}
+
+fn frame_sync(bytes: &[u8]) -> bool {
+    bytes.len() >= 2 && bytes[0] == 0xFF && bytes[1] & 0xE0 == 0xE0
+}
+
+const MPEG1_BITRATES: [[u32; 15]; 3] = [
+    [
+        0, 32, 64, 96, 128, 160, 192, 224, 256, 288, 320, 352, 384, 416, 448,
+    ],
+    [
+        0, 32, 48, 56, 64, 80, 96, 112, 128, 160, 192, 224, 256, 320, 384,
+    ],
+    [
+        0, 32, 40, 48, 56, 64, 80, 96, 112, 128, 160, 192, 224, 256, 320,
+    ],
+];
+const MPEG2_BITRATES: [[u32; 15]; 2] = [
+    [
+        0, 32, 48, 56, 64, 80, 96, 112, 128, 144, 160, 176, 192, 224, 256,
+    ],
+    [0, 8, 16, 24, 32, 40, 48, 56, 64, 80, 96, 112, 128, 144, 160],
+];
+
+fn sniff_mp3(bytes: &[u8]) -> Result<AudioInfo, String> {
+    let mut start = 0;
+    if bytes.starts_with(b"ID3") {
+        let header = bytes.get(..10).ok_or("ID3v2 tag is truncated")?;
+        // The tag size is a 28-bit "syncsafe" integer; a footer adds 10 bytes.
+        let size = header[6..10]
+            .iter()
+            .fold(0usize, |size, byte| (size << 7) | (*byte & 0x7F) as usize);
+        let footer = if header[5] & 0x10 != 0 { 10 } else { 0 };
+        start = 10 + size + footer;
+    }
+    let header = bytes
+        .get(start..start + 4)
+        .filter(|header| frame_sync(header))
+        .ok_or("MP3 file has no MPEG frame after its ID3 tag")?;
+
+    // 0 = MPEG 2.5, 2 = MPEG 2, 3 = MPEG 1; 1 is reserved.
+    let version = (header[1] >> 3) & 0x3;
+    // 1 = Layer III, 2 = Layer II, 3 = Layer I; 0 is reserved.
+    let layer = (header[1] >> 1) & 0x3;
+    if version == 1 || layer == 0 {
+        return Err("MPEG frame header uses a reserved version or layer".to_string());
+    }
+    let mpeg1 = version == 3;
+    let bitrate_index = (header[2] >> 4) as usize;
+    if bitrate_index == 0 || bitrate_index == 15 {
+        return Err("MPEG frame header uses a free-format or invalid bitrate".to_string());
+    }
+    let bitrate_kbps = if mpeg1 {
+        MPEG1_BITRATES[(3 - layer) as usize][bitrate_index]
+    } else {
+        MPEG2_BITRATES[usize::from(layer != 3)][bitrate_index]
+    };
+    let sample_rate = match ((header[2] >> 2) & 0x3, version) {
+        (3, _) => return Err("MPEG frame header uses a reserved sample rate".to_string()),
+        (index, 3) => [44100, 48000, 32000][index as usize],
+        (index, 2) => [22050, 24000, 16000][index as usize],
+        (index, _) => [11025, 12000, 8000][index as usize],
+    };
+    let mono = header[3] >> 6 == 3;
+    let channels = if mono { 1 } else { 2 };
+    let samples_per_frame = match layer {
+        3 => 384,
+        2 => 1152,
+        _ if mpeg1 => 1152,
+        _ => 576,
+    };
+
+    // A Xing/Info header in the first Layer III frame records the frame
+    // count, which is the only exact length a VBR stream has.
+    let side_info = match (mpeg1, mono) {
+        (true, true) => 17,
+        (true, false) => 32,
+        (false, true) => 9,
+        (false, false) => 17,
+    };
+    let xing = start + 4 + side_info;
+    let frames = (layer == 1
+        && matches!(bytes.get(xing..xing + 4), Some(b"Xing") | Some(b"Info"))
+        && u32_be(bytes, xing + 4).is_some_and(|flags| flags & 1 != 0))
+    .then(|| u32_be(bytes, xing + 8))
+    .flatten();
+    let duration_seconds = match frames {
+        Some(frames) => frames as f64 * samples_per_frame as f64 / sample_rate as f64,
+        None => {
+            let mut end = bytes.len();
+            if end >= start + 128 && bytes[end - 128..].starts_with(b"TAG") {
+                end -= 128;
+            }
+            (end - start) as f64 * 8.0 / (bitrate_kbps as f64 * 1000.0)
+        }
+    };
+    Ok(AudioInfo {
+        format: AudioFormat::Mp3,
+        sample_rate,
+        channels,
+        duration_seconds,
+    })
+}
+
+fn uint_property(object: &RivObject, key: u16) -> Option<u64> {
+    object
+        .properties
+        .iter()
+        .find(|property| property.key == key)
+        .and_then(|property| match property.value {
+            PropertyValueRead::UInt(value) => Some(value),
+            _ => None,
+        })
+}
+
+fn name_property(object: &RivObject, key: u16) -> String {
+    object
+        .properties
+        .iter()
+        .find(|property| property.key == key)
+        .and_then(|property| match &property.value {
+            PropertyValueRead::String(name) => Some(name.clone()),
+            _ => None,
+        })
+        .unwrap_or_default()
+}
+
+/// Finds every `AudioEvent` trigger keyed in a linear animation whose clip
+/// keeps playing after the animation ends. Audio assets are matched by file
+/// asset ordinal and need a decoded [`RivObject::audio`] to be checked.
+pub(crate) fn audio_timeline_warnings(objects: &[RivObject]) -> Vec<String> {
+    let clips = objects
+        .iter()
+        .filter(|object| {
+            matches!(
+                object.type_key,
+                type_keys::IMAGE_ASSET
+                    | type_keys::FONT_ASSET
+                    | type_keys::AUDIO_ASSET
+                    | type_keys::SCRIPT_ASSET
+            )
+        })
+        .map(|asset| {
+            let info = match &asset.audio {
+                Some(super::RivAudio::Decoded(info)) => Some(info),
+                _ => None,
+            };
+            (name_property(asset, property_keys::ASSET_NAME), info)
+        })
+        .collect::<Vec<_>>();
+    let audio_events = objects
+        .iter()
+        .filter(|object| object.type_key == type_keys::AUDIO_EVENT)
+        .filter_map(|event| {
+            // An omitted asset id means the event plays nothing.
+            let asset_id = uint_property(event, property_keys::AUDIO_EVENT_ASSET_ID)
+                .unwrap_or(u32::MAX as u64);
+            Some((
+                (event.artboard_index?, event.local_index?),
+                (
+                    name_property(event, property_keys::COMPONENT_NAME),
+                    asset_id,
+                ),
+            ))
+        })
+        .collect::<HashMap<_, _>>();
+
+    let mut warnings = Vec::new();
+    let mut animation: Option<(String, f64, u64)> = None;
+    let mut keyed_event = None;
+    let mut keys_trigger = false;
+    for object in objects {
+        match object.type_key {
+            type_keys::LINEAR_ANIMATION => {
+                let fps = uint_property(object, property_keys::LINEAR_ANIMATION_FPS).unwrap_or(60);
+                let mut end =
+                    uint_property(object, property_keys::LINEAR_ANIMATION_DURATION).unwrap_or(60);
+                if uint_property(object, property_keys::LINEAR_ANIMATION_ENABLE_WORK_AREA)
+                    .is_some_and(|enabled| enabled != 0)
+                {
+                    end = uint_property(object, property_keys::LINEAR_ANIMATION_WORK_END)
+                        .unwrap_or(end);
+                }
+                animation = Some((
+                    name_property(object, property_keys::ANIMATION_NAME),
+                    fps.max(1) as f64,
+                    end,
+                ));
+                keyed_event = None;
+            }
+            type_keys::KEYED_OBJECT => {
+                let id = uint_property(object, property_keys::KEYED_OBJECT_ID).unwrap_or(0);
+                keyed_event = object
+                    .artboard_index
+                    .and_then(|artboard| audio_events.get(&(artboard, id as usize)));
+            }
+            type_keys::KEYED_PROPERTY => {
+                keys_trigger = uint_property(object, property_keys::KEYED_PROPERTY_KEY)
+                    == Some(property_keys::EVENT_TRIGGER as u64);
+            }
+            type_keys::ARTBOARD => animation = None,
+            _ => {
+                let is_key_frame = object
+                    .properties
+                    .iter()
+                    .any(|property| property.key == property_keys::KEY_FRAME_FRAME)
+                    || object.type_key == type_keys::KEY_FRAME_CALLBACK;
+                let (Some((animation_name, fps, end)), Some((event_name, asset_id)), true, true) =
+                    (&animation, keyed_event, keys_trigger, is_key_frame)
+                else {
+                    continue;
+                };
+                let Some((asset_name, Some(info))) = clips.get(*asset_id as usize) else {
+                    continue;
+                };
+                let frame = uint_property(object, property_keys::KEY_FRAME_FRAME).unwrap_or(0);
+                let starts = frame as f64 / fps;
+                let stops = starts + info.duration_seconds;
+                let ends = *end as f64 / fps;
+                if stops > ends + 1e-6 {
+                    warnings.push(format!(
+                        "animation '{animation_name}' plays audio '{asset_name}' through event '{event_name}' at frame {frame} ({starts:.3}s); its {:.3}s clip runs to {stops:.3}s, past the animation's end at {ends:.3}s",
+                        info.duration_seconds
+                    ));
+                }
+            }
+        }
+    }
+    warnings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wav(sample_rate: u32, channels: u16, frames: u32) -> Vec<u8> {
+        let block_align = channels * 2;
+        let data_len = frames * block_align as u32;
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(b"RIFF");
+        bytes.extend_from_slice(&(36 + data_len).to_le_bytes());
+        bytes.extend_from_slice(b"WAVE");
+        bytes.extend_from_slice(b"fmt ");
+        bytes.extend_from_slice(&16u32.to_le_bytes());
+        bytes.extend_from_slice(&1u16.to_le_bytes());
+        bytes.extend_from_slice(&channels.to_le_bytes());
+        bytes.extend_from_slice(&sample_rate.to_le_bytes());
+        bytes.extend_from_slice(&(sample_rate * block_align as u32).to_le_bytes());
+        bytes.extend_from_slice(&block_align.to_le_bytes());
+        bytes.extend_from_slice(&16u16.to_le_bytes());
+        bytes.extend_from_slice(b"data");
+        bytes.extend_from_slice(&data_len.to_le_bytes());
+        bytes.resize(bytes.len() + data_len as usize, 0);
+        bytes
+    }
+
+    #[test]
+    fn test_sniff_wav_reads_the_fmt_and_data_chunks() {
+        let info = sniff_audio(&wav(8000, 2, 12000)).unwrap();
+        assert_eq!(
+            info,
+            AudioInfo {
+                format: AudioFormat::Wav,
+                sample_rate: 8000,
+                channels: 2,
+                duration_seconds: 1.5,
+            }
+        );
+        assert_eq!(info.to_string(), "WAV, 8000 Hz, 2 ch, 1.500s");
+
+        let mut truncated = wav(8000, 1, 800);
+        truncated.truncate(100);
+        assert!(
+            sniff_audio(&truncated)
+                .unwrap_err()
+                .contains("declares 1600 bytes")
+        );
+    }
+
+    #[test]
+    fn test_sniff_flac_reads_streaminfo() {
+        let mut bytes = b"fLaC".to_vec();
+        bytes.extend_from_slice(&[0x80, 0, 0, 34]);
+        bytes.extend_from_slice(&[0; 10]);
+        // 48000 Hz, 2 channels, 16 bits per sample, 96000 samples.
+        let packed: u64 = (48000 << 44) | (1 << 41) | (15 << 36) | 96000;
+        bytes.extend_from_slice(&packed.to_be_bytes());
+        bytes.extend_from_slice(&[0; 16]);
+
+        let info = sniff_audio(&bytes).unwrap();
+        assert_eq!(info.format, AudioFormat::Flac);
+        assert_eq!(info.sample_rate, 48000);
+        assert_eq!(info.channels, 2);
+        assert_eq!(info.duration_seconds, 2.0);
+    }
+
+    #[test]
+    fn test_sniff_mp3_estimates_cbr_length_and_reads_xing_frame_counts() {
+        // MPEG 1 Layer III, 128 kbps, 44.1 kHz, joint stereo, after an ID3v2 tag.
+        let mut bytes = b"ID3\x04\x00\x00\x00\x00\x00\x0A".to_vec();
+        bytes.extend_from_slice(&[0; 10]);
+        bytes.extend_from_slice(&[0xFF, 0xFB, 0x90, 0x40]);
+        bytes.resize(20 + 16_000, 0);
+        let info = sniff_audio(&bytes).unwrap();
+        assert_eq!(info.format, AudioFormat::Mp3);
+        assert_eq!(info.sample_rate, 44100);
+        assert_eq!(info.channels, 2);
+        assert_eq!(info.duration_seconds, 1.0);
+
+        // MPEG 2 Layer III mono with an Info header counting 100 frames.
+        let mut vbr = vec![0xFF, 0xF3, 0x44, 0xC0];
+        vbr.extend_from_slice(&[0; 9]);
+        vbr.extend_from_slice(b"Info");
+        vbr.extend_from_slice(&1u32.to_be_bytes());
+        vbr.extend_from_slice(&100u32.to_be_bytes());
+        vbr.resize(400, 0);
+        let info = sniff_audio(&vbr).unwrap();
+        assert_eq!(info.sample_rate, 24000);
+        assert_eq!(info.channels, 1);
+        assert_eq!(info.duration_seconds, 100.0 * 576.0 / 24000.0);
+    }
+
+    fn scheduled_clip(duration_frames: u64) -> Vec<u8> {
+        use crate::encoder::encode_riv;
+        use crate::objects::animation::{
+            KeyFrameCallback, KeyedObject, KeyedProperty, LinearAnimation,
+        };
+        use crate::objects::artboard::{Artboard, Backboard};
+        use crate::objects::assets::{AudioAsset, FileAssetContents};
+        use crate::objects::state_machine::AudioEvent;
+
+        let asset = AudioAsset::new("Chime".to_string());
+        let contents = FileAssetContents::new(wav(8000, 1, 12000));
+        let artboard = Artboard::new("Main".to_string(), 100.0, 100.0);
+        let mut event = AudioEvent::new("Ding".to_string(), 0);
+        event.asset_id = 0;
+        let animation = LinearAnimation::new("Intro", 60, duration_frames);
+        let keyed_object = KeyedObject { object_id: 1 };
+        let keyed_property = KeyedProperty {
+            property_key: property_keys::EVENT_TRIGGER as u64,
+        };
+        let key_frame = KeyFrameCallback { frame: 30 };
+        encode_riv(
+            &[
+                &Backboard,
+                &asset,
+                &contents,
+                &artboard,
+                &event,
+                &animation,
+                &keyed_object,
+                &keyed_property,
+                &key_frame,
+            ],
+            0,
+        )
+    }
+
+    #[test]
+    fn test_inspect_and_validate_report_embedded_audio_on_the_timeline() {
+        let data = scheduled_clip(60);
+        let output = super::super::inspect_riv(&data, &Default::default()).unwrap();
+        assert!(
+            output.contains("  audio: WAV, 8000 Hz, 1 ch, 1.500s\n"),
+            "{output}"
+        );
+
+        let report = super::super::validate_riv(&data).unwrap();
+        assert!(report.valid, "{:?}", report.errors);
+        assert_eq!(
+            report.warnings,
+            [
+                "animation 'Intro' plays audio 'Chime' through event 'Ding' at frame 30 (0.500s); its 1.500s clip runs to 2.000s, past the animation's end at 1.000s"
+            ]
+        );
+
+        let report = super::super::validate_riv(&scheduled_clip(120)).unwrap();
+        assert!(report.warnings.is_empty(), "{:?}", report.warnings);
+    }
+
+    #[test]
+    fn test_sniff_audio_rejects_other_files() {
+        assert_eq!(
+            sniff_audio(b"OggS\0\0\0\0").unwrap_err(),
+            "not a WAV, MP3 or FLAC file"
+        );
+        assert!(
+            sniff_audio(b"ID3\x04\x00\x00\x00\x00\x00\x00not audio")
+                .unwrap_err()
+                .contains("no MPEG frame")
+        );
+    }
+}
@@ -0,0 +1,202 @@
+use std::fmt;
+
+use serde::Serialize;
+
+/// The image formats the Rive runtime can decode for an `ImageAsset`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ImageFormat {
+    Png,
+    Jpeg,
+    Webp,
+}
+
+impl fmt::Display for ImageFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ImageFormat::Png => "PNG",
+            ImageFormat::Jpeg => "JPEG",
+            ImageFormat::Webp => "WebP",
+        })
+    }
+}
+
+/// What an image file's headers say about its pixels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct ImageInfo {
+    pub format: ImageFormat,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl fmt::Display for ImageInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}, {}x{}", self.format, self.width, self.height)
+    }
+}
+
+/// Reads the intrinsic size of a PNG, JPEG or WebP file from its headers
+/// without decoding any pixels.
+pub fn sniff_image(bytes: &[u8]) -> Result<ImageInfo, String> {
+    let (format, size) = if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+        (ImageFormat::Png, png_size(bytes))
+    } else if bytes.starts_with(&[0xFF, 0xD8]) {
+        (ImageFormat::Jpeg, jpeg_size(bytes))
+    } else if bytes.starts_with(b"RIFF") && bytes.get(8..12) == Some(b"WEBP") {
+        (ImageFormat::Webp, webp_size(bytes))
+    } else {
+        return Err("not a PNG, JPEG or WebP file".to_string());
+    };
+    let (width, height) = size.ok_or_else(|| format!("{format} header is truncated"))?;
+    if width == 0 || height == 0 {
+        return Err(format!(
+            "{format} header declares an empty {width}x{height} image"
+        ));
+    }
+    Ok(ImageInfo {
+        format,
+        width,
+        height,
+    })
+}
+
+fn u16_be(bytes: &[u8], at: usize) -> Option<u16> {
+    Some(u16::from_be_bytes(bytes.get(at..at + 2)?.try_into().ok()?))
+}
+
+fn u32_be(bytes: &[u8], at: usize) -> Option<u32> {
+    Some(u32::from_be_bytes(bytes.get(at..at + 4)?.try_into().ok()?))
+}
+
+fn u32_le(bytes: &[u8], at: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(bytes.get(at..at + 4)?.try_into().ok()?))
+}
+
+/// `IHDR` is always the first chunk.
+fn png_size(bytes: &[u8]) -> Option<(u32, u32)> {
+    if bytes.get(12..16)? != b"IHDR" {
+        return None;
+    }
+    Some((u32_be(bytes, 16)?, u32_be(bytes, 20)?))
+}
+
+/// Walks the marker segments up to the first start-of-frame.
+fn jpeg_size(bytes: &[u8]) -> Option<(u32, u32)> {
+    let mut at = 2;
+    loop {
+        if *bytes.get(at)? != 0xFF {
+            return None;
+        }
+        let marker = *bytes.get(at + 1)?;
+        match marker {
+            // Fill bytes before a marker.
+            0xFF => at += 1,
+            0x01 | 0xD0..=0xD7 => at += 2,
+            0xC0..=0xCF if !matches!(marker, 0xC4 | 0xC8 | 0xCC) => {
+                let height = u16_be(bytes, at + 5)?;
+                let width = u16_be(bytes, at + 7)?;
+                return Some((u32::from(width), u32::from(height)));
+            }
+            // End of image or start of scan before any frame header.
+            0xD9 | 0xDA => return None,
+            _ => at += 2 + usize::from(u16_be(bytes, at + 2)?),
+        }
+    }
+}
+
+/// Reads the lossy, lossless or extended header that follows `WEBP`.
+fn webp_size(bytes: &[u8]) -> Option<(u32, u32)> {
+    match bytes.get(12..16)? {
+        b"VP8 " => {
+            if bytes.get(23..26)? != [0x9D, 0x01, 0x2A] {
+                return None;
+            }
+            let width = u32::from(u16::from_le_bytes(bytes.get(26..28)?.try_into().ok()?));
+            let height = u32::from(u16::from_le_bytes(bytes.get(28..30)?.try_into().ok()?));
+            Some((width & 0x3FFF, height & 0x3FFF))
+        }
+        b"VP8L" => {
+            if *bytes.get(20)? != 0x2F {
+                return None;
+            }
+            let packed = u32_le(bytes, 21)?;
+            Some(((packed & 0x3FFF) + 1, ((packed >> 14) & 0x3FFF) + 1))
+        }
+        b"VP8X" => {
+            let width = u32_le(bytes, 24)? & 0xFF_FFFF;
+            let height = u32_le(bytes, 26)? >> 8;
+            Some((width + 1, height + 1))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sniff_png_reads_ihdr() {
+        let mut bytes = b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR".to_vec();
+        bytes.extend_from_slice(&64u32.to_be_bytes());
+        bytes.extend_from_slice(&48u32.to_be_bytes());
+        let info = sniff_image(&bytes).unwrap();
+        assert_eq!(
+            info,
+            ImageInfo {
+                format: ImageFormat::Png,
+                width: 64,
+                height: 48,
+            }
+        );
+        assert_eq!(info.to_string(), "PNG, 64x48");
+        assert_eq!(
+            sniff_image(&bytes[..20]).unwrap_err(),
+            "PNG header is truncated"
+        );
+    }
+
+    #[test]
+    fn test_sniff_jpeg_skips_segments_to_the_frame_header() {
+        let mut bytes = vec![0xFF, 0xD8, 0xFF, 0xE0, 0x00, 0x04, 0x4A, 0x46];
+        bytes.extend_from_slice(&[0xFF, 0xFF, 0xC2, 0x00, 0x0B, 0x08]);
+        bytes.extend_from_slice(&30u16.to_be_bytes());
+        bytes.extend_from_slice(&40u16.to_be_bytes());
+        let info = sniff_image(&bytes).unwrap();
+        assert_eq!(info.format, ImageFormat::Jpeg);
+        assert_eq!((info.width, info.height), (40, 30));
+    }
+
+    #[test]
+    fn test_sniff_webp_reads_lossy_lossless_and_extended_headers() {
+        let riff = |chunk: &[u8; 4], payload: &[u8]| {
+            let mut bytes = b"RIFF\0\0\0\0WEBP".to_vec();
+            bytes.extend_from_slice(chunk);
+            bytes.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+            bytes.extend_from_slice(payload);
+            bytes
+        };
+        let mut lossy = vec![0, 0, 0, 0x9D, 0x01, 0x2A];
+        lossy.extend_from_slice(&100u16.to_le_bytes());
+        lossy.extend_from_slice(&50u16.to_le_bytes());
+        let lossless_size: u32 = (20 - 1) | ((10 - 1) << 14);
+        let mut lossless = vec![0x2F];
+        lossless.extend_from_slice(&lossless_size.to_le_bytes());
+        let mut extended = vec![0; 4];
+        extended.extend_from_slice(&[199, 0, 0, 99, 0, 0]);
+
+        for (bytes, size) in [
+            (riff(b"VP8 ", &lossy), (100, 50)),
+            (riff(b"VP8L", &lossless), (20, 10)),
+            (riff(b"VP8X", &extended), (200, 100)),
+        ] {
+            let info = sniff_image(&bytes).unwrap();
+            assert_eq!(info.format, ImageFormat::Webp);
+            assert_eq!((info.width, info.height), size);
+        }
+        assert_eq!(
+            sniff_image(b"GIF89a").unwrap_err(),
+            "not a PNG, JPEG or WebP file"
+        );
+    }
+}
@@ -1,9 +1,9 @@
-use std::collections::BTreeSet;
+use std::collections::{BTreeSet, HashMap};
 
 use crate::objects::core::{property_keys, type_keys};
 use crate::objects::generated_registry;
 
-use super::parser::{ParsedRiv, PropertyValueRead, RivObject};
+use super::parser::{ParsedRiv, PropertyValueRead, RivAudio, RivInfluence, RivObject, RivWeight};
 
 #[derive(Debug, Clone, Default)]
 pub struct InspectFilter {
@@ -72,6 +72,116 @@ pub(crate) fn annotate_object_context(objects: &mut [RivObject]) {
     }
 }
 
+fn uint_property(object: &RivObject, key: u16) -> Option<u64> {
+    object
+        .properties
+        .iter()
+        .find(|property| property.key == key)
+        .and_then(|property| match property.value {
+            PropertyValueRead::UInt(value) => Some(value),
+            _ => None,
+        })
+}
+
+/// Decodes the packed `values`/`indices` of every `Weight` and `CubicWeight`
+/// into per-bone weights, resolving each tendon index through the skin of the
+/// weight's vertex. Runs on the unfiltered objects so filtered output still
+/// names its bones.
+pub(crate) fn decode_skin_weights(objects: &mut [RivObject]) {
+    let by_local_index = objects
+        .iter()
+        .enumerate()
+        .filter_map(|(position, object)| {
+            Some(((object.artboard_index?, object.local_index?), position))
+        })
+        .collect::<HashMap<_, _>>();
+    let parent_of = |object: &RivObject| {
+        let parent_id = uint_property(object, property_keys::COMPONENT_PARENT_ID).unwrap_or(0);
+        by_local_index
+            .get(&(object.artboard_index?, parent_id as usize))
+            .copied()
+    };
+
+    // Tendon bone names per skinned object, in tendon order.
+    let mut skins = HashMap::<usize, Vec<Option<String>>>::new();
+    let mut skin_owners = HashMap::<usize, usize>::new();
+    for (position, object) in objects.iter().enumerate() {
+        match object.type_key {
+            type_keys::SKIN => {
+                if let Some(owner) = parent_of(object) {
+                    skin_owners.insert(position, owner);
+                    skins.entry(owner).or_default();
+                }
+            }
+            type_keys::TENDON => {
+                let Some(owner) = parent_of(object).and_then(|skin| skin_owners.get(&skin)) else {
+                    continue;
+                };
+                let bone = uint_property(object, property_keys::TENDON_BONE_ID)
+                    .and_then(|id| by_local_index.get(&(object.artboard_index?, id as usize)))
+                    .and_then(|&bone| component_name(&objects[bone]));
+                skins.entry(*owner).or_default().push(bone);
+            }
+            _ => {}
+        }
+    }
+
+    for position in 0..objects.len() {
+        let object = &objects[position];
+        let handles: &[(&'static str, u16, u16)] = match object.type_key {
+            type_keys::WEIGHT => &[(
+                "vertex",
+                property_keys::WEIGHT_VALUES,
+                property_keys::WEIGHT_INDICES,
+            )],
+            type_keys::CUBIC_WEIGHT => &[
+                (
+                    "vertex",
+                    property_keys::WEIGHT_VALUES,
+                    property_keys::WEIGHT_INDICES,
+                ),
+                (
+                    "in",
+                    property_keys::CUBIC_WEIGHT_IN_VALUES,
+                    property_keys::CUBIC_WEIGHT_IN_INDICES,
+                ),
+                (
+                    "out",
+                    property_keys::CUBIC_WEIGHT_OUT_VALUES,
+                    property_keys::CUBIC_WEIGHT_OUT_INDICES,
+                ),
+            ],
+            _ => continue,
+        };
+        let bones = parent_of(object)
+            .and_then(|vertex| parent_of(&objects[vertex]))
+            .and_then(|owner| skins.get(&owner));
+        let weights = handles
+            .iter()
+            .map(|&(handle, values_key, indices_key)| {
+                // Unset properties keep the runtime defaults: the whole
+                // weight on the first tendon.
+                let values = uint_property(object, values_key).unwrap_or(255);
+                let indices = uint_property(object, indices_key).unwrap_or(1);
+                let influences = (0..4)
+                    .map(|slot| ((values >> (slot * 8)) as u8, (indices >> (slot * 8)) as u8))
+                    .filter(|(value, _)| *value > 0)
+                    .map(|(value, tendon)| RivInfluence {
+                        tendon,
+                        bone: bones
+                            .and_then(|bones| bones.get(usize::from(tendon).checked_sub(1)?))
+                            .cloned()
+                            .flatten(),
+                        weight: f32::from(value) / 255.0,
+                    })
+                    .collect();
+                RivWeight { handle, influences }
+            })
+            .collect();
+        objects[position].weights = weights;
+    }
+}
+
 fn matches_object_filter(filter: &InspectFilter, object: &RivObject) -> bool {
     let artboard_index_match = filter.artboard_indices.is_empty()
         || object
@@ -205,6 +315,38 @@ pub fn inspect_riv(data: &[u8], filter: &InspectFilter) -> Result<String, String
                 val_str
             ));
         }
+        for weight in &obj.weights {
+            let influences = weight
+                .influences
+                .iter()
+                .map(|influence| {
+                    let bone = influence
+                        .bone
+                        .clone()
+                        .unwrap_or_else(|| format!("tendon{}", influence.tendon));
+                    format!("{}={:.3}", bone, influence.weight)
+                })
+                .collect::<Vec<_>>();
+            out.push_str(&format!(
+                "  weights[{}]: {}\n",
+                weight.handle,
+                influences.join(" ")
+            ));
+        }
+        match &obj.audio {
+            Some(RivAudio::Decoded(info)) => out.push_str(&format!("  audio: {}\n", info)),
+            Some(RivAudio::Undecodable { error }) => {
+                out.push_str(&format!("  audio: undecodable ({})\n", error))
+            }
+            None => {}
+        }
+        if let Some(script) = &obj.script {
+            out.push_str(&format!("  script: {} lines\n", script.lines().count()));
+            for line in script.lines() {
+                out.push_str(format!("    | {}", line).trim_end());
+                out.push('\n');
+            }
+        }
     }
 
     Ok(out)
@@ -258,4 +400,63 @@ mod tests {
         assert!(output.contains("[4:1] type=3 (Shape)"));
         assert!(!output.contains("[0] type=23 (Backboard)"));
     }
+
+    #[test]
+    fn test_inspect_riv_decodes_packed_skin_weights_by_bone_name() {
+        use crate::objects::bones::{Bone, RootBone, Skin, Tendon, Weight};
+        use crate::objects::shapes::{PointsPathObject, StraightVertexObject};
+
+        let backboard = Backboard;
+        let artboard = Artboard::new("Rig".to_string(), 400.0, 400.0);
+        let upper = RootBone::new("Upper".to_string(), 0);
+        let lower = Bone::new("Lower".to_string(), 1);
+        let shape = Shape::new("Arm".to_string(), 0);
+        let path = PointsPathObject {
+            name: "Outline".to_string(),
+            parent_id: Some(3),
+            x: 0.0,
+            y: 0.0,
+            is_closed: false,
+            path_flags: 0,
+        };
+        let vertex = StraightVertexObject {
+            name: "Elbow".to_string(),
+            parent_id: Some(4),
+            x: 100.0,
+            y: 0.0,
+            radius: 0.0,
+        };
+        let skin = Skin::new("Outline_skin".to_string(), 4);
+        // Tendons in reverse bone order: index 1 is Lower.
+        let mut first = Tendon::new("Outline_tendon_Lower".to_string(), 6);
+        first.bone_id = 2;
+        let mut second = Tendon::new("Outline_tendon_Upper".to_string(), 6);
+        second.bone_id = 1;
+        let mut weight = Weight::new("Elbow_weight".to_string(), 5);
+        weight.values = 191 | 64 << 8;
+        weight.indices = 2 | 1 << 8;
+        let data = encode_riv(
+            &[
+                &backboard, &artboard, &upper, &lower, &shape, &path, &vertex, &skin, &first,
+                &second, &weight,
+            ],
+            0,
+        );
+
+        let output = inspect_riv(&data, &InspectFilter::default()).unwrap();
+        assert!(
+            output.contains("  weights[vertex]: Upper=0.749 Lower=0.251\n"),
+            "{output}"
+        );
+
+        let filter = InspectFilter {
+            type_names: vec!["Weight".to_string()],
+            ..InspectFilter::default()
+        };
+        let parsed = super::super::parser::parse_riv(&data, &filter).unwrap();
+        let weights = &parsed.objects[0].weights;
+        assert_eq!(weights.len(), 1);
+        assert_eq!(weights[0].influences[1].tendon, 1);
+        assert_eq!(weights[0].influences[1].bone.as_deref(), Some("Lower"));
+    }
 }
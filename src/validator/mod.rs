@@ -1,9 +1,13 @@
+mod audio;
 mod binary_reader;
+mod image;
 mod inspect;
 mod parser;
 
+pub use audio::{AudioFormat, AudioInfo, sniff_audio};
 #[allow(unused_imports)] // used by encoder tests
 pub use binary_reader::BinaryReader;
+pub use image::{ImageFormat, ImageInfo, sniff_image};
 pub use inspect::*;
 pub use parser::*;
 
@@ -64,7 +68,10 @@ pub fn validate_riv(data: &[u8]) -> Result<ValidationReport, String> {
         .filter(|obj| {
             matches!(
                 obj.type_key,
-                type_keys::IMAGE_ASSET | type_keys::FONT_ASSET | type_keys::AUDIO_ASSET
+                type_keys::IMAGE_ASSET
+                    | type_keys::FONT_ASSET
+                    | type_keys::AUDIO_ASSET
+                    | type_keys::SCRIPT_ASSET
             )
         })
         .count() as u64;
@@ -111,6 +118,16 @@ pub fn validate_riv(data: &[u8]) -> Result<ValidationReport, String> {
         }
     }
 
+    for obj in &parsed.objects {
+        if let Some(RivAudio::Undecodable { error }) = &obj.audio {
+            warnings.push(format!(
+                "audio asset at index {} embeds contents the runtime cannot decode: {}",
+                obj.object_index, error
+            ));
+        }
+    }
+    warnings.extend(audio::audio_timeline_warnings(&parsed.objects));
+
     let valid = errors.is_empty();
 
     Ok(ValidationReport {
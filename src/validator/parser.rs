@@ -3,10 +3,11 @@ use std::collections::HashMap;
 use serde::Serialize;
 
 use crate::objects::core::{
-    BackingType, is_bool_property, is_bytes_property, property_backing_type,
+    BackingType, is_bool_property, is_bytes_property, property_backing_type, type_keys,
 };
 use crate::objects::generated_registry;
 
+use super::audio::{AudioInfo, sniff_audio};
 use super::binary_reader::BinaryReader;
 use super::inspect::InspectFilter;
 
@@ -47,6 +48,40 @@ pub struct RivObject {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub local_index: Option<usize>,
     pub properties: Vec<RivProperty>,
+    /// Packed skin weights decoded back into per-bone values, for `Weight`
+    /// and `CubicWeight` objects.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub weights: Vec<RivWeight>,
+    /// The sniffed headers of the contents embedded in an `AudioAsset`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub audio: Option<RivAudio>,
+    /// The Luau source embedded in a `ScriptAsset`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub script: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum RivAudio {
+    Decoded(AudioInfo),
+    Undecodable { error: String },
+}
+
+/// The influences on one point of a skinned vertex: its position, or the in
+/// or out handle of a cubic vertex.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RivWeight {
+    pub handle: &'static str,
+    pub influences: Vec<RivInfluence>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RivInfluence {
+    /// The tendon index, counted from 1 in the skin's tendon order.
+    pub tendon: u8,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bone: Option<String>,
+    pub weight: f32,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -178,9 +213,25 @@ pub fn parse_riv(data: &[u8], filter: &InspectFilter) -> Result<ParsedRiv, Strin
                             prop_key
                         )
                     })? as usize;
-                    reader.read_bytes(length).ok_or_else(|| {
+                    let bytes = reader.read_bytes(length).ok_or_else(|| {
                         format!("unexpected end of data reading bytes property {}", prop_key)
                     })?;
+                    if type_key == type_keys::FILE_ASSET_CONTENTS
+                        && let Some(asset) = objects.last_mut()
+                    {
+                        match asset.type_key {
+                            type_keys::AUDIO_ASSET => {
+                                asset.audio = Some(match sniff_audio(bytes) {
+                                    Ok(info) => RivAudio::Decoded(info),
+                                    Err(error) => RivAudio::Undecodable { error },
+                                });
+                            }
+                            type_keys::SCRIPT_ASSET => {
+                                asset.script = Some(String::from_utf8_lossy(bytes).into_owned());
+                            }
+                            _ => {}
+                        }
+                    }
                     PropertyValueRead::Bytes { length }
                 }
                 BackingType::String => {
@@ -223,10 +274,14 @@ pub fn parse_riv(data: &[u8], filter: &InspectFilter) -> Result<ParsedRiv, Strin
             artboard_name: None,
             local_index: None,
             properties,
+            weights: Vec::new(),
+            audio: None,
+            script: None,
         });
     }
 
     super::inspect::annotate_object_context(&mut objects);
+    super::inspect::decode_skin_weights(&mut objects);
 
     let parsed = ParsedRiv {
         header,
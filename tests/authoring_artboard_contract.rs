@@ -0,0 +1,300 @@
+use std::path::Path;
+
+use rive_cli::{
+    authoring::lower_authoring_json,
+    builder::{SceneSpec, build_scene},
+};
+use serde_json::{Value, json};
+
+fn literal(value: f64, unit: &str) -> Value {
+    json!({ "kind": "literal", "value": value, "unit": unit })
+}
+
+fn px(value: f64) -> Value {
+    json!({ "value": value, "unit": "px" })
+}
+
+fn document() -> Value {
+    json!({
+        "authoring_format_version": 0,
+        "artboard": {
+            "id": "screen",
+            "width": px(400.0),
+            "height": px(300.0)
+        },
+        "image_assets": {
+            "aurora": "assets/textures/aurora.png"
+        },
+        "artboards": [
+            {
+                "id": "card",
+                "width": px(120.0),
+                "height": px(80.0),
+                "visual": {
+                    "nodes": [
+                        {
+                            "kind": "rectangle",
+                            "id": "panel",
+                            "width": literal(120.0, "px"),
+                            "height": literal(80.0, "px"),
+                            "fill": "#246BFD"
+                        },
+                        {
+                            "kind": "image",
+                            "id": "thumb",
+                            "asset": "aurora"
+                        }
+                    ]
+                },
+                "motion": {
+                    "poses": [
+                        {
+                            "id": "low",
+                            "targets": [
+                                { "target": "panel", "transform": { "y": literal(0.0, "px") } }
+                            ]
+                        },
+                        {
+                            "id": "high",
+                            "targets": [
+                                { "target": "panel", "transform": { "y": literal(-8.0, "px") } }
+                            ]
+                        }
+                    ],
+                    "tracks": [
+                        {
+                            "id": "pulse",
+                            "fps": 60,
+                            "duration_frames": literal(30.0, "scalar"),
+                            "loop_type": "loop",
+                            "keyframes": [
+                                { "frame": literal(0.0, "scalar"), "pose": "low" },
+                                { "frame": literal(30.0, "scalar"), "pose": "high" }
+                            ]
+                        }
+                    ]
+                },
+                "behavior": {
+                    "raw_state_machines": [
+                        {
+                            "id": "hover",
+                            "value": { "name": "CardHover", "layers": [] }
+                        }
+                    ]
+                }
+            }
+        ],
+        "visual": {
+            "nodes": [
+                {
+                    "kind": "nested_artboard",
+                    "id": "first",
+                    "artboard": "card",
+                    "animation": "pulse",
+                    "transform": {
+                        "x": literal(40.0, "px"),
+                        "y": literal(60.0, "px")
+                    }
+                },
+                {
+                    "kind": "nested_artboard",
+                    "id": "second",
+                    "artboard": "card",
+                    "state_machine": "hover"
+                }
+            ]
+        },
+        "motion": {},
+        "behavior": {}
+    })
+}
+
+fn assert_diagnostic(input: &Value, code: &str, path: &str) {
+    let error = lower_authoring_json(&input.to_string())
+        .expect_err("invalid artboard composition must fail at the authored boundary");
+    assert!(
+        error
+            .diagnostics
+            .iter()
+            .any(|diagnostic| diagnostic.code == code && diagnostic.path == path),
+        "missing {code} at {path}; diagnostics: {:#?}",
+        error.diagnostics
+    );
+}
+
+#[test]
+fn artboards_lower_into_one_multi_artboard_scene() {
+    let input = document().to_string();
+    let first = lower_authoring_json(&input).expect("multi-artboard authoring must lower");
+    let second = lower_authoring_json(&input).expect("second multi-artboard lowering");
+    assert_eq!(first.scene, second.scene);
+    assert_eq!(first.source_map, second.source_map);
+    assert!(first.scene.get("artboard").is_none());
+
+    let artboards = first.scene["artboards"].as_array().expect("artboards");
+    assert_eq!(artboards.len(), 2);
+    assert_eq!(artboards[0]["name"], "auth__screen__artboard");
+    assert_eq!(artboards[1]["name"], "auth__card__artboard");
+    assert_eq!(
+        artboards[1]["animations"][0]["name"],
+        "auth__card__pulse__animation"
+    );
+    assert_eq!(artboards[1]["state_machines"][0]["name"], "CardHover");
+
+    let card_children = artboards[1]["children"].as_array().expect("card children");
+    assert!(
+        card_children
+            .iter()
+            .all(|child| child["type"] != "image_asset"),
+        "file assets are declared once, on the primary artboard"
+    );
+    assert_eq!(
+        card_children[1]["children"][0]["asset"],
+        "auth__screen__aurora__image_asset"
+    );
+
+    let anchor = &artboards[0]["children"][1];
+    assert_eq!(
+        anchor["name"],
+        "auth__screen__first__nested_artboard_anchor"
+    );
+    assert_eq!(anchor["x"], 40.0);
+    let nested = &anchor["children"][0];
+    assert_eq!(nested["type"], "nested_artboard");
+    assert_eq!(nested["source_artboard"], "auth__card__artboard");
+    assert_eq!(nested["children"][0]["type"], "nested_simple_animation");
+    assert_eq!(
+        nested["children"][0]["animation"],
+        "auth__card__pulse__animation"
+    );
+    let machine = &artboards[0]["children"][2]["children"][0]["children"][0];
+    assert_eq!(machine["type"], "nested_state_machine");
+    assert_eq!(machine["animation"], "CardHover");
+
+    let scene: SceneSpec =
+        serde_json::from_value(first.scene).expect("multi-artboard SceneSpec must deserialize");
+    build_scene(&scene, Some(Path::new(env!("CARGO_MANIFEST_DIR"))))
+        .expect("multi-artboard scene must pass the canonical builder");
+}
+
+#[test]
+fn source_map_entries_address_each_artboard() {
+    let lowered = lower_authoring_json(&document().to_string()).expect("lowering");
+    let entry = |path: &str| {
+        lowered
+            .source_map
+            .entries
+            .iter()
+            .find(|entry| entry.authored_path == path)
+            .unwrap_or_else(|| panic!("missing source-map entry for {path}"))
+    };
+
+    assert_eq!(entry("$.artboard").scene_paths, vec!["/artboards/0"]);
+    assert_eq!(entry("$.artboards[0]").scene_paths, vec!["/artboards/1"]);
+    assert_eq!(
+        entry("$.image_assets.aurora").scene_paths,
+        vec!["/artboards/0/children/0"]
+    );
+    assert_eq!(
+        entry("$.visual.nodes[0]").scene_paths,
+        vec![
+            "/artboards/0/children/1",
+            "/artboards/0/children/1/children/0",
+            "/artboards/0/children/1/children/0/children/0"
+        ]
+    );
+    assert_eq!(
+        entry("$.artboards[0].visual.nodes[0]").runtime_names[..2],
+        ["auth__card__panel__shape", "auth__card__panel__geometry"]
+    );
+    assert_eq!(
+        entry("$.artboards[0].visual.nodes[1]").scene_paths,
+        vec![
+            "/artboards/1/children/1",
+            "/artboards/1/children/1/children/0"
+        ]
+    );
+    assert!(lowered.source_map.entries.iter().all(|entry| {
+        entry
+            .scene_paths
+            .iter()
+            .all(|path| path.starts_with("/artboards/"))
+    }));
+
+    for entry in &lowered.source_map.entries {
+        for scene_path in &entry.scene_paths {
+            assert!(
+                lowered.scene.pointer(scene_path).is_some(),
+                "{} points at missing {scene_path}",
+                entry.authored_path
+            );
+        }
+    }
+}
+
+#[test]
+fn nested_references_are_checked_by_authored_name() {
+    let mut unknown_artboard = document();
+    unknown_artboard["visual"]["nodes"][0]["artboard"] = json!("missing");
+    assert_diagnostic(
+        &unknown_artboard,
+        "unknown_artboard",
+        "$.visual.nodes[0].artboard",
+    );
+
+    let mut unknown_animation = document();
+    unknown_animation["visual"]["nodes"][0]["animation"] = json!("spin");
+    assert_diagnostic(
+        &unknown_animation,
+        "unknown_nested_animation",
+        "$.visual.nodes[0].animation",
+    );
+
+    let mut unknown_machine = document();
+    unknown_machine["visual"]["nodes"][1]["state_machine"] = json!("press");
+    assert_diagnostic(
+        &unknown_machine,
+        "unknown_nested_state_machine",
+        "$.visual.nodes[1].state_machine",
+    );
+
+    let mut duplicate = document();
+    duplicate["artboards"][0]["id"] = json!("screen");
+    assert_diagnostic(&duplicate, "duplicate_artboard", "$.artboards[0].id");
+
+    let mut invalid_widget = document();
+    invalid_widget["artboards"][0]["visual"]["nodes"][1]["asset"] = json!("missing");
+    assert_diagnostic(
+        &invalid_widget,
+        "unknown_image_asset",
+        "$.artboards[0].visual.nodes[1].asset",
+    );
+}
+
+#[test]
+fn nested_cycles_are_reported_across_artboards() {
+    let mut cycle = document();
+    cycle["artboards"][0]["visual"]["nodes"]
+        .as_array_mut()
+        .expect("card nodes")
+        .push(json!({
+            "kind": "group",
+            "id": "wrapper",
+            "children": [
+                { "kind": "nested_artboard", "id": "back", "artboard": "screen" }
+            ]
+        }));
+    let error = lower_authoring_json(&cycle.to_string()).expect_err("cycle must fail");
+    let diagnostic = &error.diagnostics[0];
+    assert_eq!(diagnostic.code, "nested_artboard_cycle");
+    assert_eq!(diagnostic.path, "$.visual.nodes[0].artboard");
+    assert!(diagnostic.message.contains("screen -> card -> screen"));
+
+    let mut itself = document();
+    itself["visual"]["nodes"][0]["artboard"] = json!("screen");
+    assert_diagnostic(
+        &itself,
+        "nested_artboard_cycle",
+        "$.visual.nodes[0].artboard",
+    );
+}
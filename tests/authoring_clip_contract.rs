@@ -0,0 +1,322 @@
+mod support;
+
+use rive_cli::authoring::{LoweredAuthoring, lower_authoring_json};
+use serde_json::{Value, json};
+use support::assert_builds;
+
+fn literal(value: f64, unit: &str) -> Value {
+    json!({ "kind": "literal", "value": value, "unit": unit })
+}
+
+fn px(value: f64) -> Value {
+    literal(value, "px")
+}
+
+fn document() -> Value {
+    json!({
+        "authoring_format_version": 0,
+        "artboard": {
+            "id": "clipstage",
+            "width": { "value": 240.0, "unit": "px" },
+            "height": { "value": 160.0, "unit": "px" }
+        },
+        "visual": {
+            "nodes": [
+                {
+                    "kind": "rectangle",
+                    "id": "mask",
+                    "width": px(120.0),
+                    "height": px(80.0),
+                    "corner_radius": px(12.0),
+                    "fill": "#000000",
+                    "transform": { "x": px(120.0), "y": px(80.0) }
+                },
+                {
+                    "kind": "ellipse",
+                    "id": "badge",
+                    "width": px(32.0),
+                    "height": px(32.0),
+                    "fill": "#F97316",
+                    "transform": { "x": px(180.0), "y": px(40.0) }
+                },
+                {
+                    "kind": "group",
+                    "id": "content",
+                    "clip": { "shape": "mask" },
+                    "z_order": { "drawable": "badge", "placement": "after" },
+                    "children": [
+                        {
+                            "kind": "rectangle",
+                            "id": "panel",
+                            "width": px(240.0),
+                            "height": px(160.0),
+                            "fill": "#2563EB",
+                            "transform": { "x": px(120.0), "y": px(80.0) }
+                        }
+                    ]
+                }
+            ]
+        },
+        "motion": {},
+        "behavior": {}
+    })
+}
+
+fn lower(input: &Value) -> LoweredAuthoring {
+    lower_authoring_json(&input.to_string()).expect("clipped groups must lower")
+}
+
+fn assert_diagnostic(input: &Value, code: &str, path: &str) {
+    let error = lower_authoring_json(&input.to_string())
+        .expect_err("invalid clip and draw order must fail at the authored boundary");
+    assert!(
+        error
+            .diagnostics
+            .iter()
+            .any(|diagnostic| diagnostic.code == code && diagnostic.path == path),
+        "missing {code} at {path}; diagnostics: {:#?}",
+        error.diagnostics
+    );
+}
+
+#[test]
+fn group_clip_and_z_order_lower_after_the_children_and_hide_the_source() {
+    let input = document();
+    let first = lower(&input);
+    let second = lower(&input);
+    assert_eq!(first.scene, second.scene);
+    assert_eq!(first.source_map, second.source_map);
+
+    let nodes = &first.scene["artboard"]["children"];
+    let mask_fill = &nodes[0]["children"][1];
+    assert_eq!(mask_fill["type"], "fill");
+    assert_eq!(mask_fill["is_visible"], false);
+    assert!(nodes[1]["children"][1].get("is_visible").is_none());
+
+    let content = nodes[2]["children"].as_array().expect("group children");
+    let types = content
+        .iter()
+        .map(|child| child["type"].as_str().expect("type"))
+        .collect::<Vec<_>>();
+    assert_eq!(types, ["shape", "clipping_shape", "draw_rules"]);
+    assert_eq!(content[1]["name"], "auth__clipstage__content__clip");
+    assert_eq!(content[1]["source"], "auth__clipstage__mask__shape");
+    assert!(content[1].get("fill_rule").is_none());
+    assert_eq!(
+        content[2]["draw_target"],
+        "auth__clipstage__content__z_order"
+    );
+    assert_eq!(
+        content[2]["children"][0]["drawable"],
+        "auth__clipstage__badge__shape"
+    );
+    assert_eq!(content[2]["children"][0]["placement_value"], 1);
+
+    let entry = first
+        .source_map
+        .entries
+        .iter()
+        .find(|entry| entry.authored_id == "content")
+        .expect("group source map entry");
+    assert_eq!(
+        entry.runtime_names,
+        [
+            "auth__clipstage__content__group",
+            "auth__clipstage__content__clip",
+            "auth__clipstage__content__draw_rules",
+            "auth__clipstage__content__z_order"
+        ]
+    );
+    for scene_path in &entry.scene_paths {
+        assert!(
+            first.scene.pointer(scene_path).is_some(),
+            "{scene_path} must resolve"
+        );
+    }
+
+    assert_builds(first.scene);
+}
+
+#[test]
+fn inverse_clip_adds_a_cover_without_claiming_the_source_geometry() {
+    let mut input = document();
+    input["visual"]["nodes"][2]["clip"] =
+        json!({ "shape": "mask", "inverse": true, "visible": true });
+    let target = |width: f64| json!({ "target": "mask", "width": px(width) });
+    input["motion"] = json!({
+        "poses": [
+            { "id": "narrow", "targets": [target(120.0)] },
+            { "id": "wide", "targets": [target(200.0)] }
+        ],
+        "tracks": [
+            {
+                "id": "reveal",
+                "fps": 60,
+                "duration_frames": literal(30.0, "scalar"),
+                "keyframes": [
+                    { "frame": literal(0.0, "scalar"), "pose": "narrow" },
+                    { "frame": literal(30.0, "scalar"), "pose": "wide" }
+                ]
+            }
+        ]
+    });
+    let lowered = lower(&input);
+
+    let nodes = &lowered.scene["artboard"]["children"];
+    let mask = nodes[0]["children"].as_array().expect("mask children");
+    assert!(mask[1].get("is_visible").is_none());
+    let cover = mask.last().expect("cover shape");
+    assert_eq!(cover["type"], "shape");
+    assert_eq!(cover["name"], "auth__clipstage__mask__clip_cover");
+    assert_eq!(cover["children"][0]["type"], "rectangle");
+    assert_eq!(nodes[2]["children"][1]["fill_rule"], "evenodd");
+
+    let entry = lowered
+        .source_map
+        .entries
+        .iter()
+        .find(|entry| entry.authored_id == "mask")
+        .expect("clip source entry");
+    assert!(
+        entry
+            .runtime_names
+            .iter()
+            .any(|name| name == "auth__clipstage__mask__clip_cover_geometry")
+    );
+
+    let keyframes = lowered.scene["artboard"]["animations"][0]["keyframes"]
+        .as_array()
+        .expect("keyframes");
+    assert!(keyframes.iter().any(|group| {
+        group["object"] == "auth__clipstage__mask__geometry" && group["property"] == "width"
+    }));
+
+    assert_builds(lowered.scene);
+}
+
+#[test]
+fn draw_rules_lower_each_layer_and_select_the_active_one() {
+    let mut input = document();
+    let group = &mut input["visual"]["nodes"][2];
+    group.as_object_mut().expect("group").remove("z_order");
+    group["draw_rules"] = json!({
+        "layers": [
+            { "id": "front", "drawable": "badge", "placement": "after" },
+            { "id": "back", "drawable": "badge", "placement": "before" }
+        ],
+        "active": "back"
+    });
+    let lowered = lower(&input);
+
+    let rules = &lowered.scene["artboard"]["children"][2]["children"][2];
+    assert_eq!(rules["type"], "draw_rules");
+    assert_eq!(
+        rules["draw_target"],
+        "auth__clipstage__content__draw_layer_1"
+    );
+    let placements = rules["children"]
+        .as_array()
+        .expect("draw targets")
+        .iter()
+        .map(|target| target["placement_value"].as_u64().expect("placement"))
+        .collect::<Vec<_>>();
+    assert_eq!(placements, [1, 0]);
+
+    assert_builds(lowered.scene);
+}
+
+#[test]
+fn clip_and_draw_references_are_checked_at_the_authored_path() {
+    let mut unknown = document();
+    unknown["visual"]["nodes"][2]["clip"]["shape"] = json!("missing");
+    assert_diagnostic(
+        &unknown,
+        "unknown_clip_shape",
+        "$.visual.nodes[2].clip.shape",
+    );
+
+    let mut later = document();
+    let nodes = later["visual"]["nodes"].as_array_mut().expect("nodes");
+    let mask = nodes.remove(0);
+    nodes.push(mask);
+    assert_diagnostic(&later, "clip_shape_order", "$.visual.nodes[1].clip.shape");
+
+    let mut group_source = document();
+    group_source["visual"]["nodes"]
+        .as_array_mut()
+        .expect("nodes")
+        .push(json!({ "kind": "group", "id": "outer", "clip": { "shape": "content" }, "children": [] }));
+    assert_diagnostic(
+        &group_source,
+        "invalid_clip_shape",
+        "$.visual.nodes[3].clip.shape",
+    );
+
+    let mut conflicting = document();
+    conflicting["visual"]["nodes"]
+        .as_array_mut()
+        .expect("nodes")
+        .push(json!({
+            "kind": "group",
+            "id": "inverted",
+            "clip": { "shape": "mask", "inverse": true },
+            "children": []
+        }));
+    assert_diagnostic(
+        &conflicting,
+        "conflicting_clip_source",
+        "$.visual.nodes[3].clip",
+    );
+
+    let mut both = document();
+    both["visual"]["nodes"][2]["draw_rules"] = json!({
+        "layers": [{ "id": "front", "drawable": "badge", "placement": "after" }]
+    });
+    assert_diagnostic(&both, "conflicting_draw_order", "$.visual.nodes[2].z_order");
+
+    let mut not_drawable = document();
+    not_drawable["visual"]["nodes"][2]["z_order"]["drawable"] = json!("content");
+    assert_diagnostic(
+        &not_drawable,
+        "draw_target_order",
+        "$.visual.nodes[2].z_order.drawable",
+    );
+
+    let mut layers = document();
+    let group = &mut layers["visual"]["nodes"][2];
+    group.as_object_mut().expect("group").remove("z_order");
+    group["draw_rules"] = json!({
+        "layers": [
+            { "id": "front", "drawable": "badge", "placement": "after" },
+            { "id": "front", "drawable": "mask", "placement": "before" }
+        ]
+    });
+    assert_diagnostic(
+        &layers,
+        "duplicate_draw_layer",
+        "$.visual.nodes[2].draw_rules.layers[1].id",
+    );
+    layers["visual"]["nodes"][2]["draw_rules"]["layers"][1]["id"] = json!("back");
+    layers["visual"]["nodes"][2]["draw_rules"]["active"] = json!("middle");
+    assert_diagnostic(
+        &layers,
+        "unknown_draw_layer",
+        "$.visual.nodes[2].draw_rules.active",
+    );
+
+    let mut nested = document();
+    nested["visual"]["nodes"][2]["children"]
+        .as_array_mut()
+        .expect("children")
+        .push(json!({
+            "kind": "group",
+            "id": "inner",
+            "z_order": { "drawable": "badge", "placement": "before" },
+            "children": []
+        }));
+    assert_diagnostic(
+        &nested,
+        "unknown_draw_target",
+        "$.visual.nodes[2].children[1].z_order.drawable",
+    );
+}
@@ -188,7 +188,8 @@ fn mixed_typed_raw_motion_preserves_order_references_and_source_identity() {
         .entries
         .iter()
         .filter(|entry| {
-            entry.authored_path.starts_with("$.motion.")
+            (entry.authored_path.starts_with("$.motion.")
+                && !entry.authored_path.starts_with("$.motion.poses["))
                 || entry.authored_path.starts_with("$.behavior.")
         })
         .map(|entry| entry.authored_id.as_str())
@@ -240,6 +241,35 @@ fn mixed_typed_raw_motion_preserves_order_references_and_source_identity() {
         &["/artboard/state_machines/0"],
     );
 
+    let keyframe_entries = first
+        .source_map
+        .entries
+        .iter()
+        .filter(|entry| entry.authored_path.starts_with("$.motion.poses["))
+        .map(|entry| (entry.authored_path.as_str(), entry.scene_paths[0].as_str()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        keyframe_entries,
+        vec![
+            (
+                "$.motion.poses[0].targets[0].transform.x",
+                "/artboard/animations/0/keyframes/0/frames/0"
+            ),
+            (
+                "$.motion.poses[1].targets[0].transform.x",
+                "/artboard/animations/0/keyframes/0/frames/1"
+            ),
+            (
+                "$.motion.poses[1].targets[0].transform.x",
+                "/artboard/animations/1/keyframes/0/frames/0"
+            ),
+            (
+                "$.motion.poses[0].targets[0].transform.x",
+                "/artboard/animations/1/keyframes/0/frames/1"
+            ),
+        ]
+    );
+
     assert_builds(first.scene);
 }
 
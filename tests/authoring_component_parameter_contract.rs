@@ -0,0 +1,296 @@
+use std::path::Path;
+
+use rive_cli::{
+    authoring::{LoweredAuthoring, lower_authoring_json},
+    builder::{SceneSpec, build_scene},
+};
+use serde_json::{Value, json};
+
+fn literal(value: f64, unit: &str) -> Value {
+    json!({ "kind": "literal", "value": value, "unit": unit })
+}
+
+fn parameter(name: &str) -> Value {
+    json!({ "parameter": name })
+}
+
+fn typed(kind: &str, value: Value) -> Value {
+    json!({ "kind": kind, "value": value })
+}
+
+fn gradient(to: Value) -> Value {
+    json!({
+        "kind": "linear_gradient",
+        "start_x": literal(0.0, "px"),
+        "start_y": literal(0.0, "px"),
+        "end_x": literal(160.0, "px"),
+        "end_y": literal(0.0, "px"),
+        "stops": [
+            { "color": "#0EA5E9", "position": literal(0.0, "scalar") },
+            { "color": to, "position": literal(1.0, "scalar") }
+        ]
+    })
+}
+
+fn document(overrides: Value) -> Value {
+    json!({
+        "authoring_format_version": 0,
+        "artboard": {
+            "id": "toolbar",
+            "width": { "value": 480.0, "unit": "px" },
+            "height": { "value": 240.0, "unit": "px" }
+        },
+        "font_assets": {
+            "inter": "assets/fonts/Inter-Bold-Subset.ttf"
+        },
+        "image_assets": {
+            "aurora": "assets/textures/aurora.png"
+        },
+        "components": [
+            {
+                "id": "button",
+                "parameters": {
+                    "width": { "value": 160.0, "unit": "px" },
+                    "surface": typed("paint", json!("#1E293B")),
+                    "accent": typed("color", json!("#F59E0B")),
+                    "label": typed("string", json!("Play")),
+                    "typeface": typed("font_asset", json!("inter")),
+                    "icon": typed("image_asset", json!("aurora"))
+                },
+                "visual": [
+                    {
+                        "kind": "rectangle",
+                        "id": "surface",
+                        "width": { "kind": "parameter", "name": "width" },
+                        "height": literal(48.0, "px"),
+                        "fill": parameter("surface"),
+                        "stroke": {
+                            "paint": gradient(parameter("accent")),
+                            "width": literal(2.0, "px")
+                        }
+                    },
+                    {
+                        "kind": "text",
+                        "id": "label",
+                        "text": parameter("label"),
+                        "font": parameter("typeface"),
+                        "font_size": literal(18.0, "px"),
+                        "fill": parameter("accent")
+                    },
+                    {
+                        "kind": "image",
+                        "id": "icon",
+                        "asset": parameter("icon")
+                    }
+                ]
+            }
+        ],
+        "visual": {
+            "nodes": [
+                {
+                    "kind": "instance",
+                    "id": "primary",
+                    "component": "button",
+                    "overrides": overrides
+                }
+            ]
+        },
+        "motion": {},
+        "behavior": {}
+    })
+}
+
+fn lower(input: &Value) -> LoweredAuthoring {
+    lower_authoring_json(&input.to_string()).expect("typed component parameters must lower")
+}
+
+fn assert_diagnostic(input: &Value, code: &str, path: &str) -> String {
+    let error = lower_authoring_json(&input.to_string())
+        .expect_err("invalid component parameters must fail at the authored boundary");
+    error
+        .diagnostics
+        .iter()
+        .find(|diagnostic| diagnostic.code == code && diagnostic.path == path)
+        .unwrap_or_else(|| panic!("missing {code} at {path}; diagnostics: {error:#?}"))
+        .message
+        .clone()
+}
+
+fn assert_builds(scene: Value) {
+    let scene: SceneSpec =
+        serde_json::from_value(scene).expect("lowered SceneSpec must deserialize");
+    build_scene(&scene, Some(Path::new(env!("CARGO_MANIFEST_DIR"))))
+        .expect("font and image sources must embed through the canonical builder");
+}
+
+fn named<'a>(scene: &'a Value, name: &str) -> &'a Value {
+    fn find<'a>(value: &'a Value, name: &str) -> Option<&'a Value> {
+        if value["name"] == name {
+            return Some(value);
+        }
+        value["children"]
+            .as_array()?
+            .iter()
+            .find_map(|child| find(child, name))
+    }
+    find(&scene["artboard"], name).unwrap_or_else(|| panic!("expected scene object {name}"))
+}
+
+#[test]
+fn typed_parameter_defaults_fill_component_fields_and_build() {
+    let input = document(json!({}));
+    let first = lower(&input);
+    let second = lower(&input);
+    assert_eq!(first.scene, second.scene);
+    assert_eq!(first.source_map, second.source_map);
+
+    let scene = &first.scene;
+    assert_eq!(
+        named(scene, "auth__toolbar__primary__surface__color")["color"],
+        "#1E293B"
+    );
+    assert_eq!(
+        named(
+            scene,
+            "auth__toolbar__primary__surface__stroke_gradient_stop_1"
+        )["color"],
+        "#F59E0B"
+    );
+    assert_eq!(
+        named(scene, "auth__toolbar__primary__label__text_run")["text"],
+        "Play"
+    );
+    assert_eq!(
+        named(scene, "auth__toolbar__primary__label__text_color")["color"],
+        "#F59E0B"
+    );
+    assert_eq!(
+        named(scene, "auth__toolbar__primary__icon__image")["asset"],
+        "auth__toolbar__aurora__image_asset"
+    );
+
+    assert_builds(first.scene);
+}
+
+#[test]
+fn overrides_replace_typed_defaults_per_instance() {
+    let lowered = lower(&document(json!({
+        "width": { "value": 200.0, "unit": "px" },
+        "surface": typed("paint", gradient(json!("#6366F1"))),
+        "accent": typed("color", json!("#FFFFFF")),
+        "label": typed("string", json!("Pause"))
+    })));
+    let scene = &lowered.scene;
+    assert_eq!(
+        named(scene, "auth__toolbar__primary__surface__gradient_stop_1")["color"],
+        "#6366F1"
+    );
+    assert_eq!(
+        named(
+            scene,
+            "auth__toolbar__primary__surface__stroke_gradient_stop_1"
+        )["color"],
+        "#FFFFFF"
+    );
+    assert_eq!(
+        named(scene, "auth__toolbar__primary__label__text_run")["text"],
+        "Pause"
+    );
+
+    let label = lowered
+        .source_map
+        .entries
+        .iter()
+        .find(|entry| entry.authored_id == "primary/label")
+        .expect("expanded label entry");
+    assert_eq!(label.authored_path, "$.visual.nodes[0].expanded[1]");
+    assert_eq!(
+        label.definition_path.as_deref(),
+        Some("$.components[0].visual[1]")
+    );
+
+    assert_builds(lowered.scene);
+}
+
+#[test]
+fn overrides_are_type_checked_against_the_definition() {
+    let message = assert_diagnostic(
+        &document(json!({ "label": typed("color", json!("#FFFFFF")) })),
+        "parameter_type_mismatch",
+        "$.visual.nodes[0].overrides.label",
+    );
+    assert!(
+        message.contains("$.components[0].parameters.label"),
+        "mismatch must name the declaration: {message}"
+    );
+
+    assert_diagnostic(
+        &document(json!({ "width": typed("string", json!("wide")) })),
+        "parameter_type_mismatch",
+        "$.visual.nodes[0].overrides.width",
+    );
+    assert_diagnostic(
+        &document(json!({ "accent": typed("color", json!("orange")) })),
+        "invalid_color",
+        "$.visual.nodes[0].overrides.accent.value",
+    );
+    assert_diagnostic(
+        &document(json!({ "icon": typed("image_asset", json!("missing")) })),
+        "unknown_image_asset",
+        "$.visual.nodes[0].overrides.icon.value",
+    );
+    assert_diagnostic(
+        &document(json!({ "typeface": typed("font_asset", json!("missing")) })),
+        "unknown_font_asset",
+        "$.visual.nodes[0].overrides.typeface.value",
+    );
+    assert_diagnostic(
+        &document(json!({ "shadow": typed("color", json!("#000000")) })),
+        "unknown_override",
+        "$.visual.nodes[0].overrides.shadow",
+    );
+}
+
+#[test]
+fn references_are_checked_at_the_component_definition() {
+    let mut wrong_kind = document(json!({}));
+    wrong_kind["components"][0]["visual"][1]["text"] = parameter("accent");
+    assert_diagnostic(
+        &wrong_kind,
+        "parameter_type_mismatch",
+        "$.components[0].visual[1].text",
+    );
+
+    let mut undeclared = document(json!({}));
+    undeclared["components"][0]["visual"][2]["asset"] = parameter("badge");
+    assert_diagnostic(
+        &undeclared,
+        "unknown_parameter",
+        "$.components[0].visual[2].asset",
+    );
+
+    let mut bad_default = document(json!({}));
+    bad_default["components"][0]["parameters"]["surface"] = typed("paint", json!("slate"));
+    assert_diagnostic(
+        &bad_default,
+        "invalid_color",
+        "$.components[0].parameters.surface.value",
+    );
+
+    let mut outside_component = document(json!({}));
+    outside_component["visual"]["nodes"]
+        .as_array_mut()
+        .expect("nodes")
+        .push(json!({
+            "kind": "text",
+            "id": "caption",
+            "text": parameter("label"),
+            "font_size": literal(12.0, "px"),
+            "fill": "#FFFFFF"
+        }));
+    assert_diagnostic(
+        &outside_component,
+        "unresolved_parameter_reference",
+        "$.visual.nodes[1].text",
+    );
+}
@@ -0,0 +1,262 @@
+mod support;
+
+use rive_cli::authoring::{LoweredAuthoring, lower_authoring_json};
+use serde_json::{Value, json};
+use support::assert_builds;
+
+fn literal(value: f64, unit: &str) -> Value {
+    json!({ "kind": "literal", "value": value, "unit": unit })
+}
+
+fn px(value: f64) -> Value {
+    literal(value, "px")
+}
+
+fn frame(value: f64) -> Value {
+    literal(value, "scalar")
+}
+
+fn document() -> Value {
+    json!({
+        "authoring_format_version": 0,
+        "artboard": {
+            "id": "card",
+            "width": { "value": 200.0, "unit": "px" },
+            "height": { "value": 200.0, "unit": "px" }
+        },
+        "audio_assets": { "chime": "chime.wav" },
+        "visual": {
+            "nodes": [
+                {
+                    "kind": "rectangle",
+                    "id": "button",
+                    "width": px(80.0),
+                    "height": px(40.0),
+                    "fill": "#2563EB"
+                }
+            ]
+        },
+        "motion": {
+            "poses": [
+                { "id": "rest", "targets": [{ "target": "button", "opacity": literal(1.0, "scalar") }] },
+                { "id": "dim", "targets": [{ "target": "button", "opacity": literal(0.5, "scalar") }] }
+            ],
+            "tracks": [
+                {
+                    "id": "pulse",
+                    "fps": 60,
+                    "duration_frames": frame(30.0),
+                    "keyframes": [
+                        { "frame": frame(0.0), "pose": "rest" },
+                        { "frame": frame(30.0), "pose": "dim" }
+                    ],
+                    "events": [
+                        { "frame": frame(20.0), "event": "pulsed" },
+                        { "frame": frame(10.0), "event": "pulsed" }
+                    ]
+                }
+            ]
+        },
+        "behavior": {
+            "events": [
+                {
+                    "kind": "general",
+                    "id": "pulsed",
+                    "properties": {
+                        "intensity": { "kind": "number", "value": 0.75 },
+                        "haptic": { "kind": "boolean", "value": true },
+                        "screen": { "kind": "string", "value": "home" }
+                    }
+                },
+                { "kind": "open_url", "id": "learn_more", "url": "https://rive.app", "target": "parent" },
+                { "kind": "audio", "id": "click", "asset": "chime" }
+            ],
+            "raw_state_machines": [
+                {
+                    "id": "interaction",
+                    "value": {
+                        "name": "interaction",
+                        "listeners": [{
+                            "target": "auth__card__button__shape",
+                            "listener_type": "down",
+                            "actions": [{ "type": "fire_event", "event": "click" }]
+                        }],
+                        "layers": [{
+                            "states": [
+                                { "type": "entry" },
+                                { "type": "exit" },
+                                { "type": "animation", "animation": "auth__card__pulse__animation" }
+                            ],
+                            "transitions": [{
+                                "from": 0,
+                                "to": 2,
+                                "events": [{ "event": "learn_more", "occurs_value": 1 }]
+                            }]
+                        }]
+                    }
+                }
+            ]
+        }
+    })
+}
+
+fn lower(input: &Value) -> LoweredAuthoring {
+    lower_authoring_json(&input.to_string()).expect("events must lower")
+}
+
+fn assert_diagnostic(input: &Value, code: &str, path: &str) {
+    let error = lower_authoring_json(&input.to_string())
+        .expect_err("invalid events must fail at the authored boundary");
+    assert!(
+        error
+            .diagnostics
+            .iter()
+            .any(|diagnostic| diagnostic.code == code && diagnostic.path == path),
+        "missing {code} at {path}; diagnostics: {:#?}",
+        error.diagnostics
+    );
+}
+
+#[test]
+fn events_lower_after_the_visual_nodes_with_typed_properties() {
+    let input = document();
+    let first = lower(&input);
+    let second = lower(&input);
+    assert_eq!(first.scene, second.scene);
+    assert_eq!(first.source_map, second.source_map);
+
+    let children = first.scene["artboard"]["children"]
+        .as_array()
+        .expect("artboard children");
+    assert_eq!(children[0]["type"], "audio_asset");
+    assert_eq!(children[0]["name"], "auth__card__chime__audio_asset");
+    assert_eq!(children[0]["source"], "chime.wav");
+
+    let pulsed = &children[2];
+    assert_eq!(pulsed["type"], "event");
+    assert_eq!(pulsed["name"], "pulsed");
+    assert_eq!(
+        pulsed["children"],
+        json!([
+            { "type": "custom_property_boolean", "name": "haptic", "property_value": true },
+            { "type": "custom_property_number", "name": "intensity", "property_value": 0.75 },
+            { "type": "custom_property_string", "name": "screen", "property_value": "home" }
+        ])
+    );
+    assert_eq!(children[3]["type"], "open_url_event");
+    assert_eq!(children[3]["url"], "https://rive.app");
+    assert_eq!(children[3]["target_value"], 1);
+    assert_eq!(children[4]["type"], "audio_event");
+    assert_eq!(children[4]["asset"], "auth__card__chime__audio_asset");
+
+    let entry = first
+        .source_map
+        .entries
+        .iter()
+        .find(|entry| entry.authored_path == "$.behavior.events[0]")
+        .expect("event source map entry");
+    assert_eq!(entry.authored_id, "pulsed");
+    assert_eq!(
+        entry.runtime_names,
+        ["pulsed", "haptic", "intensity", "screen"]
+    );
+    for entry in &first.source_map.entries {
+        for scene_path in &entry.scene_paths {
+            assert!(
+                first.scene.pointer(scene_path).is_some(),
+                "{scene_path} must resolve"
+            );
+        }
+    }
+
+    assert_builds(without_sources(first.scene));
+}
+
+#[test]
+fn track_events_key_the_event_trigger_in_frame_order() {
+    let lowered = lower(&document());
+    let animation = &lowered.scene["artboard"]["animations"][0];
+    let group = animation["keyframes"]
+        .as_array()
+        .expect("animation keyframes")
+        .iter()
+        .find(|group| group["object"] == "pulsed")
+        .expect("event trigger must be keyed");
+    assert_eq!(group["property"], "trigger");
+    assert_eq!(group["frames"][0]["frame"], 10);
+    assert_eq!(group["frames"][1]["frame"], 20);
+
+    let late = lowered
+        .source_map
+        .entries
+        .iter()
+        .find(|entry| entry.authored_path == "$.motion.tracks[0].events[0]")
+        .expect("track event source map entry");
+    assert_eq!(
+        late.scene_paths,
+        ["/artboard/animations/0/keyframes/1/frames/1"]
+    );
+    assert_builds(without_sources(lowered.scene));
+}
+
+#[test]
+fn event_references_are_checked_at_the_authored_path() {
+    let mut unknown_event = document();
+    unknown_event["motion"]["tracks"][0]["events"][0]["event"] = json!("tapped");
+    assert_diagnostic(
+        &unknown_event,
+        "unknown_event",
+        "$.motion.tracks[0].events[0].event",
+    );
+
+    let mut late = document();
+    late["motion"]["tracks"][0]["events"][0]["frame"] = frame(31.0);
+    assert_diagnostic(
+        &late,
+        "frame_out_of_range",
+        "$.motion.tracks[0].events[0].frame",
+    );
+
+    let mut twice = document();
+    twice["motion"]["tracks"][0]["events"][1]["frame"] = frame(20.0);
+    assert_diagnostic(
+        &twice,
+        "duplicate_frame",
+        "$.motion.tracks[0].events[1].frame",
+    );
+
+    let mut unknown_asset = document();
+    unknown_asset["behavior"]["events"][2]["asset"] = json!("boop");
+    assert_diagnostic(
+        &unknown_asset,
+        "unknown_audio_asset",
+        "$.behavior.events[2].asset",
+    );
+
+    let mut shared_key = document();
+    shared_key["behavior"]["events"][1]["properties"] =
+        json!({ "screen": { "kind": "string", "value": "about" } });
+    assert_diagnostic(
+        &shared_key,
+        "runtime_name_collision",
+        "$.behavior.events[1]",
+    );
+
+    let mut not_an_event = document();
+    not_an_event["behavior"]["raw_state_machines"][0]["value"]["listeners"][0]["actions"][0]["event"] =
+        json!("auth__card__button__shape");
+    assert_diagnostic(&not_an_event, "builder_rejected_scene", "$.lowered_scene");
+}
+
+/// Audio sources are resolved against the scene file on disk, which these
+/// in-memory builds do not have.
+fn without_sources(mut scene: Value) -> Value {
+    if let Some(children) = scene["artboard"]["children"].as_array_mut() {
+        for child in children {
+            if let Some(object) = child.as_object_mut() {
+                object.remove("source");
+            }
+        }
+    }
+    scene
+}
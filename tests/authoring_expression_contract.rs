@@ -0,0 +1,228 @@
+mod support;
+
+use rive_cli::authoring::{LoweredAuthoring, lower_authoring_json};
+use serde_json::{Value, json};
+use support::assert_builds;
+
+fn literal(value: f64, unit: &str) -> Value {
+    json!({ "kind": "literal", "value": value, "unit": unit })
+}
+
+fn parameter(name: &str) -> Value {
+    json!({ "kind": "parameter", "name": name })
+}
+
+fn binary(kind: &str, left: Value, right: Value) -> Value {
+    json!({ "kind": kind, "left": left, "right": right })
+}
+
+/// A rectangle whose width and x position are the expressions under test.
+fn document(width: Value, x: Value) -> Value {
+    json!({
+        "authoring_format_version": 0,
+        "artboard": {
+            "id": "maths",
+            "width": { "value": 400.0, "unit": "px" },
+            "height": { "value": 400.0, "unit": "px" }
+        },
+        "parameters": {
+            "panel": { "value": 100.0, "unit": "px" },
+            "padding": { "value": 24.0, "unit": "px" },
+            "radius": { "value": 80.0, "unit": "px" },
+            "angle": { "value": 60.0, "unit": "degrees" },
+            "progress": { "value": 0.25, "unit": "scalar" }
+        },
+        "visual": {
+            "nodes": [
+                {
+                    "kind": "rectangle",
+                    "id": "card",
+                    "width": width,
+                    "height": literal(40.0, "px"),
+                    "fill": "#0F172A",
+                    "transform": { "x": x }
+                }
+            ]
+        },
+        "motion": {},
+        "behavior": {}
+    })
+}
+
+fn lower(input: &Value) -> LoweredAuthoring {
+    lower_authoring_json(&input.to_string()).expect("extended expressions must lower")
+}
+
+fn assert_diagnostic(input: &Value, code: &str, path: &str) {
+    let error = lower_authoring_json(&input.to_string())
+        .expect_err("invalid expressions must fail at the authored boundary");
+    assert!(
+        error
+            .diagnostics
+            .iter()
+            .any(|diagnostic| diagnostic.code == code && diagnostic.path == path),
+        "missing {code} at {path}; diagnostics: {:#?}",
+        error.diagnostics
+    );
+}
+
+fn evaluated(width: Value, x: Value) -> (f64, f64) {
+    let lowered = lower(&document(width, x));
+    let card = &lowered.scene["artboard"]["children"][0];
+    let geometry = &card["children"][0];
+    (
+        geometry["width"].as_f64().expect("width"),
+        card["x"].as_f64().expect("x"),
+    )
+}
+
+#[test]
+fn layout_maths_evaluates_deterministically_and_builds() {
+    // max(panel / 2 - padding, 8px) and radius * cos(angle).
+    let width = binary(
+        "max",
+        binary(
+            "subtract",
+            json!({ "kind": "divide", "value": parameter("panel"), "divisor": 2.0 }),
+            parameter("padding"),
+        ),
+        literal(8.0, "px"),
+    );
+    let x = binary(
+        "product",
+        parameter("radius"),
+        json!({ "kind": "cos", "angle": parameter("angle") }),
+    );
+    let input = document(width.clone(), x.clone());
+    let first = lower(&input);
+    let second = lower(&input);
+    assert_eq!(first.scene, second.scene);
+
+    let (width, x) = evaluated(width, x);
+    assert_eq!(width, 26.0);
+    assert!((x - 40.0).abs() < 1e-4, "radius * cos(60deg) = {x}");
+
+    assert_builds(first.scene);
+}
+
+#[test]
+fn clamp_round_lerp_and_ratios_follow_unit_algebra() {
+    let clamped = json!({
+        "kind": "clamp",
+        "value": parameter("padding"),
+        "min": literal(8.0, "px"),
+        "max": literal(16.0, "px")
+    });
+    let rounded = json!({
+        "kind": "round",
+        "value": binary("product", parameter("panel"), literal(0.333, "scalar"))
+    });
+    assert_eq!(evaluated(clamped, rounded), (16.0, 33.0));
+
+    let lerp = json!({
+        "kind": "lerp",
+        "from": literal(40.0, "px"),
+        "to": parameter("panel"),
+        "t": parameter("progress")
+    });
+    // panel * (padding / radius): the pixel ratio cancels to a scalar.
+    let scaled = binary(
+        "product",
+        parameter("panel"),
+        binary("quotient", parameter("padding"), parameter("radius")),
+    );
+    assert_eq!(evaluated(lerp, scaled), (55.0, 30.0));
+
+    let sine = binary(
+        "product",
+        literal(10.0, "px"),
+        json!({ "kind": "sin", "angle": literal(30.0, "degrees") }),
+    );
+    let (_, x) = evaluated(literal(10.0, "px"), sine);
+    assert!((x - 5.0).abs() < 1e-6);
+}
+
+#[test]
+fn expression_diagnostics_preserve_authored_paths() {
+    let x = literal(0.0, "px");
+    assert_diagnostic(
+        &document(
+            binary("product", parameter("panel"), parameter("padding")),
+            x.clone(),
+        ),
+        "unit_mismatch",
+        "$.visual.nodes[0].width.right",
+    );
+    assert_diagnostic(
+        &document(
+            binary("quotient", literal(1.0, "scalar"), parameter("panel")),
+            x.clone(),
+        ),
+        "unit_mismatch",
+        "$.visual.nodes[0].width.right",
+    );
+    assert_diagnostic(
+        &document(
+            binary("quotient", parameter("panel"), literal(0.0, "scalar")),
+            x.clone(),
+        ),
+        "division_by_zero",
+        "$.visual.nodes[0].width.right",
+    );
+    assert_diagnostic(
+        &document(
+            json!({ "kind": "sin", "angle": parameter("panel") }),
+            x.clone(),
+        ),
+        "unit_mismatch",
+        "$.visual.nodes[0].width.angle",
+    );
+    assert_diagnostic(
+        &document(
+            json!({
+                "kind": "clamp",
+                "value": parameter("panel"),
+                "min": literal(20.0, "px"),
+                "max": literal(10.0, "px")
+            }),
+            x.clone(),
+        ),
+        "invalid_clamp_range",
+        "$.visual.nodes[0].width.max",
+    );
+    assert_diagnostic(
+        &document(
+            literal(10.0, "px"),
+            json!({ "kind": "round", "value": parameter("angle") }),
+        ),
+        "unit_mismatch",
+        "$.visual.nodes[0].transform.x.value",
+    );
+    assert_diagnostic(
+        &document(
+            json!({
+                "kind": "lerp",
+                "from": literal(0.0, "px"),
+                "to": literal(10.0, "px"),
+                "t": literal(5.0, "px")
+            }),
+            x.clone(),
+        ),
+        "unit_mismatch",
+        "$.visual.nodes[0].width.t",
+    );
+
+    let mut deep = literal(10.0, "px");
+    let mut deepest_path = "$.visual.nodes[0].width".to_string();
+    for depth in 1..=32 {
+        deep = json!({ "kind": "round", "value": deep });
+        if depth > 1 {
+            deepest_path.push_str(".value");
+        }
+    }
+    assert_diagnostic(
+        &document(deep, x),
+        "expression_depth_limit",
+        &format!("{deepest_path}.value"),
+    );
+}
@@ -0,0 +1,200 @@
+mod support;
+
+use rive_cli::authoring::{LoweredAuthoring, lower_authoring_json};
+use serde_json::{Value, json};
+use support::assert_builds;
+
+fn literal(value: f64, unit: &str) -> Value {
+    json!({ "kind": "literal", "value": value, "unit": unit })
+}
+
+fn px(value: f64) -> Value {
+    literal(value, "px")
+}
+
+fn document() -> Value {
+    json!({
+        "authoring_format_version": 0,
+        "artboard": {
+            "id": "toolbar",
+            "width": { "value": 320.0, "unit": "px" },
+            "height": { "value": 64.0, "unit": "px" }
+        },
+        "visual": {
+            "nodes": [
+                {
+                    "kind": "layout",
+                    "id": "bar",
+                    "direction": "row",
+                    "gap": px(8.0),
+                    "padding": { "top": px(4.0), "right": px(12.0), "bottom": px(4.0), "left": px(12.0) },
+                    "align": "center",
+                    "justify": "space_between",
+                    "width": { "kind": "fill" },
+                    "height": { "kind": "fixed", "value": px(64.0) },
+                    "clip": true,
+                    "children": [
+                        {
+                            "kind": "layout",
+                            "id": "icon",
+                            "width": { "kind": "fixed", "value": px(32.0) },
+                            "height": { "kind": "fixed", "value": px(32.0) },
+                            "children": [
+                                {
+                                    "kind": "ellipse",
+                                    "id": "dot",
+                                    "width": px(32.0),
+                                    "height": px(32.0),
+                                    "fill": "#F97316",
+                                    "transform": { "x": px(16.0), "y": px(16.0) }
+                                }
+                            ]
+                        },
+                        {
+                            "kind": "layout",
+                            "id": "title",
+                            "direction": "column",
+                            "width": { "kind": "fill" },
+                            "height": { "kind": "fill" }
+                        }
+                    ]
+                }
+            ]
+        },
+        "motion": {},
+        "behavior": {}
+    })
+}
+
+fn lower(input: &Value) -> LoweredAuthoring {
+    lower_authoring_json(&input.to_string()).expect("layouts must lower")
+}
+
+fn assert_diagnostic(input: &Value, code: &str, path: &str) {
+    let error = lower_authoring_json(&input.to_string())
+        .expect_err("invalid layouts must fail at the authored boundary");
+    assert!(
+        error
+            .diagnostics
+            .iter()
+            .any(|diagnostic| diagnostic.code == code && diagnostic.path == path),
+        "missing {code} at {path}; diagnostics: {:#?}",
+        error.diagnostics
+    );
+}
+
+#[test]
+fn layout_lowers_to_a_component_with_a_named_style_first() {
+    let input = document();
+    let first = lower(&input);
+    let second = lower(&input);
+    assert_eq!(first.scene, second.scene);
+    assert_eq!(first.source_map, second.source_map);
+
+    let bar = &first.scene["artboard"]["children"][0];
+    assert_eq!(bar["type"], "layout_component");
+    assert_eq!(bar["name"], "auth__toolbar__bar__layout");
+    assert_eq!(bar["style"], "auth__toolbar__bar__layout_style");
+    assert_eq!(bar["clip"], true);
+    assert_eq!(bar["width"], 100.0);
+    assert_eq!(bar["height"], 64.0);
+
+    let style = &bar["children"][0];
+    assert_eq!(style["type"], "layout_component_style");
+    assert_eq!(style["flex_direction"], 2);
+    assert_eq!(style["align_items"], 2);
+    assert_eq!(style["justify_content"], 3);
+    assert_eq!(style["gap_horizontal"], 8.0);
+    assert_eq!(style["padding_right"], 12.0);
+    assert_eq!(style["width_units"], 2);
+    assert_eq!(style["height_units"], 1);
+
+    let icon = &bar["children"][1];
+    assert_eq!(icon["children"][0]["width_units"], 1);
+    assert_eq!(
+        icon["children"][1]["name"],
+        "auth__toolbar__bar__icon__dot__shape"
+    );
+
+    let entry = first
+        .source_map
+        .entries
+        .iter()
+        .find(|entry| entry.authored_id == "bar/icon/dot")
+        .expect("nested shape source map entry");
+    assert_eq!(
+        entry.authored_path,
+        "$.visual.nodes[0].children[0].children[0]"
+    );
+    for entry in &first.source_map.entries {
+        for scene_path in &entry.scene_paths {
+            assert!(
+                first.scene.pointer(scene_path).is_some(),
+                "{scene_path} must resolve"
+            );
+        }
+    }
+
+    assert_builds(first.scene);
+}
+
+#[test]
+fn fill_grows_along_the_parent_direction_and_stretches_across_it() {
+    let lowered = lower(&document());
+    let title = &lowered.scene["artboard"]["children"][0]["children"][2];
+    let style = &title["children"][0];
+    assert_eq!(style["flex_direction"], 0);
+    // The parent is a row: width is the main axis, height the cross axis.
+    assert_eq!(style["flex_grow"], 1.0);
+    assert_eq!(style["width_units"], 3);
+    assert_eq!(style["height_units"], 2);
+    assert_eq!(title["height"], 100.0);
+
+    let mut column = document();
+    column["visual"]["nodes"][0]["direction"] = json!("column");
+    let lowered = lower(&column);
+    let style = &lowered.scene["artboard"]["children"][0]["children"][2]["children"][0];
+    assert_eq!(style["width_units"], 2);
+    assert_eq!(style["height_units"], 3);
+    assert_eq!(style["flex_grow"], 1.0);
+
+    let mut hug = document();
+    hug["visual"]["nodes"][0]["children"][1]["width"] = json!({ "kind": "hug" });
+    hug["visual"]["nodes"][0]["children"][1]["height"] = json!({ "kind": "hug" });
+    let lowered = lower(&hug);
+    let style = &lowered.scene["artboard"]["children"][0]["children"][2]["children"][0];
+    assert_eq!(style["width_units"], 3);
+    assert_eq!(style["flex_grow"], 0.0);
+    assert_builds(lowered.scene);
+}
+
+#[test]
+fn layout_values_are_checked_at_the_authored_path() {
+    let mut negative_gap = document();
+    negative_gap["visual"]["nodes"][0]["gap"] = px(-4.0);
+    assert_diagnostic(&negative_gap, "invalid_dimension", "$.visual.nodes[0].gap");
+
+    let mut zero_width = document();
+    zero_width["visual"]["nodes"][0]["children"][0]["width"]["value"] = px(0.0);
+    assert_diagnostic(
+        &zero_width,
+        "invalid_dimension",
+        "$.visual.nodes[0].children[0].width.value",
+    );
+
+    let mut degrees = document();
+    degrees["visual"]["nodes"][0]["padding"]["left"] = literal(12.0, "degrees");
+    assert_diagnostic(&degrees, "unit_mismatch", "$.visual.nodes[0].padding.left");
+
+    let mut duplicate = document();
+    duplicate["visual"]["nodes"][0]["children"][1]["id"] = json!("icon");
+    assert_diagnostic(
+        &duplicate,
+        "duplicate_id",
+        "$.visual.nodes[0].children[1].id",
+    );
+
+    let mut unknown = document();
+    unknown["visual"]["nodes"][0]["justify"] = json!("space_wide");
+    assert_diagnostic(&unknown, "invalid_json", "$");
+}
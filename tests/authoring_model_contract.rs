@@ -0,0 +1,369 @@
+mod support;
+
+use rive_cli::authoring::lower_authoring_json;
+use rive_cli::builder::{SceneSpec, build_scene};
+use rive_cli::objects::core::{PropertyValue, property_keys, type_keys};
+use serde_json::{Value, json};
+use support::assert_builds;
+
+fn literal(value: f64, unit: &str) -> Value {
+    json!({ "kind": "literal", "value": value, "unit": unit })
+}
+
+fn document(model: Value) -> String {
+    json!({
+        "authoring_format_version": 0,
+        "artboard": {
+            "id": "card",
+            "width": { "value": 320.0, "unit": "px" },
+            "height": { "value": 200.0, "unit": "px" }
+        },
+        "visual": {
+            "nodes": [
+                {
+                    "kind": "rectangle",
+                    "id": "bar",
+                    "width": literal(120.0, "px"),
+                    "height": literal(16.0, "px"),
+                    "fill": "#2563EB",
+                    "stroke": { "paint": "#0F172A", "width": literal(2.0, "px") }
+                },
+                {
+                    "kind": "text",
+                    "id": "label",
+                    "text": "Score",
+                    "font_size": literal(18.0, "px"),
+                    "fill": "#0F172A"
+                }
+            ]
+        },
+        "motion": {},
+        "behavior": {},
+        "model": model
+    })
+    .to_string()
+}
+
+fn model() -> Value {
+    json!({
+        "properties": [
+            { "kind": "number", "id": "score", "default": 42.0 },
+            { "kind": "string", "id": "title", "default": "Player" },
+            { "kind": "color", "id": "accent", "default": "#F97316" },
+            { "kind": "boolean", "id": "active", "default": true },
+            { "kind": "enum", "id": "mode", "values": ["idle", "busy"], "default": "busy" },
+            { "kind": "trigger", "id": "tap" },
+            { "kind": "list", "id": "items" }
+        ],
+        "bind": [
+            {
+                "id": "bar-width",
+                "property": "score",
+                "target": "bar",
+                "field": "width",
+                "converters": [
+                    { "kind": "multiply", "value": 2.0 },
+                    { "kind": "round", "decimals": 0 }
+                ]
+            },
+            { "id": "bar-x", "property": "score", "target": "bar", "field": "transform.x" },
+            { "id": "bar-fill", "property": "accent", "target": "bar", "field": "fill" },
+            {
+                "id": "label-text",
+                "property": "mode",
+                "target": "label",
+                "field": "text",
+                "converters": [{ "kind": "to_string" }]
+            }
+        ]
+    })
+}
+
+fn find_entry<'a>(
+    lowered: &'a rive_cli::authoring::LoweredAuthoring,
+    authored_path: &str,
+) -> &'a rive_cli::authoring::SourceMapEntry {
+    lowered
+        .source_map
+        .entries
+        .iter()
+        .find(|entry| entry.authored_path == authored_path)
+        .unwrap_or_else(|| panic!("source-map entry for {authored_path}"))
+}
+
+#[test]
+fn typed_model_lowers_view_model_instance_and_bindings_deterministically() {
+    let input = document(model());
+    let first = lower_authoring_json(&input).expect("model lowering");
+    let second = lower_authoring_json(&input).expect("second model lowering");
+    assert_eq!(first.scene, second.scene);
+    assert_eq!(first.source_map, second.source_map);
+
+    let children = first.scene["artboard"]["children"]
+        .as_array()
+        .expect("artboard children");
+    let shape = &children[0];
+    assert_eq!(shape["type"], "shape");
+    assert_eq!(shape["children"][0]["type"], "data_bind");
+    assert_eq!(shape["children"][0]["property_key"], 13);
+    assert_eq!(shape["children"][0]["flags"], 0);
+    assert_eq!(shape["children"][1]["type"], "rectangle");
+    assert_eq!(shape["children"][2]["type"], "data_bind");
+    assert_eq!(shape["children"][2]["converter_id"], 2);
+    assert_eq!(shape["children"][3]["children"][0]["type"], "solid_color");
+    assert_eq!(shape["children"][3]["children"][1]["type"], "data_bind");
+    assert!(
+        shape["children"][3]["children"][1]
+            .get("converter_id")
+            .is_none()
+    );
+
+    let view_model = children
+        .iter()
+        .find(|child| child["type"] == "view_model")
+        .expect("view model");
+    assert_eq!(view_model["name"], "card");
+    let property_types = view_model["children"]
+        .as_array()
+        .expect("view model properties")
+        .iter()
+        .map(|property| property["type"].as_str().unwrap_or_default())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        property_types,
+        [
+            "view_model_property_number",
+            "view_model_property_string",
+            "view_model_property_color",
+            "view_model_property_boolean",
+            "view_model_property_enum",
+            "view_model_property_trigger",
+            "view_model_property_list"
+        ]
+    );
+    let group = children
+        .iter()
+        .find(|child| child["type"] == "data_converter_group")
+        .expect("converter chain group");
+    assert_eq!(group["children"][0]["converter_id"], 0);
+    assert_eq!(group["children"][1]["converter_id"], 1);
+    let enum_value = children
+        .iter()
+        .find(|child| child["type"] == "view_model_instance_enum")
+        .expect("enum instance value");
+    assert_eq!(enum_value["value"], 1);
+    assert_eq!(enum_value["view_model_property_id"], 4);
+
+    let width = find_entry(&first, "$.model.bind[0]");
+    assert_eq!(width.runtime_names.len(), 3);
+    for (name, path) in width.runtime_names.iter().zip(&width.scene_paths) {
+        assert_eq!(
+            first.scene.pointer(path).expect("converter path")["name"],
+            *name
+        );
+    }
+    let fill = find_entry(&first, "$.model.bind[2]");
+    assert!(fill.runtime_names.is_empty());
+    assert_eq!(
+        first
+            .scene
+            .pointer(&fill.scene_paths[0])
+            .expect("data bind path")["type"],
+        "data_bind"
+    );
+    let bar = find_entry(&first, "$.visual.nodes[0]");
+    for (name, path) in bar.runtime_names.iter().zip(&bar.scene_paths) {
+        assert_eq!(
+            first.scene.pointer(path).expect("shifted visual path")["name"],
+            *name
+        );
+    }
+    let mode = find_entry(&first, "$.model.properties[4]");
+    assert_eq!(mode.runtime_names[0], "mode");
+    assert_eq!(mode.runtime_names[1], "auth__card__mode__data_enum");
+
+    assert_builds(first.scene);
+}
+
+#[test]
+fn bindings_to_different_properties_reference_different_sources() {
+    let input = document(json!({
+        "properties": [
+            { "kind": "number", "id": "score", "default": 42.0 },
+            { "kind": "number", "id": "offset", "default": 8.0 }
+        ],
+        "bind": [
+            { "id": "bar-width", "property": "score", "target": "bar", "field": "width" },
+            { "id": "bar-x", "property": "offset", "target": "bar", "field": "transform.x" }
+        ]
+    }));
+    let lowered = lower_authoring_json(&input).expect("model lowering");
+    let source_paths = ["$.model.bind[0]", "$.model.bind[1]"].map(|path| {
+        let entry = find_entry(&lowered, path);
+        lowered
+            .scene
+            .pointer(&entry.scene_paths[0])
+            .expect("data bind path")["source_path_ids"]
+            .clone()
+    });
+    assert_eq!(source_paths[0], json!([0, 0]));
+    assert_eq!(source_paths[1], json!([0, 1]));
+
+    let scene: SceneSpec = serde_json::from_value(lowered.scene).expect("SceneSpec");
+    let objects = build_scene(&scene, None).expect("bindings build");
+    let mut written = objects
+        .iter()
+        .filter(|object| object.type_key() == type_keys::DATA_BIND_CONTEXT)
+        .map(|object| {
+            object
+                .properties()
+                .into_iter()
+                .find(|property| property.key == property_keys::DATA_BIND_CONTEXT_SOURCE_PATH_IDS)
+                .map(|property| property.value)
+        })
+        .collect::<Vec<_>>();
+    written.sort_by_key(|value| format!("{value:?}"));
+    assert_eq!(
+        written,
+        [
+            Some(PropertyValue::Bytes(vec![0, 0])),
+            Some(PropertyValue::Bytes(vec![0, 1]))
+        ]
+    );
+}
+
+#[test]
+fn model_contract_errors_point_to_the_authored_binding() {
+    let cases = [
+        (
+            json!({
+                "properties": [{ "kind": "color", "id": "accent", "default": "#F97316" }],
+                "bind": [{ "id": "b", "property": "accent", "target": "bar", "field": "width" }]
+            }),
+            "incompatible_binding_type",
+            "$.model.bind[0].field",
+        ),
+        (
+            json!({
+                "properties": [{ "kind": "color", "id": "accent", "default": "#F97316" }],
+                "bind": [{
+                    "id": "b",
+                    "property": "accent",
+                    "target": "bar",
+                    "field": "width",
+                    "converters": [{ "kind": "add", "value": 1.0 }]
+                }]
+            }),
+            "incompatible_converter",
+            "$.model.bind[0].converters[0]",
+        ),
+        (
+            json!({
+                "properties": [{ "kind": "number", "id": "score" }],
+                "bind": [{ "id": "b", "property": "missing", "target": "bar", "field": "width" }]
+            }),
+            "unknown_model_property",
+            "$.model.bind[0].property",
+        ),
+        (
+            json!({
+                "properties": [{ "kind": "number", "id": "score" }],
+                "bind": [{ "id": "b", "property": "score", "target": "ghost", "field": "width" }]
+            }),
+            "unknown_binding_target",
+            "$.model.bind[0].target",
+        ),
+        (
+            json!({
+                "properties": [{ "kind": "string", "id": "title" }],
+                "bind": [{ "id": "b", "property": "title", "target": "bar", "field": "text" }]
+            }),
+            "unsupported_binding_field",
+            "$.model.bind[0].field",
+        ),
+        (
+            json!({
+                "properties": [{ "kind": "number", "id": "score" }],
+                "bind": [
+                    { "id": "a", "property": "score", "target": "bar", "field": "width" },
+                    { "id": "b", "property": "score", "target": "bar", "field": "width" }
+                ]
+            }),
+            "duplicate_binding",
+            "$.model.bind[1].field",
+        ),
+        (
+            json!({
+                "properties": [
+                    { "kind": "enum", "id": "mode", "values": ["idle"], "default": "busy" }
+                ]
+            }),
+            "unknown_enum_value",
+            "$.model.properties[0].default",
+        ),
+        (
+            json!({
+                "properties": [{ "kind": "color", "id": "accent", "default": "orange" }]
+            }),
+            "invalid_color",
+            "$.model.properties[0].default",
+        ),
+        (
+            json!({
+                "properties": [
+                    { "kind": "number", "id": "score" },
+                    { "kind": "string", "id": "score" }
+                ]
+            }),
+            "duplicate_model_property",
+            "$.model.properties[1].id",
+        ),
+        (
+            json!({
+                "properties": [{ "kind": "number", "id": "score" }],
+                "bind": [{
+                    "id": "b",
+                    "property": "score",
+                    "target": "bar",
+                    "field": "width",
+                    "converters": [{ "kind": "divide", "value": 0.0 }]
+                }]
+            }),
+            "division_by_zero",
+            "$.model.bind[0].converters[0].value",
+        ),
+    ];
+
+    for (model, expected_code, expected_path) in cases {
+        let error =
+            lower_authoring_json(&document(model)).expect_err("invalid model contract must fail");
+        let found = error
+            .diagnostics
+            .iter()
+            .any(|diagnostic| diagnostic.code == expected_code && diagnostic.path == expected_path);
+        assert!(
+            found,
+            "expected {expected_code} at {expected_path}, got {:?}",
+            error.diagnostics
+        );
+    }
+}
+
+#[test]
+fn documents_without_a_model_lower_unchanged() {
+    let input = json!({
+        "authoring_format_version": 0,
+        "artboard": {
+            "id": "card",
+            "width": { "value": 320.0, "unit": "px" },
+            "height": { "value": 200.0, "unit": "px" }
+        },
+        "visual": { "nodes": [] },
+        "motion": {},
+        "behavior": {}
+    })
+    .to_string();
+
+    let lowered = lower_authoring_json(&input).expect("model-free lowering");
+    assert_eq!(lowered.scene["artboard"]["children"], json!([]));
+}
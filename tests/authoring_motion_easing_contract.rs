@@ -257,3 +257,202 @@ fn cubic_easing_control_points_use_scalar_expression_validation() {
         "$.motion.easings[0].x1",
     );
 }
+
+#[test]
+fn presets_and_elastic_easings_lower_to_runtime_interpolators() {
+    let mut input = document();
+    input["motion"]["easings"] = json!([
+        { "kind": "preset", "id": "overshoot", "preset": "ease_out_back" },
+        {
+            "kind": "elastic",
+            "id": "wobble",
+            "easing": "in_out",
+            "amplitude": literal(1.5, "scalar"),
+            "period": literal(0.8, "scalar")
+        }
+    ]);
+    input["motion"]["tracks"][0]["keyframes"][0]["easing"] = json!("overshoot");
+    let mut wobble_track = input["motion"]["tracks"][0].clone();
+    wobble_track["id"] = json!("wobble");
+    wobble_track["keyframes"][0]["easing"] = json!("wobble");
+    input["motion"]["tracks"]
+        .as_array_mut()
+        .expect("motion tracks")
+        .push(wobble_track);
+
+    let lowered = lower(&input);
+    let animations = &lowered.scene["artboard"]["animations"];
+    let preset = &animations[0]["interpolators"][0];
+    assert_eq!(preset["type"], "cubic");
+    assert_eq!(
+        [&preset["x1"], &preset["y1"], &preset["x2"], &preset["y2"]],
+        [&json!(0.34), &json!(1.56), &json!(0.64), &json!(1.0)]
+    );
+
+    let elastic = &animations[1]["interpolators"][0];
+    assert_eq!(elastic["type"], "elastic");
+    assert_eq!(elastic["easing_value"], 2);
+    assert_eq!(elastic["amplitude"], 1.5);
+    assert_eq!(elastic["period"], 0.8);
+    let first_frame = &keyed_x(&animations[1])["frames"][0];
+    assert_eq!(
+        first_frame["interpolator"],
+        "auth__easing_2dstage__wobble__interpolator"
+    );
+
+    let scene: SceneSpec =
+        serde_json::from_value(lowered.scene.clone()).expect("lowered SceneSpec");
+    let objects = build_scene(&scene, None).expect("elastic easing must build");
+    assert_eq!(
+        objects
+            .iter()
+            .filter(|object| object.type_key() == type_keys::ELASTIC_INTERPOLATOR)
+            .count(),
+        1
+    );
+}
+
+#[test]
+fn spring_easing_bakes_linear_keyframes_within_tolerance() {
+    let mut input = document();
+    input["motion"]["easings"] = json!([{
+        "kind": "spring",
+        "id": "bouncy",
+        "stiffness": literal(170.0, "scalar"),
+        "damping": literal(12.0, "scalar"),
+        "mass": literal(1.0, "scalar"),
+        "tolerance": literal(0.002, "scalar")
+    }]);
+    input["motion"]["tracks"][0]["keyframes"][0]["easing"] = json!("bouncy");
+    input["motion"]["tracks"][0]["duration_frames"] = literal(90.0, "scalar");
+    input["motion"]["tracks"][0]["keyframes"][1]["frame"] = literal(90.0, "scalar");
+
+    let first = lower(&input);
+    let second = lower(&input);
+    assert_eq!(first.scene, second.scene);
+    assert_eq!(first.source_map, second.source_map);
+
+    let animation = &first.scene["artboard"]["animations"][0];
+    assert!(animation.get("interpolators").is_none());
+    let frames = keyed_x(animation)["frames"]
+        .as_array()
+        .expect("baked x frames");
+    assert!(frames.len() > 3 && frames.len() < 45, "{}", frames.len());
+    assert!(
+        frames
+            .iter()
+            .all(|frame| frame["interpolation"] == "linear")
+    );
+    assert_eq!(frames[0]["value"], 32.0);
+    assert_eq!(frames[frames.len() - 1]["frame"], 90);
+    assert_eq!(frames[frames.len() - 1]["value"], 128.0);
+    let numbers = frames
+        .iter()
+        .map(|frame| frame["frame"].as_u64().expect("whole frame"))
+        .collect::<Vec<_>>();
+    assert!(numbers.windows(2).all(|pair| pair[0] < pair[1]));
+    assert!(
+        frames
+            .iter()
+            .any(|frame| frame["value"].as_f64().expect("number") > 128.0),
+        "an underdamped spring overshoots its target"
+    );
+
+    let spring_source = first
+        .source_map
+        .entries
+        .iter()
+        .find(|entry| entry.authored_id == "bouncy")
+        .expect("spring source-map entry");
+    assert_eq!(spring_source.authored_path, "$.motion.easings[0]");
+    assert!(spring_source.runtime_names.is_empty());
+    assert_eq!(spring_source.scene_paths.len(), 2 * (frames.len() - 2));
+    assert_eq!(
+        spring_source.scene_paths[0],
+        "/artboard/animations/0/keyframes/0/frames/1"
+    );
+    for scene_path in &spring_source.scene_paths {
+        assert!(first.scene.pointer(scene_path).is_some(), "{scene_path}");
+    }
+
+    let mut looser = input.clone();
+    looser["motion"]["easings"][0]["tolerance"] = literal(0.05, "scalar");
+    let looser = lower(&looser);
+    let looser_frames = keyed_x(&looser.scene["artboard"]["animations"][0])["frames"]
+        .as_array()
+        .expect("looser frames")
+        .len();
+    assert!(looser_frames < frames.len());
+
+    assert_eq!(cubic_interpolator_count(&first.scene), 0);
+}
+
+#[test]
+fn elastic_and_spring_parameters_are_validated_at_authored_paths() {
+    let spring = |field: &str, value: Value| {
+        let mut input = document();
+        let mut easing = json!({
+            "kind": "spring",
+            "id": "bouncy",
+            "stiffness": literal(170.0, "scalar"),
+            "damping": literal(12.0, "scalar"),
+            "mass": literal(1.0, "scalar")
+        });
+        easing[field] = value;
+        input["motion"]["easings"] = json!([easing]);
+        input["motion"]["tracks"][0]["keyframes"][0]["easing"] = json!("bouncy");
+        input
+    };
+    assert_diagnostic(
+        &spring("stiffness", literal(0.0, "scalar")),
+        "invalid_spring_easing",
+        "$.motion.easings[0].stiffness",
+    );
+    assert_diagnostic(
+        &spring("damping", literal(-1.0, "scalar")),
+        "invalid_spring_easing",
+        "$.motion.easings[0].damping",
+    );
+    assert_diagnostic(
+        &spring("damping", literal(0.0, "scalar")),
+        "invalid_spring_easing",
+        "$.motion.easings[0].damping",
+    );
+    assert_diagnostic(
+        &spring("mass", literal(1.0, "scalar")),
+        "spring_not_settled",
+        "$.motion.tracks[0].keyframes[0].easing",
+    );
+    assert_diagnostic(
+        &spring("tolerance", literal(0.5, "scalar")),
+        "invalid_spring_easing",
+        "$.motion.easings[0].tolerance",
+    );
+
+    let mut long_segment = spring("mass", literal(1.0, "scalar"));
+    long_segment["motion"]["tracks"][0]["duration_frames"] = literal(20_000.0, "scalar");
+    long_segment["motion"]["tracks"][0]["keyframes"][1]["frame"] = literal(20_000.0, "scalar");
+    assert_diagnostic(
+        &long_segment,
+        "spring_segment_limit",
+        "$.motion.tracks[0].keyframes[0].easing",
+    );
+
+    let mut elastic = document();
+    elastic["motion"]["easings"] = json!([{
+        "kind": "elastic",
+        "id": "wobble",
+        "amplitude": literal(1.0, "scalar"),
+        "period": literal(0.0, "scalar")
+    }]);
+    assert_diagnostic(
+        &elastic,
+        "invalid_elastic_easing",
+        "$.motion.easings[0].period",
+    );
+
+    let mut unknown_preset = document();
+    unknown_preset["motion"]["easings"] =
+        json!([{ "kind": "preset", "id": "odd", "preset": "ease_sideways" }]);
+    assert_diagnostic(&unknown_preset, "invalid_json", "$");
+}
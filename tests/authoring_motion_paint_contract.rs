@@ -0,0 +1,342 @@
+mod support;
+
+use rive_cli::authoring::{AuthoringError, LoweredAuthoring, lower_authoring_json};
+use serde_json::{Value, json};
+use support::assert_builds;
+
+fn literal(value: f64, unit: &str) -> Value {
+    json!({ "kind": "literal", "value": value, "unit": unit })
+}
+
+fn gradient() -> Value {
+    json!({
+        "kind": "linear_gradient",
+        "start_x": literal(0.0, "px"),
+        "start_y": literal(0.0, "px"),
+        "end_x": literal(64.0, "px"),
+        "end_y": literal(0.0, "px"),
+        "stops": [
+            { "color": "#0EA5E9", "position": literal(0.0, "scalar") },
+            { "color": "#6366F1", "position": literal(1.0, "scalar") }
+        ]
+    })
+}
+
+fn document() -> Value {
+    json!({
+        "authoring_format_version": 0,
+        "artboard": {
+            "id": "paint-stage",
+            "width": { "value": 320.0, "unit": "px" },
+            "height": { "value": 200.0, "unit": "px" }
+        },
+        "visual": {
+            "nodes": [
+                {
+                    "kind": "rectangle",
+                    "id": "card",
+                    "width": literal(120.0, "px"),
+                    "height": literal(72.0, "px"),
+                    "fill": "#172554",
+                    "stroke": {
+                        "paint": "#F59E0B",
+                        "width": literal(2.0, "px"),
+                        "trim": {
+                            "start": literal(0.0, "scalar"),
+                            "end": literal(1.0, "scalar"),
+                            "mode": "sequential"
+                        }
+                    }
+                },
+                {
+                    "kind": "star",
+                    "id": "badge",
+                    "width": literal(64.0, "px"),
+                    "height": literal(64.0, "px"),
+                    "points": 5,
+                    "inner_radius": literal(0.45, "scalar"),
+                    "fill": gradient()
+                }
+            ]
+        },
+        "motion": {
+            "poses": [
+                {
+                    "id": "rest",
+                    "targets": [
+                        {
+                            "target": "card",
+                            "corner_radius": literal(0.0, "px"),
+                            "fill": "#172554",
+                            "stroke": {
+                                "paint": "#F59E0B",
+                                "width": literal(2.0, "px"),
+                                "trim": {
+                                    "start": literal(0.0, "scalar"),
+                                    "end": literal(0.25, "scalar"),
+                                    "offset": literal(0.0, "scalar")
+                                }
+                            }
+                        },
+                        {
+                            "target": "badge",
+                            "gradient": {
+                                "stops": [
+                                    { "color": "#0EA5E9", "position": literal(0.0, "scalar") },
+                                    { "color": "#6366F1" }
+                                ]
+                            }
+                        }
+                    ]
+                },
+                {
+                    "id": "lit",
+                    "targets": [
+                        {
+                            "target": "card",
+                            "corner_radius": literal(16.0, "px"),
+                            "fill": "#DC2626",
+                            "stroke": {
+                                "paint": "#FFFFFF",
+                                "width": literal(6.0, "px"),
+                                "trim": {
+                                    "start": literal(0.1, "scalar"),
+                                    "end": literal(1.0, "scalar"),
+                                    "offset": literal(0.5, "scalar")
+                                }
+                            }
+                        },
+                        {
+                            "target": "badge",
+                            "gradient": {
+                                "stops": [
+                                    { "color": "#F43F5E", "position": literal(0.4, "scalar") },
+                                    { "color": "#FACC15" }
+                                ]
+                            }
+                        }
+                    ]
+                }
+            ],
+            "tracks": [
+                {
+                    "id": "glow",
+                    "fps": 60,
+                    "duration_frames": literal(30.0, "scalar"),
+                    "keyframes": [
+                        { "frame": literal(0.0, "scalar"), "pose": "rest" },
+                        { "frame": literal(30.0, "scalar"), "pose": "lit" }
+                    ]
+                }
+            ]
+        },
+        "behavior": {}
+    })
+}
+
+fn lower(input: &Value) -> LoweredAuthoring {
+    lower_authoring_json(&input.to_string()).expect("paint motion must lower")
+}
+
+fn has_diagnostic(error: &AuthoringError, code: &str, path: &str) -> bool {
+    error
+        .diagnostics
+        .iter()
+        .any(|diagnostic| diagnostic.code == code && diagnostic.path == path)
+}
+
+fn assert_diagnostic(input: &Value, code: &str, path: &str) {
+    let error = lower_authoring_json(&input.to_string())
+        .expect_err("invalid paint motion must fail at the authored boundary");
+    assert!(
+        has_diagnostic(&error, code, path),
+        "missing {code} at {path}; diagnostics: {:#?}",
+        error.diagnostics
+    );
+}
+
+fn keyframe_group<'a>(animation: &'a Value, object: &str, property: &str) -> &'a Value {
+    animation["keyframes"]
+        .as_array()
+        .expect("animation keyframes")
+        .iter()
+        .find(|group| group["object"] == object && group["property"] == property)
+        .unwrap_or_else(|| panic!("expected keyed {object}.{property}"))
+}
+
+fn frame_values(group: &Value) -> Vec<Value> {
+    group["frames"]
+        .as_array()
+        .expect("keyframe frames")
+        .iter()
+        .map(|frame| frame["value"].clone())
+        .collect()
+}
+
+#[test]
+fn paint_pose_tracks_lower_deterministically_and_build() {
+    let input = document();
+    let first = lower(&input);
+    let second = lower(&input);
+    assert_eq!(first.scene, second.scene);
+    assert_eq!(first.source_map, second.source_map);
+
+    let animation = &first.scene["artboard"]["animations"][0];
+    let keyed = |object: &str, property: &str| {
+        frame_values(keyframe_group(
+            animation,
+            &format!("auth__paint_2dstage__{object}"),
+            property,
+        ))
+    };
+
+    assert_eq!(
+        keyed("card__geometry", "corner_radius"),
+        vec![json!(0.0), json!(16.0)]
+    );
+    assert_eq!(
+        keyed("card__color", "color"),
+        vec![json!("#172554"), json!("#DC2626")]
+    );
+    assert_eq!(
+        keyed("card__stroke_color", "color"),
+        vec![json!("#F59E0B"), json!("#FFFFFF")]
+    );
+    assert_eq!(
+        keyed("card__stroke", "thickness"),
+        vec![json!(2.0), json!(6.0)]
+    );
+    assert_eq!(
+        keyed("card__stroke_trim", "trim_start"),
+        vec![json!(0.0), json!(0.1)]
+    );
+    assert_eq!(
+        keyed("card__stroke_trim", "trim_end"),
+        vec![json!(0.25), json!(1.0)]
+    );
+    assert_eq!(
+        keyed("card__stroke_trim", "trim_offset"),
+        vec![json!(0.0), json!(0.5)]
+    );
+    assert_eq!(
+        keyed("badge__gradient_stop_0", "color"),
+        vec![json!("#0EA5E9"), json!("#F43F5E")]
+    );
+    assert_eq!(
+        keyed("badge__gradient_stop_0", "position"),
+        vec![json!(0.0), json!(0.4)]
+    );
+    assert_eq!(
+        keyed("badge__gradient_stop_1", "color"),
+        vec![json!("#6366F1"), json!("#FACC15")]
+    );
+
+    assert_builds(first.scene);
+}
+
+#[test]
+fn paint_keyframes_map_back_to_their_pose_channels() {
+    let lowered = lower(&document());
+    let keyframe_entries = lowered
+        .source_map
+        .entries
+        .iter()
+        .filter(|entry| entry.authored_path.starts_with("$.motion.poses["))
+        .collect::<Vec<_>>();
+    assert_eq!(keyframe_entries.len(), 20);
+
+    let fill = keyframe_entries
+        .iter()
+        .find(|entry| entry.authored_path == "$.motion.poses[1].targets[0].fill")
+        .expect("lit fill keyframe entry");
+    assert_eq!(fill.authored_id, "card");
+    assert!(fill.runtime_names.is_empty());
+    assert!(
+        fill.scene_paths[0].starts_with("/artboard/animations/0/keyframes/")
+            && fill.scene_paths[0].ends_with("/frames/1")
+    );
+    assert!(keyframe_entries.iter().any(|entry| {
+        entry.authored_path == "$.motion.poses[0].targets[1].gradient.stops[1].color"
+    }));
+
+    for entry in &lowered.source_map.entries {
+        for scene_path in &entry.scene_paths {
+            assert!(
+                lowered.scene.pointer(scene_path).is_some(),
+                "{} points at missing {scene_path}",
+                entry.authored_path
+            );
+        }
+    }
+}
+
+#[test]
+fn paint_diagnostics_preserve_authored_paths() {
+    let mut bad_color = document();
+    bad_color["motion"]["poses"][1]["targets"][0]["fill"] = json!("crimson");
+    assert_diagnostic(
+        &bad_color,
+        "invalid_color",
+        "$.motion.poses[1].targets[0].fill",
+    );
+
+    let mut negative_width = document();
+    negative_width["motion"]["poses"][1]["targets"][0]["stroke"]["width"] = literal(-1.0, "px");
+    assert_diagnostic(
+        &negative_width,
+        "invalid_dimension",
+        "$.motion.poses[1].targets[0].stroke.width",
+    );
+
+    let mut trim_out_of_range = document();
+    trim_out_of_range["motion"]["poses"][1]["targets"][0]["stroke"]["trim"]["end"] =
+        literal(1.5, "scalar");
+    assert_diagnostic(
+        &trim_out_of_range,
+        "invalid_ratio",
+        "$.motion.poses[1].targets[0].stroke.trim.end",
+    );
+
+    let mut solid_on_gradient = document();
+    for pose in solid_on_gradient["motion"]["poses"]
+        .as_array_mut()
+        .expect("motion poses")
+    {
+        pose["targets"][1]["fill"] = json!("#FFFFFF");
+    }
+    assert_diagnostic(
+        &solid_on_gradient,
+        "unsupported_motion_property",
+        "$.motion.poses[0].targets[1].fill",
+    );
+
+    let mut missing_stop = document();
+    for pose in missing_stop["motion"]["poses"]
+        .as_array_mut()
+        .expect("motion poses")
+    {
+        pose["targets"][1]["gradient"]["stops"]
+            .as_array_mut()
+            .expect("gradient stops")
+            .push(json!({ "color": "#000000" }));
+    }
+    assert_diagnostic(
+        &missing_stop,
+        "unsupported_motion_property",
+        "$.motion.poses[0].targets[1].gradient.stops[2].color",
+    );
+}
+
+#[test]
+fn gradient_stops_are_ambiguous_when_fill_and_stroke_are_both_gradients() {
+    let mut input = document();
+    input["visual"]["nodes"][1]["stroke"] = json!({
+        "paint": gradient(),
+        "width": literal(2.0, "px")
+    });
+    assert_diagnostic(
+        &input,
+        "ambiguous_motion_property_target",
+        "$.motion.poses[0].targets[1].gradient.stops[0].color",
+    );
+}
@@ -0,0 +1,250 @@
+mod support;
+
+use rive_cli::authoring::{LoweredAuthoring, lower_authoring_json};
+use serde_json::{Value, json};
+use support::assert_builds;
+
+fn literal(value: f64, unit: &str) -> Value {
+    json!({ "kind": "literal", "value": value, "unit": unit })
+}
+
+fn document(offset: Value) -> Value {
+    json!({
+        "authoring_format_version": 0,
+        "artboard": {
+            "id": "stage",
+            "width": { "value": 320.0, "unit": "px" },
+            "height": { "value": 120.0, "unit": "px" }
+        },
+        "visual": {
+            "nodes": [
+                {
+                    "kind": "grid",
+                    "id": "dots",
+                    "columns": 5,
+                    "rows": 1,
+                    "column_step": literal(48.0, "px"),
+                    "row_step": literal(0.0, "px"),
+                    "item": {
+                        "kind": "rectangle",
+                        "id": "dot",
+                        "width": literal(24.0, "px"),
+                        "height": literal(24.0, "px"),
+                        "fill": "#2563EB"
+                    }
+                }
+            ]
+        },
+        "motion": {
+            "poses": [
+                {
+                    "id": "hidden",
+                    "targets": [
+                        {
+                            "target": "dot",
+                            "opacity": literal(0.0, "scalar"),
+                            "transform": { "y": literal(16.0, "px") }
+                        }
+                    ]
+                },
+                {
+                    "id": "shown",
+                    "targets": [
+                        {
+                            "target": "dot",
+                            "opacity": literal(1.0, "scalar"),
+                            "transform": { "y": literal(0.0, "px") }
+                        }
+                    ]
+                }
+            ],
+            "tracks": [
+                {
+                    "id": "entrance",
+                    "fps": 60,
+                    "duration_frames": literal(20.0, "scalar"),
+                    "keyframes": [
+                        { "frame": literal(0.0, "scalar"), "pose": "hidden" },
+                        { "frame": literal(20.0, "scalar"), "pose": "shown" }
+                    ],
+                    "stagger": { "pattern": "dots", "offset": offset }
+                }
+            ]
+        },
+        "behavior": {}
+    })
+}
+
+fn step(kind: &str, frames: f64) -> Value {
+    json!({ "kind": kind, "step_frames": literal(frames, "scalar") })
+}
+
+fn lower(input: &Value) -> LoweredAuthoring {
+    lower_authoring_json(&input.to_string()).expect("staggered motion must lower")
+}
+
+fn assert_diagnostic(input: &Value, code: &str, path: &str) {
+    let error = lower_authoring_json(&input.to_string())
+        .expect_err("invalid staggered motion must fail at the authored boundary");
+    assert!(
+        error
+            .diagnostics
+            .iter()
+            .any(|diagnostic| diagnostic.code == code && diagnostic.path == path),
+        "missing {code} at {path}; diagnostics: {:#?}",
+        error.diagnostics
+    );
+}
+
+/// First frame of each copy's opacity group, in copy order.
+fn opacity_starts(lowered: &LoweredAuthoring) -> Vec<(String, u64)> {
+    lowered.scene["artboard"]["animations"][0]["keyframes"]
+        .as_array()
+        .expect("keyframe groups")
+        .iter()
+        .filter(|group| group["property"] == "opacity")
+        .map(|group| {
+            (
+                group["object"].as_str().expect("object").to_string(),
+                group["frames"][0]["frame"].as_u64().expect("frame"),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn staggered_tracks_replay_poses_on_every_copy_and_build() {
+    let input = document(step("linear", 4.0));
+    let first = lower(&input);
+    let second = lower(&input);
+    assert_eq!(first.scene, second.scene);
+    assert_eq!(first.source_map, second.source_map);
+
+    let animation = &first.scene["artboard"]["animations"][0];
+    assert_eq!(animation["duration"], 36);
+    let starts = opacity_starts(&first);
+    assert_eq!(
+        starts,
+        (0..5)
+            .map(|column| (
+                format!("auth__stage__dots__r0c{column}__dot__shape"),
+                4 * column
+            ))
+            .collect::<Vec<_>>()
+    );
+    let groups = animation["keyframes"].as_array().expect("keyframe groups");
+    assert_eq!(groups.len(), 10);
+    let last = &groups[9]["frames"];
+    assert_eq!(last[0]["frame"], 16);
+    assert_eq!(last[1]["frame"], 36);
+
+    assert_builds(first.scene);
+}
+
+#[test]
+fn stagger_orders_offset_copies_by_rank() {
+    let offsets = |offset: Value| {
+        opacity_starts(&lower(&document(offset)))
+            .into_iter()
+            .map(|(_, frame)| frame)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(offsets(step("reverse", 2.0)), [8, 6, 4, 2, 0]);
+    assert_eq!(offsets(step("from_center", 3.0)), [6, 3, 0, 3, 6]);
+    assert_eq!(
+        offsets(json!({
+            "kind": "expression",
+            "frames": {
+                "kind": "multiply",
+                "value": { "kind": "parameter", "name": "copy_index" },
+                "factor": 5.0
+            }
+        })),
+        [0, 5, 10, 15, 20]
+    );
+}
+
+#[test]
+fn staggered_keyframes_map_back_to_the_stagger_rule() {
+    let lowered = lower(&document(step("linear", 4.0)));
+    let entries = lowered
+        .source_map
+        .entries
+        .iter()
+        .filter(|entry| entry.authored_path == "$.motion.tracks[0].stagger")
+        .collect::<Vec<_>>();
+    assert_eq!(entries.len(), 5 * 2 * 2);
+
+    let entry = entries
+        .iter()
+        .find(|entry| {
+            entry.authored_id == "dots/r0c3/dot"
+                && entry.definition_path.as_deref() == Some("$.motion.poses[1].targets[0].opacity")
+        })
+        .expect("shown opacity on the fourth copy");
+    let frame = lowered
+        .scene
+        .pointer(&entry.scene_paths[0])
+        .expect("scene path resolves");
+    assert_eq!(frame["frame"], 32);
+    assert_eq!(frame["value"], 1.0);
+}
+
+#[test]
+fn stagger_diagnostics_preserve_authored_paths() {
+    let mut unknown = document(step("linear", 4.0));
+    unknown["motion"]["tracks"][0]["stagger"]["pattern"] = json!("missing");
+    assert_diagnostic(
+        &unknown,
+        "unknown_stagger_pattern",
+        "$.motion.tracks[0].stagger.pattern",
+    );
+
+    let mut not_a_pattern = document(step("linear", 4.0));
+    not_a_pattern["visual"]["nodes"]
+        .as_array_mut()
+        .expect("nodes")
+        .push(json!({
+            "kind": "rectangle",
+            "id": "solo",
+            "width": literal(8.0, "px"),
+            "height": literal(8.0, "px"),
+            "fill": "#000000"
+        }));
+    not_a_pattern["motion"]["tracks"][0]["stagger"]["pattern"] = json!("solo");
+    assert_diagnostic(
+        &not_a_pattern,
+        "unknown_stagger_pattern",
+        "$.motion.tracks[0].stagger.pattern",
+    );
+
+    assert_diagnostic(
+        &document(step("linear", 1.5)),
+        "invalid_stagger_offset",
+        "$.motion.tracks[0].stagger.offset.step_frames",
+    );
+
+    let mut shadowed = document(json!({
+        "kind": "expression",
+        "frames": { "kind": "parameter", "name": "copy_index" }
+    }));
+    shadowed["parameters"] = json!({ "copy_index": { "value": 2.0, "unit": "scalar" } });
+    assert_diagnostic(&shadowed, "invalid_parameter", "$.parameters.copy_index");
+
+    let mut outside_copy = document(step("linear", 4.0));
+    outside_copy["motion"]["poses"][0]["targets"][0]["target"] = json!("dots");
+    assert_diagnostic(
+        &outside_copy,
+        "unknown_motion_target",
+        "$.motion.poses[0].targets[0].target",
+    );
+
+    let mut over_budget = document(step("linear", 0.0));
+    over_budget["visual"]["nodes"][0]["columns"] = json!(100);
+    over_budget["visual"]["nodes"][0]["rows"] = json!(30);
+    assert_diagnostic(
+        &over_budget,
+        "motion_keyframe_expansion_limit",
+        "$.motion.tracks[0].stagger",
+    );
+}
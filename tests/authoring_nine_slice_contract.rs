@@ -0,0 +1,180 @@
+use std::path::Path;
+
+use rive_cli::{
+    authoring::{authoring_schema, lower_authoring_json},
+    builder::{SceneSpec, build_scene},
+    objects::core::{PropertyValue, property_keys, type_keys},
+};
+use serde_json::{Value, json};
+
+fn literal(value: f64, unit: &str) -> Value {
+    json!({ "kind": "literal", "value": value, "unit": unit })
+}
+
+fn pose(id: &str, scale_x: f64) -> Value {
+    json!({
+        "id": id,
+        "targets": [{
+            "target": "frame",
+            "transform": { "scale_x": literal(scale_x, "scalar") }
+        }]
+    })
+}
+
+fn document() -> Value {
+    json!({
+        "authoring_format_version": 0,
+        "artboard": {
+            "id": "ninestage",
+            "width": { "value": 768.0, "unit": "px" },
+            "height": { "value": 256.0, "unit": "px" }
+        },
+        "image_assets": {
+            "panel": "assets/textures/aurora.png"
+        },
+        "visual": {
+            "nodes": [{
+                "kind": "nine_slice",
+                "id": "frame",
+                "asset": "panel",
+                "insets": {
+                    "left": literal(32.0, "px"),
+                    "top": literal(48.0, "px"),
+                    "right": literal(32.0, "px"),
+                    "bottom": literal(48.0, "px")
+                },
+                "edges": "repeat",
+                "transform": {
+                    "x": literal(384.0, "px"),
+                    "y": literal(128.0, "px")
+                }
+            }]
+        },
+        "motion": {
+            "poses": [pose("narrow", 1.0), pose("wide", 2.5)],
+            "tracks": [{
+                "id": "grow",
+                "fps": 60,
+                "duration_frames": literal(60.0, "scalar"),
+                "keyframes": [
+                    { "frame": literal(0.0, "scalar"), "pose": "narrow", "interpolation": "linear" },
+                    { "frame": literal(60.0, "scalar"), "pose": "wide", "interpolation": "linear" }
+                ]
+            }]
+        },
+        "behavior": {}
+    })
+}
+
+fn assert_diagnostic(input: &Value, code: &str, path: &str) {
+    let error = lower_authoring_json(&input.to_string())
+        .expect_err("invalid nine-slice must fail at the authored boundary");
+    assert!(
+        error
+            .diagnostics
+            .iter()
+            .any(|diagnostic| diagnostic.code == code && diagnostic.path == path),
+        "missing {code} at {path}; diagnostics: {:#?}",
+        error.diagnostics
+    );
+}
+
+fn float_property(object: &dyn rive_cli::objects::core::RiveObject, key: u16) -> Option<f32> {
+    object
+        .properties()
+        .into_iter()
+        .find_map(|property| match property.value {
+            PropertyValue::Float(value) if property.key == key => Some(value),
+            _ => None,
+        })
+}
+
+#[test]
+fn nine_slice_lowers_to_one_scene_object_that_carries_its_transform() {
+    let input = document().to_string();
+    let first = lower_authoring_json(&input).expect("first nine-slice lowering");
+    let second = lower_authoring_json(&input).expect("second nine-slice lowering");
+    assert_eq!(first.scene, second.scene);
+    assert_eq!(first.source_map, second.source_map);
+
+    let frame = &first.scene["artboard"]["children"][1];
+    assert_eq!(frame["type"], "nine_slice");
+    assert_eq!(frame["name"], "auth__ninestage__frame__nine_slice");
+    assert_eq!(frame["asset"], "auth__ninestage__panel__image_asset");
+    assert_eq!(frame["left"], 32.0);
+    assert_eq!(frame["top"], 48.0);
+    assert_eq!(frame["edges"], "repeat");
+    assert_eq!(frame["center"], "stretch");
+    assert_eq!(frame["x"], 384.0);
+    assert_eq!(frame["scale_x"], 1.0);
+
+    let entry = first
+        .source_map
+        .entries
+        .iter()
+        .find(|entry| entry.authored_id == "frame")
+        .expect("nine-slice source-map entry");
+    assert_eq!(
+        entry.runtime_names,
+        vec!["auth__ninestage__frame__nine_slice"]
+    );
+    assert_eq!(entry.scene_paths, vec!["/artboard/children/1"]);
+
+    let scale = first.scene["artboard"]["animations"][0]["keyframes"]
+        .as_array()
+        .expect("typed animation keyframes")
+        .iter()
+        .find(|group| group["property"] == "scale_x")
+        .expect("a scale_x pose must key the nine-slice");
+    assert_eq!(scale["object"], "auth__ninestage__frame__nine_slice");
+    assert_eq!(scale["frames"][1]["value"], 2.5);
+
+    let scene: SceneSpec =
+        serde_json::from_value(first.scene).expect("nine-slice SceneSpec must deserialize");
+    let objects = build_scene(&scene, Some(Path::new(env!("CARGO_MANIFEST_DIR"))))
+        .expect("the embedded image must size the nine-slice");
+    let offsets = objects
+        .iter()
+        .filter(|object| {
+            object.type_key() == type_keys::AXIS_X || object.type_key() == type_keys::AXIS_Y
+        })
+        .map(|object| float_property(object.as_ref(), property_keys::AXIS_OFFSET))
+        .collect::<Vec<_>>();
+    // aurora.png is 256px square.
+    assert_eq!(
+        offsets,
+        vec![Some(0.125), Some(0.875), Some(0.1875), Some(0.8125)]
+    );
+    let repeated = objects
+        .iter()
+        .filter(|object| object.type_key() == type_keys::NSLICER_TILE_MODE)
+        .count();
+    assert_eq!(repeated, 4, "only the four edges repeat");
+}
+
+#[test]
+fn nine_slice_rejects_negative_insets_and_unknown_assets() {
+    let mut negative = document();
+    negative["visual"]["nodes"][0]["insets"]["right"] = literal(-1.0, "px");
+    assert_diagnostic(
+        &negative,
+        "invalid_dimension",
+        "$.visual.nodes[0].insets.right",
+    );
+
+    let mut unknown = document();
+    unknown["visual"]["nodes"][0]["asset"] = json!("missing");
+    assert_diagnostic(&unknown, "unknown_image_asset", "$.visual.nodes[0].asset");
+
+    let mut tile_mode = document();
+    tile_mode["visual"]["nodes"][0]["center"] = json!("mirror");
+    assert_diagnostic(&tile_mode, "invalid_json", "$");
+}
+
+#[test]
+fn nine_slice_is_published_in_the_authoring_schema() {
+    let schema = serde_json::to_string(&authoring_schema()).expect("serialize schema");
+    assert!(schema.contains("\"nine_slice\""));
+    assert!(schema.contains("NineSliceInsetsSpec"));
+    assert!(schema.contains("NineSliceTileMode"));
+}
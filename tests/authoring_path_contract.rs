@@ -0,0 +1,215 @@
+mod support;
+
+use rive_cli::authoring::{LoweredAuthoring, lower_authoring_json};
+use serde_json::{Value, json};
+use support::assert_builds;
+
+fn literal(value: f64, unit: &str) -> Value {
+    json!({ "kind": "literal", "value": value, "unit": unit })
+}
+
+fn px(value: f64) -> Value {
+    literal(value, "px")
+}
+
+fn document(path: Value) -> Value {
+    json!({
+        "authoring_format_version": 0,
+        "artboard": {
+            "id": "icons",
+            "width": { "value": 200.0, "unit": "px" },
+            "height": { "value": 200.0, "unit": "px" }
+        },
+        "visual": {
+            "nodes": [
+                {
+                    "kind": "path",
+                    "id": "badge",
+                    "path": path,
+                    "fill": "#F97316",
+                    "stroke": {
+                        "paint": "#7C2D12",
+                        "width": px(2.0),
+                        "trim": {
+                            "start": literal(0.0, "scalar"),
+                            "end": literal(0.5, "scalar"),
+                            "mode": "sequential"
+                        }
+                    },
+                    "transform": { "x": px(40.0), "y": px(40.0) }
+                }
+            ]
+        },
+        "motion": {},
+        "behavior": {}
+    })
+}
+
+/// A rounded tab: straight top edge, quarter arc, quadratic hook, closed.
+const SVG: &str = "M0 0H80A20 20 0 0 1 100 20Q100 60 60 60L0 60Z";
+
+fn segments() -> Value {
+    json!([
+        { "kind": "move", "x": px(0.0), "y": px(0.0) },
+        { "kind": "line", "x": px(80.0), "y": px(0.0) },
+        {
+            "kind": "arc",
+            "rx": px(20.0),
+            "ry": px(20.0),
+            "sweep": true,
+            "x": px(100.0),
+            "y": px(20.0)
+        },
+        { "kind": "quad", "x1": px(100.0), "y1": px(60.0), "x": px(60.0), "y": px(60.0) },
+        { "kind": "line", "x": px(0.0), "y": px(60.0) },
+        { "kind": "close" }
+    ])
+}
+
+fn lower(input: &Value) -> LoweredAuthoring {
+    lower_authoring_json(&input.to_string()).expect("path nodes must lower")
+}
+
+fn assert_diagnostic(input: &Value, code: &str, path: &str) {
+    let error = lower_authoring_json(&input.to_string())
+        .expect_err("invalid path data must fail at the authored boundary");
+    assert!(
+        error
+            .diagnostics
+            .iter()
+            .any(|diagnostic| diagnostic.code == code && diagnostic.path == path),
+        "missing {code} at {path}; diagnostics: {:#?}",
+        error.diagnostics
+    );
+}
+
+#[test]
+fn svg_path_lowers_to_points_path_with_fill_stroke_and_trim() {
+    let input = document(json!(SVG));
+    let first = lower(&input);
+    let second = lower(&input);
+    assert_eq!(first.scene, second.scene);
+    assert_eq!(first.source_map, second.source_map);
+
+    let shape = &first.scene["artboard"]["children"][0];
+    assert_eq!(shape["name"], "auth__icons__badge__shape");
+    let children = shape["children"].as_array().expect("shape children");
+    let types = children
+        .iter()
+        .map(|child| child["type"].as_str().expect("type"))
+        .collect::<Vec<_>>();
+    assert_eq!(types, ["points_path", "fill", "stroke"]);
+
+    let contour = &children[0];
+    assert_eq!(contour["is_closed"], true);
+    let vertices = contour["children"].as_array().expect("vertices");
+    let kinds = vertices
+        .iter()
+        .map(|vertex| vertex["type"].as_str().expect("type"))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        kinds,
+        [
+            "straight_vertex",
+            "cubic_detached_vertex",
+            "cubic_detached_vertex",
+            "cubic_detached_vertex",
+            "straight_vertex"
+        ]
+    );
+    assert_eq!(vertices[1]["x"], 80.0);
+    assert_eq!(vertices[1]["in_distance"], 0.0);
+    assert_eq!(vertices[2]["x"], 100.0);
+    assert_eq!(vertices[2]["y"], 20.0);
+    assert_eq!(children[2]["children"][1]["type"], "trim_path");
+
+    let entry = first
+        .source_map
+        .entries
+        .iter()
+        .find(|entry| entry.authored_id == "badge")
+        .expect("path source map entry");
+    assert_eq!(
+        entry.runtime_names[..3],
+        [
+            "auth__icons__badge__shape",
+            "auth__icons__badge__path_0",
+            "auth__icons__badge__path_0_vertex_0"
+        ]
+    );
+    for scene_path in &entry.scene_paths {
+        assert!(
+            first.scene.pointer(scene_path).is_some(),
+            "{scene_path} must resolve"
+        );
+    }
+
+    assert_builds(first.scene);
+}
+
+#[test]
+fn typed_segments_match_the_equivalent_svg_string() {
+    let svg = lower(&document(json!(SVG)));
+    let typed = lower(&document(segments()));
+    assert_eq!(svg.scene, typed.scene);
+
+    let mut parameterized = document(segments());
+    parameterized["parameters"] = json!({ "tab": { "value": 80.0, "unit": "px" } });
+    parameterized["visual"]["nodes"][0]["path"][1]["x"] =
+        json!({ "kind": "parameter", "name": "tab" });
+    assert_eq!(lower(&parameterized).scene, svg.scene);
+}
+
+#[test]
+fn multiple_contours_become_sibling_points_paths() {
+    let lowered = lower(&document(json!("M0 0L40 0L40 40ZM60 0C80 0 100 20 100 40")));
+    let children = lowered.scene["artboard"]["children"][0]["children"]
+        .as_array()
+        .expect("shape children");
+    assert_eq!(children[0]["is_closed"], true);
+    assert_eq!(children[1]["is_closed"], false);
+    assert_eq!(children[1]["name"], "auth__icons__badge__path_1");
+    assert_eq!(children[2]["type"], "fill");
+    assert_builds(lowered.scene);
+}
+
+#[test]
+fn path_diagnostics_preserve_authored_paths() {
+    assert_diagnostic(
+        &document(json!("M0 0L10")),
+        "invalid_path_data",
+        "$.visual.nodes[0].path",
+    );
+    assert_diagnostic(
+        &document(json!("M0 0L10 0M5 5")),
+        "invalid_path_data",
+        "$.visual.nodes[0].path",
+    );
+
+    let mut missing_move = segments();
+    missing_move.as_array_mut().expect("segments").remove(0);
+    assert_diagnostic(
+        &document(missing_move),
+        "invalid_path_data",
+        "$.visual.nodes[0].path[0]",
+    );
+
+    let mut wrong_unit = segments();
+    wrong_unit[1]["x"] = literal(1.0, "degrees");
+    assert_diagnostic(
+        &document(wrong_unit),
+        "unit_mismatch",
+        "$.visual.nodes[0].path[1].x",
+    );
+
+    let mut lone_move = segments();
+    lone_move
+        .as_array_mut()
+        .expect("segments")
+        .push(json!({ "kind": "move", "x": px(5.0), "y": px(5.0) }));
+    assert_diagnostic(
+        &document(lone_move),
+        "invalid_path_data",
+        "$.visual.nodes[0].path[6]",
+    );
+}
@@ -0,0 +1,207 @@
+mod support;
+
+use rive_cli::authoring::{LoweredAuthoring, lower_authoring_json};
+use serde_json::{Value, json};
+use support::assert_builds;
+
+const SQUARE: &str = "M-40 -40H40V40H-40Z";
+
+fn literal(value: f64, unit: &str) -> Value {
+    json!({ "kind": "literal", "value": value, "unit": unit })
+}
+
+fn px(value: f64) -> Value {
+    literal(value, "px")
+}
+
+fn pose(id: &str, outline: Value) -> Value {
+    json!({ "id": id, "targets": [{ "target": "blob", "outline": outline }] })
+}
+
+fn document() -> Value {
+    json!({
+        "authoring_format_version": 0,
+        "artboard": {
+            "id": "morph",
+            "width": { "value": 200.0, "unit": "px" },
+            "height": { "value": 200.0, "unit": "px" }
+        },
+        "visual": {
+            "nodes": [
+                {
+                    "kind": "path",
+                    "id": "blob",
+                    "path": SQUARE,
+                    "fill": "#2563EB",
+                    "transform": { "x": px(100.0), "y": px(100.0) }
+                },
+                {
+                    "kind": "rectangle",
+                    "id": "card",
+                    "width": px(40.0),
+                    "height": px(40.0),
+                    "fill": "#F97316"
+                }
+            ]
+        },
+        "motion": {
+            "poses": [
+                pose("square", json!(SQUARE)),
+                pose("round", json!({ "kind": "ellipse", "width": px(80.0), "height": px(80.0) })),
+                pose(
+                    "spiky",
+                    json!({
+                        "kind": "star",
+                        "width": px(80.0),
+                        "height": px(80.0),
+                        "points": 5,
+                        "inner_radius": literal(0.5, "scalar")
+                    })
+                )
+            ],
+            "tracks": [
+                {
+                    "id": "morph",
+                    "fps": 60,
+                    "duration_frames": literal(60.0, "scalar"),
+                    "keyframes": [
+                        { "frame": literal(0.0, "scalar"), "pose": "square" },
+                        { "frame": literal(30.0, "scalar"), "pose": "round" },
+                        { "frame": literal(60.0, "scalar"), "pose": "spiky" }
+                    ]
+                }
+            ]
+        },
+        "behavior": {}
+    })
+}
+
+fn lower(input: &Value) -> LoweredAuthoring {
+    lower_authoring_json(&input.to_string()).expect("path morphs must lower")
+}
+
+fn assert_diagnostic(input: &Value, code: &str, path: &str) {
+    let error = lower_authoring_json(&input.to_string())
+        .expect_err("impossible morphs must fail at the authored boundary");
+    assert!(
+        error
+            .diagnostics
+            .iter()
+            .any(|diagnostic| diagnostic.code == code && diagnostic.path == path),
+        "missing {code} at {path}; diagnostics: {:#?}",
+        error.diagnostics
+    );
+}
+
+fn frames(animation: &Value, object: &str, property: &str) -> Vec<f64> {
+    animation["keyframes"]
+        .as_array()
+        .expect("animation keyframes")
+        .iter()
+        .find(|group| group["object"] == object && group["property"] == property)
+        .unwrap_or_else(|| panic!("{object}.{property} must be keyed"))["frames"]
+        .as_array()
+        .expect("frames")
+        .iter()
+        .map(|frame| frame["value"].as_f64().expect("numeric keyframe"))
+        .collect()
+}
+
+#[test]
+fn outlines_resample_to_a_shared_vertex_count_with_synced_keyframes() {
+    let input = document();
+    let first = lower(&input);
+    let second = lower(&input);
+    assert_eq!(first.scene, second.scene);
+    assert_eq!(first.source_map, second.source_map);
+
+    // The five-point star needs ten vertices, so the square resamples too.
+    let contour = &first.scene["artboard"]["children"][0]["children"][0];
+    let vertices = contour["children"].as_array().expect("vertices");
+    assert_eq!(vertices.len(), 10);
+    assert!(
+        vertices
+            .iter()
+            .all(|vertex| vertex["type"] == "cubic_detached_vertex")
+    );
+
+    let animation = &first.scene["artboard"]["animations"][0];
+    for (index, vertex) in vertices.iter().enumerate() {
+        let name = format!("auth__morph__blob__path_0_vertex_{index}");
+        assert_eq!(vertex["name"], name.as_str());
+        for property in [
+            "x",
+            "y",
+            "in_rotation",
+            "in_distance",
+            "out_rotation",
+            "out_distance",
+        ] {
+            let values = frames(animation, &name, property);
+            assert_eq!(values.len(), 3, "{name}.{property}");
+            // The square pose is the rest outline, so it keys the rest values.
+            let rest = vertex[property].as_f64().expect("rest value");
+            assert!(
+                (values[0] - rest).abs() < 1e-9,
+                "{name}.{property} {values:?} {rest}"
+            );
+        }
+        let x = frames(animation, &name, "x")[1];
+        let y = frames(animation, &name, "y")[1];
+        let radius = (x * x + y * y).sqrt();
+        assert!((radius - 40.0).abs() < 0.5, "{name} lies off the circle");
+    }
+
+    let entry = first
+        .source_map
+        .entries
+        .iter()
+        .find(|entry| entry.authored_path == "$.motion.poses[1].targets[0].outline")
+        .expect("outline keyframes map back to the pose");
+    assert_eq!(entry.authored_id, "blob");
+
+    assert_builds(first.scene);
+}
+
+#[test]
+fn morph_topology_and_targets_are_checked_at_the_outline() {
+    let mut two_contours = document();
+    two_contours["motion"]["poses"][1]["targets"][0]["outline"] =
+        json!("M0 0L10 0L10 10ZM20 20L30 20L30 30Z");
+    assert_diagnostic(
+        &two_contours,
+        "morph_topology_mismatch",
+        "$.motion.poses[1].targets[0].outline",
+    );
+
+    let mut open = document();
+    open["motion"]["poses"][2]["targets"][0]["outline"] = json!("M0 0L10 0L10 10");
+    assert_diagnostic(
+        &open,
+        "morph_topology_mismatch",
+        "$.motion.poses[2].targets[0].outline",
+    );
+
+    let mut too_many = document();
+    too_many["motion"]["poses"][2]["targets"][0]["outline"] = json!({
+        "kind": "polygon",
+        "width": px(80.0),
+        "height": px(80.0),
+        "points": 300
+    });
+    assert_diagnostic(
+        &too_many,
+        "morph_vertex_limit",
+        "$.motion.poses[2].targets[0].outline.points",
+    );
+
+    let mut not_a_path = document();
+    for pose in not_a_path["motion"]["poses"].as_array_mut().expect("poses") {
+        pose["targets"][0]["target"] = json!("card");
+    }
+    assert_diagnostic(
+        &not_a_path,
+        "unsupported_motion_property",
+        "$.motion.poses[0].targets[0].outline",
+    );
+}
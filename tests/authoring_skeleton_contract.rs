@@ -0,0 +1,281 @@
+mod support;
+
+use rive_cli::authoring::{LoweredAuthoring, lower_authoring_json};
+use serde_json::{Value, json};
+use support::assert_builds;
+
+fn literal(value: f64, unit: &str) -> Value {
+    json!({ "kind": "literal", "value": value, "unit": unit })
+}
+
+fn px(value: f64) -> Value {
+    literal(value, "px")
+}
+
+fn degrees(value: f64) -> Value {
+    literal(value, "degrees")
+}
+
+fn document() -> Value {
+    json!({
+        "authoring_format_version": 0,
+        "artboard": {
+            "id": "puppet",
+            "width": { "value": 240.0, "unit": "px" },
+            "height": { "value": 200.0, "unit": "px" }
+        },
+        "visual": {
+            "nodes": [
+                {
+                    "kind": "ellipse",
+                    "id": "handle",
+                    "width": px(12.0),
+                    "height": px(12.0),
+                    "fill": "#EF4444",
+                    "transform": { "x": px(180.0), "y": px(60.0) }
+                },
+                {
+                    "kind": "skeleton",
+                    "id": "rig",
+                    "transform": { "x": px(40.0), "y": px(100.0) },
+                    "chains": [
+                        {
+                            "x": px(0.0),
+                            "y": px(0.0),
+                            "bones": [
+                                { "id": "upper", "length": px(60.0) },
+                                { "id": "lower", "length": px(50.0), "angle": degrees(30.0) }
+                            ]
+                        },
+                        {
+                            "parent": "upper",
+                            "bones": [
+                                { "id": "thumb", "length": px(20.0), "angle": degrees(-45.0) }
+                            ]
+                        }
+                    ],
+                    "bindings": [
+                        { "kind": "rigid", "node": "hand", "bone": "lower" },
+                        { "kind": "skinned", "node": "arm", "bones": ["upper", "lower"] }
+                    ],
+                    "ik": [
+                        { "id": "reach", "bone": "lower", "target": "handle" }
+                    ],
+                    "children": [
+                        {
+                            "kind": "path",
+                            "id": "arm",
+                            "path": "M0 -10L110 -10L110 10L0 10Z",
+                            "fill": "#2563EB"
+                        },
+                        {
+                            "kind": "ellipse",
+                            "id": "hand",
+                            "width": px(16.0),
+                            "height": px(16.0),
+                            "fill": "#F97316",
+                            "transform": { "x": px(50.0) }
+                        }
+                    ]
+                }
+            ]
+        },
+        "motion": {
+            "poses": [
+                {
+                    "id": "rest",
+                    "targets": [{ "target": "rig/upper", "transform": { "rotation": degrees(0.0) } }]
+                },
+                {
+                    "id": "raised",
+                    "targets": [{ "target": "rig/upper", "transform": { "rotation": degrees(-40.0) } }]
+                }
+            ],
+            "tracks": [
+                {
+                    "id": "wave",
+                    "fps": 60,
+                    "duration_frames": literal(30.0, "scalar"),
+                    "keyframes": [
+                        { "frame": literal(0.0, "scalar"), "pose": "rest" },
+                        { "frame": literal(30.0, "scalar"), "pose": "raised" }
+                    ]
+                }
+            ]
+        },
+        "behavior": {}
+    })
+}
+
+fn lower(input: &Value) -> LoweredAuthoring {
+    lower_authoring_json(&input.to_string()).expect("skeletons must lower")
+}
+
+fn assert_diagnostic(input: &Value, code: &str, path: &str) {
+    let error = lower_authoring_json(&input.to_string())
+        .expect_err("invalid skeletons must fail at the authored boundary");
+    assert!(
+        error
+            .diagnostics
+            .iter()
+            .any(|diagnostic| diagnostic.code == code && diagnostic.path == path),
+        "missing {code} at {path}; diagnostics: {:#?}",
+        error.diagnostics
+    );
+}
+
+#[test]
+fn skeleton_lowers_bone_chains_with_bound_children_and_ik() {
+    let input = document();
+    let first = lower(&input);
+    let second = lower(&input);
+    assert_eq!(first.scene, second.scene);
+    assert_eq!(first.source_map, second.source_map);
+
+    let rig = &first.scene["artboard"]["children"][1];
+    assert_eq!(rig["type"], "node");
+    assert_eq!(rig["name"], "auth__puppet__rig__skeleton");
+    assert_eq!(rig["x"], 40.0);
+
+    let upper = &rig["children"][0];
+    assert_eq!(upper["type"], "root_bone");
+    assert_eq!(upper["name"], "auth__puppet__rig__upper__bone");
+    assert_eq!(upper["length"], 60.0);
+
+    // The chain continues first, then the chain branching from its tip.
+    let lower_bone = &upper["children"][0];
+    assert_eq!(lower_bone["type"], "bone");
+    assert_eq!(lower_bone["name"], "auth__puppet__rig__lower__bone");
+    let angle = lower_bone["rotation"].as_f64().expect("rest angle");
+    assert!((angle - 30_f64.to_radians()).abs() < 1e-9);
+    assert_eq!(
+        upper["children"][1]["name"],
+        "auth__puppet__rig__thumb__bone"
+    );
+
+    let hand = &lower_bone["children"][0];
+    assert_eq!(hand["name"], "auth__puppet__rig__hand__shape");
+    assert_eq!(hand["x"], 50.0);
+    let ik = &lower_bone["children"][1];
+    assert_eq!(ik["type"], "ik_constraint");
+    assert_eq!(ik["name"], "auth__puppet__rig__reach__ik");
+    assert_eq!(ik["target"], "auth__puppet__handle__shape");
+    assert_eq!(ik["parent_bone_count"], 1);
+
+    // Skinned paths stay beside the bones and bind every contour.
+    let arm = &rig["children"][1];
+    assert_eq!(arm["name"], "auth__puppet__rig__arm__shape");
+    assert_eq!(
+        arm["children"][0]["skin"]["bones"],
+        json!([
+            "auth__puppet__rig__upper__bone",
+            "auth__puppet__rig__lower__bone"
+        ])
+    );
+    assert_eq!(rig["children"].as_array().map(Vec::len), Some(2));
+
+    for (authored_id, authored_path) in [
+        ("rig/lower", "$.visual.nodes[1].chains[0].bones[1]"),
+        ("rig/thumb", "$.visual.nodes[1].chains[1].bones[0]"),
+        ("rig/reach", "$.visual.nodes[1].ik[0]"),
+        ("rig/hand", "$.visual.nodes[1].children[1]"),
+    ] {
+        let entry = first
+            .source_map
+            .entries
+            .iter()
+            .find(|entry| entry.authored_id == authored_id)
+            .unwrap_or_else(|| panic!("{authored_id} source map entry"));
+        assert_eq!(entry.authored_path, authored_path);
+    }
+    for entry in &first.source_map.entries {
+        for scene_path in &entry.scene_paths {
+            assert!(
+                first.scene.pointer(scene_path).is_some(),
+                "{scene_path} must resolve"
+            );
+        }
+    }
+
+    assert_builds(first.scene);
+}
+
+#[test]
+fn poses_turn_bones_by_their_authored_id() {
+    let lowered = lower(&document());
+    let animation = &lowered.scene["artboard"]["animations"][0];
+    let group = animation["keyframes"]
+        .as_array()
+        .expect("animation keyframes")
+        .iter()
+        .find(|group| {
+            group["object"] == "auth__puppet__rig__upper__bone" && group["property"] == "rotation"
+        })
+        .expect("bone rotation must be keyed");
+    let raised = group["frames"][1]["value"].as_f64().expect("numeric frame");
+    assert!((raised - (-40_f64).to_radians()).abs() < 1e-9);
+    assert_builds(lowered.scene);
+}
+
+#[test]
+fn skeleton_references_are_checked_at_the_authored_path() {
+    let mut zero_length = document();
+    zero_length["visual"]["nodes"][1]["chains"][0]["bones"][0]["length"] = px(0.0);
+    assert_diagnostic(
+        &zero_length,
+        "invalid_dimension",
+        "$.visual.nodes[1].chains[0].bones[0].length",
+    );
+
+    let mut duplicate = document();
+    duplicate["visual"]["nodes"][1]["chains"][0]["bones"][1]["id"] = json!("hand");
+    assert_diagnostic(
+        &duplicate,
+        "duplicate_id",
+        "$.visual.nodes[1].chains[0].bones[1].id",
+    );
+
+    let mut both_origins = document();
+    both_origins["visual"]["nodes"][1]["chains"][1]["x"] = px(10.0);
+    assert_diagnostic(
+        &both_origins,
+        "invalid_bone_chain",
+        "$.visual.nodes[1].chains[1].parent",
+    );
+
+    let mut unknown_bone = document();
+    unknown_bone["visual"]["nodes"][1]["bindings"][0]["bone"] = json!("wrist");
+    assert_diagnostic(
+        &unknown_bone,
+        "unknown_bone",
+        "$.visual.nodes[1].bindings[0].bone",
+    );
+
+    let mut skinned_ellipse = document();
+    skinned_ellipse["visual"]["nodes"][1]["bindings"] = json!([
+        { "kind": "skinned", "node": "hand", "bones": ["upper"] }
+    ]);
+    assert_diagnostic(
+        &skinned_ellipse,
+        "invalid_skin_binding",
+        "$.visual.nodes[1].bindings[0].node",
+    );
+
+    let mut too_many_bones = document();
+    too_many_bones["visual"]["nodes"][1]["ik"][0]["bone_count"] = json!(3);
+    assert_diagnostic(
+        &too_many_bones,
+        "invalid_bone_count",
+        "$.visual.nodes[1].ik[0].bone_count",
+    );
+
+    // Targets resolve in file order, so a later node is not eligible.
+    let mut later_target = document();
+    let handle = later_target["visual"]["nodes"][0].clone();
+    later_target["visual"]["nodes"] = json!([later_target["visual"]["nodes"][1], handle]);
+    assert_diagnostic(
+        &later_target,
+        "unknown_ik_target",
+        "$.visual.nodes[0].ik[0].target",
+    );
+}
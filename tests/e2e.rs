@@ -4461,6 +4461,83 @@ fn test_generate_nslicer() {
     let _ = generate_and_validate_output("nslicer", "coverage");
 }
 
+#[test]
+fn test_generate_nine_slice_cuts_axes_from_the_embedded_image_size() {
+    // aurora.png is 256px square, so 48px insets cut at 48/256 and 208/256.
+    assert_generate_validate_inspect(
+        "nine_slice",
+        &[
+            "name(4) string(\"panel_nslicer\")",
+            "offset(675) float(0.1875)",
+            "offset(675) float(0.8125)",
+            "normalized(676) uint(1)",
+        ],
+    );
+}
+
+#[test]
+fn test_nine_slice_corners_keep_their_pixels_while_the_frame_grows() {
+    let (riv, _guard) = generate_and_validate_output("nine_slice", "render");
+    let out = std::env::temp_dir().join(format!("rive_nine_slice_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&out);
+    let render = cargo_run(&[
+        "render",
+        riv.to_str().unwrap(),
+        "--animation",
+        "grow",
+        "--frames",
+        "0,60",
+        "--width",
+        "768",
+        "--height",
+        "256",
+        "-o",
+        out.to_str().unwrap(),
+    ]);
+    assert!(
+        render.status.success(),
+        "render failed: {}",
+        String::from_utf8_lossy(&render.stderr)
+    );
+    let start = rive_cli::render::image::analyze(&out.join("frame_00000.png")).unwrap();
+    let end = rive_cli::render::image::analyze(&out.join("frame_00060.png")).unwrap();
+    assert_eq!((start.width, start.height), (768, 256));
+
+    // The 256px frame centred at x=384 spans 256..512 and, scaled 2.5x,
+    // 64..704; each 48px corner must move without being stretched.
+    let mean_difference = |from_x: u32, to_x: u32| {
+        let mut total = 0u64;
+        for y in 0..48 {
+            for dx in 0..48 {
+                let at = |image: &rive_cli::render::image::ImageInfo, x: u32| {
+                    let index = ((y * image.width + x) * 4) as usize;
+                    image.rgba[index..index + 4].to_vec()
+                };
+                let (a, b) = (at(&start, from_x + dx), at(&end, to_x + dx));
+                total += a
+                    .iter()
+                    .zip(&b)
+                    .map(|(a, b)| a.abs_diff(*b) as u64)
+                    .sum::<u64>();
+            }
+        }
+        total as f64 / (48.0 * 48.0 * 4.0)
+    };
+    for (corner, from_x, to_x) in [("top-left", 256, 64), ("top-right", 464, 656)] {
+        let difference = mean_difference(from_x, to_x);
+        assert!(
+            difference < 4.0,
+            "the {corner} corner changed by {difference:.2} per channel"
+        );
+    }
+    assert!(
+        mean_difference(256, 256) > 4.0,
+        "the frame must have grown past its starting corner"
+    );
+
+    let _ = std::fs::remove_dir_all(&out);
+}
+
 fn assert_generate_fails_with(fixture: &str, expected_stderr: &str) {
     let input = fixture_path(&format!("{}.json", fixture));
     let output = temp_output(fixture);
@@ -4846,6 +4923,404 @@ fn test_asset_source_errors_are_actionable() {
     let _ = std::fs::remove_dir_all(&dir);
 }
 
+/// A 16-bit mono PCM WAV of `frames` silent samples at 8 kHz.
+fn silent_wav(frames: u32) -> Vec<u8> {
+    let data_len = frames * 2;
+    let mut bytes = Vec::new();
+    bytes.extend_from_slice(b"RIFF");
+    bytes.extend_from_slice(&(36 + data_len).to_le_bytes());
+    bytes.extend_from_slice(b"WAVEfmt ");
+    bytes.extend_from_slice(&16u32.to_le_bytes());
+    bytes.extend_from_slice(&1u16.to_le_bytes());
+    bytes.extend_from_slice(&1u16.to_le_bytes());
+    bytes.extend_from_slice(&8000u32.to_le_bytes());
+    bytes.extend_from_slice(&16000u32.to_le_bytes());
+    bytes.extend_from_slice(&2u16.to_le_bytes());
+    bytes.extend_from_slice(&16u16.to_le_bytes());
+    bytes.extend_from_slice(b"data");
+    bytes.extend_from_slice(&data_len.to_le_bytes());
+    bytes.resize(bytes.len() + data_len as usize, 0);
+    bytes
+}
+
+#[test]
+fn test_generate_validates_audio_and_warns_about_clips_past_the_animation() {
+    let dir = std::env::temp_dir().join(format!("rive_audio_src_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).expect("temp dir");
+    let scene = dir.join("scene.json");
+    let output = dir.join("out.riv");
+    std::fs::write(dir.join("chime.wav"), silent_wav(12000)).expect("write wav");
+    std::fs::write(dir.join("broken.wav"), b"RIFF\0\0\0\0WAVEjunk").expect("write wav");
+
+    let write_scene = |source: &str| {
+        std::fs::write(
+            &scene,
+            format!(
+                r#"{{"scene_format_version":1,"artboard":{{"name":"A","width":10,"height":10,
+                   "children":[{{"type":"audio_asset","name":"Chime","source":"{source}"}},
+                               {{"type":"audio_event","name":"Ding","asset":"Chime"}}],
+                   "animations":[{{"name":"Intro","fps":60,"duration":60,"keyframes":[
+                       {{"object":"Ding","property":"trigger",
+                         "frames":[{{"frame":30,"value":0,"interpolation":"hold"}}]}}]}}]}}}}"#
+            ),
+        )
+        .expect("write scene");
+    };
+
+    write_scene("chime.wav");
+    let generated = cargo_run(&[
+        "generate",
+        scene.to_str().unwrap(),
+        "-o",
+        output.to_str().unwrap(),
+    ]);
+    let stderr = String::from_utf8_lossy(&generated.stderr).into_owned();
+    assert!(generated.status.success(), "generate failed: {stderr}");
+    assert!(
+        stderr.contains(
+            "warning: animation 'Intro' plays audio 'Chime' through event 'Ding' at frame 30 (0.500s); its 1.500s clip runs to 2.000s"
+        ),
+        "unexpected stderr: {stderr}"
+    );
+    let inspected = cargo_run(&["inspect", output.to_str().unwrap()]);
+    assert!(
+        String::from_utf8_lossy(&inspected.stdout).contains("  audio: WAV, 8000 Hz, 1 ch, 1.500s"),
+        "inspect should show the decoded clip"
+    );
+
+    write_scene("broken.wav");
+    let broken = cargo_run(&[
+        "generate",
+        scene.to_str().unwrap(),
+        "-o",
+        output.to_str().unwrap(),
+    ]);
+    let stderr = String::from_utf8_lossy(&broken.stderr).into_owned();
+    assert!(!broken.status.success(), "undecodable audio should fail");
+    assert!(
+        stderr.contains(
+            "asset 'Chime' source 'broken.wav' is not decodable audio: WAV file has no 'fmt ' chunk"
+        ),
+        "unexpected error: {stderr}"
+    );
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_asset_library_is_shared_and_identical_bytes_embed_once() {
+    use sha2::{Digest, Sha256};
+
+    let root = std::env::temp_dir().join(format!("rive_asset_library_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&root);
+    std::fs::create_dir_all(root.join("assets")).expect("assets dir");
+    std::fs::create_dir_all(root.join("scenes")).expect("scenes dir");
+    std::fs::write(root.join("package.json"), "{}").expect("project marker");
+    let logo = b"\x89PNG\r\n\x1a\nnot really a png";
+    std::fs::write(root.join("assets/logo.png"), logo).expect("write logo");
+    let digest = Sha256::digest(logo)
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect::<String>();
+    let write_library = |sha256: &str| {
+        std::fs::write(
+            root.join("assets/library.json"),
+            format!(
+                r#"{{"asset_library_version":1,"assets":{{
+                   "logo":{{"kind":"image","source":"logo.png","sha256":"{sha256}"}}}}}}"#
+            ),
+        )
+        .expect("write library");
+    };
+    write_library(&digest);
+
+    let scene = root.join("scenes/scene.json");
+    let output = root.join("scenes/out.riv");
+    std::fs::write(
+        &scene,
+        r#"{"scene_format_version":1,"asset_library":"../assets/library.json",
+            "artboard":{"name":"A","width":100,"height":100,"children":[
+              {"type":"image_asset","name":"Logo","library":"logo"},
+              {"type":"image_asset","name":"HeaderLogo","source":"../assets/logo.png"},
+              {"type":"image","name":"Footer","asset":"Logo","x":50,"y":80},
+              {"type":"image","name":"Header","asset":"HeaderLogo","x":50,"y":20}]}}"#,
+    )
+    .expect("write scene");
+    let generated = cargo_run(&[
+        "generate",
+        scene.to_str().unwrap(),
+        "-o",
+        output.to_str().unwrap(),
+    ]);
+    assert!(
+        generated.status.success(),
+        "generate failed: {}",
+        String::from_utf8_lossy(&generated.stderr)
+    );
+    let inspect = cargo_run(&["inspect", "--json", output.to_str().unwrap()]);
+    let parsed: serde_json::Value =
+        serde_json::from_slice(&inspect.stdout).expect("inspect --json output");
+    let objects = json_objects(&parsed);
+    assert_eq!(find_objects_by_type(objects, "ImageAsset").len(), 1);
+    assert_eq!(find_objects_by_type(objects, "FileAssetContents").len(), 1);
+    for image in find_objects_by_type(objects, "Image") {
+        assert_eq!(uint_property(image, "assetId"), 0);
+    }
+
+    write_library(&"0".repeat(64));
+    let drifted = cargo_run(&[
+        "generate",
+        scene.to_str().unwrap(),
+        "-o",
+        output.to_str().unwrap(),
+    ]);
+    let stderr = String::from_utf8_lossy(&drifted.stderr).into_owned();
+    assert!(
+        !drifted.status.success(),
+        "a changed library source should fail"
+    );
+    assert!(
+        stderr.contains(&format!(
+            "library asset 'logo' source 'logo.png' has sha256 {digest}, but asset library '../assets/library.json' declares"
+        )),
+        "unexpected error: {stderr}"
+    );
+
+    let _ = std::fs::remove_dir_all(&root);
+}
+
+#[test]
+fn test_generate_external_assets_writes_files_and_a_manifest() {
+    let root = std::env::temp_dir().join(format!("rive_external_assets_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&root);
+    std::fs::create_dir_all(&root).expect("temp dir");
+    std::fs::write(root.join("package.json"), "{}").expect("project marker");
+    let logo = b"\x89PNG\r\n\x1a\nnot really a png";
+    std::fs::write(root.join("logo.png"), logo).expect("write logo");
+
+    let scene = root.join("scene.json");
+    let output = root.join("hero.riv");
+    let write_scene = |second_asset_id: u32| {
+        std::fs::write(
+            &scene,
+            format!(
+                r#"{{"scene_format_version":1,
+                   "artboard":{{"name":"A","width":100,"height":100,"children":[
+                     {{"type":"image_asset","name":"Logo.png","source":"logo.png","asset_id":7}},
+                     {{"type":"image_asset","name":"Logo.jpg","source":"scene.json","asset_id":{second_asset_id}}},
+                     {{"type":"image","name":"Header","asset":"Logo.png","x":50,"y":20}}]}}}}"#
+            ),
+        )
+        .expect("write scene");
+    };
+    write_scene(8);
+    let generated = cargo_run(&[
+        "generate",
+        scene.to_str().unwrap(),
+        "-o",
+        output.to_str().unwrap(),
+        "--assets",
+        "external",
+        "--json",
+    ]);
+    assert!(
+        generated.status.success(),
+        "generate failed: {}",
+        String::from_utf8_lossy(&generated.stderr)
+    );
+    let report: serde_json::Value =
+        serde_json::from_slice(&generated.stdout).expect("generate --json output");
+    let assets_dir = root.join("hero_assets");
+    let manifest_path = assets_dir.join("manifest.json");
+    assert_eq!(
+        report["assets_manifest"].as_str(),
+        Some(manifest_path.to_str().unwrap())
+    );
+
+    let inspect = cargo_run(&["inspect", "--json", output.to_str().unwrap()]);
+    let parsed: serde_json::Value =
+        serde_json::from_slice(&inspect.stdout).expect("inspect --json output");
+    let objects = json_objects(&parsed);
+    assert_eq!(find_objects_by_type(objects, "ImageAsset").len(), 2);
+    assert!(find_objects_by_type(objects, "FileAssetContents").is_empty());
+
+    assert_eq!(
+        std::fs::read(assets_dir.join("Logo-7.png")).expect("external logo"),
+        logo
+    );
+    let manifest: serde_json::Value =
+        serde_json::from_slice(&std::fs::read(&manifest_path).expect("manifest"))
+            .expect("manifest json");
+    assert_eq!(manifest["external_assets_version"], 1);
+    assert_eq!(manifest["riv"], "hero.riv");
+    assert_eq!(manifest["assets"][0]["asset_id"], 7);
+    assert_eq!(manifest["assets"][0]["name"], "Logo.png");
+    assert_eq!(manifest["assets"][0]["kind"], "image");
+    assert_eq!(manifest["assets"][0]["file"], "Logo-7.png");
+    assert_eq!(manifest["assets"][0]["bytes"], logo.len());
+    assert_eq!(manifest["assets"][1]["file"], "Logo-8.png");
+
+    // A modified file is refused before the browser is involved.
+    std::fs::write(assets_dir.join("Logo-7.png"), b"changed").expect("tamper");
+    let render = cargo_run(&[
+        "render",
+        output.to_str().unwrap(),
+        "-o",
+        root.join("renders").to_str().unwrap(),
+        "--assets",
+        assets_dir.to_str().unwrap(),
+        "--browser",
+        root.join("no-browser").to_str().unwrap(),
+    ]);
+    let stderr = String::from_utf8_lossy(&render.stderr).into_owned();
+    assert!(!render.status.success(), "a modified asset should fail");
+    assert!(
+        stderr.contains("external asset 'Logo.png'") && stderr.contains("but the manifest records"),
+        "unexpected error: {stderr}"
+    );
+
+    write_scene(7);
+    let clash = cargo_run(&[
+        "generate",
+        scene.to_str().unwrap(),
+        "-o",
+        output.to_str().unwrap(),
+        "--assets",
+        "external",
+    ]);
+    let stderr = String::from_utf8_lossy(&clash.stderr).into_owned();
+    assert!(!clash.status.success(), "clashing file names should fail");
+    assert!(
+        stderr.contains(
+            "assets 'Logo.png' and 'Logo.jpg' would both be written as external file 'Logo-7.png'"
+        ),
+        "unexpected error: {stderr}"
+    );
+
+    let _ = std::fs::remove_dir_all(&root);
+}
+
+#[test]
+fn test_generate_embeds_script_sources_and_checks_their_inputs() {
+    let dir = std::env::temp_dir().join(format!("rive_script_src_{}", std::process::id()));
+    std::fs::create_dir_all(dir.join("scripts")).expect("temp dir");
+    let scene = dir.join("scene.json");
+    let output = dir.join("out.riv");
+    std::fs::write(
+        dir.join("scripts/wobble.luau"),
+        "--!strict\n-- @input amplitude number\n\nfunction init(self)\n  return true\nend\n",
+    )
+    .expect("write script");
+    std::fs::write(
+        dir.join("scripts/broken.luau"),
+        "function init(self)\n  if self then\n    return\nend\n",
+    )
+    .expect("write script");
+
+    let write_scene = |source: &str, input: &str| {
+        std::fs::write(
+            &scene,
+            format!(
+                r#"{{"scene_format_version":1,"artboard":{{"name":"A","width":10,"height":10,
+                   "children":[{{"type":"script_asset","name":"Wobble","source":"{source}"}},
+                               {{"type":"scripted_drawable","name":"Blob","script":"Wobble",
+                                 "children":[{{"type":"{input}","name":"amplitude"}}]}}]}}}}"#
+            ),
+        )
+        .expect("write scene");
+    };
+    let generate = || {
+        cargo_run(&[
+            "generate",
+            scene.to_str().unwrap(),
+            "-o",
+            output.to_str().unwrap(),
+        ])
+    };
+
+    write_scene("scripts/wobble.luau", "script_input_number");
+    let generated = generate();
+    assert!(
+        generated.status.success(),
+        "generate failed: {}",
+        String::from_utf8_lossy(&generated.stderr)
+    );
+    let inspected = cargo_run(&["inspect", output.to_str().unwrap()]);
+    let stdout = String::from_utf8_lossy(&inspected.stdout).into_owned();
+    assert!(
+        stdout.contains(
+            "  script: 6 lines\n    | --!strict\n    | -- @input amplitude number\n    |\n"
+        ),
+        "inspect should print the embedded source: {stdout}"
+    );
+
+    write_scene("scripts/wobble.luau", "script_input_boolean");
+    let mismatched = generate();
+    assert!(!mismatched.status.success(), "mismatched input should fail");
+    assert!(
+        String::from_utf8_lossy(&mismatched.stderr).contains(
+            "'Blob' gives its script input 'amplitude' as a boolean, but the script declares it as number"
+        ),
+        "unexpected error: {}",
+        String::from_utf8_lossy(&mismatched.stderr)
+    );
+
+    write_scene("scripts/broken.luau", "script_input_number");
+    let broken = generate();
+    assert!(!broken.status.success(), "unbalanced script should fail");
+    assert!(
+        String::from_utf8_lossy(&broken.stderr).contains(
+            "asset 'Wobble' source 'scripts/broken.luau': line 1: 'function' is never closed"
+        ),
+        "unexpected error: {}",
+        String::from_utf8_lossy(&broken.stderr)
+    );
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_validate_counts_script_assets_before_image_assets() {
+    let dir = std::env::temp_dir().join(format!("rive_script_ordinal_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).expect("temp dir");
+    let scene = dir.join("scene.json");
+    let output = dir.join("out.riv");
+    std::fs::write(
+        dir.join("wobble.luau"),
+        "function init(self)\n  return true\nend\n",
+    )
+    .expect("write script");
+    std::fs::write(
+        &scene,
+        r#"{"scene_format_version":1,"artboard":{"name":"A","width":10,"height":10,
+           "children":[{"type":"script_asset","name":"Wobble","source":"wobble.luau"},
+                       {"type":"image_asset","name":"Hero","asset_id":100},
+                       {"type":"image","name":"HeroSprite","asset_id":1}]}}"#,
+    )
+    .expect("write scene");
+
+    let generated = cargo_run(&[
+        "generate",
+        scene.to_str().unwrap(),
+        "-o",
+        output.to_str().unwrap(),
+    ]);
+    assert!(
+        generated.status.success(),
+        "generate failed: {}",
+        String::from_utf8_lossy(&generated.stderr)
+    );
+    let validated = cargo_run(&["validate", output.to_str().unwrap()]);
+    assert!(
+        validated.status.success(),
+        "validate failed: {}{}",
+        String::from_utf8_lossy(&validated.stdout),
+        String::from_utf8_lossy(&validated.stderr)
+    );
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
 fn render_interaction(
     riv: &std::path::Path,
     out: &std::path::Path,
@@ -4933,6 +5408,133 @@ fn test_pointer_and_scheduled_input_change_the_render() {
     let _ = std::fs::remove_dir_all(&root);
 }
 
+#[test]
+fn test_generate_scroll_list_resolves_its_physics_and_scroll_bar() {
+    assert_generate_validate_inspect(
+        "scroll_list",
+        &[
+            "directionValue(722) uint(1)",
+            "physicsId(726) uint(38)",
+            "elasticFactor(730) float(0.66)",
+            "scrollConstraintId(725) uint(37)",
+        ],
+    );
+}
+
+#[test]
+fn test_generate_joystick_drag_resolves_animations_handle_and_align_target() {
+    assert_generate_validate_inspect(
+        "joystick_drag",
+        &[
+            "xId(301) uint(0)",
+            "yId(302) uint(1)",
+            "handleSourceId(313) uint(9)",
+            "targetId(240) uint(9)",
+        ],
+    );
+}
+
+fn render_drag(
+    riv: &std::path::Path,
+    state_machine: &str,
+    out: &std::path::Path,
+    extra: &[&str],
+) -> Vec<Vec<u8>> {
+    let mut args: Vec<&str> = vec![
+        "render",
+        riv.to_str().unwrap(),
+        "--state-machine",
+        state_machine,
+        "--frames",
+        "0,9,40",
+        "-o",
+        out.to_str().unwrap(),
+    ];
+    args.extend_from_slice(extra);
+    let result = cargo_run(&args);
+    assert!(
+        result.status.success(),
+        "render failed: {}",
+        String::from_utf8_lossy(&result.stderr)
+    );
+    ["frame_00000.png", "frame_00009.png", "frame_00040.png"]
+        .iter()
+        .map(|name| std::fs::read(out.join(name)).expect("frame should exist"))
+        .collect()
+}
+
+#[test]
+fn test_drag_gesture_scrolls_the_list() {
+    let (riv, _guard) = generate_and_validate_output("scroll_list", "drag");
+    let root = std::env::temp_dir().join(format!("rive_scroll_drag_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&root);
+
+    let drag = ["--pointer", "drag:160,400->160,120@10-30"];
+    let plain = render_drag(&riv, "ScrollMachine", &root.join("plain"), &[]);
+    let dragged = render_drag(&riv, "ScrollMachine", &root.join("dragged"), &drag);
+    let repeat = render_drag(&riv, "ScrollMachine", &root.join("repeat"), &drag);
+
+    assert_eq!(
+        plain[1], dragged[1],
+        "frame 9 precedes the drag and must be unchanged"
+    );
+    assert_ne!(
+        plain[2], dragged[2],
+        "dragging up inside the viewport must scroll the list by frame 40"
+    );
+    assert_eq!(
+        dragged, repeat,
+        "the same drag must render byte-identically"
+    );
+
+    let manifest: serde_json::Value = serde_json::from_slice(
+        &std::fs::read(root.join("dragged").join("manifest.json")).expect("manifest"),
+    )
+    .expect("manifest json");
+    let pointers = manifest["applied_pointers"]
+        .as_array()
+        .expect("applied pointers");
+    assert_eq!(pointers.len(), 22, "a down, twenty moves and an up");
+    assert_eq!(pointers[0]["event"], "down");
+    assert_eq!(pointers[10]["y"], 260.0);
+    assert_eq!(pointers[21]["event"], "up");
+
+    let _ = std::fs::remove_dir_all(&root);
+}
+
+#[test]
+fn test_drag_gesture_moves_the_joystick_handle() {
+    let (riv, _guard) = generate_and_validate_output("joystick_drag", "drag");
+    let root = std::env::temp_dir().join(format!("rive_joystick_drag_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&root);
+
+    let plain = render_drag(&riv, "StickMachine", &root.join("plain"), &[]);
+    let dragged = render_drag(
+        &riv,
+        "StickMachine",
+        &root.join("dragged"),
+        &["--pointer", "drag:200,200->290,120@10-20"],
+    );
+    let outside = render_drag(
+        &riv,
+        "StickMachine",
+        &root.join("outside"),
+        &["--pointer", "drag:8,8->40,8@10-20"],
+    );
+
+    assert_eq!(plain[1], dragged[1], "frame 9 precedes the drag");
+    assert_ne!(
+        plain[2], dragged[2],
+        "dragging the handle must tilt the arrow through both animations"
+    );
+    assert_eq!(
+        plain, outside,
+        "a drag outside the pad must not move the handle"
+    );
+
+    let _ = std::fs::remove_dir_all(&root);
+}
+
 #[test]
 fn test_pointer_requires_a_state_machine() {
     let (riv, _guard) = generate_and_validate_output("pointer_interaction", "pointer_guard");
@@ -4960,6 +5562,33 @@ fn test_pointer_requires_a_state_machine() {
     let _ = std::fs::remove_dir_all(&out);
 }
 
+#[test]
+fn test_view_model_trigger_requires_a_state_machine() {
+    let (riv, _guard) = generate_and_validate_output("pointer_interaction", "view_model_guard");
+    let out = std::env::temp_dir().join(format!("rive_view_model_guard_{}", std::process::id()));
+    let result = cargo_run(&[
+        "render",
+        riv.to_str().unwrap(),
+        "--view-model",
+        "tap=trigger@0",
+        "--frames",
+        "0",
+        "-o",
+        out.to_str().unwrap(),
+    ]);
+    assert!(
+        !result.status.success(),
+        "a view model trigger without --state-machine should fail"
+    );
+    assert!(
+        String::from_utf8_lossy(&result.stderr)
+            .contains("a --view-model trigger only applies when --state-machine is given"),
+        "unexpected error: {}",
+        String::from_utf8_lossy(&result.stderr)
+    );
+    let _ = std::fs::remove_dir_all(&out);
+}
+
 fn generate_scene_expecting_failure(scene: &str, tag: &str) -> String {
     let dir = std::env::temp_dir().join(format!("rive_reject_{}_{}", tag, std::process::id()));
     std::fs::create_dir_all(&dir).expect("temp dir");
@@ -0,0 +1,298 @@
+#![cfg(feature = "mcp")]
+
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::time::{Duration, Instant};
+
+use serde_json::{Value, json};
+
+const STARTUP_TIMEOUT: Duration = Duration::from_secs(20);
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(15);
+
+struct Server {
+    child: Child,
+    url: String,
+}
+
+impl Server {
+    fn start(root: &Path, token: Option<&str>) -> Self {
+        Self::start_with(root, token, &[])
+    }
+
+    fn start_with(root: &Path, token: Option<&str>, extra: &[&str]) -> Self {
+        let mut command = Command::new(env!("CARGO_BIN_EXE_rive-cli"));
+        command
+            .args(["--mcp-http", "127.0.0.1:0", "--mcp-root"])
+            .arg(root)
+            .args(extra)
+            .env_remove("RIVE_MCP_TOKEN")
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped());
+        if let Some(token) = token {
+            command.env("RIVE_MCP_TOKEN", token);
+        }
+        let mut child = command.spawn().expect("start MCP HTTP server");
+        let stderr = child.stderr.take().expect("server stderr");
+        let (sender, receiver) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            for line in BufReader::new(stderr).lines().map_while(Result::ok) {
+                if let Some(rest) = line.split("listening on ").nth(1) {
+                    let url = rest.split_whitespace().next().unwrap_or_default();
+                    let _ = sender.send(url.to_string());
+                }
+            }
+        });
+        let url = receiver
+            .recv_timeout(STARTUP_TIMEOUT)
+            .expect("server must report its listening address");
+        Self { child, url }
+    }
+
+    fn post(&self, session: Option<&str>, token: Option<&str>, body: Value) -> ureq::Response {
+        let mut request = ureq::post(&self.url)
+            .set("content-type", "application/json")
+            .set("accept", "application/json, text/event-stream");
+        if let Some(session) = session {
+            request = request.set("mcp-session-id", session);
+        }
+        if let Some(token) = token {
+            request = request.set("authorization", &format!("Bearer {token}"));
+        }
+        request.send_string(&body.to_string()).expect("MCP request")
+    }
+
+    fn initialize(&self, workdir: Option<&str>) -> Result<String, Value> {
+        let mut request = ureq::post(&self.url)
+            .set("content-type", "application/json")
+            .set("accept", "application/json, text/event-stream");
+        if let Some(workdir) = workdir {
+            request = request.set("x-rive-workdir", workdir);
+        }
+        let response = request
+            .send_string(&initialize_request().to_string())
+            .expect("initialize request");
+        let session = response.header("mcp-session-id").map(str::to_string);
+        let message = response_message(response, 0);
+        if let Some(error) = message.get("error") {
+            return Err(error.clone());
+        }
+        let session = session.expect("initialize must open a session");
+        self.post(
+            Some(&session),
+            None,
+            json!({ "jsonrpc": "2.0", "method": "notifications/initialized" }),
+        );
+        Ok(session)
+    }
+
+    fn call_tool(&self, session: &str, id: u64, name: &str, arguments: Value) -> Value {
+        let response = self.post(
+            Some(session),
+            None,
+            json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "method": "tools/call",
+                "params": { "name": name, "arguments": arguments }
+            }),
+        );
+        response_message(response, id)
+    }
+}
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+fn initialize_request() -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": 0,
+        "method": "initialize",
+        "params": {
+            "protocolVersion": "2024-11-05",
+            "capabilities": {},
+            "clientInfo": { "name": "mcp-http-test", "version": "0" }
+        }
+    })
+}
+
+fn response_message(response: ureq::Response, id: u64) -> Value {
+    let body = response.into_string().expect("response body");
+    body.lines()
+        .filter_map(|line| line.strip_prefix("data:"))
+        .map(str::trim)
+        .filter(|data| !data.is_empty())
+        .filter_map(|data| serde_json::from_str::<Value>(data).ok())
+        .find(|message| message["id"] == id)
+        .unwrap_or_else(|| panic!("no response with id {id} in {body}"))
+}
+
+fn scratch(name: &str) -> PathBuf {
+    let root = std::env::temp_dir().join(format!("rive_mcp_http_{name}_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&root);
+    for workdir in ["alpha", "beta"] {
+        std::fs::create_dir_all(root.join(workdir)).expect("workdir");
+    }
+    root
+}
+
+#[test]
+fn concurrent_sessions_stay_inside_their_working_directories() {
+    let root = scratch("sessions");
+    let server = Server::start(&root, None);
+
+    let sessions = std::thread::scope(|scope| {
+        let handles = ["alpha", "beta", "alpha", "beta"]
+            .map(|workdir| scope.spawn(|| server.initialize(Some(workdir)).expect("session")));
+        handles.map(|handle| handle.join().expect("session thread"))
+    });
+    let mut distinct = sessions.to_vec();
+    distinct.sort();
+    distinct.dedup();
+    assert_eq!(
+        distinct.len(),
+        sessions.len(),
+        "every session needs its own id"
+    );
+
+    let written = server.call_tool(
+        &sessions[0],
+        1,
+        "new",
+        json!({ "template": "shape", "output": "scene.json" }),
+    );
+    assert!(written.get("result").is_some(), "{written}");
+    assert!(root.join("alpha/scene.json").is_file());
+    assert!(!root.join("beta/scene.json").exists());
+
+    let escaped = server.call_tool(
+        &sessions[1],
+        2,
+        "new",
+        json!({ "template": "shape", "output": "../alpha/stolen.json" }),
+    );
+    let message = escaped["error"]["message"].as_str().unwrap_or_default();
+    assert!(
+        message.contains("escapes the session working directory"),
+        "{escaped}"
+    );
+    assert!(!root.join("alpha/stolen.json").exists());
+
+    let validated = server.call_tool(
+        &sessions[2],
+        3,
+        "validate",
+        json!({ "file": "../beta/missing.riv" }),
+    );
+    assert!(validated.get("error").is_some(), "{validated}");
+
+    let _ = std::fs::remove_dir_all(&root);
+}
+
+#[test]
+fn working_directories_outside_the_root_are_rejected_at_initialize() {
+    let root = scratch("workdir");
+    let server = Server::start(&root, None);
+    let error = server
+        .initialize(Some(".."))
+        .expect_err("escaping working directory must fail");
+    assert!(
+        error["message"]
+            .as_str()
+            .unwrap_or_default()
+            .contains("escapes the server root"),
+        "{error}"
+    );
+    let _ = std::fs::remove_dir_all(&root);
+}
+
+#[test]
+fn file_size_cap_applies_to_reads_and_asset_sources() {
+    let root = scratch("size_cap");
+    std::fs::write(root.join("alpha/large.riv"), [0u8; 128]).expect("large file");
+    std::fs::write(root.join("alpha/texture.png"), [0u8; 128]).expect("large asset");
+    let server = Server::start_with(&root, None, &["--mcp-max-file-bytes", "64"]);
+    let session = server.initialize(Some("alpha")).expect("session");
+
+    let validated = server.call_tool(&session, 1, "validate", json!({ "file": "large.riv" }));
+    assert_eq!(
+        validated["error"]["data"]["violation"], "file_too_large",
+        "{validated}"
+    );
+
+    let scene = json!({
+        "scene_format_version": 1,
+        "artboard": {
+            "name": "A",
+            "width": 100,
+            "height": 100,
+            "children": [{ "type": "image_asset", "name": "T", "source": "texture.png" }]
+        }
+    });
+    let generated = server.call_tool(
+        &session,
+        2,
+        "generate",
+        json!({ "scene_json": scene.to_string() }),
+    );
+    let message = generated["error"]["message"].as_str().unwrap_or_default();
+    assert!(message.contains("over the 64 byte limit"), "{generated}");
+
+    let _ = std::fs::remove_dir_all(&root);
+}
+
+#[test]
+fn bearer_token_is_required_when_configured() {
+    let root = scratch("token");
+    let server = Server::start(&root, Some("s3cret"));
+    for token in [None, Some("wrong")] {
+        let mut request = ureq::post(&server.url)
+            .set("content-type", "application/json")
+            .set("accept", "application/json, text/event-stream");
+        if let Some(token) = token {
+            request = request.set("authorization", &format!("Bearer {token}"));
+        }
+        match request.send_string(&initialize_request().to_string()) {
+            Err(ureq::Error::Status(status, _)) => assert_eq!(status, 401),
+            other => panic!("unauthenticated request must be rejected, got {other:?}"),
+        }
+    }
+    let response = server.post(None, Some("s3cret"), initialize_request());
+    assert!(response.header("mcp-session-id").is_some());
+    let _ = std::fs::remove_dir_all(&root);
+}
+
+#[cfg(unix)]
+#[test]
+fn terminate_signal_shuts_the_server_down_gracefully() {
+    let root = scratch("shutdown");
+    let mut server = Server::start(&root, None);
+    server.initialize(None).expect("session before shutdown");
+
+    let status = Command::new("kill")
+        .args(["-TERM", &server.child.id().to_string()])
+        .status()
+        .expect("send SIGTERM");
+    assert!(status.success());
+    let started = Instant::now();
+    let exit = loop {
+        if let Some(exit) = server.child.try_wait().expect("poll server") {
+            break exit;
+        }
+        assert!(
+            started.elapsed() < SHUTDOWN_TIMEOUT,
+            "server must exit after SIGTERM"
+        );
+        std::thread::sleep(Duration::from_millis(50));
+    };
+    assert!(
+        exit.success(),
+        "graceful shutdown must exit cleanly: {exit}"
+    );
+    let _ = std::fs::remove_dir_all(&root);
+}